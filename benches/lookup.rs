@@ -0,0 +1,56 @@
+//! Compares `ipa::describe`, which looks symbols up in a perfect-hash
+//! map, with the scan over the grapheme tables it used to do. The scan
+//! only finds the base symbol, while `describe` also reads the
+//! diacritics after it. Run with `cargo bench`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use allo::graphemes;
+use allo::ipa::{self, Description};
+
+const ROUNDS: usize = 2_000;
+
+/// Every chart symbol, as `describe` used to find them.
+fn table() -> Vec<(&'static str, Description)> {
+    let consonants = ipa::PULMONIC
+        .into_iter()
+        .chain(graphemes::CLICKS.into_iter().zip(ipa::CLICKS))
+        .chain(graphemes::IMPLOSIVES.into_iter().zip(ipa::IMPLOSIVES))
+        .chain(graphemes::COARTICULATED.into_iter().zip(ipa::COARTICULATED))
+        .map(|(g, c)| (g, Description::Consonant(c)));
+    let vowels = ipa::VOWELS
+        .into_iter()
+        .map(|(g, v)| (g, Description::Vowel(v)));
+    consonants.chain(vowels).collect()
+}
+
+/// The longest symbol in `table` that `symbol` starts with.
+fn scan(table: &[(&'static str, Description)], symbol: &str) -> Option<Description> {
+    table
+        .iter()
+        .filter(|(g, _)| symbol.starts_with(g))
+        .max_by_key(|(g, _)| g.len())
+        .map(|(_, d)| *d)
+}
+
+fn time(name: &str, inputs: &[String], mut f: impl FnMut(&str) -> Option<Description>) {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for input in inputs {
+            black_box(f(black_box(input)));
+        }
+    }
+    let per = start.elapsed() / (ROUNDS * inputs.len()) as u32;
+    println!("{:<8} {:>8.1?} per symbol", name, per);
+}
+
+fn main() {
+    let table = table();
+    let inputs: Vec<String> = table
+        .iter()
+        .flat_map(|(g, _)| [g.to_string(), format!("{}\u{2B0}", g)])
+        .collect();
+    time("scan", &inputs, |s| scan(&table, s));
+    time("describe", &inputs, ipa::describe);
+}
@@ -0,0 +1,139 @@
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::str::FromStr;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::ToString, vec, vec::Vec};
+
+use crate::diacritics::Diacritic;
+use crate::ipa::{Consonant, Manner};
+#[cfg(feature = "alloc")]
+use crate::parser;
+use crate::parser::TIE_BARS;
+#[cfg(feature = "alloc")]
+use crate::segment::{ParseSegmentError, Segment};
+use crate::suprasegmentals::Length;
+
+/// A stop released into a fricative at (nearly) the same place, e.g.
+/// `t͡ʃ`. Written with the tie bar U+0361 between its halves.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Affricate {
+    stop: Consonant,
+    fricative: Consonant,
+}
+
+impl Affricate {
+    /// Joins `stop` and `fricative`, returning `None` unless `stop` is
+    /// a plosive and `fricative` a central or lateral fricative.
+    ///
+    /// Spacing modifiers such as `ʰ`, secondary articulations and
+    /// length describe the release, so any on `stop` are moved to
+    /// `fricative`: `tʰ` and `ʃ` make `t͡ʃʰ`.
+    pub const fn new(stop: Consonant, fricative: Consonant) -> Option<Self> {
+        if !matches!(
+            (stop.manner(), fricative.manner()),
+            (Manner::Plosive, Manner::Fricative { .. } | Manner::LatFric)
+        ) {
+            return None;
+        }
+        let (mut stop, mut fricative) = (stop, fricative);
+        let mut i = 0;
+        while i < Diacritic::ALL.len() {
+            let d = Diacritic::ALL[i];
+            if !d.is_combining() && stop.diacritics().contains(d) {
+                stop = stop.with_diacritics(stop.diacritics().without(d));
+                fricative = fricative.with_diacritic(d);
+            }
+            i += 1;
+        }
+        if stop.secondary().is_some() {
+            if fricative.secondary().is_none() {
+                fricative = fricative.with_secondary(stop.secondary());
+            }
+            stop = stop.with_secondary(None);
+        }
+        if !stop.length().is_short() {
+            if fricative.length().is_short() {
+                fricative = fricative.with_length(stop.length());
+            }
+            stop = stop.with_length(Length::Short);
+        }
+        Some(Affricate { stop, fricative })
+    }
+
+    pub const fn stop(&self) -> Consonant {
+        self.stop
+    }
+
+    pub const fn fricative(&self) -> Consonant {
+        self.fricative
+    }
+
+    /// The length, which is written on the fricative: `t͡ʃː`.
+    pub const fn length(&self) -> Length {
+        self.fricative.length()
+    }
+
+    pub const fn with_length(self, length: Length) -> Self {
+        Affricate {
+            stop: self.stop.with_length(Length::Short),
+            fricative: self.fricative.with_length(length),
+        }
+    }
+
+    /// Attaches `diacritic`. Spacing modifiers such as `ʰ` describe the
+    /// release and go on the fricative; combining marks such as `̥`
+    /// go on both halves.
+    pub const fn with_diacritic(self, diacritic: Diacritic) -> Self {
+        let stop = match diacritic.is_combining() {
+            true => self.stop.with_diacritic(diacritic),
+            false => self.stop,
+        };
+        Affricate {
+            stop,
+            fricative: self.fricative.with_diacritic(diacritic),
+        }
+    }
+}
+
+impl fmt::Display for Affricate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.stop, TIE_BARS[0], self.fricative)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromStr for Affricate {
+    type Err = ParseSegmentError;
+
+    /// Parses an affricate written with either tie bar (`"t͡ʃ"`,
+    /// `"t͜ʃ"`) or without one (`"tʃ"`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let halves: Vec<&str> = match s.split_once(TIE_BARS) {
+            Some((stop, fricative)) => vec![stop, fricative],
+            None => parser::tokenize(s),
+        };
+        let [stop, fricative] = halves[..] else {
+            return Err(ParseSegmentError::NotAffricate(s.to_string()));
+        };
+        let fricative = fricative
+            .parse()
+            .map_err(|e: ParseSegmentError| e.shifted(parser::offset_in(s, fricative)));
+        match (stop.parse()?, fricative?) {
+            (Segment::Consonant(stop), Segment::Consonant(fricative)) => {
+                Affricate::new(stop, fricative)
+                    .ok_or_else(|| ParseSegmentError::NotAffricate(s.to_string()))
+            }
+            _ => Err(ParseSegmentError::NotAffricate(s.to_string())),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&str> for Affricate {
+    type Error = ParseSegmentError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
@@ -0,0 +1,287 @@
+use core::fmt;
+
+use alloc::{vec, vec::Vec};
+
+use crate::diacritics::Diacritic;
+use crate::features::{self, Weights};
+use crate::segment::Segment;
+
+/// The costs `align_with` and `edit_distance` minimize: substituting
+/// one segment for another costs their feature distance under
+/// `weights`, pairing a gap with a segment of the second transcription
+/// (inserting it) costs `insertion`, and pairing a segment of the first
+/// with a gap (deleting it) costs `deletion`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Costs {
+    pub weights: Weights,
+    pub insertion: f32,
+    pub deletion: f32,
+}
+
+impl Default for Costs {
+    fn default() -> Self {
+        Costs {
+            weights: Weights::default(),
+            insertion: 3.0,
+            deletion: 3.0,
+        }
+    }
+}
+
+/// An alignment of two transcriptions: pairs of segments in order,
+/// with `None` for a gap on either side.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Alignment {
+    pub pairs: Vec<(Option<Segment>, Option<Segment>)>,
+    pub cost: f32,
+}
+
+impl Alignment {
+    /// The pairs where both sides have a segment.
+    pub fn matches(&self) -> impl Iterator<Item = (Segment, Segment)> + '_ {
+        self.pairs.iter().filter_map(|pair| match *pair {
+            (Some(a), Some(b)) => Some((a, b)),
+            _ => None,
+        })
+    }
+
+    pub fn gaps(&self) -> usize {
+        self.pairs
+            .iter()
+            .filter(|(a, b)| a.is_none() || b.is_none())
+            .count()
+    }
+}
+
+/// Aligns `a` with `b` under the default costs.
+pub fn align(a: &[Segment], b: &[Segment]) -> Alignment {
+    align_with(a, b, &Costs::default())
+}
+
+/// Aligns `a` with `b` by Needleman–Wunsch, finding the alignment of
+/// least total cost. Ties prefer substitutions, then deletions.
+pub fn align_with(a: &[Segment], b: &[Segment], costs: &Costs) -> Alignment {
+    let (n, m) = (a.len(), b.len());
+    let width = m + 1;
+    let mut table = vec![0.0f32; (n + 1) * width];
+    for i in 1..=n {
+        table[i * width] = i as f32 * costs.deletion;
+    }
+    for (j, cell) in table[..width].iter_mut().enumerate() {
+        *cell = j as f32 * costs.insertion;
+    }
+    let substitution = |i: usize, j: usize| features::distance(&a[i], &b[j], &costs.weights);
+    for i in 1..=n {
+        for j in 1..=m {
+            let diagonal = table[(i - 1) * width + j - 1] + substitution(i - 1, j - 1);
+            let up = table[(i - 1) * width + j] + costs.deletion;
+            let left = table[i * width + j - 1] + costs.insertion;
+            table[i * width + j] = diagonal.min(up).min(left);
+        }
+    }
+
+    let mut pairs = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        let here = table[i * width + j];
+        if i > 0 && j > 0 && here == table[(i - 1) * width + j - 1] + substitution(i - 1, j - 1) {
+            pairs.push((Some(a[i - 1]), Some(b[j - 1])));
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && (j == 0 || here == table[(i - 1) * width + j] + costs.deletion) {
+            pairs.push((Some(a[i - 1]), None));
+            i -= 1;
+        } else {
+            pairs.push((None, Some(b[j - 1])));
+            j -= 1;
+        }
+    }
+    pairs.reverse();
+    Alignment {
+        pairs,
+        cost: table[n * width + m],
+    }
+}
+
+/// One step of an edit script turning one transcription into another.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Edit {
+    /// A segment left as it is.
+    Keep(Segment),
+    /// A segment replaced by another.
+    Substitute(Segment, Segment),
+    /// A segment of the second transcription added.
+    Insert(Segment),
+    /// A segment of the first transcription removed.
+    Delete(Segment),
+}
+
+/// The weighted Levenshtein distance from `a` to `b` and an edit
+/// script achieving it, in order: the alignment of `align_with` read
+/// as edits.
+///
+/// ```
+/// use allo::align::{self, Costs, Edit};
+/// use allo::parser;
+///
+/// let word = |s| parser::parse(s).unwrap();
+/// let costs = Costs { insertion: 1.0, ..Costs::default() };
+/// let (distance, script) = align::edit_distance(&word("kat"), &word("kast"), &costs);
+/// assert_eq!(distance, 1.0);
+/// assert_eq!(script[2], Edit::Insert("s".parse().unwrap()));
+/// ```
+pub fn edit_distance(a: &[Segment], b: &[Segment], costs: &Costs) -> (f32, Vec<Edit>) {
+    let alignment = align_with(a, b, costs);
+    let script = alignment
+        .pairs
+        .into_iter()
+        .filter_map(|pair| match pair {
+            (Some(x), Some(y)) if x == y => Some(Edit::Keep(x)),
+            (Some(x), Some(y)) => Some(Edit::Substitute(x, y)),
+            (Some(x), None) => Some(Edit::Delete(x)),
+            (None, Some(y)) => Some(Edit::Insert(y)),
+            (None, None) => None,
+        })
+        .collect();
+    (alignment.cost, script)
+}
+
+/// A segment-level difference between two transcriptions, as `diff`
+/// reports it. Positions count segments of the first transcription from
+/// zero; an insertion's is that of the segment it goes before.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Change {
+    Substituted {
+        position: usize,
+        from: Segment,
+        to: Segment,
+    },
+    Inserted {
+        position: usize,
+        segment: Segment,
+    },
+    Deleted {
+        position: usize,
+        segment: Segment,
+    },
+    DiacriticAdded {
+        position: usize,
+        diacritic: Diacritic,
+    },
+    DiacriticRemoved {
+        position: usize,
+        diacritic: Diacritic,
+    },
+}
+
+impl Change {
+    pub const fn position(&self) -> usize {
+        match self {
+            Change::Substituted { position, .. }
+            | Change::Inserted { position, .. }
+            | Change::Deleted { position, .. }
+            | Change::DiacriticAdded { position, .. }
+            | Change::DiacriticRemoved { position, .. } => *position,
+        }
+    }
+}
+
+/// Describes the change, e.g. `"ʃ → s at position 3"` or `"ʰ deleted at
+/// position 0"`. Combining marks are shown on a dotted circle.
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mark = |f: &mut fmt::Formatter<'_>, d: &Diacritic| match d.is_combining() {
+            true => write!(f, "\u{25CC}{}", d.mark()),
+            false => write!(f, "{}", d.mark()),
+        };
+        match self {
+            Change::Substituted { position, from, to } => {
+                write!(f, "{from} → {to} at position {position}")
+            }
+            Change::Inserted { position, segment } => {
+                write!(f, "{segment} inserted at position {position}")
+            }
+            Change::Deleted { position, segment } => {
+                write!(f, "{segment} deleted at position {position}")
+            }
+            Change::DiacriticAdded {
+                position,
+                diacritic,
+            } => {
+                mark(f, diacritic)?;
+                write!(f, " added at position {position}")
+            }
+            Change::DiacriticRemoved {
+                position,
+                diacritic,
+            } => {
+                mark(f, diacritic)?;
+                write!(f, " deleted at position {position}")
+            }
+        }
+    }
+}
+
+/// The changes from `a` to `b` under the default costs.
+///
+/// ```
+/// use allo::align;
+/// use allo::parser;
+///
+/// let gold = parser::parse("tʰaʃi").unwrap();
+/// let heard = parser::parse("tasi").unwrap();
+/// let changes: Vec<String> = align::diff(&gold, &heard).iter().map(|c| c.to_string()).collect();
+/// assert_eq!(changes, ["ʰ deleted at position 0", "ʃ → s at position 2"]);
+/// ```
+pub fn diff(a: &[Segment], b: &[Segment]) -> Vec<Change> {
+    diff_with(a, b, &Costs::default())
+}
+
+/// Aligns `a` with `b` by `edit_distance` and lists the changes from one
+/// to the other in order. A substitution between segments that differ
+/// only in their diacritics is reported as each diacritic added or
+/// removed.
+pub fn diff_with(a: &[Segment], b: &[Segment], costs: &Costs) -> Vec<Change> {
+    let (_, script) = edit_distance(a, b, costs);
+    let mut changes = Vec::new();
+    let mut position = 0;
+    for edit in script {
+        match edit {
+            Edit::Keep(_) => position += 1,
+            Edit::Substitute(from, to) => {
+                substitution(position, from, to, &mut changes);
+                position += 1;
+            }
+            Edit::Insert(segment) => changes.push(Change::Inserted { position, segment }),
+            Edit::Delete(segment) => {
+                changes.push(Change::Deleted { position, segment });
+                position += 1;
+            }
+        }
+    }
+    changes
+}
+
+fn substitution(position: usize, from: Segment, to: Segment, changes: &mut Vec<Change>) {
+    let (before, after) = (from.diacritics(), to.diacritics());
+    let removed: Vec<Diacritic> = before.iter().filter(|d| !after.contains(*d)).collect();
+    let added: Vec<Diacritic> = after.iter().filter(|d| !before.contains(*d)).collect();
+    // the same segment once both have every diacritic of either
+    let union = |s: Segment, extra: &[Diacritic]| extra.iter().fold(s, |s, d| s.with_diacritic(*d));
+    if union(from, &added) != union(to, &removed) {
+        changes.push(Change::Substituted { position, from, to });
+        return;
+    }
+    changes.extend(
+        removed
+            .into_iter()
+            .map(|diacritic| Change::DiacriticRemoved {
+                position,
+                diacritic,
+            }),
+    );
+    changes.extend(added.into_iter().map(|diacritic| Change::DiacriticAdded {
+        position,
+        diacritic,
+    }));
+}
@@ -0,0 +1,141 @@
+//! Americanist phonetic notation, the alphabet of the Boasian tradition
+//! that most older descriptions of North American languages are
+//! written in (as standardized by Herzog et al. 1934).
+//!
+//! Where it differs from the IPA it mostly writes a postalveolar with a
+//! háček, an affricate with one letter and a glide with a Latin letter:
+//!
+//! | Americanist | IPA   |   | Americanist | IPA   |
+//! |-------------|-------|---|-------------|-------|
+//! | `š` `ž`     | ʃ ʒ   |   | `c` `ʒ`     | t͡s d͡z |
+//! | `č` `ǯ`     | t͡ʃ d͡ʒ |   | `ƛ` `λ`     | t͡ɬ d͡ɮ |
+//! | `ł`         | ɬ     |   | `x̣` `x̌`     | χ     |
+//! | `y`         | j     |   | `ü` `ö`     | y ø   |
+//! | `ñ`         | ɲ     |   | `·`         | ː     |
+//!
+//! An apostrophe or a comma above marks an ejective, and an ogonek a
+//! nasal vowel. Text is compared as `normalize::normalize_ipa` rewrites
+//! it, so precomposed and decomposed letters convert alike; `from_ipa`
+//! writes letters decomposed.
+//!
+//! ```
+//! use allo::americanist;
+//!
+//! assert_eq!(americanist::to_ipa("ƛ'ičaq").unwrap(), "t͡ɬʼit͡ʃaq");
+//! assert_eq!(americanist::from_ipa("jãː").unwrap(), "yą·");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::normalize;
+use crate::notation::{self, Table, UnknownSymbol};
+use crate::parser;
+use crate::segment::{ParseSegmentError, Segment};
+
+/// Americanist letters and marks and their IPA equivalents. Where
+/// several map to the same IPA, the first listed is the one produced by
+/// `from_ipa`.
+pub const TABLE: &Table = &[
+    // stops and affricates
+    ("p", "p"),
+    ("b", "b"),
+    ("t", "t"),
+    ("d", "d"),
+    ("k", "k"),
+    ("g", "ɡ"),
+    ("q", "q"),
+    ("ʔ", "ʔ"),
+    ("c", "t͡s"),
+    ("ʒ", "d͡z"),
+    ("c\u{30C}", "t͡ʃ"),
+    ("ʒ\u{30C}", "d͡ʒ"),
+    ("j\u{30C}", "d͡ʒ"),
+    ("ƛ", "t͡ɬ"),
+    ("λ", "d͡ɮ"),
+    // fricatives
+    ("f", "f"),
+    ("v", "v"),
+    ("θ", "θ"),
+    ("δ", "ð"),
+    ("ð", "ð"),
+    ("s", "s"),
+    ("z", "z"),
+    ("s\u{30C}", "ʃ"),
+    ("z\u{30C}", "ʒ"),
+    ("ł", "ɬ"),
+    ("x", "x"),
+    ("x\u{323}", "χ"),
+    ("x\u{30C}", "χ"),
+    ("ɣ", "ɣ"),
+    ("h", "h"),
+    ("h\u{323}", "ħ"),
+    ("ʕ", "ʕ"),
+    // sonorants
+    ("m", "m"),
+    ("n", "n"),
+    ("n\u{303}", "ɲ"),
+    ("ŋ", "ŋ"),
+    ("l", "l"),
+    ("r", "r"),
+    ("w", "w"),
+    ("y", "j"),
+    // vowels
+    ("i", "i"),
+    ("e", "e"),
+    ("ɛ", "ɛ"),
+    ("ε", "ɛ"),
+    ("æ", "æ"),
+    ("a", "a"),
+    ("ɨ", "ɨ"),
+    ("ə", "ə"),
+    ("u", "u"),
+    ("o", "o"),
+    ("ɔ", "ɔ"),
+    ("u\u{308}", "y"),
+    ("o\u{308}", "ø"),
+    ("ą", "a\u{303}"),
+    ("ę", "e\u{303}"),
+    ("į", "i\u{303}"),
+    ("ǫ", "o\u{303}"),
+    ("ų", "u\u{303}"),
+    // marks
+    ("ʼ", "ʼ"),
+    ("'", "ʼ"),
+    ("\u{313}", "ʼ"),
+    ("\u{315}", "ʼ"),
+    ("ʷ", "ʷ"),
+    ("·", "ː"),
+    (":", "ː"),
+    ("\u{328}", "\u{303}"),
+    ("\u{303}", "\u{303}"),
+    ("\u{301}", "\u{301}"),
+    ("\u{300}", "\u{300}"),
+];
+
+/// Converts an Americanist transcription to IPA.
+pub fn to_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    let (normalized, starts) = normalize::normalize_mapped(s);
+    notation::to_ipa(&normalized, TABLE).map_err(|e| UnknownSymbol {
+        offset: normalize::original_offset(&starts, e.offset),
+        ..e
+    })
+}
+
+/// Converts an IPA transcription to Americanist notation.
+pub fn from_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    notation::from_ipa(s, TABLE)
+}
+
+/// Parses an Americanist transcription directly into segments.
+pub fn to_segments(s: &str) -> Result<Vec<Segment>, ParseSegmentError> {
+    parser::parse(&to_ipa(s)?)
+}
+
+/// Writes `segments` in Americanist notation.
+pub fn from_segments(segments: &[Segment]) -> Result<String, UnknownSymbol> {
+    let ipa: String = segments.iter().map(Segment::to_string).collect();
+    from_ipa(&ipa)
+}
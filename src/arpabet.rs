@@ -0,0 +1,178 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::parser;
+use crate::segment::{ParseSegmentError, Segment};
+use crate::suprasegmentals::{Stress, Suprasegmental};
+
+/// ARPAbet phone codes and their (General American) IPA equivalents.
+///
+/// Vowel codes are listed without their stress digit; see `stress`.
+/// Diphthongs map to two IPA segments. Where several
+/// codes share an IPA spelling, `from_segments` prefers the codes
+/// used by CMUdict.
+pub const TABLE: [(&str, &str); 50] = [
+    // vowels
+    ("AA", "ɑ"),
+    ("AE", "æ"),
+    ("AH", "ʌ"),
+    ("AO", "ɔ"),
+    ("AW", "aʊ"),
+    ("AX", "ə"),
+    ("AXR", "ə\u{2DE}"),
+    ("AY", "aɪ"),
+    ("EH", "ɛ"),
+    ("ER", "ɜ\u{2DE}"),
+    ("EY", "eɪ"),
+    ("IH", "ɪ"),
+    ("IX", "ɨ"),
+    ("IY", "i"),
+    ("OW", "oʊ"),
+    ("OY", "ɔɪ"),
+    ("UH", "ʊ"),
+    ("UW", "u"),
+    ("UX", "ʉ"),
+    // consonants
+    ("B", "b"),
+    ("CH", "t͡ʃ"),
+    ("D", "d"),
+    ("DH", "ð"),
+    ("DX", "ɾ"),
+    ("EL", "l\u{329}"),
+    ("EM", "m\u{329}"),
+    ("EN", "n\u{329}"),
+    ("F", "f"),
+    ("G", "ɡ"),
+    ("HH", "h"),
+    ("JH", "d͡ʒ"),
+    ("K", "k"),
+    ("L", "l"),
+    ("M", "m"),
+    ("N", "n"),
+    ("NG", "ŋ"),
+    ("NX", "ɾ\u{303}"),
+    ("P", "p"),
+    ("Q", "ʔ"),
+    ("R", "ɹ"),
+    ("S", "s"),
+    ("SH", "ʃ"),
+    ("T", "t"),
+    ("TH", "θ"),
+    ("V", "v"),
+    ("W", "w"),
+    ("WH", "ʍ"),
+    ("Y", "j"),
+    ("Z", "z"),
+    ("ZH", "ʒ"),
+];
+
+/// Unstressed variants of `AH` and `ER`, which are conventionally
+/// transcribed with different symbols.
+const UNSTRESSED: [(&str, &str); 2] = [("AH", "ə"), ("ER", "ə\u{2DE}")];
+
+/// Splits a phone code into its base code and stress digit, e.g.
+/// `"IY1"` into `("IY", Some(1))`.
+pub fn split_stress(phone: &str) -> (&str, Option<u8>) {
+    match phone.as_bytes().last() {
+        Some(d @ b'0'..=b'2') => (&phone[..phone.len() - 1], Some(d - b'0')),
+        _ => (phone, None),
+    }
+}
+
+/// The stress mark for a CMUdict-style stress digit: `1` is primary,
+/// `2` secondary, and `0` unstressed.
+pub fn stress(digit: u8) -> Option<Stress> {
+    match digit {
+        1 => Some(Stress::Primary),
+        2 => Some(Stress::Secondary),
+        _ => None,
+    }
+}
+
+/// The IPA for a single phone code, stress digit included.
+pub fn phone_to_ipa(phone: &str) -> Option<&'static str> {
+    let upper = phone.to_ascii_uppercase();
+    let (code, digit) = split_stress(&upper);
+    if digit == Some(0) {
+        if let Some((_, ipa)) = UNSTRESSED.iter().find(|(c, _)| *c == code) {
+            return Some(ipa);
+        }
+    }
+    TABLE.iter().find(|(c, _)| *c == code).map(|(_, ipa)| *ipa)
+}
+
+/// Converts whitespace-separated ARPAbet phones to IPA segments, e.g.
+/// `"HH AH0 L OW1"` to `həlˈoʊ`.
+///
+/// Stress marks are placed directly before the stressed vowel, as
+/// ARPAbet carries no syllable boundaries.
+pub fn to_segments(s: &str) -> Result<Vec<Segment>, ParseSegmentError> {
+    let mut segments = Vec::new();
+    for phone in s.split_whitespace() {
+        let ipa =
+            phone_to_ipa(phone).ok_or_else(|| ParseSegmentError::Unknown(phone.to_string()))?;
+        if let Some(mark) = split_stress(phone).1.and_then(stress) {
+            segments.push(Segment::Suprasegmental(Suprasegmental::Stress(mark)));
+        }
+        segments.extend(parser::parse(ipa)?);
+    }
+    Ok(segments)
+}
+
+/// Converts ARPAbet phones to an IPA string.
+pub fn to_ipa(s: &str) -> Result<String, ParseSegmentError> {
+    Ok(to_segments(s)?.iter().map(Segment::to_string).collect())
+}
+
+/// Converts IPA segments back to space-separated ARPAbet phones.
+///
+/// Every vowel gets a stress digit, taken from a stress mark directly
+/// before it, and `0` otherwise. Diphthongs and affricates are matched
+/// before single segments.
+pub fn from_segments(segments: &[Segment]) -> Result<String, ParseSegmentError> {
+    let rendered: Vec<String> = segments.iter().map(Segment::to_string).collect();
+    let mut phones = Vec::new();
+    let mut pending: Option<Stress> = None;
+    let mut i = 0;
+    while i < segments.len() {
+        if let Segment::Suprasegmental(Suprasegmental::Stress(mark)) = segments[i] {
+            pending = Some(mark);
+            i += 1;
+            continue;
+        }
+        let pair = rendered
+            .get(i + 1)
+            .map(|next| format!("{}{}", rendered[i], next));
+        let (code, width) = pair
+            .as_deref()
+            .and_then(code_for)
+            .map(|code| (code, 2))
+            .or_else(|| code_for(&rendered[i]).map(|code| (code, 1)))
+            .ok_or_else(|| ParseSegmentError::Unknown(rendered[i].clone()))?;
+        if segments[i].is_vowel() {
+            let digit = match pending.take() {
+                Some(Stress::Primary) => '1',
+                Some(Stress::Secondary) => '2',
+                None => '0',
+            };
+            phones.push(format!("{}{}", code, digit));
+        } else {
+            phones.push(code.to_string());
+        }
+        i += width;
+    }
+    Ok(phones.join(" "))
+}
+
+/// The code for `ipa`, which may spell an affricate with or without
+/// its tie bar.
+fn code_for(ipa: &str) -> Option<&'static str> {
+    UNSTRESSED
+        .iter()
+        .chain(TABLE.iter())
+        .find(|(_, i)| *i == ipa || i.replace(parser::TIE_BARS, "") == ipa)
+        .map(|(code, _)| *code)
+}
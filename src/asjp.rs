@@ -0,0 +1,266 @@
+//! ASJPcode, the 41-symbol alphabet of the Automated Similarity
+//! Judgment Program (Brown et al. 2008): 7 vowels and 34 consonants,
+//! each standing for a class of sounds, with four modifiers:
+//!
+//! * `*` after a symbol nasalizes it,
+//! * `"` after a symbol glottalizes it,
+//! * `~` after two symbols and `$` after three make them one sound, as
+//!   `tw~` for `tʷ`.
+//!
+//! Writing segments in ASJPcode keeps only what it distinguishes, so
+//! `ɪ` and `y` are both `i`; reading it gives each symbol a typical
+//! member of its class.
+//!
+//! ```
+//! use allo::asjp;
+//!
+//! let segments = allo::parser::parse("ʃɪpʷ").unwrap();
+//! assert_eq!(asjp::from_segments(&segments), "Sipw~");
+//! assert_eq!(asjp::to_ipa("Sipw~").unwrap(), "ʃipʷ");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::diacritics::Diacritic;
+use crate::ipa::{
+    Airstream, Articulation, Backness, Consonant, Height, Manner, SecondaryArticulation, Vowel,
+};
+use crate::notation::UnknownSymbol;
+use crate::parser;
+use crate::segment::{ParseSegmentError, Segment};
+
+/// The ASJP symbols and the IPA each is read as.
+pub const SYMBOLS: [(char, &str); 41] = [
+    // vowels
+    ('i', "i"),
+    ('e', "e"),
+    ('E', "æ"),
+    ('3', "ə"),
+    ('a', "a"),
+    ('u', "u"),
+    ('o', "o"),
+    // consonants
+    ('p', "p"),
+    ('b', "b"),
+    ('m', "m"),
+    ('f', "f"),
+    ('v', "v"),
+    ('8', "θ"),
+    ('4', "n\u{32A}"),
+    ('t', "t"),
+    ('d', "d"),
+    ('s', "s"),
+    ('z', "z"),
+    ('c', "t͡s"),
+    ('n', "n"),
+    ('S', "ʃ"),
+    ('Z', "ʒ"),
+    ('C', "t͡ʃ"),
+    ('j', "d͡ʒ"),
+    ('T', "c"),
+    ('5', "ɲ"),
+    ('k', "k"),
+    ('g', "ɡ"),
+    ('x', "x"),
+    ('N', "ŋ"),
+    ('q', "q"),
+    ('G', "ɢ"),
+    ('X', "χ"),
+    ('7', "ʔ"),
+    ('h', "h"),
+    ('l', "l"),
+    ('L', "ʎ"),
+    ('w', "w"),
+    ('y', "j"),
+    ('r', "r"),
+    ('!', "ǃ"),
+];
+
+/// Reads ASJPcode as IPA. Whitespace is copied through unchanged.
+pub fn to_ipa(code: &str) -> Result<String, UnknownSymbol> {
+    let mut out = String::new();
+    // the IPA of each symbol of the current word
+    let mut symbols: Vec<String> = Vec::new();
+    for (offset, c) in code.char_indices() {
+        let error = UnknownSymbol { offset, symbol: c };
+        match c {
+            c if c.is_whitespace() => {
+                out.extend(symbols.drain(..));
+                out.push(c);
+            }
+            '*' | '"' => {
+                let last = symbols.last_mut().ok_or(error)?;
+                last.push(if c == '*' { '\u{303}' } else { 'ʼ' });
+            }
+            '~' | '$' => {
+                let joined = if c == '~' { 2 } else { 3 };
+                let parts = symbols
+                    .len()
+                    .checked_sub(joined)
+                    .map(|start| symbols.split_off(start))
+                    .ok_or(error)?;
+                let sound = parts.into_iter().reduce(|a, b| join(&a, &b)).unwrap();
+                symbols.push(sound);
+            }
+            c => {
+                let (_, ipa) = SYMBOLS.iter().find(|(s, _)| *s == c).ok_or(error)?;
+                symbols.push(ipa.to_string());
+            }
+        }
+    }
+    out.extend(symbols);
+    Ok(out)
+}
+
+/// Two sounds written as one: a glide or `h` after another sound as its
+/// superscript, anything else tied.
+fn join(a: &str, b: &str) -> String {
+    let mark = match b {
+        "w" => "ʷ",
+        "j" => "ʲ",
+        "h" => "ʰ",
+        "ʔ" => "ˀ",
+        _ => return [a, "\u{361}", b].concat(),
+    };
+    [a, mark].concat()
+}
+
+/// Reads ASJPcode directly into segments.
+pub fn to_segments(code: &str) -> Result<Vec<Segment>, ParseSegmentError> {
+    parser::parse(&to_ipa(code)?)
+}
+
+/// Writes IPA in ASJPcode, word by word.
+pub fn from_ipa(s: &str) -> Result<String, ParseSegmentError> {
+    let words: Vec<String> = s
+        .split_whitespace()
+        .map(|word| Ok(from_segments(&parser::parse(word)?)))
+        .collect::<Result<_, ParseSegmentError>>()?;
+    Ok(words.join(" "))
+}
+
+/// Writes `segments` in ASJPcode. Stress, length, tone and other
+/// suprasegmentals are left out, as ASJPcode has no symbols for them.
+pub fn from_segments(segments: &[Segment]) -> String {
+    let mut out = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Vowel(v) => out.push_str(&vowel(v)),
+            Segment::Consonant(c) => {
+                out.push(symbol(c));
+                modifiers(c, &mut out);
+            }
+            Segment::Affricate(a) => {
+                let (stop, fricative) = (a.stop(), a.fricative());
+                let sibilant = matches!(fricative.manner(), Manner::Fricative { sibilant: true });
+                let voiced = stop.phonation().is_voiced();
+                match fricative.articulation() {
+                    Articulation::Alveolar if sibilant => out.push('c'),
+                    Articulation::Postalveolar
+                    | Articulation::Retroflex
+                    | Articulation::Palatal
+                        if sibilant =>
+                    {
+                        out.push(if voiced { 'j' } else { 'C' })
+                    }
+                    _ => {
+                        out.push(symbol(&stop));
+                        out.push(symbol(&fricative));
+                        out.push('~');
+                    }
+                }
+                // spacing marks go on the fricative half
+                modifiers(&fricative, &mut out);
+            }
+            Segment::Suprasegmental(_) | Segment::Tone(_) => {}
+        }
+    }
+    out
+}
+
+fn vowel(v: &Vowel) -> String {
+    let open = matches!(v.height(), Height::NearOpen | Height::Open);
+    let close = matches!(v.height(), Height::Close | Height::NearClose);
+    let symbol = match v.backness() {
+        Backness::Front | Backness::NearFront if close => 'i',
+        Backness::Front if v.height() == Height::Open => 'a',
+        Backness::Front | Backness::NearFront if open => 'E',
+        Backness::Front | Backness::NearFront => 'e',
+        Backness::Central if open => 'a',
+        Backness::Central => '3',
+        Backness::NearBack | Backness::Back if close => 'u',
+        Backness::NearBack | Backness::Back if open => 'a',
+        Backness::NearBack | Backness::Back => 'o',
+    };
+    let mut out = symbol.to_string();
+    if v.diacritics().contains(Diacritic::Nasalized) {
+        out.push('*');
+    }
+    out
+}
+
+/// The symbol for the class of `c`.
+fn symbol(c: &Consonant) -> char {
+    use Articulation::*;
+    let voiced = c.phonation().is_voiced();
+    let pick = |voiceless: char, voiced_: char| if voiced { voiced_ } else { voiceless };
+    let dental = c.diacritics().contains(Diacritic::Dental);
+    match (c.manner(), c.articulation()) {
+        _ if c.airstream() == Airstream::VelaricIngressive => '!',
+        (Manner::Nasal, Bilabial | Labiodental) => 'm',
+        (Manner::Nasal, Dental | Linguolabial) => '4',
+        (Manner::Nasal, Alveolar) if dental => '4',
+        (Manner::Nasal, Palatal) => '5',
+        (Manner::Nasal, Velar | Uvular) => 'N',
+        (Manner::Nasal, _) => 'n',
+        (Manner::Plosive, Bilabial | Labiodental) => pick('p', 'b'),
+        (Manner::Plosive, Palatal) => 'T',
+        (Manner::Plosive, Velar) => pick('k', 'g'),
+        (Manner::Plosive, Uvular) => pick('q', 'G'),
+        (Manner::Plosive, Pharyngeal | Epiglottal | Glottal) => '7',
+        (Manner::Plosive, _) => pick('t', 'd'),
+        (Manner::Fricative { .. }, Bilabial) => pick('p', 'b'),
+        (Manner::Fricative { .. }, Labiodental) => pick('f', 'v'),
+        (Manner::Fricative { sibilant: true }, Alveolar) => pick('s', 'z'),
+        (Manner::Fricative { sibilant: true }, Dental) if dental => pick('s', 'z'),
+        (Manner::Fricative { .. }, Linguolabial | Dental | Alveolar) => '8',
+        (Manner::Fricative { sibilant: true }, Palatal)
+        | (Manner::Fricative { .. }, Postalveolar | Retroflex) => pick('S', 'Z'),
+        (Manner::Fricative { .. }, Palatal | Velar) => 'x',
+        (Manner::Fricative { .. }, Uvular | Pharyngeal | Epiglottal) => 'X',
+        (Manner::Fricative { .. }, Glottal) => 'h',
+        (Manner::LatApprox, Dental | Alveolar) if voiced => 'l',
+        (Manner::LatFric | Manner::LatApprox | Manner::LatTapFlap, _) => 'L',
+        (Manner::Approximant, Bilabial) => 'w',
+        (Manner::Approximant, Labiodental) => 'v',
+        (Manner::Approximant, Palatal) => 'y',
+        (Manner::Approximant, Velar) => 'w',
+        (Manner::Approximant, Glottal) => 'h',
+        (Manner::Approximant | Manner::Trill | Manner::TapFlap, _) => 'r',
+    }
+}
+
+/// Appends the modifiers for what `c` has beyond its class.
+fn modifiers(c: &Consonant, out: &mut String) {
+    if c.airstream() == Airstream::VelaricIngressive {
+        return;
+    }
+    match c.secondary() {
+        Some(SecondaryArticulation::Labialized) => out.push_str("w~"),
+        Some(SecondaryArticulation::Palatalized) => out.push_str("y~"),
+        Some(SecondaryArticulation::Glottalized) => out.push('"'),
+        _ => {}
+    }
+    if c.diacritics().contains(Diacritic::Aspirated) {
+        out.push_str("h~");
+    }
+    if !c.airstream().is_pulmonic() {
+        out.push('"');
+    }
+    if c.diacritics().contains(Diacritic::Nasalized) {
+        out.push('*');
+    }
+}
@@ -0,0 +1,248 @@
+//! IPA charts built from the crate's own tables.
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+use crate::ipa::{self, Articulation, Backness, Consonant, Height, Manner, Phonation, Roundedness};
+use crate::parser;
+
+/// A chart of symbols in rows and columns, each cell holding a pair:
+/// voiceless and voiced for consonants, unrounded and rounded for
+/// vowels.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Chart {
+    /// `"consonants"` or `"vowels"`.
+    kind: &'static str,
+    /// What the two halves of a cell are.
+    pair: [&'static str; 2],
+    columns: Vec<&'static str>,
+    rows: Vec<&'static str>,
+    /// Row-major, `columns.len()` cells per row.
+    cells: Vec<[Option<&'static str>; 2]>,
+}
+
+/// The pulmonic consonant rows, in chart order. Sibilant and
+/// non-sibilant fricatives share a row.
+const MANNERS: [(&str, Manner); 9] = [
+    ("plosive", Manner::Plosive),
+    ("nasal", Manner::Nasal),
+    ("trill", Manner::Trill),
+    ("tap or flap", Manner::TapFlap),
+    ("fricative", Manner::Fricative { sibilant: false }),
+    ("lateral fricative", Manner::LatFric),
+    ("approximant", Manner::Approximant),
+    ("lateral approximant", Manner::LatApprox),
+    ("lateral tap or flap", Manner::LatTapFlap),
+];
+
+impl Chart {
+    /// The pulmonic consonant chart: a column per articulation and a
+    /// row per manner, leaving out columns with no symbols.
+    pub fn consonants() -> Self {
+        let row = |m: Manner| {
+            MANNERS
+                .iter()
+                .position(|(_, r)| match (r, m) {
+                    (Manner::Fricative { .. }, Manner::Fricative { .. }) => true,
+                    _ => *r == m,
+                })
+                .unwrap()
+        };
+        let column = |a: Articulation| Articulation::ALL.iter().position(|c| *c == a).unwrap();
+        let mut grid = vec![[None; 2]; MANNERS.len() * Articulation::ALL.len()];
+        for (grapheme, c) in ipa::PULMONIC {
+            let cell =
+                &mut grid[row(c.manner()) * Articulation::ALL.len() + column(c.articulation())];
+            let slot = match c.phonation() {
+                Phonation::Voiceless => 0,
+                _ => 1,
+            };
+            // Where two symbols share a cell, as sibilant `ɕ` and
+            // non-sibilant `ç` do, the one with fewer marks wins, then
+            // the non-sibilant.
+            let key = |g: &str, c: &Consonant| {
+                (
+                    g.chars().count(),
+                    c.manner() == Manner::Fricative { sibilant: true },
+                )
+            };
+            match cell[slot] {
+                Some((g, old)) if key(g, &old) <= key(grapheme, &c) => {}
+                _ => cell[slot] = Some((grapheme, c)),
+            }
+        }
+
+        let used: Vec<usize> = (0..Articulation::ALL.len())
+            .filter(|&a| {
+                (0..MANNERS.len()).any(|m| grid[m * Articulation::ALL.len() + a] != [None, None])
+            })
+            .collect();
+        Chart {
+            kind: "consonants",
+            pair: ["voiceless", "voiced"],
+            columns: used.iter().map(|&a| Articulation::ALL[a].name()).collect(),
+            rows: MANNERS.iter().map(|(name, _)| *name).collect(),
+            cells: (0..MANNERS.len())
+                .flat_map(|m| used.iter().map(move |&a| (m, a)))
+                .map(|(m, a)| grid[m * Articulation::ALL.len() + a].map(|s| s.map(|(g, _)| g)))
+                .collect(),
+        }
+    }
+
+    /// The vowel chart: a column per backness and a row per height.
+    pub fn vowels() -> Self {
+        let mut cells = vec![[None; 2]; Height::ALL.len() * Backness::ALL.len()];
+        for (grapheme, v) in ipa::VOWELS {
+            let row = Height::ALL.iter().position(|h| *h == v.height()).unwrap();
+            let column = Backness::ALL
+                .iter()
+                .position(|b| *b == v.backness())
+                .unwrap();
+            let slot = match v.roundedness() {
+                Roundedness::Unrounded => 0,
+                Roundedness::Rounded => 1,
+            };
+            let cell: &mut [Option<&str>; 2] = &mut cells[row * Backness::ALL.len() + column];
+            if cell[slot].is_none_or(|g: &str| g.chars().count() > grapheme.chars().count()) {
+                cell[slot] = Some(grapheme);
+            }
+        }
+        Chart {
+            kind: "vowels",
+            pair: ["unrounded", "rounded"],
+            columns: Backness::ALL.iter().map(Backness::name).collect(),
+            rows: Height::ALL.iter().map(Height::name).collect(),
+            cells,
+        }
+    }
+
+    pub fn columns(&self) -> &[&'static str] {
+        &self.columns
+    }
+
+    pub fn rows(&self) -> &[&'static str] {
+        &self.rows
+    }
+
+    /// The pair of symbols at `row` and `column`.
+    pub fn cell(&self, row: usize, column: usize) -> [Option<&'static str>; 2] {
+        self.cells[row * self.columns.len() + column]
+    }
+
+    /// Renders the chart as a plain-text grid, each cell holding its
+    /// pair of symbols at either edge:
+    ///
+    /// ```text
+    ///         | bilabial | labiodental | ...
+    /// --------+----------+-------------+
+    /// plosive | p      b |             |
+    /// ```
+    pub fn render_ascii(&self) -> String {
+        let label_width = self.rows.iter().map(|r| width(r)).max().unwrap_or(0);
+        let widths: Vec<usize> = self.columns.iter().map(|c| width(c).max(3)).collect();
+        let mut out = String::new();
+
+        out.push_str(&pad(" ", label_width));
+        for (column, w) in self.columns.iter().zip(&widths) {
+            out.push_str(" | ");
+            out.push_str(&pad(column, *w));
+        }
+        newline(&mut out);
+        out.push_str(&"-".repeat(label_width));
+        for w in &widths {
+            out.push_str("-+-");
+            out.push_str(&"-".repeat(*w));
+        }
+        out.push('\n');
+
+        for (r, row) in self.rows.iter().enumerate() {
+            out.push_str(&pad(row, label_width));
+            for (c, w) in widths.iter().enumerate() {
+                let [left, right] = self.cell(r, c);
+                let (left, right) = (left.unwrap_or(""), right.unwrap_or(""));
+                out.push_str(" | ");
+                out.push_str(left);
+                out.push_str(&" ".repeat(w - width(left) - width(right)));
+                out.push_str(right);
+            }
+            newline(&mut out);
+        }
+        out
+    }
+
+    /// Renders the chart as an HTML `<table>`. The table has the
+    /// classes `ipa-chart` and `ipa-consonants` or `ipa-vowels`; row
+    /// and column headers carry their name as a class (`plosive`,
+    /// `close-mid`), as does every cell, and the two symbols of a cell
+    /// are `<span>`s classed by their half of the pair (`voiceless`
+    /// and `voiced`, or `unrounded` and `rounded`). Empty cells have
+    /// the class `empty`.
+    pub fn render_html(&self) -> String {
+        let mut out = format!("<table class=\"ipa-chart ipa-{}\">\n", self.kind);
+        out.push_str("<thead>\n<tr><td></td>");
+        for column in &self.columns {
+            let class = class(column);
+            out.push_str(&format!(
+                "<th scope=\"col\" class=\"{}\">{}</th>",
+                class, column
+            ));
+        }
+        out.push_str("</tr>\n</thead>\n<tbody>\n");
+        for (r, row) in self.rows.iter().enumerate() {
+            let row_class = class(row);
+            out.push_str(&format!(
+                "<tr><th scope=\"row\" class=\"{}\">{}</th>",
+                row_class, row
+            ));
+            for (c, column) in self.columns.iter().enumerate() {
+                let cell = self.cell(r, c);
+                let empty = if cell == [None, None] { " empty" } else { "" };
+                out.push_str(&format!(
+                    "<td class=\"{} {}{}\">",
+                    row_class,
+                    class(column),
+                    empty
+                ));
+                for (symbol, half) in cell.iter().zip(self.pair) {
+                    if let Some(symbol) = symbol {
+                        out.push_str(&format!(
+                            "<span class=\"{}\">{}</span>",
+                            half,
+                            escape(symbol)
+                        ));
+                    }
+                }
+                out.push_str("</td>");
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</tbody>\n</table>\n");
+        out
+    }
+}
+
+/// A header name as a CSS class, e.g. `tap or flap` to `tap-or-flap`.
+fn class(name: &str) -> String {
+    name.replace(' ', "-")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The number of columns `s` takes up in a terminal, not counting
+/// combining marks.
+fn width(s: &str) -> usize {
+    s.chars().filter(|c| !parser::is_combining(*c)).count()
+}
+
+/// Ends a line of `out`, dropping trailing spaces.
+fn newline(out: &mut String) {
+    out.truncate(out.trim_end_matches(' ').len());
+    out.push('\n');
+}
+
+fn pad(s: &str, w: usize) -> String {
+    format!("{}{}", s, " ".repeat(w - width(s)))
+}
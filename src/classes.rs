@@ -0,0 +1,242 @@
+use core::ops::{BitAnd, BitOr, Not};
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::features::{Feature, FeatureBundle};
+use crate::ipa::{self, Articulation, Manner, Place};
+use crate::segment::Segment;
+
+/// A class of segments, built from the predicates in this module and
+/// combined with `&`, `|`, and `!`:
+///
+/// ```
+/// use allo::classes::*;
+///
+/// let nonsibilant_voiced_fricatives: Vec<_> = Segments::all()
+///     .filter(voiced() & fricative() & !sibilant())
+///     .collect();
+/// assert!(nonsibilant_voiced_fricatives.iter().any(|s| s.to_string() == "ɣ"));
+/// ```
+#[derive(Clone, Debug)]
+pub enum Class {
+    /// Every segment.
+    Any,
+    /// Segments whose features are subsumed by the bundle.
+    Features(FeatureBundle),
+    Place(Place),
+    Articulation(Articulation),
+    Manner(Manner),
+    Predicate(fn(&Segment) -> bool),
+    /// Exactly these segments, diacritics included.
+    Segments(Vec<Segment>),
+    Not(Box<Class>),
+    And(Box<Class>, Box<Class>),
+    Or(Box<Class>, Box<Class>),
+}
+
+impl Class {
+    pub fn matches(&self, segment: &Segment) -> bool {
+        let consonant = segment.as_consonant();
+        match self {
+            Class::Any => true,
+            Class::Features(bundle) => segment.features().is_some_and(|f| bundle.subsumes(&f)),
+            Class::Place(place) => consonant.is_some_and(|c| c.place() == *place),
+            Class::Articulation(a) => consonant.is_some_and(|c| c.articulation() == *a),
+            Class::Manner(m) => consonant.is_some_and(|c| c.manner() == *m),
+            Class::Predicate(p) => p(segment),
+            Class::Segments(segments) => segments.contains(segment),
+            Class::Not(class) => !class.matches(segment),
+            Class::And(a, b) => a.matches(segment) && b.matches(segment),
+            Class::Or(a, b) => a.matches(segment) || b.matches(segment),
+        }
+    }
+}
+
+impl BitAnd for Class {
+    type Output = Class;
+
+    fn bitand(self, rhs: Class) -> Class {
+        Class::And(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl BitOr for Class {
+    type Output = Class;
+
+    fn bitor(self, rhs: Class) -> Class {
+        Class::Or(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl Not for Class {
+    type Output = Class;
+
+    fn not(self) -> Class {
+        Class::Not(Box::new(self))
+    }
+}
+
+impl From<FeatureBundle> for Class {
+    fn from(bundle: FeatureBundle) -> Self {
+        Class::Features(bundle)
+    }
+}
+
+/// The segments listed, as a class.
+pub fn one_of(segments: impl IntoIterator<Item = Segment>) -> Class {
+    Class::Segments(segments.into_iter().collect())
+}
+
+/// The class a built-in category stands for in rule and pattern
+/// notation: `V` for vowels, `C` for consonants, `N` for nasals and
+/// `S` for sibilants.
+pub fn named(name: &str) -> Option<Class> {
+    match name {
+        "V" => Some(vowel()),
+        "C" => Some(consonant()),
+        "N" => Some(nasal()),
+        "S" => Some(sibilant()),
+        _ => None,
+    }
+}
+
+/// Segments with `feature` set to `value`.
+pub fn feature(feature: Feature, value: bool) -> Class {
+    Class::Features(FeatureBundle::new().with(feature, value))
+}
+
+pub fn consonant() -> Class {
+    Class::Predicate(Segment::is_consonant)
+}
+
+pub fn vowel() -> Class {
+    Class::Predicate(Segment::is_vowel)
+}
+
+pub fn voiced() -> Class {
+    feature(Feature::Voice, true)
+}
+
+pub fn voiceless() -> Class {
+    feature(Feature::Voice, false)
+}
+
+pub fn obstruent() -> Class {
+    feature(Feature::Sonorant, false)
+}
+
+pub fn sonorant() -> Class {
+    feature(Feature::Sonorant, true)
+}
+
+pub fn plosive() -> Class {
+    Class::Manner(Manner::Plosive)
+}
+
+pub fn nasal() -> Class {
+    Class::Manner(Manner::Nasal)
+}
+
+/// Central and lateral fricatives, sibilant or not.
+pub fn fricative() -> Class {
+    Class::Predicate(|s| {
+        s.as_consonant()
+            .is_some_and(|c| matches!(c.manner(), Manner::Fricative { .. } | Manner::LatFric))
+    })
+}
+
+pub fn sibilant() -> Class {
+    Class::Manner(Manner::Fricative { sibilant: true })
+}
+
+pub fn lateral() -> Class {
+    feature(Feature::Lateral, true)
+}
+
+pub fn labial() -> Class {
+    Class::Place(Place::Labial)
+}
+
+pub fn coronal() -> Class {
+    Class::Place(Place::Corona)
+}
+
+pub fn dorsal() -> Class {
+    Class::Place(Place::Dorsal)
+}
+
+pub fn laryngeal() -> Class {
+    Class::Place(Place::Laryngeal)
+}
+
+pub fn place(place: Place) -> Class {
+    Class::Place(place)
+}
+
+pub fn articulation(articulation: Articulation) -> Class {
+    Class::Articulation(articulation)
+}
+
+pub fn manner(manner: Manner) -> Class {
+    Class::Manner(manner)
+}
+
+/// A collection of segments to query, by default the whole IPA chart.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Segments(Vec<Segment>);
+
+impl Segments {
+    /// Every consonant and vowel with a cell in the IPA chart, in
+    /// chart order: pulmonic consonants, clicks, implosives, the
+    /// doubly articulated `w ʍ ɥ ɧ`, vowels.
+    pub fn all() -> Self {
+        let mut all = Segments::consonants();
+        all.0.extend(Segments::vowels().0);
+        all
+    }
+
+    pub fn consonants() -> Self {
+        Segments(ipa::consonants().map(Segment::Consonant).collect())
+    }
+
+    pub fn vowels() -> Self {
+        Segments(
+            ipa::VOWELS
+                .iter()
+                .map(|(_, v)| Segment::Vowel(*v))
+                .collect(),
+        )
+    }
+
+    /// The segments belonging to `class`.
+    pub fn filter(&self, class: Class) -> impl Iterator<Item = Segment> + '_ {
+        self.0.iter().copied().filter(move |s| class.matches(s))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Segment> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<Segment> for Segments {
+    fn from_iter<I: IntoIterator<Item = Segment>>(iter: I) -> Self {
+        Segments(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Segments {
+    type Item = Segment;
+    type IntoIter = alloc::vec::IntoIter<Segment>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
@@ -0,0 +1,164 @@
+//! The CMU Pronouncing Dictionary: a lexicon of ARPAbet pronunciations
+//! in the plain-text format CMUdict is distributed in, one word to a
+//! line followed by its phones.
+//!
+//! ```text
+//! ;;; a comment
+//! HELLO  HH AH0 L OW1
+//! HELLO(2)  HH EH0 L OW1
+//! ```
+//!
+//! Alternative pronunciations are marked with a number in parentheses
+//! and kept in the order they are given. Words are looked up without
+//! regard to case; lines starting with `;;;` or `#` and anything after
+//! a `#` on a line are comments, as in both the classic and the current
+//! releases.
+//!
+//! ```
+//! use allo::cmudict::Lexicon;
+//!
+//! let lexicon: Lexicon = "HELLO  HH AH0 L OW1\nHELLO(2)  HH EH0 L OW1".parse().unwrap();
+//! assert_eq!(lexicon.arpabet("hello"), ["HH AH0 L OW1", "HH EH0 L OW1"]);
+//! let ipa: String = lexicon.ipa("Hello").unwrap().iter().map(|s| s.to_string()).collect();
+//! assert_eq!(ipa, "həlˈoʊ");
+//! ```
+
+use core::fmt;
+use core::str::FromStr;
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::arpabet;
+use crate::error::LineError;
+use crate::g2p::{Dictionary, Transcriber};
+use crate::segment::{ParseSegmentError, Segment};
+
+/// Words and their ARPAbet pronunciations.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Lexicon {
+    /// Lowercased words, each with its pronunciations as
+    /// space-separated phones.
+    words: BTreeMap<String, Vec<String>>,
+}
+
+impl Lexicon {
+    pub fn new() -> Self {
+        Lexicon::default()
+    }
+
+    /// Adds a pronunciation of `word` after any it already has, or an
+    /// error if a phone is not ARPAbet.
+    pub fn insert(&mut self, word: &str, phones: &str) -> Result<(), ParseSegmentError> {
+        let phones: Vec<&str> = phones.split_whitespace().collect();
+        if let Some(phone) = phones.iter().find(|p| arpabet::phone_to_ipa(p).is_none()) {
+            return Err(ParseSegmentError::Unknown(phone.to_string()));
+        }
+        self.words
+            .entry(word.to_lowercase())
+            .or_default()
+            .push(phones.join(" "));
+        Ok(())
+    }
+
+    /// The number of words, not counting alternative pronunciations.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// The words, lowercased and in alphabetical order.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.keys().map(String::as_str)
+    }
+
+    /// The pronunciations of `word` in ARPAbet, the first the
+    /// dictionary's main one.
+    pub fn arpabet(&self, word: &str) -> &[String] {
+        self.words.get(&word.to_lowercase()).map_or(&[], |p| &p[..])
+    }
+
+    /// The pronunciations of `word` as IPA segments.
+    pub fn pronunciations(&self, word: &str) -> Vec<Vec<Segment>> {
+        self.arpabet(word)
+            .iter()
+            .filter_map(|p| arpabet::to_segments(p).ok())
+            .collect()
+    }
+
+    /// The main pronunciation of `word` as IPA segments.
+    pub fn ipa(&self, word: &str) -> Option<Vec<Segment>> {
+        arpabet::to_segments(self.arpabet(word).first()?).ok()
+    }
+
+    /// A pronouncing dictionary of the main pronunciation of each word.
+    pub fn to_dictionary(&self) -> Dictionary {
+        self.words
+            .iter()
+            .filter_map(|(word, p)| Some((word.clone(), arpabet::to_segments(&p[0]).ok()?)))
+            .collect()
+    }
+}
+
+impl FromStr for Lexicon {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lexicon = Lexicon::new();
+        for (n, line) in s.lines().enumerate() {
+            let line = match line.find('#') {
+                Some(i) => &line[..i],
+                None => line,
+            };
+            if line.trim().is_empty() || line.starts_with(";;;") {
+                continue;
+            }
+            let error = |kind| ParseError { line: n + 1, kind };
+            let (word, phones) = line
+                .trim()
+                .split_once(char::is_whitespace)
+                .ok_or(error(ParseErrorKind::MissingPronunciation))?;
+            let word = match word.strip_suffix(')').and_then(|w| w.rsplit_once('(')) {
+                Some((base, variant)) if variant.bytes().all(|b| b.is_ascii_digit()) => base,
+                _ => word,
+            };
+            lexicon
+                .insert(word, phones)
+                .map_err(|e| error(ParseErrorKind::Phone(e)))?;
+        }
+        Ok(lexicon)
+    }
+}
+
+impl Transcriber for Lexicon {
+    fn transcribe(&self, word: &str) -> Option<Vec<Segment>> {
+        self.ipa(word)
+    }
+}
+
+/// What is wrong with a line of a dictionary.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A word without phones.
+    MissingPronunciation,
+    /// A phone that is not ARPAbet.
+    Phone(ParseSegmentError),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::MissingPronunciation => write!(f, "no pronunciation"),
+            ParseErrorKind::Phone(e) => e.fmt(f),
+        }
+    }
+}
+
+/// An error on a line of a dictionary.
+pub type ParseError = LineError<ParseErrorKind>;
@@ -0,0 +1,202 @@
+//! Scoring candidate cognates across word lists, for comparative work.
+//!
+//! Two words are scored on their alignment (`align::align`) in two
+//! ways: how little it costs, scaled to `0.0`–`1.0`, and how many of
+//! its pairs fall in the same SCA sound class (`lingpy`). Word lists
+//! are `delimited::Entry` lists, one per language, and words are only
+//! compared with words of the same gloss in other lists.
+//!
+//! ```
+//! use allo::cognates;
+//! use allo::delimited::Entry;
+//!
+//! let entry = |gloss: &str, word: &str| Entry {
+//!     grapheme: None,
+//!     phonemes: allo::parser::parse(word).unwrap(),
+//!     gloss: Some(gloss.to_string()),
+//! };
+//! let latin = [entry("father", "pater"), entry("hand", "manus")];
+//! let gothic = [entry("father", "fadar"), entry("hand", "handus")];
+//! let greek = [entry("father", "pateːr"), entry("hand", "kheir")];
+//! let sets = cognates::sets(&[&latin, &gothic, &greek], 0.6);
+//! assert_eq!(sets[0].gloss, "father");
+//! assert_eq!(sets[0].members.len(), 3);
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::align::{self, Costs};
+use crate::delimited::Entry;
+use crate::segment::Segment;
+
+/// How alike two words are, each score from `0.0` (nothing alike) to
+/// `1.0` (the same).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Score {
+    /// One minus the alignment cost over its most: the cost of
+    /// deleting every segment of one word and inserting every segment
+    /// of the other.
+    pub similarity: f32,
+    /// The share of the alignment's pairs whose segments have the same
+    /// SCA class, gaps counting as mismatches.
+    pub class_match: f32,
+}
+
+impl Score {
+    /// The mean of `similarity` and `class_match`, by which candidates
+    /// are ranked.
+    pub fn combined(&self) -> f32 {
+        (self.similarity + self.class_match) / 2.0
+    }
+}
+
+/// Scores `a` and `b` under the default alignment costs.
+pub fn score(a: &[Segment], b: &[Segment]) -> Score {
+    score_with(a, b, &Costs::default())
+}
+
+/// Scores `a` and `b` on their alignment under `costs`. Stress, length
+/// marks and other suprasegmentals are left out first. Two empty words
+/// score `1.0` on both.
+pub fn score_with(a: &[Segment], b: &[Segment], costs: &Costs) -> Score {
+    let segmental = |word: &[Segment]| -> Vec<Segment> {
+        word.iter().copied().filter(Segment::is_segmental).collect()
+    };
+    let (a, b) = (segmental(a), segmental(b));
+    let alignment = align::align_with(&a, &b, costs);
+    if alignment.pairs.is_empty() {
+        return Score {
+            similarity: 1.0,
+            class_match: 1.0,
+        };
+    }
+    let most = a.len() as f32 * costs.deletion + b.len() as f32 * costs.insertion;
+    let same_class = alignment
+        .matches()
+        .filter(|(x, y)| x.lingpy_class() == y.lingpy_class())
+        .count();
+    Score {
+        similarity: if most > 0.0 {
+            1.0 - alignment.cost / most
+        } else {
+            1.0
+        },
+        class_match: same_class as f32 / alignment.pairs.len() as f32,
+    }
+}
+
+/// A word of one of the lists compared: the list's index and the
+/// entry's index in the list.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Word {
+    pub list: usize,
+    pub entry: usize,
+}
+
+/// Two words of the same gloss in different lists, and their score.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pair {
+    pub gloss: String,
+    pub a: Word,
+    pub b: Word,
+    pub score: Score,
+}
+
+/// Words of one gloss linked as candidate cognates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CognateSet {
+    pub gloss: String,
+    /// The words of the set, in list order.
+    pub members: Vec<Word>,
+    /// The mean combined score of the pairs linking the set.
+    pub score: f32,
+}
+
+/// Scores every pair of words with the same gloss in different lists,
+/// best first. Entries without a gloss or a transcription are skipped.
+pub fn pairs(lists: &[&[Entry]]) -> Vec<Pair> {
+    let words: Vec<(Word, &str, &[Segment])> = lists
+        .iter()
+        .enumerate()
+        .flat_map(|(list, entries)| {
+            entries.iter().enumerate().filter_map(move |(entry, e)| {
+                let gloss = e.gloss.as_deref().filter(|_| !e.phonemes.is_empty())?;
+                Some((Word { list, entry }, gloss, &e.phonemes[..]))
+            })
+        })
+        .collect();
+    let mut pairs = Vec::new();
+    for (i, (a, gloss, x)) in words.iter().enumerate() {
+        for (b, other, y) in &words[i + 1..] {
+            if a.list != b.list && gloss == other {
+                pairs.push(Pair {
+                    gloss: gloss.to_string(),
+                    a: *a,
+                    b: *b,
+                    score: score(x, y),
+                });
+            }
+        }
+    }
+    pairs.sort_by(|p, q| q.score.combined().total_cmp(&p.score.combined()));
+    pairs
+}
+
+/// Groups words into candidate cognate sets, linking two words of a
+/// gloss whenever their pair's combined score is at least `threshold`
+/// (single linkage). Sets of one word are left out, and the sets come
+/// best first.
+pub fn sets(lists: &[&[Entry]], threshold: f32) -> Vec<CognateSet> {
+    let linked: Vec<Pair> = pairs(lists)
+        .into_iter()
+        .filter(|p| p.score.combined() >= threshold)
+        .collect();
+    // each set with the sum and count of the scores linking it
+    let mut sets: Vec<(CognateSet, f32, usize)> = Vec::new();
+    for pair in linked {
+        let combined = pair.score.combined();
+        let found: Vec<usize> = (0..sets.len())
+            .filter(|&i| sets[i].0.gloss == pair.gloss)
+            .filter(|&i| {
+                sets[i]
+                    .0
+                    .members
+                    .iter()
+                    .any(|w| *w == pair.a || *w == pair.b)
+            })
+            .collect();
+        let mut set = CognateSet {
+            gloss: pair.gloss,
+            members: vec![pair.a, pair.b],
+            score: 0.0,
+        };
+        let (mut sum, mut count) = (combined, 1);
+        // merge every set the pair touches, last first so the indices hold
+        for &i in found.iter().rev() {
+            let (other, other_sum, other_count) = sets.remove(i);
+            set.members.extend(other.members);
+            sum += other_sum;
+            count += other_count;
+        }
+        set.members.sort();
+        set.members.dedup();
+        sets.push((set, sum, count));
+    }
+    let mut sets: Vec<CognateSet> = sets
+        .into_iter()
+        .map(|(set, sum, count)| CognateSet {
+            score: sum / count as f32,
+            ..set
+        })
+        .collect();
+    sets.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| b.members.len().cmp(&a.members.len()))
+    });
+    sets
+}
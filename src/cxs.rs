@@ -0,0 +1,37 @@
+use alloc::string::String;
+
+use crate::notation::{self, Table, UnknownSymbol};
+use crate::xsampa;
+
+/// Conlang X-SAMPA (CXS) symbols and their IPA equivalents.
+///
+/// CXS is X-SAMPA as conlanging communities write it, and this table is
+/// `xsampa::TABLE` with its differences: the tie bar is `)`, as in
+/// `t)S`, so that `_` only ever begins a diacritic. Every other symbol
+/// is X-SAMPA's, including `&` for `ɶ`.
+pub const TABLE: &Table = &cxs();
+
+const LEN: usize = xsampa::TABLE.len();
+
+const fn cxs() -> [(&'static str, &'static str); LEN] {
+    let mut table = [("", ""); LEN];
+    let mut i = 0;
+    while i < LEN {
+        table[i] = match xsampa::TABLE[i] {
+            (x, ipa) if matches!(x.as_bytes(), b"_") => (")", ipa),
+            pair => pair,
+        };
+        i += 1;
+    }
+    table
+}
+
+/// Converts a CXS transcription to IPA, e.g. `"t)S_h"` to `"t͡ʃʰ"`.
+pub fn to_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    notation::to_ipa(s, TABLE)
+}
+
+/// Converts an IPA transcription to CXS.
+pub fn from_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    notation::from_ipa_separated(s, TABLE, "-")
+}
@@ -0,0 +1,311 @@
+//! Reading and writing inventories and word lists as delimited text,
+//! CSV or TSV, as a spreadsheet exports it:
+//!
+//! ```text
+//! grapheme,phoneme,gloss
+//! sheep,/ʃiːp/,a woolly animal
+//! ship,ʃɪp,"a boat, but larger"
+//! ```
+//!
+//! The columns are given by a `Format`, in order, and cells may be
+//! quoted with `"` as in CSV, a doubled `""` standing for a quote.
+//! Transcriptions may be written with or without slashes or brackets.
+//! Quoted cells cannot span lines.
+
+use core::fmt;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::error::LineError;
+use crate::inventory::Inventory;
+use crate::parser;
+use crate::segment::{ParseSegmentError, Segment};
+
+/// What a column of a delimited file holds.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Column {
+    /// The word in the language's spelling.
+    Grapheme,
+    /// The transcription, or for an inventory a single segment.
+    Phoneme,
+    Gloss,
+    /// A column that is skipped when reading and left empty when
+    /// writing.
+    Ignore,
+}
+
+impl Column {
+    /// The column's name in a header row.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Column::Grapheme => "grapheme",
+            Column::Phoneme => "phoneme",
+            Column::Gloss => "gloss",
+            Column::Ignore => "",
+        }
+    }
+}
+
+/// A word of a word list.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Entry {
+    pub grapheme: Option<String>,
+    pub phonemes: Vec<Segment>,
+    pub gloss: Option<String>,
+}
+
+/// The layout of a delimited file.
+///
+/// ```
+/// use allo::delimited::{Column, Format};
+///
+/// let format = Format::csv().with_columns(&[Column::Grapheme, Column::Phoneme, Column::Gloss]);
+/// let entries = format.read_entries("grapheme,phoneme,gloss\nship,/ʃɪp/,\"a boat, large\"").unwrap();
+/// assert_eq!(entries[0].grapheme.as_deref(), Some("ship"));
+/// assert_eq!(entries[0].gloss.as_deref(), Some("a boat, large"));
+/// assert_eq!(format.write_entries(&entries), "grapheme,phoneme,gloss\nship,ʃɪp,\"a boat, large\"\n");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Format {
+    delimiter: char,
+    header: bool,
+    columns: Vec<Column>,
+}
+
+impl Format {
+    /// Comma-separated values with a header row and the columns
+    /// grapheme, phoneme and gloss.
+    pub fn csv() -> Format {
+        Format {
+            delimiter: ',',
+            header: true,
+            columns: Vec::from([Column::Grapheme, Column::Phoneme, Column::Gloss]),
+        }
+    }
+
+    /// Tab-separated values, otherwise as `csv`.
+    pub fn tsv() -> Format {
+        Format {
+            delimiter: '\t',
+            ..Format::csv()
+        }
+    }
+
+    pub fn with_delimiter(mut self, delimiter: char) -> Format {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Whether the first row names the columns. It is skipped when
+    /// reading, and written from `Column::name` when writing.
+    pub fn with_header(mut self, header: bool) -> Format {
+        self.header = header;
+        self
+    }
+
+    pub fn with_columns(mut self, columns: &[Column]) -> Format {
+        self.columns = columns.to_vec();
+        self
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// The rows of `text`, without the header, each with its line
+    /// number counting from 1. Blank lines are skipped.
+    fn rows<'a>(
+        &'a self,
+        text: &'a str,
+    ) -> impl Iterator<Item = Result<(usize, Vec<String>), ParseError>> + 'a {
+        text.lines()
+            .enumerate()
+            .skip(self.header as usize)
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(n, line)| {
+                let kind = ParseErrorKind::UnterminatedQuote;
+                let cells = self.split(line).ok_or(ParseError { line: n + 1, kind })?;
+                Ok((n + 1, cells))
+            })
+    }
+
+    /// The cells of `line`, or `None` if a quote is left open.
+    fn split(&self, line: &str) -> Option<Vec<String>> {
+        let mut cells = Vec::new();
+        let mut cell = String::new();
+        let mut chars = line.chars().peekable();
+        let mut quoted = false;
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                '"' if quoted => quoted = false,
+                '"' if cell.trim().is_empty() => {
+                    cell.clear();
+                    quoted = true;
+                }
+                c if c == self.delimiter && !quoted => cells.push(core::mem::take(&mut cell)),
+                c => cell.push(c),
+            }
+        }
+        cells.push(cell);
+        (!quoted).then_some(cells)
+    }
+
+    /// The cell in `column` of `cells`, trimmed.
+    fn cell<'a>(&self, cells: &'a [String], column: Column) -> Option<&'a str> {
+        let i = self.columns.iter().position(|c| *c == column)?;
+        cells.get(i).map(|c| c.trim())
+    }
+
+    /// Reads a word list. Rows without a transcription have no
+    /// phonemes, and empty grapheme and gloss cells are `None`.
+    pub fn read_entries(&self, text: &str) -> Result<Vec<Entry>, ParseError> {
+        self.rows(text)
+            .map(|row| {
+                let (line, cells) = row?;
+                let text = |column| {
+                    self.cell(&cells, column)
+                        .filter(|c| !c.is_empty())
+                        .map(str::to_string)
+                };
+                let phonemes = match self.cell(&cells, Column::Phoneme) {
+                    Some(cell) => transcription(cell).map_err(|e| ParseError {
+                        line,
+                        kind: ParseErrorKind::Segment(e),
+                    })?,
+                    None => Vec::new(),
+                };
+                Ok(Entry {
+                    grapheme: text(Column::Grapheme),
+                    phonemes,
+                    gloss: text(Column::Gloss),
+                })
+            })
+            .collect()
+    }
+
+    /// Reads an inventory, one segment to a row in the phoneme
+    /// column. Other columns are ignored.
+    pub fn read_inventory(&self, text: &str) -> Result<Inventory, ParseError> {
+        let mut inventory = Inventory::new();
+        for row in self.rows(text) {
+            let (line, cells) = row?;
+            let error = |kind| ParseError { line, kind };
+            let cell = self
+                .cell(&cells, Column::Phoneme)
+                .filter(|c| !c.is_empty())
+                .ok_or(error(ParseErrorKind::MissingPhoneme))?;
+            let segment = transcription(cell)
+                .and_then(|segments| match segments[..] {
+                    [segment] => Ok(segment),
+                    _ => cell.parse(),
+                })
+                .map_err(|e| error(ParseErrorKind::Segment(e)))?;
+            inventory.insert(segment);
+        }
+        Ok(inventory)
+    }
+
+    /// Writes a word list, with a header row if the format has one.
+    pub fn write_entries(&self, entries: &[Entry]) -> String {
+        let mut out = self.header_row();
+        for entry in entries {
+            let phonemes: String = entry.phonemes.iter().map(|s| s.to_string()).collect();
+            let cells = self.columns.iter().map(|column| match column {
+                Column::Grapheme => entry.grapheme.as_deref().unwrap_or(""),
+                Column::Phoneme => &phonemes,
+                Column::Gloss => entry.gloss.as_deref().unwrap_or(""),
+                Column::Ignore => "",
+            });
+            self.write_row(&mut out, cells);
+        }
+        out
+    }
+
+    /// Writes an inventory, one segment to a row in chart order, with
+    /// the other columns left empty.
+    pub fn write_inventory(&self, inventory: &Inventory) -> String {
+        let mut out = self.header_row();
+        for segment in inventory.iter() {
+            let segment = segment.to_string();
+            let cells = self.columns.iter().map(|column| match column {
+                Column::Phoneme => segment.as_str(),
+                _ => "",
+            });
+            self.write_row(&mut out, cells);
+        }
+        out
+    }
+
+    fn header_row(&self) -> String {
+        let mut out = String::new();
+        if self.header {
+            self.write_row(&mut out, self.columns.iter().map(Column::name));
+        }
+        out
+    }
+
+    /// Appends a row, quoting cells that hold the delimiter, a quote or
+    /// a line break.
+    fn write_row<'a>(&self, out: &mut String, cells: impl Iterator<Item = &'a str>) {
+        for (i, cell) in cells.enumerate() {
+            if i > 0 {
+                out.push(self.delimiter);
+            }
+            match cell.contains([self.delimiter, '"', '\n', '\r']) {
+                true => {
+                    out.push('"');
+                    out.push_str(&cell.replace('"', "\"\""));
+                    out.push('"');
+                }
+                false => out.push_str(cell),
+            }
+        }
+        out.push('\n');
+    }
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Format::csv()
+    }
+}
+
+/// The segments of a transcription, with any slashes or brackets
+/// around it.
+fn transcription(cell: &str) -> Result<Vec<Segment>, ParseSegmentError> {
+    let inner = [('/', '/'), ('[', ']')]
+        .iter()
+        .find_map(|(open, close)| cell.strip_prefix(*open)?.strip_suffix(*close))
+        .unwrap_or(cell);
+    parser::parse(inner)
+}
+
+/// What is wrong with a row of a delimited file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A quoted cell not closed by the end of the line.
+    UnterminatedQuote,
+    /// An inventory row with an empty or missing phoneme column.
+    MissingPhoneme,
+    Segment(ParseSegmentError),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::UnterminatedQuote => write!(f, "unterminated quote"),
+            ParseErrorKind::MissingPhoneme => write!(f, "no phoneme"),
+            ParseErrorKind::Segment(e) => e.fmt(f),
+        }
+    }
+}
+
+/// An error on a line of a delimited file.
+pub type ParseError = LineError<ParseErrorKind>;
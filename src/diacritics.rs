@@ -0,0 +1,339 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// Where a diacritic is drawn relative to the base symbol it modifies.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Placement {
+    /// A combining mark drawn above the base, e.g. `ŋ̊`.
+    Above,
+    /// A combining mark drawn below the base, e.g. `n̥`.
+    Below,
+    /// A combining mark drawn through the base, e.g. `ɫ`.
+    Through,
+    /// A spacing modifier letter written after the base, e.g. `tʰ`.
+    After,
+}
+
+/// Every diacritic listed in the IPA chart, in chart order.
+///
+/// Each variant has a preferred mark and, for several of the marks
+/// drawn below the base, an alternate mark drawn above it for use on
+/// symbols with descenders (`ŋ̊` rather than `ŋ̥`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Diacritic {
+    Voiceless,
+    Voiced,
+    Aspirated,
+    MoreRounded,
+    LessRounded,
+    Advanced,
+    Retracted,
+    Centralized,
+    MidCentralized,
+    Syllabic,
+    NonSyllabic,
+    Rhoticity,
+    BreathyVoiced,
+    CreakyVoiced,
+    Linguolabial,
+    Labialized,
+    Palatalized,
+    Velarized,
+    Pharyngealized,
+    VelarizedOrPharyngealized,
+    Raised,
+    Lowered,
+    AdvancedTongueRoot,
+    RetractedTongueRoot,
+    Dental,
+    Apical,
+    Laminal,
+    Nasalized,
+    NasalRelease,
+    LateralRelease,
+    NoAudibleRelease,
+}
+
+impl Diacritic {
+    pub const ALL: [Diacritic; 31] = [
+        Diacritic::Voiceless,
+        Diacritic::Voiced,
+        Diacritic::Aspirated,
+        Diacritic::MoreRounded,
+        Diacritic::LessRounded,
+        Diacritic::Advanced,
+        Diacritic::Retracted,
+        Diacritic::Centralized,
+        Diacritic::MidCentralized,
+        Diacritic::Syllabic,
+        Diacritic::NonSyllabic,
+        Diacritic::Rhoticity,
+        Diacritic::BreathyVoiced,
+        Diacritic::CreakyVoiced,
+        Diacritic::Linguolabial,
+        Diacritic::Labialized,
+        Diacritic::Palatalized,
+        Diacritic::Velarized,
+        Diacritic::Pharyngealized,
+        Diacritic::VelarizedOrPharyngealized,
+        Diacritic::Raised,
+        Diacritic::Lowered,
+        Diacritic::AdvancedTongueRoot,
+        Diacritic::RetractedTongueRoot,
+        Diacritic::Dental,
+        Diacritic::Apical,
+        Diacritic::Laminal,
+        Diacritic::Nasalized,
+        Diacritic::NasalRelease,
+        Diacritic::LateralRelease,
+        Diacritic::NoAudibleRelease,
+    ];
+
+    /// The preferred mark for this diacritic.
+    pub const fn mark(&self) -> char {
+        match self {
+            Diacritic::Voiceless => '\u{325}',
+            Diacritic::Voiced => '\u{32C}',
+            Diacritic::Aspirated => '\u{2B0}',
+            Diacritic::MoreRounded => '\u{339}',
+            Diacritic::LessRounded => '\u{31C}',
+            Diacritic::Advanced => '\u{31F}',
+            Diacritic::Retracted => '\u{320}',
+            Diacritic::Centralized => '\u{308}',
+            Diacritic::MidCentralized => '\u{33D}',
+            Diacritic::Syllabic => '\u{329}',
+            Diacritic::NonSyllabic => '\u{32F}',
+            Diacritic::Rhoticity => '\u{2DE}',
+            Diacritic::BreathyVoiced => '\u{324}',
+            Diacritic::CreakyVoiced => '\u{330}',
+            Diacritic::Linguolabial => '\u{33C}',
+            Diacritic::Labialized => '\u{2B7}',
+            Diacritic::Palatalized => '\u{2B2}',
+            Diacritic::Velarized => '\u{2E0}',
+            Diacritic::Pharyngealized => '\u{2E4}',
+            Diacritic::VelarizedOrPharyngealized => '\u{334}',
+            Diacritic::Raised => '\u{31D}',
+            Diacritic::Lowered => '\u{31E}',
+            Diacritic::AdvancedTongueRoot => '\u{318}',
+            Diacritic::RetractedTongueRoot => '\u{319}',
+            Diacritic::Dental => '\u{32A}',
+            Diacritic::Apical => '\u{33A}',
+            Diacritic::Laminal => '\u{33B}',
+            Diacritic::Nasalized => '\u{303}',
+            Diacritic::NasalRelease => '\u{207F}',
+            Diacritic::LateralRelease => '\u{2E1}',
+            Diacritic::NoAudibleRelease => '\u{31A}',
+        }
+    }
+
+    /// The diacritic's name on the IPA chart, in the form used in
+    /// segment names, e.g. `"breathy voiced"` or `"nasal release"`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Diacritic::Voiceless => "voiceless",
+            Diacritic::Voiced => "voiced",
+            Diacritic::Aspirated => "aspirated",
+            Diacritic::MoreRounded => "more rounded",
+            Diacritic::LessRounded => "less rounded",
+            Diacritic::Advanced => "advanced",
+            Diacritic::Retracted => "retracted",
+            Diacritic::Centralized => "centralized",
+            Diacritic::MidCentralized => "mid-centralized",
+            Diacritic::Syllabic => "syllabic",
+            Diacritic::NonSyllabic => "non-syllabic",
+            Diacritic::Rhoticity => "rhoticized",
+            Diacritic::BreathyVoiced => "breathy voiced",
+            Diacritic::CreakyVoiced => "creaky voiced",
+            Diacritic::Linguolabial => "linguolabial",
+            Diacritic::Labialized => "labialized",
+            Diacritic::Palatalized => "palatalized",
+            Diacritic::Velarized => "velarized",
+            Diacritic::Pharyngealized => "pharyngealized",
+            Diacritic::VelarizedOrPharyngealized => "velarized or pharyngealized",
+            Diacritic::Raised => "raised",
+            Diacritic::Lowered => "lowered",
+            Diacritic::AdvancedTongueRoot => "advanced tongue root",
+            Diacritic::RetractedTongueRoot => "retracted tongue root",
+            Diacritic::Dental => "dental",
+            Diacritic::Apical => "apical",
+            Diacritic::Laminal => "laminal",
+            Diacritic::Nasalized => "nasalized",
+            Diacritic::NasalRelease => "nasal release",
+            Diacritic::LateralRelease => "lateral release",
+            Diacritic::NoAudibleRelease => "no audible release",
+        }
+    }
+
+    /// Where the preferred mark is drawn.
+    pub const fn placement(&self) -> Placement {
+        match self {
+            Diacritic::Aspirated
+            | Diacritic::Rhoticity
+            | Diacritic::Labialized
+            | Diacritic::Palatalized
+            | Diacritic::Velarized
+            | Diacritic::Pharyngealized
+            | Diacritic::NasalRelease
+            | Diacritic::LateralRelease => Placement::After,
+            Diacritic::Centralized
+            | Diacritic::MidCentralized
+            | Diacritic::Nasalized
+            | Diacritic::NoAudibleRelease => Placement::Above,
+            Diacritic::VelarizedOrPharyngealized => Placement::Through,
+            _ => Placement::Below,
+        }
+    }
+
+    /// The mark for this diacritic when drawn at `placement`, if the
+    /// IPA provides one.
+    ///
+    /// Besides the preferred mark, some diacritics have an alternate
+    /// form: an above variant for voiceless, syllabic, non-syllabic
+    /// and dental, and a spacing variant for advanced, retracted,
+    /// raised and lowered.
+    pub const fn variant(&self, placement: Placement) -> Option<char> {
+        if self.placement() as u8 == placement as u8 {
+            return Some(self.mark());
+        }
+        match (self, placement) {
+            (Diacritic::Voiceless, Placement::Above) => Some('\u{30A}'),
+            (Diacritic::Syllabic, Placement::Above) => Some('\u{30D}'),
+            (Diacritic::NonSyllabic, Placement::Above) => Some('\u{311}'),
+            (Diacritic::Dental, Placement::Above) => Some('\u{346}'),
+            (Diacritic::Advanced, Placement::After) => Some('\u{2D6}'),
+            (Diacritic::Retracted, Placement::After) => Some('\u{2D7}'),
+            (Diacritic::Raised, Placement::After) => Some('\u{2D4}'),
+            (Diacritic::Lowered, Placement::After) => Some('\u{2D5}'),
+            _ => None,
+        }
+    }
+
+    /// Looks up the diacritic written with `c`, accepting both
+    /// preferred and alternate marks.
+    pub const fn from_char(c: char) -> Option<Diacritic> {
+        const PLACEMENTS: [Placement; 4] = [
+            Placement::Above,
+            Placement::Below,
+            Placement::Through,
+            Placement::After,
+        ];
+        let mut i = 0;
+        while i < Diacritic::ALL.len() {
+            let mut p = 0;
+            while p < PLACEMENTS.len() {
+                if matches!(Diacritic::ALL[i].variant(PLACEMENTS[p]), Some(v) if v == c) {
+                    return Some(Diacritic::ALL[i]);
+                }
+                p += 1;
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Whether this diacritic is written with a combining mark, as
+    /// opposed to a spacing modifier letter.
+    pub const fn is_combining(&self) -> bool {
+        !matches!(self.placement(), Placement::After)
+    }
+}
+
+/// A set of diacritics attached to a single base symbol.
+///
+/// Iteration (and therefore rendering) always follows the order of
+/// `Diacritic::ALL`, with combining marks before spacing modifiers so
+/// that no combining mark ends up stacked on a superscript.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Diacritics(u32);
+
+impl Diacritics {
+    pub const fn new() -> Self {
+        Diacritics(0)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub const fn contains(&self, diacritic: Diacritic) -> bool {
+        self.0 & (1 << diacritic as u32) != 0
+    }
+
+    pub const fn with(self, diacritic: Diacritic) -> Self {
+        Diacritics(self.0 | (1 << diacritic as u32))
+    }
+
+    pub const fn without(self, diacritic: Diacritic) -> Self {
+        Diacritics(self.0 & !(1 << diacritic as u32))
+    }
+
+    pub fn insert(&mut self, diacritic: Diacritic) {
+        *self = self.with(diacritic);
+    }
+
+    pub fn remove(&mut self, diacritic: Diacritic) {
+        *self = self.without(diacritic);
+    }
+
+    pub const fn union(self, other: Diacritics) -> Self {
+        Diacritics(self.0 | other.0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Diacritic> + '_ {
+        let combining = Diacritic::ALL.into_iter().filter(|d| d.is_combining());
+        let spacing = Diacritic::ALL.into_iter().filter(|d| !d.is_combining());
+        combining.chain(spacing).filter(|d| self.contains(*d))
+    }
+}
+
+impl FromIterator<Diacritic> for Diacritics {
+    fn from_iter<I: IntoIterator<Item = Diacritic>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Diacritics::new(), |set, d| set.with(d))
+    }
+}
+
+/// Base symbols with a descender, on which marks normally drawn below
+/// are moved above when an above variant exists.
+const DESCENDERS: &str = "gjpqyçŋɟɡɣɲɳɻʝʎʐʂɥɱɰɽʈβ";
+
+/// Whether `base` descends below the baseline.
+pub fn has_descender(base: char) -> bool {
+    DESCENDERS.contains(base)
+}
+
+/// Writes `base` followed by each diacritic in `diacritics`.
+///
+/// Marks normally drawn below are drawn above instead when the last
+/// character of `base` has a descender, so `ŋ` with
+/// `Diacritic::Voiceless` renders as `ŋ̊`.
+#[cfg(feature = "alloc")]
+pub fn attach(base: &str, diacritics: Diacritics) -> String {
+    let mut s = String::from(base);
+    write_marks(&mut s, base, diacritics).unwrap();
+    s
+}
+
+/// Writes the marks `attach` would add to `base`, without `base`
+/// itself.
+pub fn write_marks(w: &mut impl fmt::Write, base: &str, diacritics: Diacritics) -> fmt::Result {
+    let descender = base.chars().last().is_some_and(has_descender);
+    for d in diacritics.iter() {
+        let mark = match d.placement() {
+            Placement::Below if descender => d.variant(Placement::Above).unwrap_or(d.mark()),
+            _ => d.mark(),
+        };
+        w.write_char(mark)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,170 @@
+//! Dolgopolsky's sound classes (Dolgopolsky 1964), ten classes of
+//! consonants that sounds rarely leave as languages change, for quick
+//! cognate screening: two words whose first two consonants fall in the
+//! same classes are candidate cognates.
+//!
+//! | Class | Sounds                                                                        |
+//! |-------|-------------------------------------------------------------------------------|
+//! | `P`   | labial obstruents: p b f v β…                                                 |
+//! | `T`   | dental obstruents: t d θ ð…                                                   |
+//! | `S`   | sibilant fricatives: s z ʃ ʒ…                                                 |
+//! | `K`   | velar, uvular and palatal obstruents, sibilant affricates: k ɡ x q c t͡s t͡ʃ… |
+//! | `M`   | labial nasals: m ɱ                                                            |
+//! | `N`   | other nasals: n ɲ ŋ…                                                          |
+//! | `R`   | liquids: r l ɾ ɹ…                                                             |
+//! | `W`   | labial and velar glides: w ʋ ɰ                                                |
+//! | `J`   | the palatal glide j                                                           |
+//! | `H`   | laryngeals: h ʔ ħ ʕ…                                                          |
+//!
+//! Vowels belong to no class, except that non-syllabic vowels are
+//! glides.
+//!
+//! ```
+//! use allo::dolgopolsky::{self, Dolgopolsky};
+//! use allo::parser;
+//!
+//! let segments = parser::parse("ɡʷʰermos").unwrap();
+//! assert_eq!(segments[0].dolgopolsky_class(), Some(Dolgopolsky::K));
+//! assert_eq!(segments[1].dolgopolsky_class(), None);
+//! assert_eq!(dolgopolsky::reduce(&segments), "KRMS");
+//! ```
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::affricate::Affricate;
+use crate::diacritics::Diacritic;
+use crate::ipa::{Airstream, Articulation, Backness, Consonant, Manner, Roundedness, Vowel};
+use crate::segment::Segment;
+
+/// One of Dolgopolsky's consonant classes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dolgopolsky {
+    /// Labial obstruents.
+    P,
+    /// Dental obstruents.
+    T,
+    /// Sibilant fricatives.
+    S,
+    /// Velar, uvular and palatal obstruents, and sibilant affricates.
+    K,
+    /// Labial nasals.
+    M,
+    /// Other nasals.
+    N,
+    /// Liquids.
+    R,
+    /// Labial and velar glides.
+    W,
+    /// The palatal glide.
+    J,
+    /// Laryngeals.
+    H,
+}
+
+impl Dolgopolsky {
+    /// Every class, in Dolgopolsky's order.
+    pub const ALL: [Dolgopolsky; 10] = [
+        Dolgopolsky::P,
+        Dolgopolsky::T,
+        Dolgopolsky::S,
+        Dolgopolsky::K,
+        Dolgopolsky::M,
+        Dolgopolsky::N,
+        Dolgopolsky::R,
+        Dolgopolsky::W,
+        Dolgopolsky::J,
+        Dolgopolsky::H,
+    ];
+
+    /// The letter the class is written with.
+    pub const fn letter(&self) -> char {
+        match self {
+            Dolgopolsky::P => 'P',
+            Dolgopolsky::T => 'T',
+            Dolgopolsky::S => 'S',
+            Dolgopolsky::K => 'K',
+            Dolgopolsky::M => 'M',
+            Dolgopolsky::N => 'N',
+            Dolgopolsky::R => 'R',
+            Dolgopolsky::W => 'W',
+            Dolgopolsky::J => 'J',
+            Dolgopolsky::H => 'H',
+        }
+    }
+}
+
+impl fmt::Display for Dolgopolsky {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+/// The class of `segment`, or `None` for syllabic vowels,
+/// suprasegmentals and tones.
+pub const fn of(segment: &Segment) -> Option<Dolgopolsky> {
+    match segment {
+        Segment::Consonant(c) => Some(consonant(c)),
+        Segment::Affricate(a) => Some(affricate(a)),
+        Segment::Vowel(v) if v.diacritics().contains(Diacritic::NonSyllabic) => Some(glide(v)),
+        Segment::Vowel(_) | Segment::Suprasegmental(_) | Segment::Tone(_) => None,
+    }
+}
+
+pub(crate) const fn consonant(c: &Consonant) -> Dolgopolsky {
+    use Articulation::*;
+    if matches!(c.airstream(), Airstream::VelaricIngressive) {
+        return Dolgopolsky::K;
+    }
+    match (c.manner(), c.articulation()) {
+        (_, Pharyngeal | Epiglottal | Glottal) => Dolgopolsky::H,
+        (Manner::Nasal, Bilabial | Labiodental) => Dolgopolsky::M,
+        (Manner::Nasal, _) => Dolgopolsky::N,
+        (Manner::Plosive | Manner::Fricative { .. }, Bilabial | Labiodental) => Dolgopolsky::P,
+        (Manner::Fricative { sibilant: true }, Palatal) => Dolgopolsky::S,
+        (Manner::Plosive | Manner::Fricative { .. }, Palatal | Velar | Uvular) => Dolgopolsky::K,
+        (Manner::Fricative { sibilant: true }, _) => Dolgopolsky::S,
+        (Manner::Plosive | Manner::Fricative { .. }, _) => Dolgopolsky::T,
+        (Manner::Approximant, Bilabial | Labiodental | Velar) => Dolgopolsky::W,
+        (Manner::Approximant, Palatal) => Dolgopolsky::J,
+        (Manner::TapFlap | Manner::Trill, Bilabial | Labiodental) => Dolgopolsky::W,
+        (
+            Manner::Approximant
+            | Manner::TapFlap
+            | Manner::Trill
+            | Manner::LatFric
+            | Manner::LatApprox
+            | Manner::LatTapFlap,
+            _,
+        ) => Dolgopolsky::R,
+    }
+}
+
+/// Sibilant affricates are `K`; others go by their stop.
+const fn affricate(a: &Affricate) -> Dolgopolsky {
+    match a.fricative().manner() {
+        Manner::Fricative { sibilant: true } => Dolgopolsky::K,
+        _ => consonant(&a.stop()),
+    }
+}
+
+/// A non-syllabic vowel is `J` if front or unrounded, and `W` otherwise.
+const fn glide(v: &Vowel) -> Dolgopolsky {
+    match (v.backness(), v.roundedness()) {
+        (Backness::Front | Backness::NearFront, _) | (_, Roundedness::Unrounded) => Dolgopolsky::J,
+        _ => Dolgopolsky::W,
+    }
+}
+
+/// Reduces `segments` to the letters of their classes, leaving out
+/// vowels and suprasegmentals, as in `"KRMS"` for `ɡʷʰermos`.
+#[cfg(feature = "alloc")]
+pub fn reduce(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .filter_map(of)
+        .map(|class| class.letter())
+        .collect()
+}
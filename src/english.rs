@@ -0,0 +1,429 @@
+//! A rule-based English transcriber, giving broad IPA for ordinary
+//! words in General American or Received Pronunciation.
+//!
+//! Spelling is read letter by letter in the manner of the NRL rules
+//! (Elovitz et al. 1976): each rule rewrites a string of letters in a
+//! context, the first rule that fits winning. The rules give General
+//! American, which RP then derives from: non-prevocalic `r` is dropped
+//! with its vowel lengthened or centred, the long vowels are marked
+//! long, the LOT vowel is `ɒ`, and `oʊ` is `əʊ`. Common words the rules
+//! would get wrong are looked up in a built-in list first, and
+//! `English::with_exception` adds more.
+//!
+//! ```
+//! use allo::english::{Dialect, English};
+//! use allo::g2p::Transcriber;
+//!
+//! let render = |w: Vec<allo::Segment>| w.iter().map(|s| s.to_string()).collect::<String>();
+//! let ga = English::new(Dialect::GeneralAmerican);
+//! let rp = English::new(Dialect::ReceivedPronunciation);
+//! assert_eq!(ga.transcribe("park").map(render).as_deref(), Some("pɑɹk"));
+//! assert_eq!(rp.transcribe("park").map(render).as_deref(), Some("pɑːk"));
+//! assert_eq!(rp.transcribe("home").map(render).as_deref(), Some("həʊm"));
+//! ```
+//!
+//! Stress is not marked, and vowels are given their full quality even
+//! where they would be reduced.
+
+use alloc::{string::String, vec::Vec};
+
+use crate::g2p::{Dictionary, Transcriber};
+use crate::parser;
+use crate::segment::Segment;
+
+/// A standard accent of English.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Dialect {
+    /// General American: rhotic, with `ɑ` in _lot_ and `oʊ` in _go_.
+    #[default]
+    GeneralAmerican,
+    /// Received Pronunciation: non-rhotic, with `ɒ` in _lot_ and `əʊ`
+    /// in _go_.
+    ReceivedPronunciation,
+}
+
+/// An English transcriber for one dialect.
+#[derive(Clone, Debug, Default)]
+pub struct English {
+    dialect: Dialect,
+    exceptions: Dictionary,
+}
+
+impl English {
+    pub fn new(dialect: Dialect) -> Self {
+        English {
+            dialect,
+            exceptions: Dictionary::new(),
+        }
+    }
+
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    /// Adds a pronunciation for `word`, used as given in place of the
+    /// rules and the built-in exceptions.
+    pub fn with_exception(mut self, word: &str, segments: Vec<Segment>) -> Self {
+        self.exceptions.insert(&word.to_lowercase(), segments);
+        self
+    }
+
+    /// The exceptions added with `with_exception`.
+    pub fn exceptions(&self) -> &Dictionary {
+        &self.exceptions
+    }
+
+    /// The General American transcription of `word` by rule, or `None`
+    /// if it has a letter outside `a`–`z`.
+    fn by_rule(word: &str) -> Option<String> {
+        let word = word.as_bytes();
+        let mut out = String::new();
+        let mut i = 0;
+        while i < word.len() {
+            let (_, matched, _, phonemes) = RULES.iter().find(|(left, matched, right, _)| {
+                word[i..].starts_with(matched.as_bytes())
+                    && fits_left(left, &word[..i])
+                    && fits(right.as_bytes(), &word[i + matched.len()..])
+            })?;
+            out.push_str(phonemes);
+            i += matched.len();
+        }
+        Some(out)
+    }
+}
+
+impl Transcriber for English {
+    fn transcribe(&self, word: &str) -> Option<Vec<Segment>> {
+        let word = word.to_lowercase();
+        if let Some(segments) = self.exceptions.get(&word) {
+            return Some(segments.to_vec());
+        }
+        let builtin = EXCEPTIONS.iter().find(|(w, _, _)| *w == word);
+        let ga = match builtin {
+            Some((_, ga, _)) => String::from(*ga),
+            None => English::by_rule(&word)?,
+        };
+        let ipa = match (self.dialect, builtin) {
+            (Dialect::GeneralAmerican, _) => ga,
+            (Dialect::ReceivedPronunciation, Some((_, _, rp))) if !rp.is_empty() => {
+                String::from(*rp)
+            }
+            (Dialect::ReceivedPronunciation, _) => received(&ga),
+        };
+        parser::parse(&ipa).ok()
+    }
+}
+
+const VOWEL_LETTERS: &[u8] = b"aeiouy";
+const VOWELS: &str = "aeiouæɑɒɔəɛɪʊʌɜ";
+
+/// Whether the letters before a match, read backwards from the
+/// nearest, fit the context `left`.
+fn fits_left(left: &str, before: &[u8]) -> bool {
+    let context: Vec<u8> = left.bytes().rev().collect();
+    let letters: Vec<u8> = before.iter().rev().copied().collect();
+    fits(&context, &letters)
+}
+
+/// Whether `letters` start with something `context` describes. In a
+/// context, ` ` is the edge of the word, `#` one or more vowels, `:`
+/// any number of consonants, `^` one consonant, `+` a front vowel
+/// (`e`, `i` or `y`), `%` a suffix (`e`, `ed`, `er`, `es`, `ely` or
+/// `ing`) ending the word, and a letter itself.
+fn fits(context: &[u8], letters: &[u8]) -> bool {
+    let vowel = |c: &u8| VOWEL_LETTERS.contains(c);
+    let Some((&c, rest)) = context.split_first() else {
+        return true;
+    };
+    match c {
+        b' ' => letters.is_empty() && fits(rest, letters),
+        b'#' => {
+            let n = letters.iter().take_while(|c| vowel(c)).count();
+            n > 0 && fits(rest, &letters[n..])
+        }
+        b':' => {
+            let n = letters.iter().take_while(|c| !vowel(c)).count();
+            (0..=n).any(|k| fits(rest, &letters[k..]))
+        }
+        b'^' => letters.first().is_some_and(|c| !vowel(c)) && fits(rest, &letters[1..]),
+        b'+' => letters.first().is_some_and(|c| b"eiy".contains(c)) && fits(rest, &letters[1..]),
+        b'%' => ["ing", "ely", "ed", "er", "es", "e"]
+            .iter()
+            .any(|s| letters == s.as_bytes() && fits(rest, &[])),
+        c => letters.first() == Some(&c) && fits(rest, &letters[1..]),
+    }
+}
+
+/// The RP form of a General American transcription.
+fn received(ga: &str) -> String {
+    let chars: Vec<char> = ga.chars().collect();
+    // Whether the `ɹ` at `i` comes before a vowel.
+    let prevocalic = |i: usize| chars.get(i + 1).is_some_and(|c| VOWELS.contains(*c));
+    let r_after = |i: usize| chars.get(i + 1) == Some(&'ɹ') && !prevocalic(i + 1);
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            'ɑ' if r_after(i) => {
+                out.push_str("ɑː");
+                i += 1;
+            }
+            'ɑ' => out.push('ɒ'),
+            'ɔ' if r_after(i) => {
+                out.push_str("ɔː");
+                i += 1;
+            }
+            'ɔ' if chars.get(i + 1) == Some(&'ɪ') => out.push('ɔ'),
+            'ɔ' => out.push_str("ɔː"),
+            'ɛ' | 'ɪ' | 'ʊ' if r_after(i) => {
+                out.push(c);
+                out.push('ə');
+                i += 1;
+            }
+            'i' | 'u' if r_after(i) => {
+                out.push_str(if c == 'i' { "ɪə" } else { "ʊə" });
+                i += 1;
+            }
+            'ɜ' | 'ə' if chars.get(i + 1) == Some(&'˞') => {
+                out.push_str(if c == 'ɜ' { "ɜː" } else { "ə" });
+                i += 1;
+            }
+            // Final unstressed _happy_ `i` stays short.
+            'i' | 'u' if c == 'u' || i + 1 < chars.len() => {
+                out.push(c);
+                out.push('ː');
+            }
+            'o' if chars.get(i + 1) == Some(&'ʊ') => {
+                out.push_str("əʊ");
+                i += 1;
+            }
+            'ɹ' if !prevocalic(i) => {}
+            c => out.push(c),
+        }
+        i += 1;
+    }
+    out
+}
+
+/// The letter-to-sound rules, as left context, letters, right context
+/// and General American phonemes, tried in order.
+#[rustfmt::skip]
+const RULES: &[(&str, &str, &str, &str)] = &[
+    ("", "a", " ", "ə"),
+    (" ", "are", " ", "ɑɹ"),
+    ("", "ar", "#", "ɛɹ"),
+    ("", "ar", "", "ɑɹ"),
+    ("", "ai", "", "eɪ"),
+    ("", "ay", "", "eɪ"),
+    ("", "au", "", "ɔ"),
+    ("", "aw", "", "ɔ"),
+    ("", "all", "", "ɔl"),
+    ("", "alk", "", "ɔk"),
+    ("", "a", "^%", "eɪ"),
+    ("w", "a", "", "ɑ"),
+    ("", "a", "", "æ"),
+    ("", "bb", "", "b"),
+    ("m", "b", " ", ""),
+    ("", "b", "", "b"),
+    ("", "ch", "", "t͡ʃ"),
+    ("", "ck", "", "k"),
+    ("", "c", "+", "s"),
+    ("", "c", "", "k"),
+    ("", "dge", "", "d͡ʒ"),
+    ("", "dd", "", "d"),
+    ("", "d", "", "d"),
+    ("#:t", "ed", " ", "ɪd"),
+    ("#:d", "ed", " ", "ɪd"),
+    ("#:p", "ed", " ", "t"),
+    ("#:k", "ed", " ", "t"),
+    ("#:s", "ed", " ", "t"),
+    ("#:f", "ed", " ", "t"),
+    ("#:x", "ed", " ", "t"),
+    ("#:h", "ed", " ", "t"),
+    ("#:", "ed", " ", "d"),
+    ("#:s", "es", " ", "ɪz"),
+    ("#:x", "es", " ", "ɪz"),
+    ("#:z", "es", " ", "ɪz"),
+    ("#:h", "es", " ", "ɪz"),
+    ("#:g", "es", " ", "ɪz"),
+    ("#:c", "es", " ", "ɪz"),
+    ("#:p", "es", " ", "s"),
+    ("#:t", "es", " ", "s"),
+    ("#:k", "es", " ", "s"),
+    ("#:f", "es", " ", "s"),
+    ("#:", "es", " ", "z"),
+    ("#:", "e", " ", ""),
+    (" :", "e", " ", "i"),
+    ("", "ee", "", "i"),
+    ("", "ea", "", "i"),
+    ("", "ey", " ", "i"),
+    ("", "ei", "", "eɪ"),
+    ("", "ew", "", "u"),
+    ("", "e", "^%", "i"),
+    ("", "er", "#", "ɛɹ"),
+    ("", "er", "", "ə˞"),
+    ("", "e", "", "ɛ"),
+    ("", "ff", "", "f"),
+    ("", "f", "", "f"),
+    ("", "ge", " ", "d͡ʒ"),
+    ("", "gg", "", "ɡ"),
+    ("", "gh", "", ""),
+    ("", "g", "y", "d͡ʒ"),
+    ("", "g", "", "ɡ"),
+    (" ", "h", "", "h"),
+    ("#", "h", "#", "h"),
+    ("", "h", "", ""),
+    ("", "igh", "", "aɪ"),
+    ("", "ign", " ", "aɪn"),
+    ("", "ind", "", "aɪnd"),
+    ("", "ild", "", "aɪld"),
+    ("", "i", "^%", "aɪ"),
+    ("", "ir", "", "ɜ˞"),
+    ("", "ie", " ", "aɪ"),
+    ("", "ie", "", "i"),
+    (" :", "i", " ", "aɪ"),
+    ("", "i", "", "ɪ"),
+    ("", "j", "", "d͡ʒ"),
+    (" ", "kn", "", "n"),
+    ("", "k", "", "k"),
+    ("", "ll", "", "l"),
+    ("", "l", "", "l"),
+    ("", "mm", "", "m"),
+    ("", "m", "", "m"),
+    ("", "nn", "", "n"),
+    ("", "ng", "", "ŋ"),
+    ("", "nk", "", "ŋk"),
+    ("", "n", "", "n"),
+    ("", "oo", "k", "ʊ"),
+    ("", "oo", "", "u"),
+    ("", "ough", "t", "ɔ"),
+    ("", "ou", "", "aʊ"),
+    ("", "ow", " ", "oʊ"),
+    ("", "ow", "", "aʊ"),
+    ("", "oy", "", "ɔɪ"),
+    ("", "oi", "", "ɔɪ"),
+    ("", "oa", "", "oʊ"),
+    ("", "or", "", "ɔɹ"),
+    ("", "old", "", "oʊld"),
+    ("", "o", "^%", "oʊ"),
+    (" :", "o", " ", "oʊ"),
+    ("", "o", "", "ɑ"),
+    ("", "ph", "", "f"),
+    ("", "pp", "", "p"),
+    ("", "p", "", "p"),
+    ("", "qu", "", "kw"),
+    ("", "q", "", "k"),
+    ("", "rr", "", "ɹ"),
+    ("", "r", "", "ɹ"),
+    ("", "sh", "", "ʃ"),
+    ("", "sion", "", "ʒən"),
+    ("", "ss", "", "s"),
+    ("#", "s", "#", "z"),
+    ("b", "s", " ", "z"),
+    ("d", "s", " ", "z"),
+    ("g", "s", " ", "z"),
+    ("l", "s", " ", "z"),
+    ("m", "s", " ", "z"),
+    ("n", "s", " ", "z"),
+    ("r", "s", " ", "z"),
+    ("v", "s", " ", "z"),
+    ("", "s", "", "s"),
+    ("", "tion", "", "ʃən"),
+    ("", "tch", "", "t͡ʃ"),
+    ("", "th", "", "θ"),
+    ("", "tt", "", "t"),
+    ("", "t", "", "t"),
+    ("r", "u", "^%", "u"),
+    ("l", "u", "^%", "u"),
+    ("", "u", "^%", "ju"),
+    ("", "ur", "", "ɜ˞"),
+    ("", "ue", " ", "u"),
+    ("", "ui", "", "u"),
+    ("", "u", "", "ʌ"),
+    ("", "v", "", "v"),
+    ("", "wh", "", "w"),
+    (" ", "wr", "", "ɹ"),
+    ("", "w", "", "w"),
+    ("", "x", "", "ks"),
+    (" ", "y", "#", "j"),
+    (" :", "y", " ", "aɪ"),
+    ("#:", "y", " ", "i"),
+    ("", "y", "", "ɪ"),
+    ("", "zz", "", "z"),
+    ("", "z", "", "z"),
+];
+
+/// Common words the rules get wrong, in General American and, where
+/// `received` would not derive it, RP.
+#[rustfmt::skip]
+const EXCEPTIONS: &[(&str, &str, &str)] = &[
+    ("a", "ə", ""),
+    ("an", "ən", ""),
+    ("are", "ɑɹ", ""),
+    ("been", "bɪn", "biːn"),
+    ("break", "bɹeɪk", ""),
+    ("bread", "bɹɛd", ""),
+    ("brother", "bɹʌðə˞", ""),
+    ("come", "kʌm", ""),
+    ("could", "kʊd", ""),
+    ("do", "du", ""),
+    ("does", "dʌz", ""),
+    ("done", "dʌn", ""),
+    ("enough", "ɪnʌf", ""),
+    ("eye", "aɪ", ""),
+    ("father", "fɑðə˞", "fɑːðə"),
+    ("friend", "fɹɛnd", ""),
+    ("from", "fɹʌm", "fɹɒm"),
+    ("full", "fʊl", ""),
+    ("give", "ɡɪv", ""),
+    ("great", "ɡɹeɪt", ""),
+    ("has", "hæz", ""),
+    ("have", "hæv", ""),
+    ("head", "hɛd", ""),
+    ("his", "hɪz", ""),
+    ("i", "aɪ", ""),
+    ("is", "ɪz", ""),
+    ("live", "lɪv", ""),
+    ("love", "lʌv", ""),
+    ("mother", "mʌðə˞", ""),
+    ("move", "muv", ""),
+    ("none", "nʌn", ""),
+    ("of", "ʌv", "ɒv"),
+    ("one", "wʌn", ""),
+    ("other", "ʌðə˞", ""),
+    ("people", "pipəl", ""),
+    ("pull", "pʊl", ""),
+    ("push", "pʊʃ", ""),
+    ("put", "pʊt", ""),
+    ("said", "sɛd", ""),
+    ("says", "sɛz", ""),
+    ("should", "ʃʊd", ""),
+    ("sure", "ʃʊɹ", "ʃɔː"),
+    ("some", "sʌm", ""),
+    ("that", "ðæt", ""),
+    ("the", "ðə", ""),
+    ("their", "ðɛɹ", ""),
+    ("them", "ðɛm", ""),
+    ("then", "ðɛn", ""),
+    ("there", "ðɛɹ", ""),
+    ("these", "ðiz", ""),
+    ("they", "ðeɪ", ""),
+    ("this", "ðɪs", ""),
+    ("those", "ðoʊz", ""),
+    ("though", "ðoʊ", ""),
+    ("through", "θɹu", ""),
+    ("to", "tu", ""),
+    ("two", "tu", ""),
+    ("was", "wʌz", "wɒz"),
+    ("water", "wɔtə˞", ""),
+    ("were", "wɜ˞", ""),
+    ("what", "wʌt", "wɒt"),
+    ("where", "wɛɹ", ""),
+    ("who", "hu", ""),
+    ("with", "wɪθ", ""),
+    ("woman", "wʊmən", ""),
+    ("women", "wɪmɪn", ""),
+    ("would", "wʊd", ""),
+    ("you", "ju", ""),
+];
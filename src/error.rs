@@ -0,0 +1,96 @@
+//! The error for IPA that cannot be read, saying where and why, and
+//! the error for a line of a file that cannot be read.
+
+use core::error;
+use core::fmt;
+
+/// Why a symbol could not be read, with byte offsets into the input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// There was nothing to read.
+    Empty,
+    /// A character that is not a known symbol or mark where it
+    /// appears, such as the `7` of `t7`.
+    UnknownSymbol { offset: usize, symbol: char },
+    /// A diacritic or modifier with no symbol before it to attach to,
+    /// such as a leading `ʰ`.
+    DanglingDiacritic { offset: usize, mark: char },
+    /// A mark or tie bar that cannot join the symbols around it, such
+    /// as the ejective mark on a click or a tie bar between a stop and
+    /// a vowel.
+    InvalidCombination { offset: usize, mark: char },
+}
+
+impl Error {
+    /// The byte offset of the offending character, if there is one.
+    pub const fn offset(&self) -> Option<usize> {
+        match self {
+            Error::Empty => None,
+            Error::UnknownSymbol { offset, .. }
+            | Error::DanglingDiacritic { offset, .. }
+            | Error::InvalidCombination { offset, .. } => Some(*offset),
+        }
+    }
+
+    /// The error with its offset moved `by` bytes later, for an error
+    /// found in a slice starting `by` bytes into the input.
+    pub(crate) const fn shifted(self, by: usize) -> Self {
+        match self.offset() {
+            Some(offset) => self.at(offset + by),
+            None => self,
+        }
+    }
+
+    /// The error with its offset, if it has one, set to `offset`.
+    pub(crate) const fn at(self, offset: usize) -> Self {
+        match self {
+            Error::Empty => Error::Empty,
+            Error::UnknownSymbol { symbol, .. } => Error::UnknownSymbol { offset, symbol },
+            Error::DanglingDiacritic { mark, .. } => Error::DanglingDiacritic { offset, mark },
+            Error::InvalidCombination { mark, .. } => Error::InvalidCombination { offset, mark },
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Empty => write!(f, "no symbol to read"),
+            Error::UnknownSymbol { offset, symbol } => {
+                write!(f, "unknown IPA symbol `{}` at byte {}", symbol, offset)
+            }
+            Error::DanglingDiacritic { offset, mark } => {
+                write!(
+                    f,
+                    "`\u{25CC}{}` at byte {} has no symbol to attach to",
+                    mark, offset
+                )
+            }
+            Error::InvalidCombination { offset, mark } => {
+                write!(
+                    f,
+                    "`{}` at byte {} cannot join the symbols around it",
+                    mark, offset
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// An error on `line` of a file read line by line, counting from 1,
+/// and what is wrong with it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineError<K> {
+    pub line: usize,
+    pub kind: K,
+}
+
+impl<K: fmt::Display> fmt::Display for LineError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl<K: fmt::Debug + fmt::Display> error::Error for LineError<K> {}
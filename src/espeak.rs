@@ -0,0 +1,136 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::notation::{self, Table, UnknownSymbol};
+use crate::parser;
+use crate::segment::{ParseSegmentError, Segment};
+
+/// eSpeak NG phoneme mnemonics, as `espeak-ng -x` prints them, and
+/// their IPA equivalents.
+///
+/// The mnemonics follow Kirshenbaum, but each language's phoneme table
+/// gives them its own values; this table follows English, so that `a`
+/// is `æ` and `r` is `ɹ`, with the symbols other languages share added
+/// on. Where several mnemonics map to the same IPA, the first listed is
+/// the one produced by `from_ipa`.
+pub const TABLE: &Table = &[
+    // consonants
+    ("p", "p"),
+    ("b", "b"),
+    ("t", "t"),
+    ("d", "d"),
+    ("c", "c"),
+    ("J", "ɟ"),
+    ("k", "k"),
+    ("g", "ɡ"),
+    ("q", "q"),
+    ("G", "ɢ"),
+    ("?", "ʔ"),
+    ("tS", "t͡ʃ"),
+    ("dZ", "d͡ʒ"),
+    ("m", "m"),
+    ("M", "ɱ"),
+    ("n", "n"),
+    ("n^", "ɲ"),
+    ("N", "ŋ"),
+    ("m-", "m\u{329}"),
+    ("n-", "n\u{329}"),
+    ("l-", "l\u{329}"),
+    ("t#", "ɾ"),
+    ("*", "ɾ"),
+    ("r", "ɹ"),
+    ("R", "ʁ"),
+    ("B", "β"),
+    ("f", "f"),
+    ("v", "v"),
+    ("T", "θ"),
+    ("D", "ð"),
+    ("s", "s"),
+    ("z", "z"),
+    ("S", "ʃ"),
+    ("Z", "ʒ"),
+    ("C", "ç"),
+    ("x", "x"),
+    ("Q", "ɣ"),
+    ("X", "χ"),
+    ("H", "ħ"),
+    ("h", "h"),
+    ("l#", "ɬ"),
+    ("j", "j"),
+    ("l", "l"),
+    ("l^", "ʎ"),
+    ("w", "w"),
+    // vowels
+    ("i", "i"),
+    ("i:", "iː"),
+    ("I", "ɪ"),
+    ("I#", "ɨ"),
+    ("y", "y"),
+    ("Y", "ø"),
+    ("e", "e"),
+    ("E", "ɛ"),
+    ("W", "œ"),
+    ("a", "æ"),
+    ("a#", "ɐ"),
+    ("A", "ɑ"),
+    ("A:", "ɑː"),
+    ("0", "ɒ"),
+    ("V", "ʌ"),
+    ("O", "ɔ"),
+    ("O:", "ɔː"),
+    ("o", "o"),
+    ("U", "ʊ"),
+    ("u", "u"),
+    ("u:", "uː"),
+    ("@", "ə"),
+    ("3", "ə\u{2DE}"),
+    ("3:", "ɜː"),
+    ("@L", "əl"),
+    // diphthongs
+    ("eI", "eɪ"),
+    ("aI", "aɪ"),
+    ("OI", "ɔɪ"),
+    ("aU", "aʊ"),
+    ("oU", "oʊ"),
+    ("@U", "əʊ"),
+    ("i@", "ɪə"),
+    ("e@", "eə"),
+    ("U@", "ʊə"),
+    ("aI@", "aɪə"),
+    ("aU@", "aʊə"),
+    // suprasegmentals and diacritics
+    ("'", "ˈ"),
+    (",", "ˌ"),
+    (":", "ː"),
+    ("~", "\u{303}"),
+    // marks with no mnemonic of their own: aspiration is written as a
+    // following `h` and other tied affricates unbroken, as `tl#`
+    ("h", "ʰ"),
+    ("", "\u{361}"),
+    ("", "\u{35C}"),
+    // unstressed, which IPA leaves unmarked
+    ("%", ""),
+];
+
+/// Converts eSpeak mnemonics to IPA, e.g. `"h@l'oU"` to `"həlˈoʊ"`.
+pub fn to_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    notation::to_ipa(s, TABLE)
+}
+
+/// Converts an IPA transcription to eSpeak mnemonics.
+pub fn from_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    notation::from_ipa(s, TABLE)
+}
+
+/// Parses eSpeak mnemonics directly into segments.
+pub fn to_segments(s: &str) -> Result<Vec<Segment>, ParseSegmentError> {
+    parser::parse(&to_ipa(s)?)
+}
+
+/// Writes `segments` as eSpeak mnemonics.
+pub fn from_segments(segments: &[Segment]) -> Result<String, UnknownSymbol> {
+    let ipa: String = segments.iter().map(Segment::to_string).collect();
+    from_ipa(&ipa)
+}
@@ -0,0 +1,218 @@
+//! The Extensions to the IPA (extIPA) for transcribing disordered
+//! speech: extra consonant symbols, diacritics for articulations the
+//! IPA has no marks for, indeterminate sounds, and the bracketing
+//! conventions for silent articulation and pauses.
+
+use core::fmt;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::parser;
+use crate::segment::{ParseSegmentError, Segment};
+
+/// Symbols of the extIPA that are not IPA letters, paired with their
+/// names: consonants first, then the circles for indeterminate sounds.
+#[rustfmt::skip]
+pub const SYMBOLS: &[(&str, &str)] = &[
+    ("\u{2A9}", "velopharyngeal fricative"), // ʩ
+    ("\u{2AA}", "voiceless lateral and median alveolar fricative"), // ʪ
+    ("\u{2AB}", "voiced lateral and median alveolar fricative"), // ʫ
+    ("\u{2AC}", "bilabial percussive"), // ʬ
+    ("\u{2AD}", "bidental percussive"), // ʭ
+    ("\u{A1}", "sublaminal lower alveolar percussive"), // ¡
+    ("\u{25EF}", "indeterminate sound"), // ◯
+    ("\u{24B8}", "indeterminate consonant"), // Ⓒ
+    ("\u{24CB}", "indeterminate vowel"), // Ⓥ
+    ("\u{24C5}", "indeterminate plosive"), // Ⓟ
+    ("\u{24BB}", "indeterminate fricative"), // Ⓕ
+    ("\u{24C3}", "indeterminate nasal"), // Ⓝ
+];
+
+/// Marks of the extIPA, paired with their names. All are combining
+/// marks except the airflow arrows, which follow the sound.
+///
+/// **Note** that the strong and weak articulation marks are read as
+/// stiff and slack voice by `Segment`'s parser, but keep their extIPA
+/// meaning here.
+#[rustfmt::skip]
+pub const DIACRITICS: &[(char, &str)] = &[
+    ('\u{346}', "dentolabial"), // ◌͆
+    ('\u{347}', "labioalveolar"), // ◌͇
+    ('\u{348}', "strong articulation"), // ◌͈
+    ('\u{349}', "weak articulation"), // ◌͉
+    ('\u{34A}', "denasal"), // ◌͊
+    ('\u{34B}', "nasal escape"), // ◌͋
+    ('\u{34C}', "velopharyngeal friction"), // ◌͌
+    ('\u{34D}', "spread-lip"), // ◌͍
+    ('\u{34E}', "whistled"), // ◌͎
+    ('\u{2193}', "ingressive airflow"), // ↓
+    ('\u{2191}', "egressive airflow"), // ↑
+];
+
+/// A sound in an extIPA transcription: an IPA segment or extIPA
+/// symbol, with any extIPA marks written on it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sound {
+    base: Base,
+    marks: Vec<char>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Base {
+    Segment(Segment),
+    Symbol(&'static str, &'static str),
+}
+
+impl Sound {
+    /// The IPA segment the sound is written with, or `None` for an
+    /// extIPA symbol.
+    pub fn segment(&self) -> Option<Segment> {
+        match self.base {
+            Base::Segment(s) => Some(s),
+            Base::Symbol(..) => None,
+        }
+    }
+
+    /// The extIPA marks on the sound, in the order written.
+    pub fn marks(&self) -> &[char] {
+        &self.marks
+    }
+
+    /// The sound's name, with its combining marks named first and its
+    /// airflow after: `p͆↓` is the dentolabial voiceless bilabial
+    /// plosive with ingressive airflow.
+    pub fn name(&self) -> String {
+        let (combining, spacing): (Vec<char>, Vec<char>) =
+            self.marks.iter().partition(|&&m| parser::is_combining(m));
+        let mut name: Vec<&str> = combining.into_iter().filter_map(mark_name).collect();
+        let base = match self.base {
+            Base::Segment(s) => s.name(),
+            Base::Symbol(_, name) => name.to_string(),
+        };
+        name.push(&base);
+        let mut name = name.join(" ");
+        if !spacing.is_empty() {
+            let spacing: Vec<&str> = spacing.into_iter().filter_map(mark_name).collect();
+            name.push_str(" with ");
+            name.push_str(&spacing.join(" and "));
+        }
+        name
+    }
+}
+
+impl fmt::Display for Sound {
+    /// Writes the base with the combining marks before any spacing
+    /// modifiers it carries, then the airflow arrows.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let base = match self.base {
+            Base::Segment(s) => s.to_string(),
+            Base::Symbol(symbol, _) => symbol.to_string(),
+        };
+        let split = base
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| parser::is_modifier(*c) && !parser::is_combining(*c))
+            .last()
+            .map_or(base.len(), |(i, _)| i);
+        let (combining, spacing): (Vec<char>, Vec<char>) =
+            self.marks.iter().partition(|&&m| parser::is_combining(m));
+        f.write_str(&base[..split])?;
+        combining.iter().try_for_each(|m| write!(f, "{}", m))?;
+        f.write_str(&base[split..])?;
+        spacing.iter().try_for_each(|m| write!(f, "{}", m))
+    }
+}
+
+/// A pause, written as one to three dots in parentheses.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Pause {
+    Short,
+    Medium,
+    Long,
+}
+
+/// One unit of an extIPA transcription.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Unit {
+    Sound(Sound),
+    /// Articulation without sound, written in parentheses, e.g. `(ʃ)`.
+    Silent(Vec<Sound>),
+    Pause(Pause),
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unit::Sound(s) => s.fmt(f),
+            Unit::Silent(sounds) => {
+                f.write_str("(")?;
+                sounds.iter().try_for_each(|s| s.fmt(f))?;
+                f.write_str(")")
+            }
+            Unit::Pause(pause) => {
+                let dots = match pause {
+                    Pause::Short => "(.)",
+                    Pause::Medium => "(..)",
+                    Pause::Long => "(...)",
+                };
+                f.write_str(dots)
+            }
+        }
+    }
+}
+
+/// The name of extIPA mark `c`.
+pub fn mark_name(c: char) -> Option<&'static str> {
+    DIACRITICS
+        .iter()
+        .find(|(m, _)| *m == c)
+        .map(|(_, name)| *name)
+}
+
+/// Parses an extIPA transcription, e.g. `"ʩ(ʃ)(..)p͆↓"`. Tokens are
+/// split as by `parser::tokenize`; a token is an extIPA symbol or an
+/// IPA segment once its extIPA marks are taken off, and an airflow
+/// arrow belongs to the sound before it.
+pub fn parse(s: &str) -> Result<Vec<Unit>, ParseSegmentError> {
+    let mut units = Vec::new();
+    let mut tokens = parser::tokenize(s).into_iter();
+    while let Some(token) = tokens.next() {
+        if token == "(" {
+            let inner: Vec<&str> = tokens.by_ref().take_while(|t| *t != ")").collect();
+            let pause = match inner[..] {
+                ["."] => Some(Pause::Short),
+                [".", "."] => Some(Pause::Medium),
+                [".", ".", "."] => Some(Pause::Long),
+                _ => None,
+            };
+            units.push(match pause {
+                Some(pause) => Unit::Pause(pause),
+                None if inner.is_empty() => return Err(ParseSegmentError::Empty),
+                None => Unit::Silent(inner.into_iter().map(sound).collect::<Result<_, _>>()?),
+            });
+            continue;
+        }
+        let mut chars = token.chars();
+        if let (Some(arrow), None) = (chars.next(), chars.next()) {
+            if let (Some(_), Some(Unit::Sound(prev))) = (mark_name(arrow), units.last_mut()) {
+                prev.marks.push(arrow);
+                continue;
+            }
+        }
+        units.push(Unit::Sound(sound(token)?));
+    }
+    Ok(units)
+}
+
+fn sound(token: &str) -> Result<Sound, ParseSegmentError> {
+    let marks: Vec<char> = token.chars().filter(|&c| mark_name(c).is_some()).collect();
+    let base: String = token.chars().filter(|&c| mark_name(c).is_none()).collect();
+    let base = match SYMBOLS.iter().find(|(symbol, _)| *symbol == base) {
+        Some(&(symbol, name)) => Base::Symbol(symbol, name),
+        None => Base::Segment(base.parse()?),
+    };
+    Ok(Sound { base, marks })
+}
@@ -0,0 +1,1030 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::string::ToString;
+
+use crate::affricate::Affricate;
+#[cfg(feature = "alloc")]
+use crate::classes::Segments;
+use crate::diacritics::Diacritic;
+use crate::ipa::{
+    Airstream, Articulation, Backness, Consonant, Height, Manner, Phonation, Place,
+    SecondaryArticulation, Vowel,
+};
+use crate::segment::Segment;
+
+/// Binary distinctive features, in the tradition of SPE (Chomsky &
+/// Halle 1968) with the place features of later feature geometry.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Feature {
+    Syllabic,
+    Consonantal,
+    Sonorant,
+    Continuant,
+    DelayedRelease,
+    Nasal,
+    Lateral,
+    Strident,
+    Voice,
+    SpreadGlottis,
+    ConstrictedGlottis,
+    Labial,
+    Round,
+    Coronal,
+    Anterior,
+    Distributed,
+    Dorsal,
+    High,
+    Low,
+    Front,
+    Back,
+    Tense,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 22] = [
+        Feature::Syllabic,
+        Feature::Consonantal,
+        Feature::Sonorant,
+        Feature::Continuant,
+        Feature::DelayedRelease,
+        Feature::Nasal,
+        Feature::Lateral,
+        Feature::Strident,
+        Feature::Voice,
+        Feature::SpreadGlottis,
+        Feature::ConstrictedGlottis,
+        Feature::Labial,
+        Feature::Round,
+        Feature::Coronal,
+        Feature::Anterior,
+        Feature::Distributed,
+        Feature::Dorsal,
+        Feature::High,
+        Feature::Low,
+        Feature::Front,
+        Feature::Back,
+        Feature::Tense,
+    ];
+
+    /// The feature's conventional name, e.g. `"delayed_release"`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Feature::Syllabic => "syllabic",
+            Feature::Consonantal => "consonantal",
+            Feature::Sonorant => "sonorant",
+            Feature::Continuant => "continuant",
+            Feature::DelayedRelease => "delayed_release",
+            Feature::Nasal => "nasal",
+            Feature::Lateral => "lateral",
+            Feature::Strident => "strident",
+            Feature::Voice => "voice",
+            Feature::SpreadGlottis => "spread_glottis",
+            Feature::ConstrictedGlottis => "constricted_glottis",
+            Feature::Labial => "labial",
+            Feature::Round => "round",
+            Feature::Coronal => "coronal",
+            Feature::Anterior => "anterior",
+            Feature::Distributed => "distributed",
+            Feature::Dorsal => "dorsal",
+            Feature::High => "high",
+            Feature::Low => "low",
+            Feature::Front => "front",
+            Feature::Back => "back",
+            Feature::Tense => "tense",
+        }
+    }
+
+    /// Looks up a feature by its name or a common abbreviation, e.g.
+    /// `"voice"`, `"son"`, or `"cons"`.
+    pub fn from_name(name: &str) -> Option<Feature> {
+        const ABBREVIATIONS: [(&str, Feature); 10] = [
+            ("syl", Feature::Syllabic),
+            ("cons", Feature::Consonantal),
+            ("son", Feature::Sonorant),
+            ("cont", Feature::Continuant),
+            ("del_rel", Feature::DelayedRelease),
+            ("strid", Feature::Strident),
+            ("sg", Feature::SpreadGlottis),
+            ("cg", Feature::ConstrictedGlottis),
+            ("ant", Feature::Anterior),
+            ("dist", Feature::Distributed),
+        ];
+        Feature::ALL
+            .into_iter()
+            .find(|f| f.name() == name)
+            .or_else(|| {
+                ABBREVIATIONS
+                    .iter()
+                    .find(|(abbr, _)| *abbr == name)
+                    .map(|(_, f)| *f)
+            })
+    }
+
+    const fn bit(&self) -> u32 {
+        1 << *self as u32
+    }
+}
+
+/// A (possibly partial) specification of features, each of which is
+/// either `+`, `-`, or left unspecified.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FeatureBundle {
+    plus: u32,
+    minus: u32,
+}
+
+impl FeatureBundle {
+    /// A bundle with every feature unspecified.
+    pub const fn new() -> Self {
+        FeatureBundle { plus: 0, minus: 0 }
+    }
+
+    /// Returns the bundle with `feature` set to `value`.
+    pub const fn with(mut self, feature: Feature, value: bool) -> Self {
+        if value {
+            self.plus |= feature.bit();
+            self.minus &= !feature.bit();
+        } else {
+            self.minus |= feature.bit();
+            self.plus &= !feature.bit();
+        }
+        self
+    }
+
+    /// Returns the bundle with `feature` unspecified.
+    pub const fn without(mut self, feature: Feature) -> Self {
+        self.plus &= !feature.bit();
+        self.minus &= !feature.bit();
+        self
+    }
+
+    pub fn set(&mut self, feature: Feature, value: bool) {
+        *self = self.with(feature, value);
+    }
+
+    /// The value of `feature`, or `None` if it is unspecified.
+    pub const fn get(&self, feature: Feature) -> Option<bool> {
+        if self.plus & feature.bit() != 0 {
+            Some(true)
+        } else if self.minus & feature.bit() != 0 {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `feature` has the value `value`.
+    pub const fn is(&self, feature: Feature, value: bool) -> bool {
+        matches!(self.get(feature), Some(v) if v == value)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.plus == 0 && self.minus == 0
+    }
+
+    /// The number of specified features.
+    pub const fn len(&self) -> usize {
+        (self.plus | self.minus).count_ones() as usize
+    }
+
+    /// The specified features and their values, in `Feature::ALL` order.
+    pub fn iter(&self) -> impl Iterator<Item = (Feature, bool)> + '_ {
+        Feature::ALL
+            .into_iter()
+            .filter_map(|f| self.get(f).map(|v| (f, v)))
+    }
+
+    /// Combines two bundles, returning `None` if they disagree on any
+    /// feature specified in both.
+    pub const fn unify(&self, other: &FeatureBundle) -> Option<FeatureBundle> {
+        if self.plus & other.minus != 0 || self.minus & other.plus != 0 {
+            return None;
+        }
+        Some(FeatureBundle {
+            plus: self.plus | other.plus,
+            minus: self.minus | other.minus,
+        })
+    }
+
+    /// Whether `other` agrees with every feature specified here, i.e.
+    /// whether `self` is a description that `other` satisfies.
+    pub const fn subsumes(&self, other: &FeatureBundle) -> bool {
+        self.plus & !other.plus == 0 && self.minus & !other.minus == 0
+    }
+
+    /// `other` with every feature specified here overwritten, as when
+    /// a rule's structural change is applied to a segment.
+    pub const fn overwrite(&self, other: &FeatureBundle) -> FeatureBundle {
+        let mask = self.plus | self.minus;
+        FeatureBundle {
+            plus: (other.plus & !mask) | self.plus,
+            minus: (other.minus & !mask) | self.minus,
+        }
+    }
+
+    /// The features specified in both bundles with opposite values.
+    pub fn conflicts(&self, other: &FeatureBundle) -> impl Iterator<Item = Feature> {
+        let mask = (self.plus & other.minus) | (self.minus & other.plus);
+        Feature::ALL
+            .into_iter()
+            .filter(move |f| mask & f.bit() != 0)
+    }
+
+    /// The features whose value (or specification) differs between the
+    /// two bundles.
+    pub fn differences(&self, other: &FeatureBundle) -> impl Iterator<Item = Feature> {
+        let mask = (self.plus ^ other.plus) | (self.minus ^ other.minus);
+        Feature::ALL
+            .into_iter()
+            .filter(move |f| mask & f.bit() != 0)
+    }
+
+    /// The features on which the two bundles agree.
+    pub const fn intersection(&self, other: &FeatureBundle) -> FeatureBundle {
+        FeatureBundle {
+            plus: self.plus & other.plus,
+            minus: self.minus & other.minus,
+        }
+    }
+}
+
+impl fmt::Display for FeatureBundle {
+    /// Writes the bundle as a feature matrix, e.g. `[+voice, -sonorant]`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, (feature, value)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}{}", if value { '+' } else { '-' }, feature.name())?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// A weight for each feature, used by `distance`. The default weighs
+/// every feature equally at `1.0`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Weights([f32; Feature::ALL.len()]);
+
+impl Weights {
+    /// Every feature weighted `weight`.
+    pub const fn uniform(weight: f32) -> Self {
+        Weights([weight; Feature::ALL.len()])
+    }
+
+    pub const fn with(mut self, feature: Feature, weight: f32) -> Self {
+        self.0[feature as usize] = weight;
+        self
+    }
+
+    pub const fn get(&self, feature: Feature) -> f32 {
+        self.0[feature as usize]
+    }
+
+    /// The sum of all weights, the largest possible distance.
+    pub fn total(&self) -> f32 {
+        self.0.iter().sum()
+    }
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Weights::uniform(1.0)
+    }
+}
+
+/// The feature bundle of a consonant.
+pub fn consonant(c: &Consonant) -> FeatureBundle {
+    use Feature::*;
+    let manner = c.manner();
+    let articulation = c.articulation();
+    let diacritics = c.diacritics();
+    let secondary = c.secondary();
+    let sonorant = matches!(
+        manner,
+        Manner::Nasal
+            | Manner::Approximant
+            | Manner::TapFlap
+            | Manner::Trill
+            | Manner::LatApprox
+            | Manner::LatTapFlap
+    ) && c.airstream().is_pulmonic();
+    let continuant = !matches!(manner, Manner::Nasal | Manner::Plosive);
+    let fricative = matches!(manner, Manner::Fricative { .. } | Manner::LatFric);
+    let glottal = matches!(articulation, Articulation::Glottal);
+    let consonantal = !glottal && !matches!(manner, Manner::Approximant);
+    let strident = matches!(manner, Manner::Fricative { sibilant: true })
+        || (fricative
+            && matches!(
+                articulation,
+                Articulation::Labiodental | Articulation::Uvular
+            ));
+
+    let mut b = FeatureBundle::new()
+        .with(Syllabic, diacritics.contains(Diacritic::Syllabic))
+        .with(Consonantal, consonantal)
+        .with(Sonorant, sonorant)
+        .with(Continuant, continuant)
+        .with(DelayedRelease, fricative)
+        .with(
+            Nasal,
+            matches!(manner, Manner::Nasal) || diacritics.contains(Diacritic::Nasalized),
+        )
+        .with(
+            Lateral,
+            matches!(
+                manner,
+                Manner::LatFric | Manner::LatApprox | Manner::LatTapFlap
+            ),
+        )
+        .with(Strident, strident)
+        .with(Voice, c.phonation().is_voiced())
+        .with(
+            SpreadGlottis,
+            diacritics.contains(Diacritic::Aspirated)
+                || c.phonation() == Phonation::Breathy
+                || (glottal && fricative),
+        )
+        .with(
+            ConstrictedGlottis,
+            matches!(
+                c.airstream(),
+                Airstream::GlottalicEgressive | Airstream::GlottalicIngressive
+            ) || c.phonation() == Phonation::Creaky
+                || secondary == Some(SecondaryArticulation::Glottalized)
+                || (glottal && matches!(manner, Manner::Plosive)),
+        );
+
+    let labialized = secondary == Some(SecondaryArticulation::Labialized);
+    b.set(Labial, matches!(c.place(), Place::Labial) || labialized);
+    b.set(Round, labialized);
+    let coronal = matches!(c.place(), Place::Corona);
+    b.set(Coronal, coronal);
+    if coronal {
+        b.set(
+            Anterior,
+            matches!(
+                articulation,
+                Articulation::Linguolabial | Articulation::Dental | Articulation::Alveolar
+            ),
+        );
+        let laminal = matches!(
+            articulation,
+            Articulation::Dental | Articulation::Postalveolar | Articulation::Palatal
+        );
+        b.set(
+            Distributed,
+            (laminal || diacritics.contains(Diacritic::Laminal))
+                && !diacritics.contains(Diacritic::Apical),
+        );
+    }
+    let dorsal = matches!(
+        articulation,
+        Articulation::Palatal | Articulation::Velar | Articulation::Uvular
+    );
+    b.set(Dorsal, dorsal);
+    if dorsal {
+        b.set(
+            High,
+            matches!(articulation, Articulation::Palatal | Articulation::Velar),
+        );
+        b.set(Low, false);
+        b.set(Front, matches!(articulation, Articulation::Palatal));
+        b.set(Back, !matches!(articulation, Articulation::Palatal));
+    }
+    if matches!(
+        articulation,
+        Articulation::Pharyngeal | Articulation::Epiglottal
+    ) {
+        b.set(Low, true);
+    }
+    // A second place adds its own place features, so /k͡p/ is both
+    // [+labial] and [+dorsal]; where the two disagree the positive
+    // value wins. Labial-dorsal glides and fricatives are rounded.
+    if let Some(second) = c.coarticulation() {
+        let other = consonant(&Consonant::new(second.into(), manner, c.phonation()));
+        for feature in [
+            Labial,
+            Coronal,
+            Anterior,
+            Distributed,
+            Dorsal,
+            High,
+            Low,
+            Front,
+            Back,
+        ] {
+            if let Some(value) = other.get(feature) {
+                if value || b.get(feature).is_none() {
+                    b.set(feature, value);
+                }
+            }
+        }
+        if continuant && b.is(Labial, true) && b.is(Dorsal, true) {
+            b.set(Round, true);
+        }
+    }
+    // Secondary articulations raise or retract the tongue body, as a
+    // high front, high back, or low back vowel would.
+    let body = match secondary {
+        Some(SecondaryArticulation::Palatalized) => Some((true, false, true, false)),
+        Some(SecondaryArticulation::Velarized) => Some((true, false, false, true)),
+        Some(SecondaryArticulation::Pharyngealized) => Some((false, true, false, true)),
+        _ => None,
+    };
+    if let Some((high, low, front, back)) = body {
+        b.set(High, high);
+        b.set(Low, low);
+        b.set(Front, front);
+        b.set(Back, back);
+    }
+    b
+}
+
+/// The feature bundle of a vowel.
+pub fn vowel(v: &Vowel) -> FeatureBundle {
+    use Feature::*;
+    let diacritics = v.diacritics();
+    let height = v.height();
+    let backness = v.backness();
+    let lax = matches!(
+        height,
+        Height::NearClose | Height::Mid | Height::OpenMid | Height::NearOpen
+    );
+    FeatureBundle::new()
+        .with(Syllabic, !diacritics.contains(Diacritic::NonSyllabic))
+        .with(Consonantal, false)
+        .with(Sonorant, true)
+        .with(Continuant, true)
+        .with(DelayedRelease, false)
+        .with(Nasal, diacritics.contains(Diacritic::Nasalized))
+        .with(Lateral, false)
+        .with(Strident, false)
+        .with(Voice, !diacritics.contains(Diacritic::Voiceless))
+        .with(SpreadGlottis, diacritics.contains(Diacritic::BreathyVoiced))
+        .with(
+            ConstrictedGlottis,
+            diacritics.contains(Diacritic::CreakyVoiced),
+        )
+        .with(Labial, v.is_rounded())
+        .with(Round, v.is_rounded())
+        .with(Coronal, false)
+        .with(Dorsal, true)
+        .with(High, matches!(height, Height::Close | Height::NearClose))
+        .with(Low, matches!(height, Height::Open | Height::NearOpen))
+        .with(
+            Front,
+            matches!(backness, Backness::Front | Backness::NearFront),
+        )
+        .with(
+            Back,
+            matches!(backness, Backness::Back | Backness::NearBack),
+        )
+        .with(Tense, !lax)
+}
+
+/// The feature bundle of an affricate: its fricative's, released
+/// from a full closure.
+pub fn affricate(a: &Affricate) -> FeatureBundle {
+    consonant(&a.fricative())
+        .with(Feature::Continuant, false)
+        .with(Feature::DelayedRelease, true)
+}
+
+/// The feature bundle of a segment, or `None` for suprasegmentals and
+/// tones.
+pub fn of(segment: &Segment) -> Option<FeatureBundle> {
+    match segment {
+        Segment::Consonant(c) => Some(consonant(c)),
+        Segment::Affricate(a) => Some(affricate(a)),
+        Segment::Vowel(v) => Some(vowel(v)),
+        Segment::Suprasegmental(_) | Segment::Tone(_) => None,
+    }
+}
+
+/// Diacritics whose effect is captured by the feature bundle, and
+/// which `realize` adds back as needed.
+#[cfg(feature = "alloc")]
+const FEATURAL: [Diacritic; 9] = [
+    Diacritic::Voiceless,
+    Diacritic::Voiced,
+    Diacritic::Aspirated,
+    Diacritic::BreathyVoiced,
+    Diacritic::CreakyVoiced,
+    Diacritic::Nasalized,
+    Diacritic::Labialized,
+    Diacritic::Syllabic,
+    Diacritic::NonSyllabic,
+];
+
+/// Finds the segment best matching `bundle`: the chart segment with
+/// the fewest differing features, with diacritics added for voicing,
+/// aspiration, nasalization, rounding and syllabicity where the chart
+/// segment still disagrees with `bundle`.
+///
+/// Returns `None` only if `bundle` is empty.
+#[cfg(feature = "alloc")]
+pub fn realize(bundle: &FeatureBundle) -> Option<Segment> {
+    realize_near(bundle, None)
+}
+
+/// `realize`, breaking ties between equally good chart segments in
+/// favour of one articulated where `near` is, then of one written
+/// without diacritics.
+#[cfg(feature = "alloc")]
+fn realize_near(bundle: &FeatureBundle, near: Option<&Segment>) -> Option<Segment> {
+    if bundle.is_empty() {
+        return None;
+    }
+    let (mut best, have) = Segments::all()
+        .iter()
+        .filter_map(|s| s.features().map(|f| (s, f)))
+        .min_by_key(|(s, f)| {
+            let moved = near.is_some_and(|n| !same_position(n, s));
+            // Prefer plain letters such as `t` over marked ones like `t̼`.
+            let marked = s.to_string().chars().count() > 1;
+            (f.differences(bundle).count(), moved, marked)
+        })?;
+    let wants = |f: Feature, v: bool| bundle.is(f, v) && !have.is(f, v);
+    let vowel = best.is_vowel();
+    let marks = [
+        (wants(Feature::Voice, false), Diacritic::Voiceless),
+        (wants(Feature::Voice, true) && !vowel, Diacritic::Voiced),
+        (
+            wants(Feature::SpreadGlottis, true) && !vowel && !bundle.is(Feature::Voice, true),
+            Diacritic::Aspirated,
+        ),
+        (
+            wants(Feature::SpreadGlottis, true) && (vowel || bundle.is(Feature::Voice, true)),
+            Diacritic::BreathyVoiced,
+        ),
+        (
+            wants(Feature::ConstrictedGlottis, true),
+            Diacritic::CreakyVoiced,
+        ),
+        (wants(Feature::Nasal, true), Diacritic::Nasalized),
+        (wants(Feature::Round, true) && !vowel, Diacritic::Labialized),
+        (
+            wants(Feature::Syllabic, true) && !vowel,
+            Diacritic::Syllabic,
+        ),
+        (
+            wants(Feature::Syllabic, false) && vowel,
+            Diacritic::NonSyllabic,
+        ),
+    ];
+    for (wanted, mark) in marks {
+        if wanted {
+            best = best.with_diacritic(mark);
+        }
+    }
+    Some(best)
+}
+
+#[cfg(feature = "alloc")]
+fn same_position(a: &Segment, b: &Segment) -> bool {
+    match (a, b) {
+        (Segment::Consonant(a), Segment::Consonant(b)) => a.articulation() == b.articulation(),
+        (Segment::Vowel(a), Segment::Vowel(b)) => {
+            a.height() == b.height() && a.backness() == b.backness()
+        }
+        _ => false,
+    }
+}
+
+/// Applies a feature change to `segment`, e.g. `[-voice]` to `/b/`
+/// gives `/p/`. Diacritics that no feature accounts for, such as the
+/// dental or apical marks, are kept, as is a secondary articulation
+/// the change does not contradict, and the length.
+#[cfg(feature = "alloc")]
+pub fn change(segment: &Segment, change: &FeatureBundle) -> Option<Segment> {
+    let changed = change_quality(segment, change)?;
+    Some(match segment.length() {
+        Some(length) => changed.with_length(length),
+        None => changed,
+    })
+}
+
+/// Applies `change` to the quality of `segment`, leaving out its
+/// length, which `change` puts back.
+#[cfg(feature = "alloc")]
+fn change_quality(segment: &Segment, change: &FeatureBundle) -> Option<Segment> {
+    if let Segment::Affricate(a) = segment {
+        return change_affricate(a, change);
+    }
+    let target = change.overwrite(&of(segment)?);
+    // A secondary articulation is set aside while the primary one is
+    // realized, and put back unless the change undoes it, e.g. `[-round]`
+    // on `/kʷ/`.
+    let (primary, secondary) = match segment {
+        Segment::Consonant(c) => (Segment::Consonant(c.with_secondary(None)), c.secondary()),
+        _ => (*segment, None),
+    };
+    let realized = realize_near(&change.overwrite(&of(&primary)?), Some(&primary))?;
+    let kept = primary
+        .diacritics()
+        .iter()
+        .filter(|d| !FEATURAL.contains(d))
+        .fold(realized, |s, d| s.with_diacritic(d));
+    let restored = match (kept, secondary) {
+        (Segment::Consonant(c), Some(_)) if c.secondary().is_none() => {
+            Segment::Consonant(c.with_secondary(secondary))
+        }
+        _ => return Some(kept),
+    };
+    let off = |s: &Segment| of(s).map_or(usize::MAX, |f| f.differences(&target).count());
+    Some(if off(&restored) < off(&kept) {
+        restored
+    } else {
+        kept
+    })
+}
+
+/// `change` for affricates, which are changed half by half. A change
+/// to `[+continuant]` leaves only the fricative, and one the halves
+/// cannot take, such as `[+nasal]`, is realized as for other segments.
+#[cfg(feature = "alloc")]
+fn change_affricate(a: &Affricate, bundle: &FeatureBundle) -> Option<Segment> {
+    let fricative = Segment::Consonant(a.fricative());
+    if bundle.is(Feature::Continuant, true) {
+        return change(&fricative, bundle);
+    }
+    let halves = bundle
+        .without(Feature::Continuant)
+        .without(Feature::DelayedRelease);
+    let changed = match (
+        change(&Segment::Consonant(a.stop()), &halves)?,
+        change(&fricative, &halves)?,
+    ) {
+        (Segment::Consonant(stop), Segment::Consonant(fricative)) => {
+            Affricate::new(stop, fricative).map(Segment::Affricate)
+        }
+        _ => None,
+    };
+    let target = bundle.overwrite(&affricate(a));
+    changed
+        .filter(|s| of(s) == Some(target))
+        .or_else(|| realize_near(&target, Some(&fricative)))
+}
+
+/// The weighted sum of the features on which `a` and `b` differ,
+/// counting a feature specified in one and not the other as a
+/// difference. Segments without features (suprasegmentals and tones)
+/// are at distance `0.0` from themselves and `weights.total()` from
+/// anything else.
+pub fn distance(a: &Segment, b: &Segment, weights: &Weights) -> f32 {
+    match (of(a), of(b)) {
+        (Some(x), Some(y)) => x.differences(&y).map(|f| weights.get(f)).sum(),
+        _ if a == b => 0.0,
+        _ => weights.total(),
+    }
+}
+
+/// The smallest feature specification that picks out exactly
+/// `segments` from `inventory`, or `None` if they are not a natural
+/// class there: no bundle shared by all of them excludes every other
+/// segment of the inventory. Of several smallest specifications, the
+/// one with the earliest features in `Feature::ALL` order is chosen.
+///
+/// Segments of `segments` missing from `inventory` are counted as
+/// part of it; an empty set, or one with a segment that has no
+/// features, is not a natural class.
+///
+/// ```
+/// use allo::features::{self, Feature, FeatureBundle};
+///
+/// let inventory = allo::parser::parse("ptkbdɡmnaiu").unwrap();
+/// let voiced_stops = allo::parser::parse("bdɡ").unwrap();
+/// assert_eq!(
+///     features::natural_class(&voiced_stops, &inventory),
+///     Some(FeatureBundle::new().with(Feature::Sonorant, false).with(Feature::Voice, true))
+/// );
+/// let odd = allo::parser::parse("pa").unwrap();
+/// assert_eq!(features::natural_class(&odd, &inventory), None);
+/// ```
+pub fn natural_class(segments: &[Segment], inventory: &[Segment]) -> Option<FeatureBundle> {
+    let mut shared: Option<FeatureBundle> = None;
+    for segment in segments {
+        let bundle = of(segment)?;
+        shared = Some(shared.map_or(bundle, |s| s.intersection(&bundle)));
+    }
+    let shared = shared?;
+    let mut features = [(Feature::ALL[0], false); Feature::ALL.len()];
+    let mut n = 0;
+    for specified in shared.iter() {
+        features[n] = specified;
+        n += 1;
+    }
+    let picks_out = |bundle: &FeatureBundle| {
+        inventory
+            .iter()
+            .all(|s| segments.contains(s) || !of(s).is_some_and(|f| bundle.subsumes(&f)))
+    };
+    (0..=n).find_map(|size| smallest(&features[..n], size, FeatureBundle::new(), &picks_out))
+}
+
+/// The first bundle adding `size` of `features` to `bundle` that
+/// `picks_out` accepts.
+fn smallest(
+    features: &[(Feature, bool)],
+    size: usize,
+    bundle: FeatureBundle,
+    picks_out: &impl Fn(&FeatureBundle) -> bool,
+) -> Option<FeatureBundle> {
+    if size == 0 {
+        return picks_out(&bundle).then_some(bundle);
+    }
+    (0..=features.len().checked_sub(size)?).find_map(|i| {
+        let (feature, value) = features[i];
+        smallest(
+            &features[i + 1..],
+            size - 1,
+            bundle.with(feature, value),
+            picks_out,
+        )
+    })
+}
+
+/// A feature system segments can be described in, for tools that
+/// expect one other than this crate's own.
+///
+/// ```
+/// use allo::features::FeatureSystem;
+///
+/// let spec = FeatureSystem::PanPhon.featurize(&allo::ipa!("kʰ")).unwrap();
+/// assert_eq!(spec.get("sg"), Some(true));
+/// assert_eq!(spec.get("velaric"), Some(false));
+/// assert_eq!(FeatureSystem::PanPhon.features().count(), 24);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FeatureSystem {
+    /// This crate's features, as `Feature` and `of` give them.
+    #[default]
+    Native,
+    /// The features of Chomsky & Halle (1968), with `vocalic` in
+    /// place of `syllabic` and the laryngeal features named as there.
+    Spe,
+    /// The features of Hayes (2009), _Introductory Phonology_, which
+    /// add `approximant`, `tap`, `trill`, `labiodental` and `long`.
+    Hayes,
+    /// The 24 features of PanPhon (Mortensen et al. 2016), in its
+    /// order.
+    PanPhon,
+}
+
+/// How a system's feature is read off a segment and its native
+/// features.
+type Reading = (&'static str, fn(&Segment, &FeatureBundle) -> Option<bool>);
+
+const NATIVE: [Reading; 22] = [
+    ("syllabic", |_, b| b.get(Feature::Syllabic)),
+    ("consonantal", |_, b| b.get(Feature::Consonantal)),
+    ("sonorant", |_, b| b.get(Feature::Sonorant)),
+    ("continuant", |_, b| b.get(Feature::Continuant)),
+    ("delayed_release", |_, b| b.get(Feature::DelayedRelease)),
+    ("nasal", |_, b| b.get(Feature::Nasal)),
+    ("lateral", |_, b| b.get(Feature::Lateral)),
+    ("strident", |_, b| b.get(Feature::Strident)),
+    ("voice", |_, b| b.get(Feature::Voice)),
+    ("spread_glottis", |_, b| b.get(Feature::SpreadGlottis)),
+    ("constricted_glottis", |_, b| {
+        b.get(Feature::ConstrictedGlottis)
+    }),
+    ("labial", |_, b| b.get(Feature::Labial)),
+    ("round", |_, b| b.get(Feature::Round)),
+    ("coronal", |_, b| b.get(Feature::Coronal)),
+    ("anterior", |_, b| b.get(Feature::Anterior)),
+    ("distributed", |_, b| b.get(Feature::Distributed)),
+    ("dorsal", |_, b| b.get(Feature::Dorsal)),
+    ("high", |_, b| b.get(Feature::High)),
+    ("low", |_, b| b.get(Feature::Low)),
+    ("front", |_, b| b.get(Feature::Front)),
+    ("back", |_, b| b.get(Feature::Back)),
+    ("tense", |_, b| b.get(Feature::Tense)),
+];
+
+const SPE: [Reading; 19] = [
+    ("vocalic", |_, b| Some(vocalic(b))),
+    ("consonantal", |_, b| b.get(Feature::Consonantal)),
+    ("sonorant", |_, b| b.get(Feature::Sonorant)),
+    ("high", |_, b| b.get(Feature::High)),
+    ("low", |_, b| b.get(Feature::Low)),
+    ("back", |_, b| b.get(Feature::Back)),
+    ("round", |_, b| b.get(Feature::Round)),
+    ("anterior", |_, b| b.get(Feature::Anterior)),
+    ("coronal", |_, b| b.get(Feature::Coronal)),
+    ("distributed", |_, b| b.get(Feature::Distributed)),
+    ("tense", |_, b| b.get(Feature::Tense)),
+    ("continuant", |_, b| b.get(Feature::Continuant)),
+    ("delayed_release", |_, b| b.get(Feature::DelayedRelease)),
+    ("nasal", |_, b| b.get(Feature::Nasal)),
+    ("lateral", |_, b| b.get(Feature::Lateral)),
+    ("strident", |_, b| b.get(Feature::Strident)),
+    ("voice", |_, b| b.get(Feature::Voice)),
+    ("heightened_subglottal_pressure", |_, b| {
+        b.get(Feature::SpreadGlottis)
+    }),
+    ("glottal_constriction", |_, b| {
+        b.get(Feature::ConstrictedGlottis)
+    }),
+];
+
+const HAYES: [Reading; 27] = [
+    ("syllabic", |_, b| b.get(Feature::Syllabic)),
+    ("long", |s, _| Some(long(s))),
+    ("consonantal", |_, b| b.get(Feature::Consonantal)),
+    ("sonorant", |_, b| b.get(Feature::Sonorant)),
+    ("continuant", |_, b| b.get(Feature::Continuant)),
+    ("delayed_release", |_, b| b.get(Feature::DelayedRelease)),
+    ("approximant", |_, b| Some(approximant(b))),
+    ("tap", |s, _| {
+        Some(manner(s, &[Manner::TapFlap, Manner::LatTapFlap]))
+    }),
+    ("trill", |s, _| Some(manner(s, &[Manner::Trill]))),
+    ("nasal", |_, b| b.get(Feature::Nasal)),
+    ("voice", |_, b| b.get(Feature::Voice)),
+    ("spread_glottis", |_, b| b.get(Feature::SpreadGlottis)),
+    ("constricted_glottis", |_, b| {
+        b.get(Feature::ConstrictedGlottis)
+    }),
+    ("labial", |_, b| b.get(Feature::Labial)),
+    ("round", |_, b| b.get(Feature::Round)),
+    ("labiodental", |s, _| {
+        Some(
+            s.as_consonant()
+                .is_some_and(|c| c.articulation() == Articulation::Labiodental),
+        )
+    }),
+    ("coronal", |_, b| b.get(Feature::Coronal)),
+    ("anterior", |_, b| b.get(Feature::Anterior)),
+    ("distributed", |_, b| b.get(Feature::Distributed)),
+    ("strident", |_, b| b.get(Feature::Strident)),
+    ("lateral", |_, b| b.get(Feature::Lateral)),
+    ("dorsal", |_, b| b.get(Feature::Dorsal)),
+    ("high", |_, b| b.get(Feature::High)),
+    ("low", |_, b| b.get(Feature::Low)),
+    ("front", |_, b| b.get(Feature::Front)),
+    ("back", |_, b| b.get(Feature::Back)),
+    ("tense", |_, b| b.get(Feature::Tense)),
+];
+
+const PANPHON: [Reading; 24] = [
+    ("syl", |_, b| b.get(Feature::Syllabic)),
+    ("son", |_, b| b.get(Feature::Sonorant)),
+    ("cons", |_, b| b.get(Feature::Consonantal)),
+    ("cont", |_, b| b.get(Feature::Continuant)),
+    ("delrel", |_, b| b.get(Feature::DelayedRelease)),
+    ("lat", |_, b| b.get(Feature::Lateral)),
+    ("nas", |_, b| b.get(Feature::Nasal)),
+    ("strid", |_, b| b.get(Feature::Strident)),
+    ("voi", |_, b| b.get(Feature::Voice)),
+    ("sg", |_, b| b.get(Feature::SpreadGlottis)),
+    ("cg", |_, b| b.get(Feature::ConstrictedGlottis)),
+    ("ant", |_, b| b.get(Feature::Anterior)),
+    ("cor", |_, b| b.get(Feature::Coronal)),
+    ("distr", |_, b| b.get(Feature::Distributed)),
+    ("lab", |_, b| b.get(Feature::Labial)),
+    ("hi", |_, b| b.get(Feature::High)),
+    ("lo", |_, b| b.get(Feature::Low)),
+    ("back", |_, b| b.get(Feature::Back)),
+    ("round", |_, b| b.get(Feature::Round)),
+    ("velaric", |s, _| {
+        Some(
+            s.as_consonant()
+                .is_some_and(|c| c.airstream() == Airstream::VelaricIngressive),
+        )
+    }),
+    ("tense", |_, b| b.get(Feature::Tense)),
+    ("long", |s, _| Some(long(s))),
+    // Tone is a segment of its own here, so no segment bears it.
+    ("hitone", |_, _| None),
+    ("hireg", |_, _| None),
+];
+
+/// `[+vocalic]` in SPE: vowels and liquids.
+fn vocalic(b: &FeatureBundle) -> bool {
+    approximant(b) && (b.is(Feature::Syllabic, true) || b.is(Feature::Consonantal, true))
+}
+
+/// `[+approximant]` in Hayes (2009): vowels, glides and liquids.
+fn approximant(b: &FeatureBundle) -> bool {
+    b.is(Feature::Sonorant, true) && !b.is(Feature::Nasal, true)
+}
+
+fn manner(segment: &Segment, manners: &[Manner]) -> bool {
+    segment
+        .as_consonant()
+        .is_some_and(|c| manners.contains(&c.manner()))
+}
+
+fn long(segment: &Segment) -> bool {
+    segment.length().is_some_and(|l| !l.is_short())
+}
+
+impl FeatureSystem {
+    pub const ALL: [FeatureSystem; 4] = [
+        FeatureSystem::Native,
+        FeatureSystem::Spe,
+        FeatureSystem::Hayes,
+        FeatureSystem::PanPhon,
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            FeatureSystem::Native => "native",
+            FeatureSystem::Spe => "spe",
+            FeatureSystem::Hayes => "hayes",
+            FeatureSystem::PanPhon => "panphon",
+        }
+    }
+
+    /// Looks up a system by its `name`.
+    pub fn from_name(name: &str) -> Option<FeatureSystem> {
+        FeatureSystem::ALL.into_iter().find(|s| s.name() == name)
+    }
+
+    const fn readings(&self) -> &'static [Reading] {
+        match self {
+            FeatureSystem::Native => &NATIVE,
+            FeatureSystem::Spe => &SPE,
+            FeatureSystem::Hayes => &HAYES,
+            FeatureSystem::PanPhon => &PANPHON,
+        }
+    }
+
+    /// The names of the system's features, in its conventional order.
+    pub fn features(&self) -> impl Iterator<Item = &'static str> {
+        self.readings().iter().map(|(name, _)| *name)
+    }
+
+    /// The features of `segment` in this system, or `None` for
+    /// suprasegmentals and tones.
+    pub fn featurize(&self, segment: &Segment) -> Option<Specification> {
+        let bundle = of(segment)?;
+        let mut spec = Specification {
+            system: *self,
+            plus: 0,
+            minus: 0,
+        };
+        for (i, (_, read)) in self.readings().iter().enumerate() {
+            match read(segment, &bundle) {
+                Some(true) => spec.plus |= 1 << i,
+                Some(false) => spec.minus |= 1 << i,
+                None => {}
+            }
+        }
+        Some(spec)
+    }
+}
+
+/// A segment's features in some `FeatureSystem`, each `+`, `-` or
+/// unspecified.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Specification {
+    system: FeatureSystem,
+    plus: u32,
+    minus: u32,
+}
+
+impl Specification {
+    pub fn system(&self) -> FeatureSystem {
+        self.system
+    }
+
+    /// The value of the feature called `name`, or `None` if it is
+    /// unspecified or not in the system.
+    pub fn get(&self, name: &str) -> Option<bool> {
+        self.iter().find(|(n, _)| *n == name)?.1
+    }
+
+    /// Every feature of the system with its value, in the system's
+    /// order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, Option<bool>)> + '_ {
+        self.system.features().enumerate().map(|(i, name)| {
+            let value = match (self.plus >> i & 1, self.minus >> i & 1) {
+                (1, _) => Some(true),
+                (_, 1) => Some(false),
+                _ => None,
+            };
+            (name, value)
+        })
+    }
+}
+
+impl fmt::Display for Specification {
+    /// Writes the specified features as a matrix, as `FeatureBundle`
+    /// does.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        let specified = self.iter().filter_map(|(n, v)| v.map(|v| (n, v)));
+        for (i, (name, value)) in specified.enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}{}", if value { '+' } else { '-' }, name)?;
+        }
+        write!(f, "]")
+    }
+}
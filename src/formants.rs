@@ -0,0 +1,91 @@
+//! Canonical formant frequency estimates for vowels.
+//!
+//! The figures are rough estimates for each cell of the vowel chart,
+//! after the averages of Peterson & Barney (1952) for adult male
+//! speakers, with near-front and near-back vowels interpolated between
+//! their neighbours. Real vowels vary widely by speaker and language;
+//! these are meant for seeding synthesis or plotting an expected vowel
+//! space, not for classification.
+
+use crate::ipa::{Backness, Roundedness, Vowel};
+
+/// The first three formant frequencies of a vowel, in Hz.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Formants {
+    pub f1: f32,
+    pub f2: f32,
+    pub f3: f32,
+}
+
+/// A class of speaker, scaling formants for vocal tract length.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Speaker {
+    #[default]
+    Male,
+    Female,
+    Child,
+}
+
+impl Speaker {
+    /// How much higher this speaker's formants are than an adult
+    /// male's.
+    pub const fn scale(&self) -> f32 {
+        match self {
+            Speaker::Male => 1.0,
+            Speaker::Female => 1.17,
+            Speaker::Child => 1.35,
+        }
+    }
+}
+
+/// F1 by height.
+const F1: [f32; 7] = [280.0, 380.0, 420.0, 500.0, 580.0, 690.0, 780.0];
+
+/// F2 by height, for front, central and back vowels, unrounded then
+/// rounded.
+const F2: [[[f32; 3]; 2]; 7] = [
+    [[2250.0, 1650.0, 1350.0], [1900.0, 1450.0, 870.0]],
+    [[2000.0, 1600.0, 1300.0], [1750.0, 1400.0, 1000.0]],
+    [[2050.0, 1550.0, 1250.0], [1650.0, 1350.0, 900.0]],
+    [[1900.0, 1500.0, 1200.0], [1550.0, 1300.0, 900.0]],
+    [[1800.0, 1450.0, 1150.0], [1500.0, 1250.0, 900.0]],
+    [[1700.0, 1400.0, 1100.0], [1450.0, 1200.0, 900.0]],
+    [[1600.0, 1350.0, 1050.0], [1400.0, 1150.0, 950.0]],
+];
+
+/// F3 of unrounded front vowels by height; other unrounded vowels
+/// have `F3_UNROUNDED` and rounded vowels `F3_ROUNDED`.
+const F3_FRONT: [f32; 7] = [2900.0, 2550.0, 2600.0, 2550.0, 2500.0, 2450.0, 2400.0];
+const F3_UNROUNDED: f32 = 2450.0;
+const F3_ROUNDED: f32 = 2300.0;
+
+impl Vowel {
+    /// Formant estimates for an adult male speaker.
+    pub fn formants(&self) -> Formants {
+        self.formants_for(Speaker::Male)
+    }
+
+    /// Formant estimates for `speaker`.
+    pub fn formants_for(&self, speaker: Speaker) -> Formants {
+        let h = self.height() as usize;
+        let rounded = self.roundedness() == Roundedness::Rounded;
+        let f2 = &F2[h][rounded as usize];
+        let (f2, f3_front) = match self.backness() {
+            Backness::Front => (f2[0], 1.0),
+            Backness::NearFront => ((f2[0] + f2[1]) / 2.0, 0.5),
+            Backness::Central => (f2[1], 0.0),
+            Backness::NearBack => ((f2[1] + f2[2]) / 2.0, 0.0),
+            Backness::Back => (f2[2], 0.0),
+        };
+        let f3 = match rounded {
+            true => F3_ROUNDED,
+            false => F3_UNROUNDED + (F3_FRONT[h] - F3_UNROUNDED) * f3_front,
+        };
+        let scale = speaker.scale();
+        Formants {
+            f1: F1[h] * scale,
+            f2: f2 * scale,
+            f3: f3 * scale,
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Grapheme-to-phoneme conversion: turning a word's spelling into its
+//! segments. A `Transcriber` does this for one word; the crate provides
+//! a lookup in a pronouncing `Dictionary`, the rule-based mapping of an
+//! `orthography::Profile`, and combinators to chain them:
+//!
+//! ```
+//! use allo::g2p::{Dictionary, Transcriber};
+//! use allo::orthography::Profile;
+//! use allo::rules::Rule;
+//!
+//! let mut dictionary = Dictionary::new();
+//! dictionary.insert("one", allo::parser::parse("wʌn").unwrap());
+//! let profile: Profile = "Grapheme\tIPA\nt\tt\na\tæ\nn\tn".parse().unwrap();
+//! let flapping: Rule = "t → ɾ / V _ V".parse().unwrap();
+//!
+//! let transcriber = dictionary.or(profile.then(vec![flapping]));
+//! let render = |w: Vec<allo::Segment>| w.iter().map(|s| s.to_string()).collect::<String>();
+//! assert_eq!(transcriber.transcribe("one").map(render).as_deref(), Some("wʌn"));
+//! assert_eq!(transcriber.transcribe("atan").map(render).as_deref(), Some("æɾæn"));
+//! assert_eq!(transcriber.transcribe("two"), None);
+//! ```
+
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+use crate::delimited::Entry;
+use crate::orthography::Profile;
+use crate::rules::{self, Rule};
+use crate::segment::Segment;
+
+/// Something that transcribes the spelling of a word.
+pub trait Transcriber {
+    /// The segments of `word`, or `None` if it cannot be transcribed.
+    fn transcribe(&self, word: &str) -> Option<Vec<Segment>>;
+
+    /// Transcribes each whitespace-separated word of `text`, or `None`
+    /// if any of them cannot be transcribed.
+    fn transcribe_text(&self, text: &str) -> Option<Vec<Vec<Segment>>> {
+        text.split_whitespace()
+            .map(|w| self.transcribe(w))
+            .collect()
+    }
+
+    /// Tries `self`, then `other` for the words `self` cannot
+    /// transcribe.
+    fn or<T: Transcriber>(self, other: T) -> Fallback<Self, T>
+    where
+        Self: Sized,
+    {
+        Fallback {
+            first: self,
+            second: other,
+        }
+    }
+
+    /// Applies `rules` in order (see `rules::cascade`) to what `self`
+    /// transcribes.
+    fn then(self, rules: Vec<Rule>) -> Rewrite<Self>
+    where
+        Self: Sized,
+    {
+        Rewrite { inner: self, rules }
+    }
+}
+
+impl<T: Transcriber + ?Sized> Transcriber for &T {
+    fn transcribe(&self, word: &str) -> Option<Vec<Segment>> {
+        (**self).transcribe(word)
+    }
+}
+
+impl<T: Transcriber + ?Sized> Transcriber for Box<T> {
+    fn transcribe(&self, word: &str) -> Option<Vec<Segment>> {
+        (**self).transcribe(word)
+    }
+}
+
+/// A pronouncing dictionary: words looked up by their exact spelling.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Dictionary {
+    words: BTreeMap<String, Vec<Segment>>,
+}
+
+impl Dictionary {
+    pub fn new() -> Self {
+        Dictionary::default()
+    }
+
+    /// Adds or replaces the pronunciation of `word`.
+    pub fn insert(&mut self, word: &str, segments: Vec<Segment>) -> Option<Vec<Segment>> {
+        self.words.insert(String::from(word), segments)
+    }
+
+    pub fn get(&self, word: &str) -> Option<&[Segment]> {
+        self.words.get(word).map(|s| &s[..])
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// A dictionary of the entries of a word list that have both a
+    /// grapheme and a transcription, as `delimited::Format` reads them.
+    /// Of entries with the same spelling, the last wins.
+    pub fn from_entries(entries: &[Entry]) -> Dictionary {
+        entries
+            .iter()
+            .filter(|e| !e.phonemes.is_empty())
+            .filter_map(|e| Some((e.grapheme.clone()?, e.phonemes.clone())))
+            .collect()
+    }
+}
+
+impl FromIterator<(String, Vec<Segment>)> for Dictionary {
+    fn from_iter<I: IntoIterator<Item = (String, Vec<Segment>)>>(iter: I) -> Self {
+        Dictionary {
+            words: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Transcriber for Dictionary {
+    fn transcribe(&self, word: &str) -> Option<Vec<Segment>> {
+        self.get(word).map(<[Segment]>::to_vec)
+    }
+}
+
+impl Transcriber for Profile {
+    /// Transliterates grapheme by grapheme; `None` if a letter matches
+    /// no grapheme of the profile.
+    fn transcribe(&self, word: &str) -> Option<Vec<Segment>> {
+        let words = self.transliterate(word).ok()?;
+        Some(words.into_iter().flatten().collect())
+    }
+}
+
+/// The transcriber `Transcriber::or` returns.
+#[derive(Clone, Debug)]
+pub struct Fallback<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: Transcriber, B: Transcriber> Transcriber for Fallback<A, B> {
+    fn transcribe(&self, word: &str) -> Option<Vec<Segment>> {
+        self.first
+            .transcribe(word)
+            .or_else(|| self.second.transcribe(word))
+    }
+}
+
+/// The transcriber `Transcriber::then` returns.
+#[derive(Clone, Debug)]
+pub struct Rewrite<T> {
+    inner: T,
+    rules: Vec<Rule>,
+}
+
+impl<T: Transcriber> Transcriber for Rewrite<T> {
+    fn transcribe(&self, word: &str) -> Option<Vec<Segment>> {
+        Some(rules::cascade(&self.rules, &self.inner.transcribe(word)?))
+    }
+}
@@ -0,0 +1,245 @@
+//! Random words for conlangs, naming tools and test data: built from
+//! an inventory and checked against phonotactics, or sampled from a
+//! model trained on a word list.
+
+use core::ops::RangeInclusive;
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::inventory::Inventory;
+use crate::parser;
+use crate::phonotactics::{Phonotactics, Slot, Template};
+use crate::random::Rng;
+use crate::segment::{ParseSegmentError, Segment};
+
+/// How many tries `Generator::word` makes before giving up.
+const ATTEMPTS: usize = 100;
+
+/// The most segments `Markov::word` generates.
+const MAX_SEGMENTS: usize = 64;
+
+/// A generator of random words: syllables are built on the templates
+/// of its phonotactics from the inventory's segments, and words the
+/// phonotactics reject are thrown away.
+///
+/// ```
+/// use allo::generator::Generator;
+/// use allo::phonotactics::{Phonotactics, Template};
+/// use allo::random::SplitMix64;
+///
+/// let inventory = allo::parser::parse("ptkmnsaiu").unwrap().into_iter().collect();
+/// let phonotactics = Phonotactics::new().with_template(Template::parse("CV(C)").unwrap());
+/// let generator = Generator::new(inventory, phonotactics).with_syllables(2..=3);
+/// let word = generator.ipa(&mut SplitMix64::new(1)).unwrap();
+/// assert!((4..=9).contains(&word.chars().count()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Generator {
+    inventory: Inventory,
+    phonotactics: Phonotactics,
+    frequencies: Vec<(Segment, u64)>,
+    syllables: RangeInclusive<usize>,
+}
+
+impl Generator {
+    /// Words of one to three syllables, with every segment equally
+    /// likely. With no templates, syllables are `(C)V(C)`.
+    pub fn new(inventory: Inventory, phonotactics: Phonotactics) -> Self {
+        Generator {
+            inventory,
+            phonotactics,
+            frequencies: Vec::new(),
+            syllables: 1..=3,
+        }
+    }
+
+    /// Makes `segment` `weight` times as likely as a segment with no
+    /// frequency given, which weighs 1. A weight of 0 keeps it out of
+    /// generated words.
+    pub fn with_frequency(mut self, segment: Segment, weight: u64) -> Self {
+        self.frequencies.retain(|(s, _)| *s != segment);
+        self.frequencies.push((segment, weight));
+        self
+    }
+
+    /// Generates words with this many syllables.
+    pub fn with_syllables(mut self, syllables: RangeInclusive<usize>) -> Self {
+        self.syllables = syllables;
+        self
+    }
+
+    /// A random word the phonotactics allow, or `None` if none turned
+    /// up after many tries.
+    pub fn word(&self, rng: &mut impl Rng) -> Option<Vec<Segment>> {
+        let default = [Template::parse("(C)V(C)").unwrap()];
+        let templates = match self.phonotactics.templates() {
+            [] => &default[..],
+            templates => templates,
+        };
+        let consonants: Vec<Segment> = self.inventory.consonants().collect();
+        let vowels: Vec<Segment> = self.inventory.vowels().collect();
+        let (min, max) = (*self.syllables.start(), *self.syllables.end());
+        'attempt: for _ in 0..ATTEMPTS {
+            let count = min + rng.below((max.max(min) - min + 1) as u64) as usize;
+            let mut word = Vec::new();
+            for _ in 0..count {
+                let template = &templates[rng.below(templates.len() as u64) as usize];
+                for &(slot, optional) in template.slots() {
+                    if optional && rng.below(2) == 0 {
+                        continue;
+                    }
+                    let segments = match slot {
+                        Slot::C => &consonants,
+                        Slot::V => &vowels,
+                    };
+                    match self.pick(rng, segments) {
+                        Some(segment) => word.push(segment),
+                        None => continue 'attempt,
+                    }
+                }
+            }
+            if !word.is_empty() && self.phonotactics.validate(&word).is_ok() {
+                return Some(word);
+            }
+        }
+        None
+    }
+
+    /// A random word as `word` gives, written in IPA.
+    pub fn ipa(&self, rng: &mut impl Rng) -> Option<String> {
+        let word = self.word(rng)?;
+        Some(word.iter().map(ToString::to_string).collect())
+    }
+
+    /// One of `segments`, by their frequencies.
+    fn pick(&self, rng: &mut impl Rng, segments: &[Segment]) -> Option<Segment> {
+        let weights: Vec<u64> = segments
+            .iter()
+            .map(|s| {
+                self.frequencies
+                    .iter()
+                    .find(|(f, _)| f == s)
+                    .map_or(1, |&(_, w)| w)
+            })
+            .collect();
+        rng.weighted(&weights).map(|i| segments[i])
+    }
+}
+
+/// An n-gram model of a word list: each segment is sampled given the
+/// `order` segments before it, at the rates seen in training, so new
+/// words sound like the ones it learned from.
+///
+/// ```
+/// use allo::generator::Markov;
+/// use allo::random::SplitMix64;
+///
+/// let model = Markov::train(&["kalana", "kanala", "lakana"], 2).unwrap();
+/// let word = model.ipa(&mut SplitMix64::new(5));
+/// assert!(word.chars().all(|c| "kaln".contains(c)));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Markov {
+    order: usize,
+    smoothing: f64,
+    segments: BTreeSet<Segment>,
+    /// How often each segment, or the end of the word (`None`),
+    /// follows each context. Contexts at the start of a word are
+    /// padded with `None`.
+    counts: BTreeMap<Vec<Option<Segment>>, BTreeMap<Option<Segment>, u64>>,
+}
+
+impl Markov {
+    /// Trains a model of order `order` on `words`, with no smoothing.
+    pub fn train(words: &[&str], order: usize) -> Result<Markov, ParseSegmentError> {
+        let mut model = Markov {
+            order,
+            smoothing: 0.0,
+            segments: BTreeSet::new(),
+            counts: BTreeMap::new(),
+        };
+        for word in words {
+            let mut context = vec![None; order];
+            for segment in parser::parse(word)?.into_iter().map(Some).chain([None]) {
+                *model
+                    .counts
+                    .entry(context.clone())
+                    .or_default()
+                    .entry(segment)
+                    .or_insert(0) += 1;
+                model.segments.extend(segment);
+                if order > 0 {
+                    context.remove(0);
+                    context.push(segment);
+                }
+            }
+        }
+        Ok(model)
+    }
+
+    /// Adds `k` to every count (additive smoothing), so that segments
+    /// never seen after a context can still follow it. The larger `k`,
+    /// the further words stray from the training data.
+    pub fn with_smoothing(mut self, k: f64) -> Self {
+        self.smoothing = k.max(0.0);
+        self
+    }
+
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// A random word, of at most 64 segments.
+    pub fn word(&self, rng: &mut impl Rng) -> Vec<Segment> {
+        let mut word = Vec::new();
+        let mut context = vec![None; self.order];
+        while word.len() < MAX_SEGMENTS {
+            let Some(segment) = self.next(rng, &context) else {
+                break;
+            };
+            word.push(segment);
+            if self.order > 0 {
+                context.remove(0);
+                context.push(Some(segment));
+            }
+        }
+        word
+    }
+
+    /// A random word as `word` gives, written in IPA.
+    pub fn ipa(&self, rng: &mut impl Rng) -> String {
+        self.word(rng).iter().map(ToString::to_string).collect()
+    }
+
+    /// The segment after `context`, or `None` to end the word.
+    fn next(&self, rng: &mut impl Rng, context: &[Option<Segment>]) -> Option<Segment> {
+        let counts = self.counts.get(context);
+        let candidates: Vec<Option<Segment>> = self
+            .segments
+            .iter()
+            .copied()
+            .map(Some)
+            .chain([None])
+            .collect();
+        let weights: Vec<f64> = candidates
+            .iter()
+            .map(|c| {
+                let count = counts.and_then(|counts| counts.get(c)).copied();
+                count.unwrap_or(0) as f64 + self.smoothing
+            })
+            .collect();
+        let mut r = rng.unit() * weights.iter().sum::<f64>();
+        for (candidate, weight) in candidates.iter().zip(&weights) {
+            if r < *weight {
+                return *candidate;
+            }
+            r -= weight;
+        }
+        None
+    }
+}
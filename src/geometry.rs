@@ -0,0 +1,182 @@
+//! Feature geometry: the features of `features::Feature` arranged as a
+//! tree of class nodes, after Clements & Hume (1995), so that a group
+//! of features can spread from one segment to another or be delinked
+//! together:
+//!
+//! ```text
+//! Root  [consonantal, sonorant, continuant, nasal, lateral, ...]
+//! ├── Laryngeal  [voice, spread glottis, constricted glottis]
+//! └── Place
+//!     ├── Labial   [round]
+//!     ├── Coronal  [anterior, distributed]
+//!     └── Dorsal   [high, low, front, back, tense]
+//! ```
+//!
+//! The articulator nodes are the features `labial`, `coronal` and
+//! `dorsal` themselves: a segment has a Labial node if it is
+//! `[+labial]`.
+
+#[cfg(feature = "alloc")]
+use crate::features;
+use crate::features::{Feature, FeatureBundle};
+#[cfg(feature = "alloc")]
+use crate::segment::Segment;
+
+/// A class node of the feature tree.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Node {
+    Root,
+    Laryngeal,
+    Place,
+    Labial,
+    Coronal,
+    Dorsal,
+}
+
+impl Node {
+    pub const ALL: [Node; 6] = [
+        Node::Root,
+        Node::Laryngeal,
+        Node::Place,
+        Node::Labial,
+        Node::Coronal,
+        Node::Dorsal,
+    ];
+
+    /// The node this one hangs from, or `None` for the root.
+    pub const fn parent(&self) -> Option<Node> {
+        match self {
+            Node::Root => None,
+            Node::Laryngeal | Node::Place => Some(Node::Root),
+            Node::Labial | Node::Coronal | Node::Dorsal => Some(Node::Place),
+        }
+    }
+
+    pub const fn children(&self) -> &'static [Node] {
+        match self {
+            Node::Root => &[Node::Laryngeal, Node::Place],
+            Node::Place => &[Node::Labial, Node::Coronal, Node::Dorsal],
+            Node::Laryngeal | Node::Labial | Node::Coronal | Node::Dorsal => &[],
+        }
+    }
+
+    /// The features attached to this node itself, not to its
+    /// children. An articulator node's own feature comes first.
+    pub const fn features(&self) -> &'static [Feature] {
+        use Feature::*;
+        match self {
+            Node::Root => &[
+                Syllabic,
+                Consonantal,
+                Sonorant,
+                Continuant,
+                DelayedRelease,
+                Nasal,
+                Lateral,
+                Strident,
+            ],
+            Node::Laryngeal => &[Voice, SpreadGlottis, ConstrictedGlottis],
+            Node::Place => &[],
+            Node::Labial => &[Labial, Round],
+            Node::Coronal => &[Coronal, Anterior, Distributed],
+            Node::Dorsal => &[Dorsal, High, Low, Front, Back, Tense],
+        }
+    }
+
+    /// The node `feature` is attached to.
+    pub fn of(feature: Feature) -> Node {
+        Node::ALL
+            .into_iter()
+            .find(|node| node.features().contains(&feature))
+            .unwrap_or(Node::Root)
+    }
+
+    /// Whether `feature` is attached to this node or below it.
+    pub fn dominates(&self, feature: Feature) -> bool {
+        let mut node = Some(Node::of(feature));
+        while let Some(n) = node {
+            if n == *self {
+                return true;
+            }
+            node = n.parent();
+        }
+        false
+    }
+
+    /// Whether a segment with the features in `bundle` has this node:
+    /// an articulator node if its feature is `+`, Place if it has any
+    /// articulator, and the others if any feature under them is
+    /// specified.
+    pub fn is_present(&self, bundle: &FeatureBundle) -> bool {
+        match self {
+            Node::Labial | Node::Coronal | Node::Dorsal => bundle.is(self.features()[0], true),
+            Node::Place => self.children().iter().any(|n| n.is_present(bundle)),
+            Node::Root | Node::Laryngeal => {
+                bundle.iter().any(|(feature, _)| self.dominates(feature))
+            }
+        }
+    }
+}
+
+/// `to` with the node `node` of `from` spread onto it, replacing its
+/// own: every feature under `node` takes the value it has in `from`,
+/// or is left unspecified if `from` leaves it so.
+///
+/// ```
+/// use allo::features::{self, Feature};
+/// use allo::geometry::{self, Node};
+///
+/// let n = features::of(&allo::ipa!("n")).unwrap();
+/// let k = features::of(&allo::ipa!("k")).unwrap();
+/// let assimilated = geometry::spread(Node::Place, &k, &n);
+/// assert!(assimilated.is(Feature::Dorsal, true) && assimilated.is(Feature::Nasal, true));
+/// ```
+pub fn spread(node: Node, from: &FeatureBundle, to: &FeatureBundle) -> FeatureBundle {
+    Feature::ALL
+        .into_iter()
+        .filter(|f| node.dominates(*f))
+        .fold(*to, |bundle, feature| match from.get(feature) {
+            Some(value) => bundle.with(feature, value),
+            None => bundle.without(feature),
+        })
+}
+
+/// `bundle` with the node `node` delinked: every feature under it is
+/// unspecified, except that the articulators under it are set `-`,
+/// since in a binary system their absence is their minus value.
+/// Delinking Place debuccalizes a consonant; delinking Laryngeal
+/// neutralizes its voicing and aspiration.
+pub fn delink(node: Node, bundle: &FeatureBundle) -> FeatureBundle {
+    Feature::ALL
+        .into_iter()
+        .filter(|f| node.dominates(*f))
+        .fold(*bundle, |bundle, feature| {
+            match matches!(
+                feature,
+                Feature::Labial | Feature::Coronal | Feature::Dorsal
+            ) {
+                true => bundle.with(feature, false),
+                false => bundle.without(feature),
+            }
+        })
+}
+
+/// `to` assimilated to `from` by spreading `node`, as `spread` does,
+/// realized as the closest segment (see `features::realize`) with the
+/// length of `to`. `None` if either has no features.
+///
+/// ```
+/// use allo::geometry::{self, Node};
+/// use allo::ipa;
+///
+/// let assimilated = geometry::assimilate(Node::Place, &ipa!("p"), &ipa!("n"));
+/// assert_eq!(assimilated, Some(ipa!("m")));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn assimilate(node: Node, from: &Segment, to: &Segment) -> Option<Segment> {
+    let realized = features::realize(&spread(node, &features::of(from)?, &features::of(to)?))?;
+    Some(match to.length() {
+        Some(length) => realized.with_length(length),
+        None => realized,
+    })
+}
@@ -1,25 +1,68 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
 /// Graphemes: m̥ m ɱ̊ ɱ n̪̊ n̪ n̥ n ɲ̊ ɲ ŋ̊ ŋ ɴ̥ ɴ
-pub const NASALS: [&'static str; 14] = [
-    "\u{6D}", "\u{6D}", "\u{271}", "\u{271}", "\u{6E}", "\u{6E}", "\u{6E}", "\u{6E}", "\u{272}",
-    "\u{272}", "\u{14B}", "\u{14B}", "\u{274}", "\u{274}",
+pub const NASALS: [&str; 14] = [
+    "\u{6D}\u{325}",
+    "\u{6D}",
+    "\u{271}\u{30A}",
+    "\u{271}",
+    "\u{6E}\u{32A}\u{30A}",
+    "\u{6E}\u{32A}",
+    "\u{6E}\u{325}",
+    "\u{6E}",
+    "\u{272}\u{30A}",
+    "\u{272}",
+    "\u{14B}\u{30A}",
+    "\u{14B}",
+    "\u{274}\u{325}",
+    "\u{274}",
 ];
 
 /// Graphemes: p b p̪ b̪ t̪ d̪ t d ʈ ɖ c ɟ k ɡ q ɢ ʡ ʔ
-pub const PLOSIVES: [&'static str; 18] = [
-    "\u{70}", "\u{62}", "\u{70}", "\u{62}", "\u{74}", "\u{64}", "\u{74}", "\u{64}", "\u{288}",
-    "\u{256}", "\u{63}", "\u{25F}", "\u{6B}", "\u{261}", "\u{71}", "\u{262}", "\u{2A1}", "\u{294}",
+pub const PLOSIVES: [&str; 18] = [
+    "\u{70}",
+    "\u{62}",
+    "\u{70}\u{32A}",
+    "\u{62}\u{32A}",
+    "\u{74}\u{32A}",
+    "\u{64}\u{32A}",
+    "\u{74}",
+    "\u{64}",
+    "\u{288}",
+    "\u{256}",
+    "\u{63}",
+    "\u{25F}",
+    "\u{6B}",
+    "\u{261}",
+    "\u{71}",
+    "\u{262}",
+    "\u{2A1}",
+    "\u{294}",
 ];
 
 /// Graphemes: ʙ r̥  r ɽ͡r ʀ̥  ʀ ᴙ
-pub const TRILLS: [&'static str; 7] = [
-    "\u{299}", "\u{72}", "\u{72}", "\u{27D}", "\u{280}", "\u{280}", "\u{1D19}",
+pub const TRILLS: [&str; 7] = [
+    "\u{299}",
+    "\u{72}\u{325}",
+    "\u{72}",
+    "\u{27D}\u{361}\u{72}",
+    "\u{280}\u{325}",
+    "\u{280}",
+    "\u{1D19}",
 ];
 
 /// Graphemes: ⱱ̟ ⱱ ɾ̥ ɾ ɽ
-pub const TAPS: [&'static str; 5] = ["\u{2C71}", "\u{2C71}", "\u{27E}", "\u{27E}", "\u{27D}"];
+pub const TAPS: [&str; 5] = [
+    "\u{2C71}\u{31F}",
+    "\u{2C71}",
+    "\u{27E}\u{325}",
+    "\u{27E}",
+    "\u{27D}",
+];
 
 /// Graphemes: ɸ β f v θ ð s z ʃ ʒ ɕ ʑ ʂ ʐ ç ʝ x ɣ χ ʁ ħ ʕ ʜ ʢ h ɦ
-pub const FRICATIVES: [&'static str; 26] = [
+pub const FRICATIVES: [&str; 26] = [
     "\u{278}", "\u{3B2}", "\u{66}", "\u{76}", "\u{3B8}", "\u{F0}", "\u{73}", "\u{7A}", "\u{283}",
     "\u{292}", "\u{255}", "\u{291}", "\u{282}", "\u{290}", "\u{E7}", "\u{29D}", "\u{78}",
     "\u{263}", "\u{3C7}", "\u{281}", "\u{127}", "\u{295}", "\u{29C}", "\u{2A2}", "\u{68}",
@@ -27,19 +70,128 @@ pub const FRICATIVES: [&'static str; 26] = [
 ];
 
 /// Graphemes: ɬ  ɮ
-pub const LAT_FRICATIVES: [&'static str; 2] = ["\u{26C}", "\u{26E}"];
+pub const LAT_FRICATIVES: [&str; 2] = ["\u{26C}", "\u{26E}"];
 
 /// Graphemes: l ɭ ʎ ʟ
-pub const LAT_APPROX: [&'static str; 4] = ["\u{6C}", "\u{26D}", "\u{28E}", "\u{29F}"];
+pub const LAT_APPROX: [&str; 4] = ["\u{6C}", "\u{26D}", "\u{28E}", "\u{29F}"];
 
 /// Graphemes: ʋ ɹ ɻ j̊  j ɰ
-pub const APPROX: [&'static str; 6] = [
-    "\u{28B}", "\u{279}", "\u{27B}", "\u{6A}", "\u{6A}", "\u{270}",
+pub const APPROX: [&str; 6] = [
+    "\u{28B}",
+    "\u{279}",
+    "\u{27B}",
+    "\u{6A}\u{30A}",
+    "\u{6A}",
+    "\u{270}",
+];
+
+/// Graphemes: p͡f b͡v p̪͡f b̪͡v t͡θ d͡ð t͡s d͡z t͡ʃ d͡ʒ t͡ɕ d͡ʑ ʈ͡ʂ ɖ͡ʐ c͡ç ɟ͡ʝ k͡x ɡ͡ɣ q͡χ ɢ͡ʁ t͡ɬ d͡ɮ
+///
+/// Each stop is joined to its fricative with the tie bar U+0361, as
+/// `Affricate` writes them.
+pub const AFFRICATES: [&str; 22] = [
+    "\u{70}\u{361}\u{66}",
+    "\u{62}\u{361}\u{76}",
+    "\u{70}\u{32A}\u{361}\u{66}",
+    "\u{62}\u{32A}\u{361}\u{76}",
+    "\u{74}\u{361}\u{3B8}",
+    "\u{64}\u{361}\u{F0}",
+    "\u{74}\u{361}\u{73}",
+    "\u{64}\u{361}\u{7A}",
+    "\u{74}\u{361}\u{283}",
+    "\u{64}\u{361}\u{292}",
+    "\u{74}\u{361}\u{255}",
+    "\u{64}\u{361}\u{291}",
+    "\u{288}\u{361}\u{282}",
+    "\u{256}\u{361}\u{290}",
+    "\u{63}\u{361}\u{E7}",
+    "\u{25F}\u{361}\u{29D}",
+    "\u{6B}\u{361}\u{78}",
+    "\u{261}\u{361}\u{263}",
+    "\u{71}\u{361}\u{3C7}",
+    "\u{262}\u{361}\u{281}",
+    "\u{74}\u{361}\u{26C}",
+    "\u{64}\u{361}\u{26E}",
+];
+
+/// Graphemes: i y ɨ ʉ ɯ u
+///
+/// Ordered front, central, back, with each unrounded vowel followed
+/// by its rounded counterpart.
+pub const CLOSE_VOWELS: [&str; 6] = [
+    "\u{69}", "\u{79}", "\u{268}", "\u{289}", "\u{26F}", "\u{75}",
 ];
 
-/// Graphemes: pf bv p̪f b̪v tθ dð ts dz tʃ dʒ tɕ dʑ ʈʂ ɖʐ cç cç kx ɡɣ qχ ɢʁ
-pub const AFFRICATES: [&'static str; 20] = [
-    "\u{70}", "\u{62}", "\u{70}", "\u{62}", "\u{74}", "\u{64}", "\u{74}", "\u{64}", "\u{74}",
-    "\u{64}", "\u{74}", "\u{64}", "\u{288}", "\u{256}", "\u{63}", "\u{63}", "\u{6B}", "\u{261}",
-    "\u{71}", "\u{262}",
+/// Graphemes: ɪ ʏ ʊ
+///
+/// Near-front unrounded and rounded, followed by near-back rounded.
+pub const NEAR_CLOSE_VOWELS: [&str; 3] = ["\u{26A}", "\u{28F}", "\u{28A}"];
+
+/// Graphemes: e ø ɘ ɵ ɤ o
+pub const CLOSE_MID_VOWELS: [&str; 6] = [
+    "\u{65}", "\u{F8}", "\u{258}", "\u{275}", "\u{264}", "\u{6F}",
+];
+
+/// Graphemes: ə
+///
+/// The only mid vowel with a dedicated symbol; the front and back
+/// mid vowels are written with the close-mid or open-mid symbol plus
+/// a lowering or raising diacritic.
+pub const MID_VOWELS: [&str; 1] = ["\u{259}"];
+
+/// Graphemes: ɛ œ ɜ ɞ ʌ ɔ
+pub const OPEN_MID_VOWELS: [&str; 6] = [
+    "\u{25B}", "\u{153}", "\u{25C}", "\u{25E}", "\u{28C}", "\u{254}",
 ];
+
+/// Graphemes: æ ɐ
+///
+/// Front unrounded followed by central.
+pub const NEAR_OPEN_VOWELS: [&str; 2] = ["\u{E6}", "\u{250}"];
+
+/// Graphemes: a ɶ ɑ ɒ
+pub const OPEN_VOWELS: [&str; 4] = ["\u{61}", "\u{276}", "\u{251}", "\u{252}"];
+
+/// Graphemes: ʘ ǀ ǃ ǂ ǁ
+///
+/// Bilabial, dental, (post)alveolar, palatoalveolar, and alveolar
+/// lateral clicks.
+pub const CLICKS: [&str; 5] = ["\u{298}", "\u{1C0}", "\u{1C3}", "\u{1C2}", "\u{1C1}"];
+
+/// Graphemes: w ʍ ɥ ɧ
+///
+/// The doubly articulated consonants with symbols of their own, listed
+/// under "other symbols" on the IPA chart.
+pub const COARTICULATED: [&str; 4] = ["\u{77}", "\u{28D}", "\u{265}", "\u{267}"];
+
+/// Graphemes: ɓ ɗ ʄ ɠ ʛ
+///
+/// Voiced bilabial, dental/alveolar, palatal, velar, and uvular
+/// implosives.
+pub const IMPLOSIVES: [&str; 5] = ["\u{253}", "\u{257}", "\u{284}", "\u{260}", "\u{29B}"];
+
+/// Grapheme: ʼ
+///
+/// Modifier letter apostrophe, written after a pulmonic base symbol to
+/// mark it as ejective, e.g. `pʼ`.
+pub const EJECTIVE: &str = "\u{2BC}";
+
+/// Graphemes: pʼ tʼ kʼ qʼ sʼ
+///
+/// The ejective examples given in the IPA chart.
+pub const EJECTIVES: [&str; 5] = [
+    "\u{70}\u{2BC}",
+    "\u{74}\u{2BC}",
+    "\u{6B}\u{2BC}",
+    "\u{71}\u{2BC}",
+    "\u{73}\u{2BC}",
+];
+
+/// Builds an ejective by appending the `EJECTIVE` modifier to `base`.
+#[cfg(feature = "alloc")]
+pub fn ejective(base: &str) -> String {
+    let mut s = String::with_capacity(base.len() + EJECTIVE.len());
+    s.push_str(base);
+    s.push_str(EJECTIVE);
+    s
+}
@@ -0,0 +1,722 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::RangeInclusive;
+use core::str::FromStr;
+
+#[cfg(feature = "std")]
+use alloc::collections::BTreeMap;
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use crate::affricate::Affricate;
+use crate::classes::Class;
+use crate::features::{self, FeatureBundle, Weights};
+use crate::ipa;
+use crate::ipa::{Articulation, Consonant, Height, Manner, Phonation, PoA, Vowel};
+use crate::parser;
+use crate::random::Rng;
+use crate::rules::Allophone;
+use crate::segment::{chart_key, ChartKey, ParseSegmentError, Segment};
+use crate::typology::Typology;
+
+/// A language's phoneme inventory: a set of segments, iterated in
+/// the chart order of `Segment::chart_cmp` (consonants place by
+/// place, then vowels, then suprasegmentals and tones; variants with
+/// diacritics follow their base).
+///
+/// An inventory may also hold allophones for its phonemes, which
+/// `realize` applies. Equality and hashing compare the segments only.
+#[derive(Clone, Debug, Default)]
+pub struct Inventory {
+    segments: BTreeSet<(ChartKey, Segment)>,
+    allophones: Vec<Allophone>,
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Inventory::default()
+    }
+
+    /// Adds `segment`, returning whether it was new.
+    pub fn insert(&mut self, segment: Segment) -> bool {
+        self.segments.insert((chart_key(&segment), segment))
+    }
+
+    /// Parses `grapheme` as a single segment and adds it, returning
+    /// whether it was new.
+    pub fn insert_grapheme(&mut self, grapheme: &str) -> Result<bool, ParseSegmentError> {
+        Ok(self.insert(grapheme.parse()?))
+    }
+
+    /// Adds the segment best realizing `bundle` (see
+    /// `features::realize`) and returns it, or `None` if `bundle` is
+    /// empty.
+    pub fn insert_features(&mut self, bundle: &FeatureBundle) -> Option<Segment> {
+        let segment = features::realize(bundle)?;
+        self.insert(segment);
+        Some(segment)
+    }
+
+    pub fn remove(&mut self, segment: &Segment) -> bool {
+        self.segments.remove(&(chart_key(segment), *segment))
+    }
+
+    pub fn contains(&self, segment: &Segment) -> bool {
+        self.segments.contains(&(chart_key(segment), *segment))
+    }
+
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// The segments in chart order.
+    pub fn iter(&self) -> impl Iterator<Item = Segment> + '_ {
+        self.segments.iter().map(|(_, s)| *s)
+    }
+
+    pub fn consonants(&self) -> impl Iterator<Item = Segment> + '_ {
+        self.iter().filter(Segment::is_consonant)
+    }
+
+    pub fn vowels(&self) -> impl Iterator<Item = Segment> + '_ {
+        self.iter().filter(Segment::is_vowel)
+    }
+
+    /// The segments belonging to `class`.
+    pub fn filter(&self, class: Class) -> impl Iterator<Item = Segment> + '_ {
+        self.iter().filter(move |s| class.matches(s))
+    }
+
+    /// The smallest feature specification picking out exactly
+    /// `segments` from this inventory, as `features::natural_class`
+    /// finds it.
+    pub fn natural_class(&self, segments: &[Segment]) -> Option<FeatureBundle> {
+        features::natural_class(segments, &self.iter().collect::<Vec<_>>())
+    }
+
+    fn from_segments(segments: BTreeSet<(ChartKey, Segment)>) -> Self {
+        Inventory {
+            segments,
+            allophones: Vec::new(),
+        }
+    }
+
+    /// Segments in either inventory. Like the other set operations,
+    /// this keeps no allophones.
+    pub fn union(&self, other: &Inventory) -> Inventory {
+        Inventory::from_segments(self.segments.union(&other.segments).copied().collect())
+    }
+
+    /// Segments in both inventories.
+    pub fn intersection(&self, other: &Inventory) -> Inventory {
+        Inventory::from_segments(
+            self.segments
+                .intersection(&other.segments)
+                .copied()
+                .collect(),
+        )
+    }
+
+    /// Segments in `self` but not in `other`.
+    pub fn difference(&self, other: &Inventory) -> Inventory {
+        Inventory::from_segments(self.segments.difference(&other.segments).copied().collect())
+    }
+
+    /// Segments in exactly one of the inventories.
+    pub fn symmetric_difference(&self, other: &Inventory) -> Inventory {
+        Inventory::from_segments(
+            self.segments
+                .symmetric_difference(&other.segments)
+                .copied()
+                .collect(),
+        )
+    }
+
+    pub fn is_subset(&self, other: &Inventory) -> bool {
+        self.segments.is_subset(&other.segments)
+    }
+
+    /// How alike two inventories are, from 0 (nothing in common) to 1
+    /// (the same segments), with every feature weighted equally; see
+    /// `similarity_with`.
+    ///
+    /// ```
+    /// use allo::inventory::Inventory;
+    ///
+    /// let inventory = |s| allo::parser::parse(s).unwrap().into_iter().collect::<Inventory>();
+    /// let (a, b, c) = (inventory("ptkaiu"), inventory("pʰtʰkʰaiu"), inventory("ʘǀǃʔə"));
+    /// assert!(a.similarity(&b) > a.similarity(&c));
+    /// assert_eq!(a.similarity(&a), 1.0);
+    /// ```
+    pub fn similarity(&self, other: &Inventory) -> f32 {
+        self.similarity_with(other, &Weights::default())
+    }
+
+    /// The mean of two measures of how alike two inventories are: the
+    /// share of all their segments that they have in common, and how
+    /// near, in features weighted by `weights`, each segment of either
+    /// is to the nearest segment of the other, where a segment the
+    /// other has counts as 1 and one differing in every feature as 0.
+    /// Unlike the first, the second credits `pʰ` as close to `p`. Two
+    /// empty inventories score 1, and an empty and a non-empty 0.
+    pub fn similarity_with(&self, other: &Inventory, weights: &Weights) -> f32 {
+        match (self.is_empty(), other.is_empty()) {
+            (true, true) => return 1.0,
+            (true, false) | (false, true) => return 0.0,
+            (false, false) => {}
+        }
+        let shared = self.intersection(other).len() as f32;
+        let overlap = shared / self.union(other).len() as f32;
+        let nearest = |from: &Inventory, to: &Inventory| -> f32 {
+            from.iter()
+                .map(|a| {
+                    let d = to
+                        .iter()
+                        .map(|b| features::distance(&a, &b, weights))
+                        .fold(f32::INFINITY, f32::min);
+                    1.0 - d / weights.total()
+                })
+                .sum()
+        };
+        let closeness =
+            (nearest(self, other) + nearest(other, self)) / (self.len() + other.len()) as f32;
+        (overlap + closeness) / 2.0
+    }
+
+    /// A random inventory with as many consonants and vowels as
+    /// `profile` allows, sampled so as to be typologically plausible:
+    /// common segments are likelier, a segment is only added once
+    /// those it implies are present (no `ɸ` without `p`), and a voiced
+    /// obstruent is likelier when the language already pairs others of
+    /// its manner for voicing.
+    ///
+    /// ```
+    /// use allo::inventory::{Inventory, Profile};
+    /// use allo::random::SplitMix64;
+    ///
+    /// let inventory = Inventory::generate(&mut SplitMix64::new(7), &Profile::SMALL);
+    /// assert!((9..=18).contains(&inventory.len()));
+    /// ```
+    pub fn generate(rng: &mut impl Rng, profile: &Profile) -> Inventory {
+        let mut inventory = Inventory::new();
+        for (table, size) in [(CONSONANTS, &profile.consonants), (VOWELS, &profile.vowels)] {
+            let (min, max) = (*size.start(), *size.end().max(size.start()));
+            let target = min + rng.below((max - min + 1) as u64) as usize;
+            for _ in 0..target {
+                let weights: Vec<u64> = table
+                    .iter()
+                    .map(|(segment, weight, implied)| {
+                        let eligible = !inventory.contains(segment)
+                            && implied.iter().all(|s| inventory.contains(s));
+                        match eligible {
+                            false => 0,
+                            true if inventory.voicing_pairs(segment) => weight * 4,
+                            true => *weight,
+                        }
+                    })
+                    .collect();
+                let Some(i) = rng.weighted(&weights) else {
+                    break;
+                };
+                inventory.insert(table[i].0);
+            }
+        }
+        inventory
+    }
+
+    /// Adds `allophone` after those already added, along with its
+    /// phoneme if it is new.
+    pub fn add_allophone(&mut self, allophone: Allophone) {
+        self.insert(allophone.phoneme());
+        self.allophones.push(allophone);
+    }
+
+    /// The allophones, in the order `realize` applies them.
+    pub fn allophones(&self) -> &[Allophone] {
+        &self.allophones
+    }
+
+    /// Derives the narrow transcription of `word` by applying each
+    /// allophone in turn to the output of the one before, so earlier
+    /// allophones can feed or bleed later ones.
+    ///
+    /// ```
+    /// use allo::classes::vowel;
+    /// use allo::inventory::{Inventory, PhonemicWord};
+    /// use allo::ipa;
+    /// use allo::rules::Allophone;
+    ///
+    /// let mut inventory: Inventory = allo::parser::parse("tda").unwrap().into_iter().collect();
+    /// inventory.add_allophone(
+    ///     Allophone::new(ipa!("t")).with_realization(ipa!("ɾ"), [vowel().into()], [vowel().into()]),
+    /// );
+    /// let word: PhonemicWord = "/tata/".parse().unwrap();
+    /// assert_eq!(inventory.realize(&word).to_string(), "[taɾa]");
+    /// ```
+    pub fn realize(&self, word: &PhonemicWord) -> PhoneticWord {
+        self.trace(word).0
+    }
+
+    /// Realizes `word` as `realize` does, also returning where each
+    /// allophone changed it, in the order the changes were made.
+    pub fn trace(&self, word: &PhonemicWord) -> (PhoneticWord, Vec<Firing>) {
+        let mut segments = word.segments.clone();
+        let mut firings = Vec::new();
+        for (allophone, rule) in self.allophones.iter().enumerate() {
+            let input = segments.clone();
+            for i in 0..input.len() {
+                let Some(realized) = rule.realize_at(&input, i) else {
+                    continue;
+                };
+                if realized != input[i] {
+                    let realization = rule
+                        .realizations()
+                        .iter()
+                        .position(|r| r.applies_at(&input, i));
+                    firings.push(Firing {
+                        position: i,
+                        allophone,
+                        realization,
+                    });
+                }
+                segments[i] = realized;
+            }
+        }
+        (PhoneticWord { segments }, firings)
+    }
+
+    /// Whether `segment` is a voiced obstruent whose voiceless
+    /// counterpart is in the inventory, along with another voiced
+    /// obstruent of the same manner and its counterpart.
+    fn voicing_pairs(&self, segment: &Segment) -> bool {
+        let voiceless = |c: &Consonant| c.with_phonation(Phonation::Voiceless);
+        let paired = |c: &Consonant| {
+            c.phonation() == Phonation::Voiced
+                && matches!(
+                    c.manner(),
+                    Manner::Plosive | Manner::Fricative { .. } | Manner::LatFric
+                )
+                && self.contains(&Segment::Consonant(voiceless(c)))
+        };
+        let Some(c) = segment.as_consonant().filter(|c| paired(c)) else {
+            return false;
+        };
+        self.iter()
+            .filter_map(|s| s.as_consonant().copied())
+            .any(|other| other != *c && other.manner() == c.manner() && paired(&other))
+    }
+
+    /// The inventory's typological measures; see `typology`.
+    pub fn typology(&self) -> Typology {
+        Typology::of(self)
+    }
+
+    /// The holes in the inventory's own chart: segments it lacks that
+    /// complete one of its series, in chart order. A series is a set
+    /// of at least two segments differing only in place (for vowels,
+    /// only in height), such as the voiced plosives or the front
+    /// unrounded vowels, and it has a gap at each place another series
+    /// of the same manner fills. Cells the IPA has no symbol for are
+    /// not gaps.
+    ///
+    /// ```
+    /// use allo::inventory::Inventory;
+    ///
+    /// let inventory: Inventory = allo::parser::parse("ptkbdieɛuo").unwrap().into_iter().collect();
+    /// let gaps = inventory.gaps();
+    /// assert_eq!(gaps, [allo::ipa!("ɡ"), allo::ipa!("ɔ")]);
+    /// ```
+    pub fn gaps(&self) -> Vec<Segment> {
+        let mut gaps: Vec<Segment> = Vec::new();
+        for segment in self.iter() {
+            let Some(own) = series(&segment) else {
+                continue;
+            };
+            if self.iter().filter(|s| series(s) == Some(own)).count() < 2 {
+                continue;
+            }
+            for other in self.iter() {
+                match moved_to(&segment, &other) {
+                    Some(gap) if !self.contains(&gap) && !gaps.contains(&gap) => gaps.push(gap),
+                    _ => {}
+                }
+            }
+        }
+        gaps.sort_by(Segment::chart_cmp);
+        gaps
+    }
+
+    /// How symmetric the inventory is, from 0 to 1: the share of the
+    /// cells of its series, as `gaps` finds them, that it fills. An
+    /// inventory without gaps scores 1.
+    pub fn symmetry(&self) -> f32 {
+        let gaps = self.gaps().len();
+        match self.len() + gaps {
+            0 => 1.0,
+            cells => self.len() as f32 / cells as f32,
+        }
+    }
+
+    /// The minimal pairs for `a` and `b` in `words`: pairs of
+    /// transcriptions that are the same but for one position, where the
+    /// first has `a` and the second `b`. There are none unless both are
+    /// in the inventory.
+    ///
+    /// ```
+    /// use allo::inventory::Inventory;
+    /// use allo::Segment;
+    ///
+    /// let inventory: Inventory = allo::parser::parse("ptkaiu").unwrap().into_iter().collect();
+    /// let [p, t]: [Segment; 2] = ["p".parse().unwrap(), "t".parse().unwrap()];
+    /// let pairs = inventory.find_minimal_pairs(&["pik", "tik", "pata", "tata"], &p, &t);
+    /// assert_eq!(pairs.unwrap(), [("pik", "tik"), ("pata", "tata")]);
+    /// ```
+    pub fn find_minimal_pairs<'w>(
+        &self,
+        words: &[&'w str],
+        a: &Segment,
+        b: &Segment,
+    ) -> Result<Vec<(&'w str, &'w str)>, ParseSegmentError> {
+        if a == b || !self.contains(a) || !self.contains(b) {
+            return Ok(Vec::new());
+        }
+        let parsed = words
+            .iter()
+            .map(|w| parser::parse(w))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut pairs = Vec::new();
+        for (i, first) in parsed.iter().enumerate() {
+            for (j, second) in parsed.iter().enumerate() {
+                if first.len() != second.len() {
+                    continue;
+                }
+                let mut differences = first.iter().zip(second).filter(|(x, y)| x != y);
+                if differences.next() == Some((a, b)) && differences.next().is_none() {
+                    pairs.push((words[i], words[j]));
+                }
+            }
+        }
+        Ok(pairs)
+    }
+
+    /// The functional load of the contrast between `a` and `b` in
+    /// `lexicon`: the share of the lexicon's entropy lost if the two
+    /// merged, from 0 (the contrast distinguishes no words) to 1. Each
+    /// entry counts once, so a word listed twice is twice as frequent.
+    /// The load is 0 unless both are in the inventory. For a count of
+    /// the minimal pairs instead, see `find_minimal_pairs`.
+    ///
+    /// Needs the `std` feature, for logarithms.
+    #[cfg(feature = "std")]
+    pub fn functional_load(
+        &self,
+        a: &Segment,
+        b: &Segment,
+        lexicon: &[&str],
+    ) -> Result<f32, ParseSegmentError> {
+        if a == b || !self.contains(a) || !self.contains(b) {
+            return Ok(0.0);
+        }
+        let mut words = BTreeMap::new();
+        let mut merged = BTreeMap::new();
+        for word in lexicon {
+            let segments = parser::parse(word)?;
+            let merge = segments.iter().map(|s| if s == b { *a } else { *s });
+            *merged.entry(merge.collect::<Vec<_>>()).or_insert(0) += 1;
+            *words.entry(segments).or_insert(0) += 1;
+        }
+        let entropy = |counts: BTreeMap<Vec<Segment>, usize>| -> f32 {
+            let total = lexicon.len() as f32;
+            counts
+                .values()
+                .map(|&n| n as f32 / total)
+                .map(|p| -p * p.log2())
+                .sum()
+        };
+        let before = entropy(words);
+        Ok(match before > 0.0 {
+            true => (before - entropy(merged)) / before,
+            false => 0.0,
+        })
+    }
+}
+
+/// The sizes of inventory `Inventory::generate` samples, as ranges of
+/// consonant and vowel counts.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Profile {
+    pub consonants: RangeInclusive<usize>,
+    pub vowels: RangeInclusive<usize>,
+}
+
+impl Profile {
+    /// Small inventories, like that of Rotokas or Hawaiian.
+    pub const SMALL: Profile = Profile {
+        consonants: 6..=14,
+        vowels: 3..=4,
+    };
+
+    /// Around the average of about 22 consonants and 5 or 6 vowels
+    /// (after Maddieson's survey in WALS).
+    pub const AVERAGE: Profile = Profile {
+        consonants: 19..=25,
+        vowels: 5..=6,
+    };
+
+    pub const LARGE: Profile = Profile {
+        consonants: 34..=42,
+        vowels: 7..=12,
+    };
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Profile::AVERAGE
+    }
+}
+
+/// Consonants for `Inventory::generate`, each with roughly the share
+/// of languages that have it (after PHOIBLE) and the consonants it
+/// implies.
+#[rustfmt::skip]
+const CONSONANTS: &[(Segment, u64, &[Segment])] = &[
+    (ipa!("m"), 96, &[]),
+    (ipa!("k"), 90, &[]),
+    (ipa!("j"), 90, &[]),
+    (ipa!("p"), 86, &[]),
+    (ipa!("w"), 82, &[]),
+    (ipa!("n"), 78, &[]),
+    (ipa!("t"), 68, &[]),
+    (ipa!("l"), 68, &[]),
+    (ipa!("s"), 67, &[]),
+    (ipa!("ŋ"), 63, &[]),
+    (ipa!("b"), 63, &[]),
+    (ipa!("h"), 56, &[]),
+    (ipa!("ɡ"), 56, &[ipa!("k")]),
+    (ipa!("ʔ"), 48, &[]),
+    (ipa!("d"), 46, &[ipa!("t")]),
+    (ipa!("r"), 44, &[]),
+    (ipa!("f"), 43, &[]),
+    (ipa!("ɲ"), 42, &[]),
+    (ipa!("t͡ʃ"), 40, &[]),
+    (ipa!("ʃ"), 37, &[]),
+    (ipa!("ɾ"), 30, &[]),
+    (ipa!("z"), 30, &[ipa!("s")]),
+    (ipa!("v"), 28, &[]),
+    (ipa!("d͡ʒ"), 27, &[]),
+    (ipa!("x"), 22, &[]),
+    (ipa!("t͡s"), 21, &[ipa!("s")]),
+    (ipa!("kʷ"), 17, &[ipa!("k")]),
+    (ipa!("kʰ"), 17, &[ipa!("k")]),
+    (ipa!("ʒ"), 16, &[ipa!("ʃ")]),
+    (ipa!("pʰ"), 15, &[ipa!("p")]),
+    (ipa!("tʰ"), 15, &[ipa!("t")]),
+    (ipa!("ɣ"), 14, &[]),
+    (ipa!("c"), 13, &[]),
+    (ipa!("ʎ"), 13, &[ipa!("l")]),
+    (ipa!("q"), 12, &[ipa!("k")]),
+    (ipa!("ɸ"), 12, &[ipa!("p")]),
+    (ipa!("ɟ"), 11, &[]),
+    (ipa!("β"), 10, &[ipa!("b")]),
+    (ipa!("ɓ"), 10, &[ipa!("b")]),
+    (ipa!("ɗ"), 10, &[ipa!("d")]),
+    (ipa!("ʈ"), 10, &[ipa!("t")]),
+    (ipa!("ɬ"), 9, &[ipa!("l")]),
+    (ipa!("ɳ"), 8, &[ipa!("n"), ipa!("ʈ")]),
+    (ipa!("ɖ"), 8, &[ipa!("d"), ipa!("ʈ")]),
+    (ipa!("ʁ"), 8, &[]),
+    (ipa!("χ"), 7, &[]),
+    (ipa!("θ"), 6, &[]),
+    (ipa!("ð"), 5, &[]),
+    (ipa!("ħ"), 4, &[]),
+    (ipa!("ʕ"), 4, &[ipa!("ħ")]),
+];
+
+/// Vowels for `Inventory::generate`, as `CONSONANTS`.
+#[rustfmt::skip]
+const VOWELS: &[(Segment, u64, &[Segment])] = &[
+    (ipa!("a"), 100, &[]),
+    (ipa!("i"), 99, &[]),
+    (ipa!("u"), 90, &[]),
+    (ipa!("e"), 70, &[ipa!("i")]),
+    (ipa!("o"), 68, &[ipa!("u")]),
+    (ipa!("ɛ"), 40, &[ipa!("e")]),
+    (ipa!("ɔ"), 38, &[ipa!("o")]),
+    (ipa!("ə"), 30, &[]),
+    (ipa!("ɪ"), 15, &[ipa!("i")]),
+    (ipa!("ɨ"), 15, &[ipa!("i")]),
+    (ipa!("ʊ"), 14, &[ipa!("u")]),
+    (ipa!("æ"), 10, &[ipa!("ɛ")]),
+    (ipa!("ɑ"), 8, &[ipa!("a")]),
+    (ipa!("y"), 8, &[ipa!("i")]),
+    (ipa!("ɯ"), 8, &[ipa!("u")]),
+    (ipa!("ø"), 6, &[ipa!("y")]),
+    (ipa!("ʌ"), 5, &[]),
+    (ipa!("œ"), 4, &[ipa!("ø")]),
+];
+
+impl PartialEq for Inventory {
+    fn eq(&self, other: &Self) -> bool {
+        self.segments == other.segments
+    }
+}
+
+impl Eq for Inventory {}
+
+impl Hash for Inventory {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.segments.hash(state);
+    }
+}
+
+/// A broad, phonemic transcription, written between slashes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PhonemicWord {
+    segments: Vec<Segment>,
+}
+
+impl PhonemicWord {
+    pub fn new(segments: Vec<Segment>) -> Self {
+        PhonemicWord { segments }
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+}
+
+impl fmt::Display for PhonemicWord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("/")?;
+        self.segments.iter().try_for_each(|s| s.fmt(f))?;
+        f.write_str("/")
+    }
+}
+
+impl FromStr for PhonemicWord {
+    type Err = ParseSegmentError;
+
+    /// Parses a transcription, with or without its slashes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .strip_prefix('/')
+            .and_then(|s| s.strip_suffix('/'))
+            .unwrap_or(s);
+        Ok(PhonemicWord::new(parser::parse(inner)?))
+    }
+}
+
+/// A narrow, phonetic transcription, written in square brackets.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PhoneticWord {
+    segments: Vec<Segment>,
+}
+
+impl PhoneticWord {
+    pub fn new(segments: Vec<Segment>) -> Self {
+        PhoneticWord { segments }
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+}
+
+impl fmt::Display for PhoneticWord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        self.segments.iter().try_for_each(|s| s.fmt(f))?;
+        f.write_str("]")
+    }
+}
+
+/// A change `Inventory::trace` records: the allophone at index
+/// `allophone` changed the segment at `position`, by the conditioned
+/// realization at index `realization`, or by its elsewhere case if
+/// that is `None`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Firing {
+    pub position: usize,
+    pub allophone: usize,
+    pub realization: Option<usize>,
+}
+
+/// The series `segment` belongs to, represented by the segment at the
+/// first place (for vowels, the first height), or `None` for
+/// suprasegmentals and tones.
+fn series(segment: &Segment) -> Option<Segment> {
+    let first = PoA::from(Articulation::Bilabial);
+    match segment {
+        Segment::Consonant(c) => Some(Segment::Consonant(c.with_poa(first))),
+        Segment::Affricate(a) => {
+            Affricate::new(a.stop().with_poa(first), a.fricative().with_poa(first))
+                .map(Segment::Affricate)
+        }
+        Segment::Vowel(v) => Some(Segment::Vowel(
+            Vowel::new(Height::Close, v.backness(), v.roundedness())
+                .with_diacritics(v.diacritics())
+                .with_length(v.length()),
+        )),
+        _ => None,
+    }
+}
+
+/// `segment` moved to the place (or height) of `other`, if the two are
+/// of the same manner and the chart has a symbol for the result's own
+/// cell.
+fn moved_to(segment: &Segment, other: &Segment) -> Option<Segment> {
+    match (segment, other) {
+        (Segment::Consonant(c), Segment::Consonant(o)) if c.manner() == o.manner() => {
+            let moved = c.with_poa(o.poa());
+            moved.has_cell().then_some(Segment::Consonant(moved))
+        }
+        (Segment::Affricate(a), Segment::Affricate(o))
+            if a.fricative().manner() == o.fricative().manner() =>
+        {
+            let stop = a.stop().with_poa(o.stop().poa());
+            let fricative = a.fricative().with_poa(o.fricative().poa());
+            match stop.has_cell() && fricative.has_cell() {
+                true => Affricate::new(stop, fricative).map(Segment::Affricate),
+                false => None,
+            }
+        }
+        (Segment::Vowel(v), Segment::Vowel(o)) => {
+            let moved = Vowel::new(o.height(), v.backness(), v.roundedness())
+                .with_diacritics(v.diacritics())
+                .with_length(v.length());
+            moved.has_cell().then_some(Segment::Vowel(moved))
+        }
+        _ => None,
+    }
+}
+
+impl FromIterator<Segment> for Inventory {
+    fn from_iter<I: IntoIterator<Item = Segment>>(iter: I) -> Self {
+        let mut inventory = Inventory::new();
+        inventory.extend(iter);
+        inventory
+    }
+}
+
+impl Extend<Segment> for Inventory {
+    fn extend<I: IntoIterator<Item = Segment>>(&mut self, iter: I) {
+        for segment in iter {
+            self.insert(segment);
+        }
+    }
+}
+
+impl IntoIterator for Inventory {
+    type Item = Segment;
+    type IntoIter = core::iter::Map<
+        alloc::collections::btree_set::IntoIter<(ChartKey, Segment)>,
+        fn((ChartKey, Segment)) -> Segment,
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.segments.into_iter().map(|(_, s)| s)
+    }
+}
@@ -1,6 +1,20 @@
 #![allow(unused)]
 
-// use std::collections::HashMap;
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::str::FromStr;
+
+#[cfg(feature = "alloc")]
+use alloc::string::ToString;
+
+use crate::diacritics::{self, Diacritic, Diacritics};
+use crate::error::Error;
+use crate::graphemes;
+use crate::normalize;
+use crate::parser::{self, TIE_BARS};
+#[cfg(feature = "alloc")]
+use crate::segment::{ParseSegmentError, Segment};
+use crate::suprasegmentals::{Length, Suprasegmental};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Tag(u32);
@@ -27,6 +41,8 @@ pub struct Tag(u32);
 /// On the other hand, /ç/ has a place variant `Place::Coronal`,
 /// while `/ʝ/` has `Place::Dorsal`.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Place {
     Labial,
     Corona,
@@ -34,7 +50,27 @@ pub enum Place {
     Laryngeal,
 }
 
+impl Place {
+    pub const ALL: [Place; 4] = [
+        Place::Labial,
+        Place::Corona,
+        Place::Dorsal,
+        Place::Laryngeal,
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Place::Labial => "labial",
+            Place::Corona => "coronal",
+            Place::Dorsal => "dorsal",
+            Place::Laryngeal => "laryngeal",
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 /// Corresponds to a column containing consonant pairs, which are
 /// further differentiated based on `Voicing`
 pub enum Articulation {
@@ -54,14 +90,97 @@ pub enum Articulation {
     Glottal,
 }
 
+impl Articulation {
+    /// Every articulation, in chart order from the lips back.
+    pub const ALL: [Articulation; 13] = [
+        Articulation::Bilabial,
+        Articulation::Labiodental,
+        Articulation::Linguolabial,
+        Articulation::Dental,
+        Articulation::Alveolar,
+        Articulation::Postalveolar,
+        Articulation::Retroflex,
+        Articulation::Palatal,
+        Articulation::Velar,
+        Articulation::Uvular,
+        Articulation::Pharyngeal,
+        Articulation::Epiglottal,
+        Articulation::Glottal,
+    ];
+
+    /// The column name, e.g. `"labiodental"`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Articulation::Bilabial => "bilabial",
+            Articulation::Labiodental => "labiodental",
+            Articulation::Linguolabial => "linguolabial",
+            Articulation::Dental => "dental",
+            Articulation::Alveolar => "alveolar",
+            Articulation::Postalveolar => "postalveolar",
+            Articulation::Retroflex => "retroflex",
+            Articulation::Palatal => "palatal",
+            Articulation::Velar => "velar",
+            Articulation::Uvular => "uvular",
+            Articulation::Pharyngeal => "pharyngeal",
+            Articulation::Epiglottal => "epiglottal",
+            Articulation::Glottal => "glottal",
+        }
+    }
+
+    /// The `Place` this articulation falls under by default.
+    ///
+    /// **Note** that this is only a default: palatals in particular
+    /// may be either `Place::Corona` (e.g., `/ç/`) or `Place::Dorsal`
+    /// (e.g., `/ʝ/`), in which case the `PoA` should be built with
+    /// `PoA::new` directly.
+    pub const fn place(&self) -> Place {
+        match self {
+            Articulation::Bilabial | Articulation::Labiodental => Place::Labial,
+            Articulation::Linguolabial
+            | Articulation::Dental
+            | Articulation::Alveolar
+            | Articulation::Postalveolar
+            | Articulation::Retroflex => Place::Corona,
+            Articulation::Palatal | Articulation::Velar | Articulation::Uvular => Place::Dorsal,
+            Articulation::Pharyngeal | Articulation::Epiglottal | Articulation::Glottal => {
+                Place::Laryngeal
+            }
+        }
+    }
+}
+
 /// Combinator struct holding both `Place` and `Articulation`, since many IPA
 /// tables so graciously mixe the two so often.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PoA {
     place: Place,
     articulation: Articulation,
 }
 
+impl PoA {
+    pub const fn new(place: Place, articulation: Articulation) -> Self {
+        Self {
+            place,
+            articulation,
+        }
+    }
+
+    pub const fn place(&self) -> Place {
+        self.place
+    }
+
+    pub const fn articulation(&self) -> Articulation {
+        self.articulation
+    }
+}
+
+impl From<Articulation> for PoA {
+    fn from(articulation: Articulation) -> Self {
+        PoA::new(articulation.place(), articulation)
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 /// Correspond to the rows in the IPA table for (pulmonic)
 /// consonants.
@@ -78,8 +197,1845 @@ pub enum Manner {
     LatTapFlap,
 }
 
+impl Manner {
+    /// Every manner, with sibilant and non-sibilant fricatives listed
+    /// separately.
+    pub const ALL: [Manner; 10] = [
+        Manner::Nasal,
+        Manner::Plosive,
+        Manner::Fricative { sibilant: false },
+        Manner::Fricative { sibilant: true },
+        Manner::Approximant,
+        Manner::TapFlap,
+        Manner::Trill,
+        Manner::LatFric,
+        Manner::LatApprox,
+        Manner::LatTapFlap,
+    ];
+
+    /// The row name, e.g. `"lateral approximant"`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Manner::Nasal => "nasal",
+            Manner::Plosive => "plosive",
+            Manner::Fricative { sibilant: true } => "sibilant fricative",
+            Manner::Fricative { sibilant: false } => "fricative",
+            Manner::Approximant => "approximant",
+            Manner::TapFlap => "tap or flap",
+            Manner::Trill => "trill",
+            Manner::LatFric => "lateral fricative",
+            Manner::LatApprox => "lateral approximant",
+            Manner::LatTapFlap => "lateral tap or flap",
+        }
+    }
+}
+
+/// The state of the glottis. `Voiced` is modal voice; the other
+/// kinds of voice are written as the voiced symbol plus a mark.
+///
+/// **Note** that the IPA has diacritics for breathy and creaky voice
+/// only. Slack and stiff voice are marked `b͉` and `b͈`, after the
+/// lenis and fortis marks of the extIPA.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Phonation {
     Voiced,
     Voiceless,
+    /// Breathy voice or murmur, e.g. the Hindi `/bʱ/`, written `b̤`.
+    Breathy,
+    /// Creaky voice or laryngealization, written `b̰`.
+    Creaky,
+    /// Slack voice, between breathy and modal voice.
+    Slack,
+    /// Stiff voice, between modal and creaky voice.
+    Stiff,
+}
+
+impl Phonation {
+    /// Every phonation, from the most open glottis to the most closed.
+    pub const ALL: [Phonation; 6] = [
+        Phonation::Voiceless,
+        Phonation::Breathy,
+        Phonation::Slack,
+        Phonation::Voiced,
+        Phonation::Stiff,
+        Phonation::Creaky,
+    ];
+
+    /// Another name for breathy voice.
+    pub const MURMURED: Phonation = Phonation::Breathy;
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Phonation::Voiced => "voiced",
+            Phonation::Voiceless => "voiceless",
+            Phonation::Breathy => "breathy-voiced",
+            Phonation::Creaky => "creaky-voiced",
+            Phonation::Slack => "slack-voiced",
+            Phonation::Stiff => "stiff-voiced",
+        }
+    }
+
+    /// Whether the vocal folds vibrate, as in every phonation but
+    /// `Voiceless`.
+    pub const fn is_voiced(&self) -> bool {
+        !matches!(self, Phonation::Voiceless)
+    }
+
+    /// The combining mark written after the voiced symbol, or `None`
+    /// for voiceless and modal voice.
+    pub const fn mark(&self) -> Option<char> {
+        match self {
+            Phonation::Voiced | Phonation::Voiceless => None,
+            Phonation::Breathy => Some('\u{324}'),
+            Phonation::Creaky => Some('\u{330}'),
+            Phonation::Slack => Some('\u{349}'),
+            Phonation::Stiff => Some('\u{348}'),
+        }
+    }
+
+    /// The phonation marked by `c`. The breathy-voiced aspiration `ʱ`
+    /// of `bʱ` is read as breathy voice too.
+    pub const fn from_mark(c: char) -> Option<Phonation> {
+        if c == '\u{2B1}' {
+            return Some(Phonation::Breathy);
+        }
+        let mut i = 0;
+        while i < Phonation::ALL.len() {
+            if matches!(Phonation::ALL[i].mark(), Some(m) if m == c) {
+                return Some(Phonation::ALL[i]);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// The phonation of breathy or creaky `diacritic`.
+    pub const fn from_diacritic(diacritic: Diacritic) -> Option<Phonation> {
+        match diacritic {
+            Diacritic::BreathyVoiced => Some(Phonation::Breathy),
+            Diacritic::CreakyVoiced => Some(Phonation::Creaky),
+            _ => None,
+        }
+    }
+}
+
+/// The airstream mechanism used to produce a consonant. Everything in
+/// the main IPA consonant table is `PulmonicEgressive`; the remaining
+/// variants correspond to the non-pulmonic section of the chart.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Airstream {
+    #[default]
+    PulmonicEgressive,
+    /// Implosives, e.g. `/ɓ ɗ ɠ/`.
+    GlottalicIngressive,
+    /// Ejectives, e.g. `/pʼ tʼ kʼ/`.
+    GlottalicEgressive,
+    /// Clicks, e.g. `/ʘ ǀ ǃ/`.
+    VelaricIngressive,
+}
+
+impl Airstream {
+    pub const ALL: [Airstream; 4] = [
+        Airstream::PulmonicEgressive,
+        Airstream::GlottalicIngressive,
+        Airstream::GlottalicEgressive,
+        Airstream::VelaricIngressive,
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Airstream::PulmonicEgressive => "pulmonic egressive",
+            Airstream::GlottalicIngressive => "glottalic ingressive",
+            Airstream::GlottalicEgressive => "glottalic egressive",
+            Airstream::VelaricIngressive => "velaric ingressive",
+        }
+    }
+
+    pub const fn is_pulmonic(&self) -> bool {
+        matches!(self, Airstream::PulmonicEgressive)
+    }
+}
+
+/// A constriction added to a consonant's primary articulation,
+/// written as a superscript after it: `kʷ tʲ lˠ sˤ mˀ`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SecondaryArticulation {
+    Labialized,
+    Palatalized,
+    Velarized,
+    Pharyngealized,
+    Glottalized,
+}
+
+impl SecondaryArticulation {
+    pub const ALL: [SecondaryArticulation; 5] = [
+        SecondaryArticulation::Labialized,
+        SecondaryArticulation::Palatalized,
+        SecondaryArticulation::Velarized,
+        SecondaryArticulation::Pharyngealized,
+        SecondaryArticulation::Glottalized,
+    ];
+
+    /// Graphemes: ʷ ʲ ˠ ˤ ˀ
+    pub const fn mark(&self) -> char {
+        match self {
+            SecondaryArticulation::Labialized => '\u{2B7}',
+            SecondaryArticulation::Palatalized => '\u{2B2}',
+            SecondaryArticulation::Velarized => '\u{2E0}',
+            SecondaryArticulation::Pharyngealized => '\u{2E4}',
+            SecondaryArticulation::Glottalized => '\u{2C0}',
+        }
+    }
+
+    pub const fn from_char(c: char) -> Option<SecondaryArticulation> {
+        let mut i = 0;
+        while i < SecondaryArticulation::ALL.len() {
+            if SecondaryArticulation::ALL[i].mark() == c {
+                return Some(SecondaryArticulation::ALL[i]);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            SecondaryArticulation::Labialized => "labialized",
+            SecondaryArticulation::Palatalized => "palatalized",
+            SecondaryArticulation::Velarized => "velarized",
+            SecondaryArticulation::Pharyngealized => "pharyngealized",
+            SecondaryArticulation::Glottalized => "glottalized",
+        }
+    }
+
+    /// The diacritic with the same mark, which vowels carry instead.
+    /// Glottalization has none.
+    pub const fn diacritic(&self) -> Option<Diacritic> {
+        match self {
+            SecondaryArticulation::Labialized => Some(Diacritic::Labialized),
+            SecondaryArticulation::Palatalized => Some(Diacritic::Palatalized),
+            SecondaryArticulation::Velarized => Some(Diacritic::Velarized),
+            SecondaryArticulation::Pharyngealized => Some(Diacritic::Pharyngealized),
+            SecondaryArticulation::Glottalized => None,
+        }
+    }
+
+    pub const fn from_diacritic(diacritic: Diacritic) -> Option<SecondaryArticulation> {
+        match diacritic {
+            Diacritic::Labialized => Some(SecondaryArticulation::Labialized),
+            Diacritic::Palatalized => Some(SecondaryArticulation::Palatalized),
+            Diacritic::Velarized => Some(SecondaryArticulation::Velarized),
+            Diacritic::Pharyngealized => Some(SecondaryArticulation::Pharyngealized),
+            _ => None,
+        }
+    }
+}
+
+/// How a stop is released, written after it: aspirated `tʰ`, with
+/// nasal `tⁿ` or lateral `tˡ` release, or with no audible release `t̚`.
+///
+/// Releases are kept among a consonant's `diacritics`. The other
+/// marks written in the same place are not releases: the ejective
+/// `ʼ` sets the airstream, and the breathy-voiced aspiration `ʱ` of
+/// `bʱ` the phonation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Release {
+    Aspirated,
+    Nasal,
+    Lateral,
+    Unreleased,
+}
+
+impl Release {
+    pub const ALL: [Release; 4] = [
+        Release::Aspirated,
+        Release::Nasal,
+        Release::Lateral,
+        Release::Unreleased,
+    ];
+
+    /// The diacritic marking the release.
+    pub const fn diacritic(&self) -> Diacritic {
+        match self {
+            Release::Aspirated => Diacritic::Aspirated,
+            Release::Nasal => Diacritic::NasalRelease,
+            Release::Lateral => Diacritic::LateralRelease,
+            Release::Unreleased => Diacritic::NoAudibleRelease,
+        }
+    }
+
+    pub const fn from_diacritic(diacritic: Diacritic) -> Option<Release> {
+        match diacritic {
+            Diacritic::Aspirated => Some(Release::Aspirated),
+            Diacritic::NasalRelease => Some(Release::Nasal),
+            Diacritic::LateralRelease => Some(Release::Lateral),
+            Diacritic::NoAudibleRelease => Some(Release::Unreleased),
+            _ => None,
+        }
+    }
+
+    /// Graphemes: ʰ ⁿ ˡ ̚
+    pub const fn mark(&self) -> char {
+        self.diacritic().mark()
+    }
+
+    pub const fn from_char(c: char) -> Option<Release> {
+        match Diacritic::from_char(c) {
+            Some(d) => Release::from_diacritic(d),
+            None => None,
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        self.diacritic().name()
+    }
+}
+
+/// A consonant, described by its place and manner of articulation,
+/// its voicing, and the airstream mechanism producing it.
+///
+/// **Note** that a secondary articulation is a field of its own and
+/// not one of the consonant's `diacritics`: attaching
+/// `Diacritic::Labialized` to a consonant sets its secondary
+/// articulation instead.
+///
+/// Doubly articulated consonants such as `/k͡p/` and `/w/` have a
+/// second place of `coarticulation`, made at the same time as the
+/// first and with the same manner. The two are kept in chart order,
+/// so `/k͡p/` is a bilabial with a velar coarticulation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Consonant {
+    poa: PoA,
+    coarticulation: Option<Articulation>,
+    manner: Manner,
+    phonation: Phonation,
+    airstream: Airstream,
+    secondary: Option<SecondaryArticulation>,
+    diacritics: Diacritics,
+    length: Length,
+}
+
+impl Consonant {
+    /// Creates a pulmonic egressive consonant.
+    pub const fn new(poa: PoA, manner: Manner, phonation: Phonation) -> Self {
+        Self {
+            poa,
+            coarticulation: None,
+            manner,
+            phonation,
+            airstream: Airstream::PulmonicEgressive,
+            secondary: None,
+            diacritics: Diacritics::new(),
+            length: Length::Short,
+        }
+    }
+
+    pub const fn with_airstream(mut self, airstream: Airstream) -> Self {
+        self.airstream = airstream;
+        self
+    }
+
+    /// Returns the ejective counterpart of this consonant.
+    pub const fn ejective(self) -> Self {
+        self.with_airstream(Airstream::GlottalicEgressive)
+    }
+
+    pub const fn poa(&self) -> PoA {
+        self.poa
+    }
+
+    pub const fn place(&self) -> Place {
+        self.poa.place
+    }
+
+    pub const fn articulation(&self) -> Articulation {
+        self.poa.articulation
+    }
+
+    /// Moves the consonant to `poa`, keeping any coarticulation unless
+    /// it is at the new place.
+    pub const fn with_poa(mut self, poa: PoA) -> Self {
+        self.poa = poa;
+        let coarticulation = self.coarticulation;
+        self.coarticulation = None;
+        self.with_coarticulation(coarticulation)
+    }
+
+    /// The second place of articulation of a doubly articulated
+    /// consonant, e.g. velar for `/k͡p/`.
+    pub const fn coarticulation(&self) -> Option<Articulation> {
+        self.coarticulation
+    }
+
+    /// Sets the second place of articulation. If it comes before the
+    /// consonant's own articulation in chart order the two swap, so
+    /// `k` coarticulated at the lips is the same consonant as `p`
+    /// coarticulated at the velum. A coarticulation at the consonant's
+    /// own place is dropped.
+    pub const fn with_coarticulation(mut self, coarticulation: Option<Articulation>) -> Self {
+        self.coarticulation = match coarticulation {
+            Some(second) if second as u8 == self.poa.articulation as u8 => None,
+            Some(second) if (second as u8) < self.poa.articulation as u8 => {
+                let first = self.poa.articulation;
+                self.poa = PoA::new(second.place(), second);
+                Some(first)
+            }
+            other => other,
+        };
+        self
+    }
+
+    pub const fn manner(&self) -> Manner {
+        self.manner
+    }
+
+    /// The consonant with `manner`, at the same place and with the same
+    /// voicing and diacritics.
+    pub const fn with_manner(mut self, manner: Manner) -> Self {
+        self.manner = manner;
+        self
+    }
+
+    pub const fn phonation(&self) -> Phonation {
+        self.phonation
+    }
+
+    pub const fn with_phonation(mut self, phonation: Phonation) -> Self {
+        self.phonation = phonation;
+        self
+    }
+
+    pub const fn airstream(&self) -> Airstream {
+        self.airstream
+    }
+
+    pub const fn secondary(&self) -> Option<SecondaryArticulation> {
+        self.secondary
+    }
+
+    pub const fn with_secondary(mut self, secondary: Option<SecondaryArticulation>) -> Self {
+        self.secondary = secondary;
+        self
+    }
+
+    pub const fn diacritics(&self) -> Diacritics {
+        self.diacritics
+    }
+
+    pub const fn length(&self) -> Length {
+        self.length
+    }
+
+    pub const fn with_length(mut self, length: Length) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// How the consonant is released, if its diacritics say. Should it
+    /// carry several release marks, the first in `Release::ALL` is
+    /// returned.
+    pub const fn release(&self) -> Option<Release> {
+        let mut i = 0;
+        while i < Release::ALL.len() {
+            if self.diacritics.contains(Release::ALL[i].diacritic()) {
+                return Some(Release::ALL[i]);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Replaces any release marks with that of `release`.
+    pub const fn with_release(mut self, release: Option<Release>) -> Self {
+        let mut i = 0;
+        while i < Release::ALL.len() {
+            self.diacritics = self.diacritics.without(Release::ALL[i].diacritic());
+            i += 1;
+        }
+        if let Some(release) = release {
+            self.diacritics = self.diacritics.with(release.diacritic());
+        }
+        self
+    }
+
+    /// Attaches `diacritic`, or sets the secondary articulation for
+    /// the labialized, palatalized, velarized and pharyngealized
+    /// marks and the phonation for the breathy and creaky ones.
+    pub const fn with_diacritic(mut self, diacritic: Diacritic) -> Self {
+        if let Some(phonation) = Phonation::from_diacritic(diacritic) {
+            self.phonation = phonation;
+            return self;
+        }
+        match SecondaryArticulation::from_diacritic(diacritic) {
+            Some(secondary) => self.secondary = Some(secondary),
+            None => self.diacritics = self.diacritics.with(diacritic),
+        }
+        self
+    }
+
+    /// Replaces the diacritics with `diacritics`. As with
+    /// `with_diacritic`, a secondary articulation or phonation mark
+    /// among them sets the secondary articulation or phonation.
+    pub const fn with_diacritics(mut self, diacritics: Diacritics) -> Self {
+        self.diacritics = Diacritics::new();
+        let mut i = 0;
+        while i < Diacritic::ALL.len() {
+            if diacritics.contains(Diacritic::ALL[i]) {
+                self = self.with_diacritic(Diacritic::ALL[i]);
+            }
+            i += 1;
+        }
+        self
+    }
+
+    /// Whether `self` and `other` occupy the same chart cell, ignoring
+    /// diacritics. `Place` is derived from `Articulation` and is not
+    /// compared.
+    const fn same_cell(&self, other: &Consonant) -> bool {
+        self.poa.articulation as u8 == other.poa.articulation as u8
+            && match (self.coarticulation, other.coarticulation) {
+                (Some(a), Some(b)) => a as u8 == b as u8,
+                (None, None) => true,
+                _ => false,
+            }
+            && manner_eq(self.manner, other.manner)
+            && self.phonation as u8 == other.phonation as u8
+            && self.airstream as u8 == other.airstream as u8
+    }
+
+    /// The consonant its letter stands for alone, without diacritics,
+    /// secondary articulation or length, and with modal voice for
+    /// breathy, creaky, slack and stiff: `t` for `t̪ʰ`, `n` for `n̥`
+    /// and `b` for `b̤ː`. The airstream and any coarticulation are
+    /// kept, so the base of `tʼ` is `tʼ`.
+    pub fn base(&self) -> Consonant {
+        let airstream = self.airstream;
+        let plain = Consonant {
+            secondary: None,
+            diacritics: Diacritics::new(),
+            length: Length::Short,
+            ..*self
+        };
+        let cell = match airstream {
+            Airstream::GlottalicEgressive => plain.with_airstream(Airstream::PulmonicEgressive),
+            _ => plain,
+        };
+        let cell = match cell.phonation {
+            Phonation::Voiceless => cell,
+            _ => cell.with_phonation(Phonation::Voiced),
+        };
+        let opposite = match cell.phonation {
+            Phonation::Voiceless => cell.with_phonation(Phonation::Voiced),
+            _ => cell.with_phonation(Phonation::Voiceless),
+        };
+        let grapheme = cell.cell_grapheme().map(|g| (g, cell)).or_else(|| {
+            let g = opposite.cell_grapheme()?;
+            Some((g, opposite))
+        });
+        let Some((grapheme, cell)) = grapheme else {
+            return cell.with_airstream(airstream);
+        };
+        let mut chars = grapheme.chars();
+        let letter = chars.next().map_or(0, char::len_utf8);
+        let marked = chars.all(|c| Diacritic::from_char(c).is_some_and(|d| d.is_combining()));
+        match lookup(&grapheme[..letter]) {
+            Some(Description::Consonant(c)) if marked => c.with_airstream(airstream),
+            _ => cell.with_airstream(airstream),
+        }
+    }
+
+    /// Whether `Display` can write the consonant without U+FFFD: its
+    /// cell or that of the opposite voicing has a symbol, or it is
+    /// doubly articulated and can be written with a tie bar.
+    pub(crate) const fn is_writable(&self) -> bool {
+        let opposite = match self.phonation {
+            Phonation::Voiceless => self.with_phonation(Phonation::Voiced),
+            _ => self.with_phonation(Phonation::Voiceless),
+        };
+        self.has_cell() || opposite.has_cell()
+    }
+
+    /// Whether the consonant's own cell has a symbol, or it is doubly
+    /// articulated. Unlike `is_writable`, the opposite voicing does not
+    /// count, so `ʔ` has a cell but a voiced `ʔ̬` does not.
+    pub(crate) const fn has_cell(&self) -> bool {
+        let cell = match self.airstream {
+            Airstream::GlottalicEgressive => self.with_airstream(Airstream::PulmonicEgressive),
+            _ => *self,
+        };
+        let cell = match cell.phonation {
+            Phonation::Voiceless => cell,
+            _ => cell.with_phonation(Phonation::Voiced),
+        };
+        self.coarticulation.is_some() || cell.cell_grapheme().is_some()
+    }
+
+    /// The grapheme of the consonant's chart cell, excluding ejectives,
+    /// which have no table of their own.
+    const fn cell_grapheme(&self) -> Option<&'static str> {
+        if self.coarticulation.is_some() {
+            return find_cell(&graphemes::COARTICULATED, &COARTICULATED, self);
+        }
+        match self.airstream {
+            Airstream::PulmonicEgressive => {
+                let mut i = 0;
+                while i < PULMONIC.len() {
+                    if PULMONIC[i].1.same_cell(self) {
+                        return Some(PULMONIC[i].0);
+                    }
+                    i += 1;
+                }
+                None
+            }
+            Airstream::GlottalicEgressive => None,
+            Airstream::GlottalicIngressive => find_cell(&graphemes::IMPLOSIVES, &IMPLOSIVES, self),
+            Airstream::VelaricIngressive => find_cell(&graphemes::CLICKS, &CLICKS, self),
+        }
+    }
+}
+
+/// The grapheme paired with the first of `consonants` in the same cell
+/// as `c`.
+const fn find_cell(
+    graphemes: &[&'static str],
+    consonants: &[Consonant],
+    c: &Consonant,
+) -> Option<&'static str> {
+    let mut i = 0;
+    while i < consonants.len() {
+        if consonants[i].same_cell(c) {
+            return Some(graphemes[i]);
+        }
+        i += 1;
+    }
+    None
+}
+
+const fn manner_eq(a: Manner, b: Manner) -> bool {
+    match (a, b) {
+        (Manner::Fricative { sibilant: x }, Manner::Fricative { sibilant: y }) => x == y,
+        (Manner::Fricative { .. }, _) | (_, Manner::Fricative { .. }) => false,
+        _ => manner_index(a) == manner_index(b),
+    }
+}
+
+const fn manner_index(manner: Manner) -> u8 {
+    match manner {
+        Manner::Nasal => 0,
+        Manner::Plosive => 1,
+        Manner::Fricative { .. } => 2,
+        Manner::Approximant => 3,
+        Manner::TapFlap => 4,
+        Manner::Trill => 5,
+        Manner::LatFric => 6,
+        Manner::LatApprox => 7,
+        Manner::LatTapFlap => 8,
+    }
+}
+
+impl fmt::Display for Consonant {
+    /// Writes the consonant's grapheme followed by its diacritics,
+    /// combining marks first and then the release marks, then its
+    /// secondary articulation, the ejective mark and its length, as in
+    /// `t̪ʰʷ` and `kʷʼː`. Breathy, creaky, slack and stiff voice are
+    /// written as the voiced symbol with the phonation's mark. Cells
+    /// without a symbol of their own borrow the symbol of the opposite
+    /// voicing and mark the difference with a diacritic. Doubly
+    /// articulated consonants without a symbol of their own are written
+    /// as their two places joined by a tie bar, labial last as in `k͡p`.
+    /// Anything else is written as U+FFFD.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (cell, ejective) = match self.airstream {
+            Airstream::GlottalicEgressive => {
+                (self.with_airstream(Airstream::PulmonicEgressive), true)
+            }
+            _ => (*self, false),
+        };
+        let voice = cell.phonation.mark();
+        let cell = match voice {
+            Some(_) => cell.with_phonation(Phonation::Voiced),
+            None => cell,
+        };
+        let mut extra = self.diacritics;
+        let base = cell.cell_grapheme().or_else(|| {
+            let (phonation, mark) = match cell.phonation {
+                Phonation::Voiceless => (Phonation::Voiced, Diacritic::Voiceless),
+                _ => (Phonation::Voiceless, Diacritic::Voiced),
+            };
+            let g = Consonant { phonation, ..cell }.cell_grapheme()?;
+            extra.insert(mark);
+            Some(g)
+        });
+        match (base, self.coarticulation) {
+            (Some(g), _) => {
+                f.write_str(g)?;
+                if let Some(mark) = voice {
+                    fmt::Write::write_char(f, mark)?;
+                }
+                diacritics::write_marks(f, g, extra)?
+            }
+            (None, Some(second)) => {
+                let at = |articulation: Articulation| Consonant {
+                    poa: articulation.into(),
+                    coarticulation: None,
+                    ..*self
+                };
+                let (first, last) = match self.place() {
+                    Place::Labial => (at(second), at(self.articulation())),
+                    _ => (at(self.articulation()), at(second)),
+                };
+                let first = Consonant {
+                    airstream: cell.airstream,
+                    secondary: None,
+                    diacritics: Diacritics::new(),
+                    length: Length::Short,
+                    ..first
+                };
+                return write!(f, "{}{}{}", first, TIE_BARS[0], last);
+            }
+            (None, None) => f.write_str("\u{FFFD}")?,
+        }
+        if let Some(secondary) = self.secondary {
+            write!(f, "{}", secondary.mark())?;
+        }
+        if ejective {
+            f.write_str(graphemes::EJECTIVE)?;
+        }
+        f.write_str(self.length.marks())
+    }
+}
+
+const fn non_pulmonic(
+    articulation: Articulation,
+    phonation: Phonation,
+    airstream: Airstream,
+) -> Consonant {
+    Consonant::new(
+        PoA::new(articulation.place(), articulation),
+        Manner::Plosive,
+        phonation,
+    )
+    .with_airstream(airstream)
+}
+
+/// The consonants spelled by `graphemes::CLICKS`, in the same order.
+///
+/// **Note** that the lateral click `/ǁ/` is told apart from the
+/// postalveolar click `/ǃ/` by its articulation alone, as `Manner`
+/// has no lateral stop row.
+pub const CLICKS: [Consonant; 5] = [
+    non_pulmonic(
+        Articulation::Bilabial,
+        Phonation::Voiceless,
+        Airstream::VelaricIngressive,
+    ),
+    non_pulmonic(
+        Articulation::Dental,
+        Phonation::Voiceless,
+        Airstream::VelaricIngressive,
+    ),
+    non_pulmonic(
+        Articulation::Postalveolar,
+        Phonation::Voiceless,
+        Airstream::VelaricIngressive,
+    ),
+    non_pulmonic(
+        Articulation::Palatal,
+        Phonation::Voiceless,
+        Airstream::VelaricIngressive,
+    ),
+    non_pulmonic(
+        Articulation::Alveolar,
+        Phonation::Voiceless,
+        Airstream::VelaricIngressive,
+    ),
+];
+
+/// The consonants spelled by `graphemes::COARTICULATED`, in the same
+/// order: the labial-velar approximant and fricative, the
+/// labial-palatal approximant, and the palatal-velar fricative.
+pub const COARTICULATED: [Consonant; 4] = [
+    Consonant::new(
+        PoA::new(Place::Labial, Articulation::Bilabial),
+        Manner::Approximant,
+        Phonation::Voiced,
+    )
+    .with_coarticulation(Some(Articulation::Velar)),
+    Consonant::new(
+        PoA::new(Place::Labial, Articulation::Bilabial),
+        Manner::Fricative { sibilant: false },
+        Phonation::Voiceless,
+    )
+    .with_coarticulation(Some(Articulation::Velar)),
+    Consonant::new(
+        PoA::new(Place::Labial, Articulation::Bilabial),
+        Manner::Approximant,
+        Phonation::Voiced,
+    )
+    .with_coarticulation(Some(Articulation::Palatal)),
+    Consonant::new(
+        PoA::new(Place::Dorsal, Articulation::Palatal),
+        Manner::Fricative { sibilant: false },
+        Phonation::Voiceless,
+    )
+    .with_coarticulation(Some(Articulation::Velar)),
+];
+
+/// The consonants spelled by `graphemes::IMPLOSIVES`, in the same order.
+pub const IMPLOSIVES: [Consonant; 5] = [
+    non_pulmonic(
+        Articulation::Bilabial,
+        Phonation::Voiced,
+        Airstream::GlottalicIngressive,
+    ),
+    non_pulmonic(
+        Articulation::Alveolar,
+        Phonation::Voiced,
+        Airstream::GlottalicIngressive,
+    ),
+    non_pulmonic(
+        Articulation::Palatal,
+        Phonation::Voiced,
+        Airstream::GlottalicIngressive,
+    ),
+    non_pulmonic(
+        Articulation::Velar,
+        Phonation::Voiced,
+        Airstream::GlottalicIngressive,
+    ),
+    non_pulmonic(
+        Articulation::Uvular,
+        Phonation::Voiced,
+        Airstream::GlottalicIngressive,
+    ),
+];
+
+/// Vertical position of the tongue body. These are the basis for the
+/// rows in the IPA vowel chart, ordered from top to bottom.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Height {
+    Close,
+    NearClose,
+    CloseMid,
+    Mid,
+    OpenMid,
+    NearOpen,
+    Open,
+}
+
+impl Height {
+    /// Every height, from close to open.
+    pub const ALL: [Height; 7] = [
+        Height::Close,
+        Height::NearClose,
+        Height::CloseMid,
+        Height::Mid,
+        Height::OpenMid,
+        Height::NearOpen,
+        Height::Open,
+    ];
+
+    /// The row name, e.g. `"close-mid"`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Height::Close => "close",
+            Height::NearClose => "near-close",
+            Height::CloseMid => "close-mid",
+            Height::Mid => "mid",
+            Height::OpenMid => "open-mid",
+            Height::NearOpen => "near-open",
+            Height::Open => "open",
+        }
+    }
+}
+
+/// Horizontal position of the tongue body. These are the basis for
+/// the columns in the IPA vowel chart, ordered from left to right.
+///
+/// **Note** that the near-front and near-back columns are only
+/// populated in the `Height::NearClose` row, i.e., by `/ɪ ʏ ʊ/`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Backness {
+    Front,
+    NearFront,
+    Central,
+    NearBack,
+    Back,
+}
+
+impl Backness {
+    /// Every backness, from front to back.
+    pub const ALL: [Backness; 5] = [
+        Backness::Front,
+        Backness::NearFront,
+        Backness::Central,
+        Backness::NearBack,
+        Backness::Back,
+    ];
+
+    /// The column name, e.g. `"near-front"`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Backness::Front => "front",
+            Backness::NearFront => "near-front",
+            Backness::Central => "central",
+            Backness::NearBack => "near-back",
+            Backness::Back => "back",
+        }
+    }
+}
+
+/// Lip rounding. Within a vowel chart cell, the unrounded vowel is
+/// written to the left of its rounded counterpart.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Roundedness {
+    Unrounded,
+    Rounded,
+}
+
+impl Roundedness {
+    pub const ALL: [Roundedness; 2] = [Roundedness::Unrounded, Roundedness::Rounded];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Roundedness::Unrounded => "unrounded",
+            Roundedness::Rounded => "rounded",
+        }
+    }
+}
+
+/// A vowel, identified by its cell in the IPA vowel chart. This is
+/// the vowel analogue of the (`PoA`, `Manner`, `Phonation`) triple
+/// used for consonants.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vowel {
+    height: Height,
+    backness: Backness,
+    roundedness: Roundedness,
+    diacritics: Diacritics,
+    length: Length,
+}
+
+impl Vowel {
+    pub const fn new(height: Height, backness: Backness, roundedness: Roundedness) -> Self {
+        Self {
+            height,
+            backness,
+            roundedness,
+            diacritics: Diacritics::new(),
+            length: Length::Short,
+        }
+    }
+
+    pub const fn height(&self) -> Height {
+        self.height
+    }
+
+    pub const fn backness(&self) -> Backness {
+        self.backness
+    }
+
+    pub const fn roundedness(&self) -> Roundedness {
+        self.roundedness
+    }
+
+    pub const fn is_rounded(&self) -> bool {
+        matches!(self.roundedness, Roundedness::Rounded)
+    }
+
+    pub const fn diacritics(&self) -> Diacritics {
+        self.diacritics
+    }
+
+    pub const fn with_diacritic(mut self, diacritic: Diacritic) -> Self {
+        self.diacritics = self.diacritics.with(diacritic);
+        self
+    }
+
+    pub const fn with_diacritics(mut self, diacritics: Diacritics) -> Self {
+        self.diacritics = diacritics;
+        self
+    }
+
+    pub const fn length(&self) -> Length {
+        self.length
+    }
+
+    pub const fn with_length(mut self, length: Length) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// The vowel its letter stands for alone, without diacritics or
+    /// length: `e` for `ẽː`. A vowel written with another's letter and
+    /// a rounding mark has that vowel as its base.
+    pub fn base(&self) -> Vowel {
+        let plain = Vowel::new(self.height, self.backness, self.roundedness);
+        let roundedness = match self.roundedness {
+            Roundedness::Rounded => Roundedness::Unrounded,
+            Roundedness::Unrounded => Roundedness::Rounded,
+        };
+        match plain.cell_grapheme() {
+            None => Vowel {
+                roundedness,
+                ..plain
+            },
+            Some(_) => plain,
+        }
+    }
+
+    /// Whether `Display` can write the vowel without U+FFFD, with its
+    /// own symbol or that of the opposite rounding.
+    pub(crate) const fn is_writable(&self) -> bool {
+        let roundedness = match self.roundedness {
+            Roundedness::Rounded => Roundedness::Unrounded,
+            Roundedness::Unrounded => Roundedness::Rounded,
+        };
+        self.has_cell() || Vowel::new(self.height, self.backness, roundedness).has_cell()
+    }
+
+    /// Whether the vowel's own cell has a symbol, not counting the
+    /// opposite rounding.
+    pub(crate) const fn has_cell(&self) -> bool {
+        self.cell_grapheme().is_some()
+    }
+
+    const fn same_cell(&self, other: &Vowel) -> bool {
+        self.height as u8 == other.height as u8
+            && self.backness as u8 == other.backness as u8
+            && self.roundedness as u8 == other.roundedness as u8
+    }
+
+    const fn cell_grapheme(&self) -> Option<&'static str> {
+        let mut i = 0;
+        while i < VOWELS.len() {
+            if VOWELS[i].1.same_cell(self) {
+                return Some(VOWELS[i].0);
+            }
+            i += 1;
+        }
+        None
+    }
+}
+
+impl fmt::Display for Vowel {
+    /// Writes the vowel's grapheme followed by its diacritics and its
+    /// length. Cells without a symbol of their own borrow the symbol of
+    /// the opposite rounding and mark the difference with a diacritic;
+    /// anything else is written as U+FFFD.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut extra = self.diacritics;
+        let base = self.cell_grapheme().or_else(|| {
+            let (roundedness, mark) = match self.roundedness {
+                Roundedness::Rounded => (Roundedness::Unrounded, Diacritic::MoreRounded),
+                Roundedness::Unrounded => (Roundedness::Rounded, Diacritic::LessRounded),
+            };
+            let g = Vowel {
+                roundedness,
+                ..*self
+            }
+            .cell_grapheme()?;
+            extra.insert(mark);
+            Some(g)
+        });
+        match base {
+            Some(g) => {
+                f.write_str(g)?;
+                diacritics::write_marks(f, g, extra)?;
+            }
+            None => f.write_str("\u{FFFD}")?,
+        }
+        f.write_str(self.length.marks())
+    }
+}
+
+/// Looks up the grapheme of the pulmonic consonant with the given
+/// features, e.g. `"ɣ"` for a voiced velar non-sibilant fricative.
+///
+/// Only `poa.articulation()` is compared, since the place is implied
+/// by it. Returns `None` for cells the IPA leaves empty, such as a
+/// voiced pharyngeal plosive.
+pub const fn grapheme_for(poa: PoA, manner: Manner, phonation: Phonation) -> Option<&'static str> {
+    Consonant::new(poa, manner, phonation).cell_grapheme()
+}
+
+/// The vowel equivalent of `grapheme_for`, e.g. `"ø"` for a
+/// close-mid front rounded vowel.
+pub const fn vowel_grapheme_for(
+    height: Height,
+    backness: Backness,
+    roundedness: Roundedness,
+) -> Option<&'static str> {
+    Vowel::new(height, backness, roundedness).cell_grapheme()
+}
+
+/// The chart symbol spelled exactly `grapheme`, with no diacritics or
+/// other marks: `"ʃ"`, but not `"ʃʷ"`, for which see `describe`.
+/// Unlike `describe`, this is a `const fn`, so it can build tables at
+/// compile time:
+///
+/// ```
+/// use allo::ipa::{self, Manner};
+///
+/// const SH: Option<Manner> = match ipa::lookup("ʃ") {
+///     Some(d) => d.manner(),
+///     None => None,
+/// };
+/// assert_eq!(SH, Some(Manner::Fricative { sibilant: true }));
+/// ```
+pub const fn lookup(grapheme: &str) -> Option<Description> {
+    let mut i = 0;
+    while i < PULMONIC.len() {
+        if str_eq(PULMONIC[i].0, grapheme) {
+            return Some(Description::Consonant(PULMONIC[i].1));
+        }
+        i += 1;
+    }
+    let tables: [(&[&str], &[Consonant]); 3] = [
+        (&graphemes::CLICKS, &CLICKS),
+        (&graphemes::IMPLOSIVES, &IMPLOSIVES),
+        (&graphemes::COARTICULATED, &COARTICULATED),
+    ];
+    let mut t = 0;
+    while t < tables.len() {
+        let (symbols, consonants) = tables[t];
+        let mut i = 0;
+        while i < symbols.len() {
+            if str_eq(symbols[i], grapheme) {
+                return Some(Description::Consonant(consonants[i]));
+            }
+            i += 1;
+        }
+        t += 1;
+    }
+    let mut i = 0;
+    while i < VOWELS.len() {
+        if str_eq(VOWELS[i].0, grapheme) {
+            return Some(Description::Vowel(VOWELS[i].1));
+        }
+        i += 1;
+    }
+    None
+}
+
+/// `a == b`, which is not yet callable in a `const fn`.
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
 }
+
+/// The articulatory description of a single IPA symbol, as returned
+/// by `describe`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Description {
+    Consonant(Consonant),
+    Vowel(Vowel),
+}
+
+impl Description {
+    pub const fn place(&self) -> Option<Place> {
+        match self {
+            Description::Consonant(c) => Some(c.place()),
+            Description::Vowel(_) => None,
+        }
+    }
+
+    pub const fn articulation(&self) -> Option<Articulation> {
+        match self {
+            Description::Consonant(c) => Some(c.articulation()),
+            Description::Vowel(_) => None,
+        }
+    }
+
+    pub const fn manner(&self) -> Option<Manner> {
+        match self {
+            Description::Consonant(c) => Some(c.manner()),
+            Description::Vowel(_) => None,
+        }
+    }
+
+    /// Vowels are always described as voiced unless marked otherwise,
+    /// as voiceless, breathy or creaky.
+    pub const fn phonation(&self) -> Phonation {
+        match self {
+            Description::Consonant(c) => c.phonation(),
+            Description::Vowel(v) if v.diacritics().contains(Diacritic::Voiceless) => {
+                Phonation::Voiceless
+            }
+            Description::Vowel(v) if v.diacritics().contains(Diacritic::BreathyVoiced) => {
+                Phonation::Breathy
+            }
+            Description::Vowel(v) if v.diacritics().contains(Diacritic::CreakyVoiced) => {
+                Phonation::Creaky
+            }
+            Description::Vowel(_) => Phonation::Voiced,
+        }
+    }
+
+    pub const fn diacritics(&self) -> Diacritics {
+        match self {
+            Description::Consonant(c) => c.diacritics(),
+            Description::Vowel(v) => v.diacritics(),
+        }
+    }
+}
+
+/// Every consonant with a cell of its own on the IPA chart: the
+/// pulmonic consonants in `PULMONIC` order, then the clicks,
+/// implosives and the doubly articulated `w ʍ ɥ ɧ`. Cells the chart
+/// shades as impossible have no consonant.
+pub fn consonants() -> impl Iterator<Item = Consonant> {
+    PULMONIC
+        .into_iter()
+        .map(|(_, c)| c)
+        .chain(CLICKS)
+        .chain(IMPLOSIVES)
+        .chain(COARTICULATED)
+}
+
+/// Every symbol `describe` knows about, paired with its description.
+pub(crate) fn symbols() -> impl Iterator<Item = (&'static str, Description)> {
+    let consonants = PULMONIC
+        .into_iter()
+        .chain(graphemes::CLICKS.into_iter().zip(CLICKS))
+        .chain(graphemes::IMPLOSIVES.into_iter().zip(IMPLOSIVES))
+        .chain(graphemes::COARTICULATED.into_iter().zip(COARTICULATED))
+        .map(|(g, c)| (g, Description::Consonant(c)));
+    let vowels = VOWELS.into_iter().map(|(g, v)| (g, Description::Vowel(v)));
+    consonants.chain(vowels)
+}
+
+/// Describes a single IPA symbol, e.g. `describe("ɣ")` yields a voiced
+/// velar fricative.
+///
+/// The symbol may carry diacritics (`"n̪"`, `"tʰ"`) and the ejective
+/// mark (`"kʼ"`). Voicing diacritics are folded into the phonation, so
+/// `"d̥"` is described as voiceless, and the dental diacritic on an
+/// alveolar is folded into the articulation. On consonants, the marks
+/// `ʷ ʲ ˠ ˤ ˀ` set the secondary articulation, and the breathy, creaky,
+/// slack and stiff marks (and the `ʱ` of `bʱ`) the phonation. All other diacritics
+/// are kept on the description.
+///
+/// Two consonants of the same manner and airstream joined by a tie
+/// bar, as in `"k͡p"`, describe a doubly articulated consonant, taking
+/// its voicing and any secondary articulation from the second.
+pub fn describe(symbol: &str) -> Option<Description> {
+    try_describe(symbol).ok()
+}
+
+/// As `describe`, but saying what is wrong with a symbol it cannot
+/// describe, e.g. `Error::UnknownSymbol` with the offset of the `7` in
+/// `"t7"`.
+pub fn try_describe(symbol: &str) -> Result<Description, Error> {
+    let (body, length) = split_length(symbol);
+    if !body.is_empty() && !length.is_empty() {
+        return with_marks(try_describe(body)?, length).map_err(|e| e.shifted(body.len()));
+    }
+    if let Some(base) = symbol.strip_suffix(graphemes::EJECTIVE) {
+        let mark = graphemes::EJECTIVE.chars().next().unwrap();
+        if base.is_empty() {
+            return Err(Error::DanglingDiacritic { offset: 0, mark });
+        }
+        return ejective(try_describe(base)?).ok_or(Error::InvalidCombination {
+            offset: base.len(),
+            mark,
+        });
+    }
+    if let Some((first, second)) = symbol.split_once(TIE_BARS) {
+        let tie = symbol[first.len()..].chars().next().unwrap();
+        let at = first.len() + tie.len_utf8();
+        let invalid = Error::InvalidCombination {
+            offset: first.len(),
+            mark: tie,
+        };
+        if first.is_empty() || second.is_empty() {
+            return Err(Error::DanglingDiacritic {
+                offset: first.len(),
+                mark: tie,
+            });
+        }
+        let a = try_describe(first)?;
+        let b = try_describe(second).map_err(|e| e.shifted(at))?;
+        return double(a, b).ok_or(invalid);
+    }
+    let first = symbol.chars().next().ok_or(Error::Empty)?;
+    let Some((grapheme, description)) = symbol
+        .char_indices()
+        .rev()
+        .map(|(i, c)| &symbol[..i + c.len_utf8()])
+        .find_map(|prefix| SYMBOLS.get(prefix).map(|d| (prefix, *d)))
+    else {
+        if let Some(decomposed) = normalize::decompose(first) {
+            let (base, marks) = split_base(decomposed);
+            let d = with_marks(try_describe(base)?, marks)?;
+            let width = first.len_utf8();
+            return with_marks(d, &symbol[width..]).map_err(|e| e.shifted(width));
+        }
+        return Err(
+            match parser::is_combining(first) || parser::is_modifier(first) {
+                true => Error::DanglingDiacritic {
+                    offset: 0,
+                    mark: first,
+                },
+                false => Error::UnknownSymbol {
+                    offset: 0,
+                    symbol: first,
+                },
+            },
+        );
+    };
+    with_marks(description, &symbol[grapheme.len()..]).map_err(|e| e.shifted(grapheme.len()))
+}
+
+/// As `describe`, but a `const fn`, so symbols can be described at
+/// compile time. It scans the tables where `describe` hashes, so
+/// prefer `describe` at run time.
+pub const fn describe_const(symbol: &str) -> Option<Description> {
+    let (body, length) = split_length(symbol);
+    if !body.is_empty() && !length.is_empty() {
+        return match describe_const(body) {
+            Some(d) => match with_marks(d, length) {
+                Ok(d) => Some(d),
+                Err(_) => None,
+            },
+            None => None,
+        };
+    }
+    if let Some(base) = parser::strip_suffix(symbol, graphemes::EJECTIVE) {
+        return match describe_const(base) {
+            Some(d) => ejective(d),
+            None => None,
+        };
+    }
+    if let Some((first, second)) = parser::split_tie(symbol) {
+        return match (describe_const(first), describe_const(second)) {
+            (Some(a), Some(b)) => double(a, b),
+            _ => None,
+        };
+    }
+    let mut end = symbol.len();
+    while end > 0 {
+        if symbol.is_char_boundary(end) {
+            let (grapheme, marks) = symbol.split_at(end);
+            if let Some(description) = lookup(grapheme) {
+                return match with_marks(description, marks) {
+                    Ok(d) => Some(d),
+                    Err(_) => None,
+                };
+            }
+        }
+        end -= 1;
+    }
+    if let Some((first, rest)) = parser::split_first_char(symbol) {
+        if let Some(decomposed) = normalize::decompose(first) {
+            let (base, marks) = split_base(decomposed);
+            if let Some(description) = lookup(base) {
+                if let Ok(d) = with_marks(description, marks) {
+                    if let Ok(d) = with_marks(d, rest) {
+                        return Some(d);
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// A decomposition from `normalize::PRECOMPOSED` split into its letter
+/// and its marks.
+const fn split_base(decomposed: &str) -> (&str, &str) {
+    match parser::split_first_char(decomposed) {
+        Some((base, _)) => decomposed.split_at(base.len_utf8()),
+        None => ("", ""),
+    }
+}
+
+/// The ejective of a pulmonic consonant.
+const fn ejective(d: Description) -> Option<Description> {
+    match d {
+        Description::Consonant(c) if c.airstream().is_pulmonic() => {
+            Some(Description::Consonant(c.ejective()))
+        }
+        _ => None,
+    }
+}
+
+/// The doubly articulated consonant of `a` tied to `b`.
+const fn double(a: Description, b: Description) -> Option<Description> {
+    let (Description::Consonant(a), Description::Consonant(b)) = (a, b) else {
+        return None;
+    };
+    let double = manner_eq(a.manner, b.manner)
+        && a.airstream as u8 == b.airstream as u8
+        && a.articulation() as u8 != b.articulation() as u8
+        && a.coarticulation.is_none()
+        && b.coarticulation.is_none();
+    match double {
+        true => Some(Description::Consonant(
+            b.with_diacritics(b.diacritics.union(a.diacritics))
+                .with_coarticulation(Some(a.articulation())),
+        )),
+        false => None,
+    }
+}
+
+/// `symbol` split before the half-long and long marks it ends with,
+/// which follow even the ejective mark, as in `kʼː`.
+const fn split_length(symbol: &str) -> (&str, &str) {
+    let mut body = symbol;
+    loop {
+        body = match (
+            parser::strip_suffix(body, Length::Long.marks()),
+            parser::strip_suffix(body, Length::HalfLong.marks()),
+        ) {
+            (Some(rest), _) | (_, Some(rest)) => rest,
+            (None, None) => return symbol.split_at(body.len()),
+        };
+    }
+}
+
+/// The length marked by `c`, one of `ˑ ː ̆`.
+const fn length_mark(c: char) -> Option<Length> {
+    match Suprasegmental::from_char(c) {
+        Some(Suprasegmental::Length(length)) => Some(length),
+        _ => None,
+    }
+}
+
+/// `description` with the marks written after its symbol.
+const fn with_marks(description: Description, marks: &str) -> Result<Description, Error> {
+    let mut d = description;
+    let mut rest = marks;
+    while let Some((ch, after)) = parser::split_first_char(rest) {
+        rest = after;
+        d = match (
+            d,
+            SecondaryArticulation::from_char(ch),
+            Phonation::from_mark(ch),
+        ) {
+            (Description::Consonant(c), Some(secondary), _) => {
+                Description::Consonant(c.with_secondary(Some(secondary)))
+            }
+            (Description::Consonant(c), None, Some(phonation)) => {
+                Description::Consonant(c.with_phonation(phonation))
+            }
+            (d, _, _) if length_mark(ch).is_some() => {
+                let mark = length_mark(ch).unwrap();
+                match d {
+                    Description::Consonant(c) => {
+                        Description::Consonant(c.with_length(c.length.marked(mark)))
+                    }
+                    Description::Vowel(v) => {
+                        Description::Vowel(v.with_length(v.length.marked(mark)))
+                    }
+                }
+            }
+            // The cedilla of a decomposed `ç`.
+            (Description::Consonant(c), _, _)
+                if ch == normalize::CEDILLA
+                    && matches!(c.articulation(), Articulation::Palatal)
+                    && matches!(c.manner, Manner::Plosive)
+                    && matches!(c.phonation, Phonation::Voiceless)
+                    && c.airstream.is_pulmonic() =>
+            {
+                match lookup("\u{E7}") {
+                    Some(Description::Consonant(fricative)) => Description::Consonant(Consonant {
+                        poa: fricative.poa,
+                        manner: fricative.manner,
+                        ..c
+                    }),
+                    _ => unreachable!(),
+                }
+            }
+            (d, _, _) => {
+                let Some(mark) = Diacritic::from_char(ch) else {
+                    return Err(Error::UnknownSymbol {
+                        offset: marks.len() - rest.len() - ch.len_utf8(),
+                        symbol: ch,
+                    });
+                };
+                match (d, mark) {
+                    (Description::Consonant(c), Diacritic::Voiceless) => {
+                        Description::Consonant(Consonant {
+                            phonation: Phonation::Voiceless,
+                            ..c
+                        })
+                    }
+                    (Description::Consonant(c), Diacritic::Voiced) => {
+                        Description::Consonant(Consonant {
+                            phonation: Phonation::Voiced,
+                            ..c
+                        })
+                    }
+                    (Description::Consonant(c), Diacritic::Dental)
+                        if matches!(c.articulation(), Articulation::Alveolar) =>
+                    {
+                        Description::Consonant(Consonant {
+                            poa: PoA::new(Articulation::Dental.place(), Articulation::Dental),
+                            ..c
+                        })
+                    }
+                    (Description::Consonant(c), mark) => {
+                        Description::Consonant(c.with_diacritic(mark))
+                    }
+                    (Description::Vowel(v), mark) => Description::Vowel(v.with_diacritic(mark)),
+                }
+            }
+        };
+    }
+    Ok(d)
+}
+
+#[cfg(feature = "alloc")]
+impl FromStr for Consonant {
+    type Err = ParseSegmentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse()? {
+            Segment::Consonant(c) => Ok(c),
+            _ => Err(ParseSegmentError::NotConsonant(s.to_string())),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&str> for Consonant {
+    type Error = ParseSegmentError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromStr for Vowel {
+    type Err = ParseSegmentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse()? {
+            Segment::Vowel(v) => Ok(v),
+            _ => Err(ParseSegmentError::NotVowel(s.to_string())),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&str> for Vowel {
+    type Error = ParseSegmentError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+const fn pulmonic(articulation: Articulation, manner: Manner, phonation: Phonation) -> Consonant {
+    Consonant::new(
+        PoA::new(articulation.place(), articulation),
+        manner,
+        phonation,
+    )
+}
+
+/// Every pulmonic consonant in the IPA chart, paired with its grapheme.
+///
+/// Graphemes are listed in chart order: by `Manner` row, then by
+/// `Articulation` column, voiceless before voiced. Cells without a
+/// dedicated symbol are spelled with the usual diacritic, e.g. `n̥`.
+#[rustfmt::skip]
+pub const PULMONIC: [(&str, Consonant); 89] = [
+    ("\u{70}", pulmonic(Articulation::Bilabial, Manner::Plosive, Phonation::Voiceless)), // p
+    ("\u{62}", pulmonic(Articulation::Bilabial, Manner::Plosive, Phonation::Voiced)), // b
+    ("\u{70}\u{32A}", pulmonic(Articulation::Labiodental, Manner::Plosive, Phonation::Voiceless)), // p̪
+    ("\u{62}\u{32A}", pulmonic(Articulation::Labiodental, Manner::Plosive, Phonation::Voiced)), // b̪
+    ("\u{74}\u{33C}", pulmonic(Articulation::Linguolabial, Manner::Plosive, Phonation::Voiceless)), // t̼
+    ("\u{64}\u{33C}", pulmonic(Articulation::Linguolabial, Manner::Plosive, Phonation::Voiced)), // d̼
+    ("\u{74}\u{32A}", pulmonic(Articulation::Dental, Manner::Plosive, Phonation::Voiceless)), // t̪
+    ("\u{64}\u{32A}", pulmonic(Articulation::Dental, Manner::Plosive, Phonation::Voiced)), // d̪
+    ("\u{74}", pulmonic(Articulation::Alveolar, Manner::Plosive, Phonation::Voiceless)), // t
+    ("\u{64}", pulmonic(Articulation::Alveolar, Manner::Plosive, Phonation::Voiced)), // d
+    ("\u{288}", pulmonic(Articulation::Retroflex, Manner::Plosive, Phonation::Voiceless)), // ʈ
+    ("\u{256}", pulmonic(Articulation::Retroflex, Manner::Plosive, Phonation::Voiced)), // ɖ
+    ("\u{63}", pulmonic(Articulation::Palatal, Manner::Plosive, Phonation::Voiceless)), // c
+    ("\u{25F}", pulmonic(Articulation::Palatal, Manner::Plosive, Phonation::Voiced)), // ɟ
+    ("\u{6B}", pulmonic(Articulation::Velar, Manner::Plosive, Phonation::Voiceless)), // k
+    ("\u{261}", pulmonic(Articulation::Velar, Manner::Plosive, Phonation::Voiced)), // ɡ
+    ("\u{71}", pulmonic(Articulation::Uvular, Manner::Plosive, Phonation::Voiceless)), // q
+    ("\u{262}", pulmonic(Articulation::Uvular, Manner::Plosive, Phonation::Voiced)), // ɢ
+    ("\u{2A1}", pulmonic(Articulation::Epiglottal, Manner::Plosive, Phonation::Voiceless)), // ʡ
+    ("\u{294}", pulmonic(Articulation::Glottal, Manner::Plosive, Phonation::Voiceless)), // ʔ
+    ("\u{6D}\u{325}", pulmonic(Articulation::Bilabial, Manner::Nasal, Phonation::Voiceless)), // m̥
+    ("\u{6D}", pulmonic(Articulation::Bilabial, Manner::Nasal, Phonation::Voiced)), // m
+    ("\u{271}\u{30A}", pulmonic(Articulation::Labiodental, Manner::Nasal, Phonation::Voiceless)), // ɱ̊
+    ("\u{271}", pulmonic(Articulation::Labiodental, Manner::Nasal, Phonation::Voiced)), // ɱ
+    ("\u{6E}\u{33C}", pulmonic(Articulation::Linguolabial, Manner::Nasal, Phonation::Voiced)), // n̼
+    ("\u{6E}\u{32A}\u{30A}", pulmonic(Articulation::Dental, Manner::Nasal, Phonation::Voiceless)), // n̪̊
+    ("\u{6E}\u{32A}", pulmonic(Articulation::Dental, Manner::Nasal, Phonation::Voiced)), // n̪
+    ("\u{6E}\u{325}", pulmonic(Articulation::Alveolar, Manner::Nasal, Phonation::Voiceless)), // n̥
+    ("\u{6E}", pulmonic(Articulation::Alveolar, Manner::Nasal, Phonation::Voiced)), // n
+    ("\u{273}\u{30A}", pulmonic(Articulation::Retroflex, Manner::Nasal, Phonation::Voiceless)), // ɳ̊
+    ("\u{273}", pulmonic(Articulation::Retroflex, Manner::Nasal, Phonation::Voiced)), // ɳ
+    ("\u{272}\u{30A}", pulmonic(Articulation::Palatal, Manner::Nasal, Phonation::Voiceless)), // ɲ̊
+    ("\u{272}", pulmonic(Articulation::Palatal, Manner::Nasal, Phonation::Voiced)), // ɲ
+    ("\u{14B}\u{30A}", pulmonic(Articulation::Velar, Manner::Nasal, Phonation::Voiceless)), // ŋ̊
+    ("\u{14B}", pulmonic(Articulation::Velar, Manner::Nasal, Phonation::Voiced)), // ŋ
+    ("\u{274}\u{325}", pulmonic(Articulation::Uvular, Manner::Nasal, Phonation::Voiceless)), // ɴ̥
+    ("\u{274}", pulmonic(Articulation::Uvular, Manner::Nasal, Phonation::Voiced)), // ɴ
+    ("\u{299}\u{325}", pulmonic(Articulation::Bilabial, Manner::Trill, Phonation::Voiceless)), // ʙ̥
+    ("\u{299}", pulmonic(Articulation::Bilabial, Manner::Trill, Phonation::Voiced)), // ʙ
+    ("\u{72}\u{325}", pulmonic(Articulation::Alveolar, Manner::Trill, Phonation::Voiceless)), // r̥
+    ("\u{72}", pulmonic(Articulation::Alveolar, Manner::Trill, Phonation::Voiced)), // r
+    ("\u{280}\u{325}", pulmonic(Articulation::Uvular, Manner::Trill, Phonation::Voiceless)), // ʀ̥
+    ("\u{280}", pulmonic(Articulation::Uvular, Manner::Trill, Phonation::Voiced)), // ʀ
+    ("\u{2C71}\u{31F}", pulmonic(Articulation::Bilabial, Manner::TapFlap, Phonation::Voiced)), // ⱱ̟
+    ("\u{2C71}", pulmonic(Articulation::Labiodental, Manner::TapFlap, Phonation::Voiced)), // ⱱ
+    ("\u{27E}\u{325}", pulmonic(Articulation::Alveolar, Manner::TapFlap, Phonation::Voiceless)), // ɾ̥
+    ("\u{27E}", pulmonic(Articulation::Alveolar, Manner::TapFlap, Phonation::Voiced)), // ɾ
+    ("\u{27D}", pulmonic(Articulation::Retroflex, Manner::TapFlap, Phonation::Voiced)), // ɽ
+    ("\u{278}", pulmonic(Articulation::Bilabial, Manner::Fricative { sibilant: false }, Phonation::Voiceless)), // ɸ
+    ("\u{3B2}", pulmonic(Articulation::Bilabial, Manner::Fricative { sibilant: false }, Phonation::Voiced)), // β
+    ("\u{66}", pulmonic(Articulation::Labiodental, Manner::Fricative { sibilant: false }, Phonation::Voiceless)), // f
+    ("\u{76}", pulmonic(Articulation::Labiodental, Manner::Fricative { sibilant: false }, Phonation::Voiced)), // v
+    ("\u{3B8}", pulmonic(Articulation::Dental, Manner::Fricative { sibilant: false }, Phonation::Voiceless)), // θ
+    ("\u{F0}", pulmonic(Articulation::Dental, Manner::Fricative { sibilant: false }, Phonation::Voiced)), // ð
+    ("\u{73}", pulmonic(Articulation::Alveolar, Manner::Fricative { sibilant: true }, Phonation::Voiceless)), // s
+    ("\u{7A}", pulmonic(Articulation::Alveolar, Manner::Fricative { sibilant: true }, Phonation::Voiced)), // z
+    ("\u{283}", pulmonic(Articulation::Postalveolar, Manner::Fricative { sibilant: true }, Phonation::Voiceless)), // ʃ
+    ("\u{292}", pulmonic(Articulation::Postalveolar, Manner::Fricative { sibilant: true }, Phonation::Voiced)), // ʒ
+    ("\u{282}", pulmonic(Articulation::Retroflex, Manner::Fricative { sibilant: true }, Phonation::Voiceless)), // ʂ
+    ("\u{290}", pulmonic(Articulation::Retroflex, Manner::Fricative { sibilant: true }, Phonation::Voiced)), // ʐ
+    ("\u{255}", pulmonic(Articulation::Palatal, Manner::Fricative { sibilant: true }, Phonation::Voiceless)), // ɕ
+    ("\u{291}", pulmonic(Articulation::Palatal, Manner::Fricative { sibilant: true }, Phonation::Voiced)), // ʑ
+    ("\u{E7}", Consonant::new(PoA::new(Place::Corona, Articulation::Palatal), Manner::Fricative { sibilant: false }, Phonation::Voiceless)), // ç
+    ("\u{29D}", pulmonic(Articulation::Palatal, Manner::Fricative { sibilant: false }, Phonation::Voiced)), // ʝ
+    ("\u{78}", pulmonic(Articulation::Velar, Manner::Fricative { sibilant: false }, Phonation::Voiceless)), // x
+    ("\u{263}", pulmonic(Articulation::Velar, Manner::Fricative { sibilant: false }, Phonation::Voiced)), // ɣ
+    ("\u{3C7}", pulmonic(Articulation::Uvular, Manner::Fricative { sibilant: false }, Phonation::Voiceless)), // χ
+    ("\u{281}", pulmonic(Articulation::Uvular, Manner::Fricative { sibilant: false }, Phonation::Voiced)), // ʁ
+    ("\u{127}", pulmonic(Articulation::Pharyngeal, Manner::Fricative { sibilant: false }, Phonation::Voiceless)), // ħ
+    ("\u{295}", pulmonic(Articulation::Pharyngeal, Manner::Fricative { sibilant: false }, Phonation::Voiced)), // ʕ
+    ("\u{29C}", pulmonic(Articulation::Epiglottal, Manner::Fricative { sibilant: false }, Phonation::Voiceless)), // ʜ
+    ("\u{2A2}", pulmonic(Articulation::Epiglottal, Manner::Fricative { sibilant: false }, Phonation::Voiced)), // ʢ
+    ("\u{68}", pulmonic(Articulation::Glottal, Manner::Fricative { sibilant: false }, Phonation::Voiceless)), // h
+    ("\u{266}", pulmonic(Articulation::Glottal, Manner::Fricative { sibilant: false }, Phonation::Voiced)), // ɦ
+    ("\u{26C}", pulmonic(Articulation::Alveolar, Manner::LatFric, Phonation::Voiceless)), // ɬ
+    ("\u{26E}", pulmonic(Articulation::Alveolar, Manner::LatFric, Phonation::Voiced)), // ɮ
+    ("\u{28B}", pulmonic(Articulation::Labiodental, Manner::Approximant, Phonation::Voiced)), // ʋ
+    ("\u{279}", pulmonic(Articulation::Alveolar, Manner::Approximant, Phonation::Voiced)), // ɹ
+    ("\u{27B}", pulmonic(Articulation::Retroflex, Manner::Approximant, Phonation::Voiced)), // ɻ
+    ("\u{6A}\u{30A}", pulmonic(Articulation::Palatal, Manner::Approximant, Phonation::Voiceless)), // j̊
+    ("\u{6A}", pulmonic(Articulation::Palatal, Manner::Approximant, Phonation::Voiced)), // j
+    ("\u{270}", pulmonic(Articulation::Velar, Manner::Approximant, Phonation::Voiced)), // ɰ
+    ("\u{6C}\u{325}", pulmonic(Articulation::Alveolar, Manner::LatApprox, Phonation::Voiceless)), // l̥
+    ("\u{6C}", pulmonic(Articulation::Alveolar, Manner::LatApprox, Phonation::Voiced)), // l
+    ("\u{26D}", pulmonic(Articulation::Retroflex, Manner::LatApprox, Phonation::Voiced)), // ɭ
+    ("\u{28E}\u{325}", pulmonic(Articulation::Palatal, Manner::LatApprox, Phonation::Voiceless)), // ʎ̥
+    ("\u{28E}", pulmonic(Articulation::Palatal, Manner::LatApprox, Phonation::Voiced)), // ʎ
+    ("\u{29F}", pulmonic(Articulation::Velar, Manner::LatApprox, Phonation::Voiced)), // ʟ
+    ("\u{27A}", pulmonic(Articulation::Alveolar, Manner::LatTapFlap, Phonation::Voiced)), // ɺ
+];
+
+/// Every vowel in the IPA chart, paired with its grapheme, in chart
+/// order. The front and back mid vowels and the open central vowel
+/// are spelled with the usual diacritics, e.g. `e̞`.
+#[rustfmt::skip]
+pub const VOWELS: [(&str, Vowel); 33] = [
+    ("\u{69}", Vowel::new(Height::Close, Backness::Front, Roundedness::Unrounded)), // i
+    ("\u{79}", Vowel::new(Height::Close, Backness::Front, Roundedness::Rounded)), // y
+    ("\u{268}", Vowel::new(Height::Close, Backness::Central, Roundedness::Unrounded)), // ɨ
+    ("\u{289}", Vowel::new(Height::Close, Backness::Central, Roundedness::Rounded)), // ʉ
+    ("\u{26F}", Vowel::new(Height::Close, Backness::Back, Roundedness::Unrounded)), // ɯ
+    ("\u{75}", Vowel::new(Height::Close, Backness::Back, Roundedness::Rounded)), // u
+    ("\u{26A}", Vowel::new(Height::NearClose, Backness::NearFront, Roundedness::Unrounded)), // ɪ
+    ("\u{28F}", Vowel::new(Height::NearClose, Backness::NearFront, Roundedness::Rounded)), // ʏ
+    ("\u{28A}", Vowel::new(Height::NearClose, Backness::NearBack, Roundedness::Rounded)), // ʊ
+    ("\u{65}", Vowel::new(Height::CloseMid, Backness::Front, Roundedness::Unrounded)), // e
+    ("\u{F8}", Vowel::new(Height::CloseMid, Backness::Front, Roundedness::Rounded)), // ø
+    ("\u{258}", Vowel::new(Height::CloseMid, Backness::Central, Roundedness::Unrounded)), // ɘ
+    ("\u{275}", Vowel::new(Height::CloseMid, Backness::Central, Roundedness::Rounded)), // ɵ
+    ("\u{264}", Vowel::new(Height::CloseMid, Backness::Back, Roundedness::Unrounded)), // ɤ
+    ("\u{6F}", Vowel::new(Height::CloseMid, Backness::Back, Roundedness::Rounded)), // o
+    ("\u{65}\u{31E}", Vowel::new(Height::Mid, Backness::Front, Roundedness::Unrounded)), // e̞
+    ("\u{F8}\u{31E}", Vowel::new(Height::Mid, Backness::Front, Roundedness::Rounded)), // ø̞
+    ("\u{259}", Vowel::new(Height::Mid, Backness::Central, Roundedness::Unrounded)), // ə
+    ("\u{264}\u{31E}", Vowel::new(Height::Mid, Backness::Back, Roundedness::Unrounded)), // ɤ̞
+    ("\u{6F}\u{31E}", Vowel::new(Height::Mid, Backness::Back, Roundedness::Rounded)), // o̞
+    ("\u{25B}", Vowel::new(Height::OpenMid, Backness::Front, Roundedness::Unrounded)), // ɛ
+    ("\u{153}", Vowel::new(Height::OpenMid, Backness::Front, Roundedness::Rounded)), // œ
+    ("\u{25C}", Vowel::new(Height::OpenMid, Backness::Central, Roundedness::Unrounded)), // ɜ
+    ("\u{25E}", Vowel::new(Height::OpenMid, Backness::Central, Roundedness::Rounded)), // ɞ
+    ("\u{28C}", Vowel::new(Height::OpenMid, Backness::Back, Roundedness::Unrounded)), // ʌ
+    ("\u{254}", Vowel::new(Height::OpenMid, Backness::Back, Roundedness::Rounded)), // ɔ
+    ("\u{E6}", Vowel::new(Height::NearOpen, Backness::Front, Roundedness::Unrounded)), // æ
+    ("\u{250}", Vowel::new(Height::NearOpen, Backness::Central, Roundedness::Unrounded)), // ɐ
+    ("\u{61}", Vowel::new(Height::Open, Backness::Front, Roundedness::Unrounded)), // a
+    ("\u{276}", Vowel::new(Height::Open, Backness::Front, Roundedness::Rounded)), // ɶ
+    ("\u{61}\u{308}", Vowel::new(Height::Open, Backness::Central, Roundedness::Unrounded)), // ä
+    ("\u{251}", Vowel::new(Height::Open, Backness::Back, Roundedness::Unrounded)), // ɑ
+    ("\u{252}", Vowel::new(Height::Open, Backness::Back, Roundedness::Rounded)), // ɒ
+];
+
+/// The symbols of `symbols()` in a perfect-hash map built at compile
+/// time, so that `describe` looks up each prefix of its input instead
+/// of scanning every table.
+pub(crate) static SYMBOLS: phf::Map<&'static str, Description> = phf::phf_map! {
+    "\u{70}" => Description::Consonant(PULMONIC[0].1), // p
+    "\u{62}" => Description::Consonant(PULMONIC[1].1), // b
+    "\u{70}\u{32A}" => Description::Consonant(PULMONIC[2].1), // p̪
+    "\u{62}\u{32A}" => Description::Consonant(PULMONIC[3].1), // b̪
+    "\u{74}\u{33C}" => Description::Consonant(PULMONIC[4].1), // t̼
+    "\u{64}\u{33C}" => Description::Consonant(PULMONIC[5].1), // d̼
+    "\u{74}\u{32A}" => Description::Consonant(PULMONIC[6].1), // t̪
+    "\u{64}\u{32A}" => Description::Consonant(PULMONIC[7].1), // d̪
+    "\u{74}" => Description::Consonant(PULMONIC[8].1), // t
+    "\u{64}" => Description::Consonant(PULMONIC[9].1), // d
+    "\u{288}" => Description::Consonant(PULMONIC[10].1), // ʈ
+    "\u{256}" => Description::Consonant(PULMONIC[11].1), // ɖ
+    "\u{63}" => Description::Consonant(PULMONIC[12].1), // c
+    "\u{25F}" => Description::Consonant(PULMONIC[13].1), // ɟ
+    "\u{6B}" => Description::Consonant(PULMONIC[14].1), // k
+    "\u{261}" => Description::Consonant(PULMONIC[15].1), // ɡ
+    "\u{71}" => Description::Consonant(PULMONIC[16].1), // q
+    "\u{262}" => Description::Consonant(PULMONIC[17].1), // ɢ
+    "\u{2A1}" => Description::Consonant(PULMONIC[18].1), // ʡ
+    "\u{294}" => Description::Consonant(PULMONIC[19].1), // ʔ
+    "\u{6D}\u{325}" => Description::Consonant(PULMONIC[20].1), // m̥
+    "\u{6D}" => Description::Consonant(PULMONIC[21].1), // m
+    "\u{271}\u{30A}" => Description::Consonant(PULMONIC[22].1), // ɱ̊
+    "\u{271}" => Description::Consonant(PULMONIC[23].1), // ɱ
+    "\u{6E}\u{33C}" => Description::Consonant(PULMONIC[24].1), // n̼
+    "\u{6E}\u{32A}\u{30A}" => Description::Consonant(PULMONIC[25].1), // n̪̊
+    "\u{6E}\u{32A}" => Description::Consonant(PULMONIC[26].1), // n̪
+    "\u{6E}\u{325}" => Description::Consonant(PULMONIC[27].1), // n̥
+    "\u{6E}" => Description::Consonant(PULMONIC[28].1), // n
+    "\u{273}\u{30A}" => Description::Consonant(PULMONIC[29].1), // ɳ̊
+    "\u{273}" => Description::Consonant(PULMONIC[30].1), // ɳ
+    "\u{272}\u{30A}" => Description::Consonant(PULMONIC[31].1), // ɲ̊
+    "\u{272}" => Description::Consonant(PULMONIC[32].1), // ɲ
+    "\u{14B}\u{30A}" => Description::Consonant(PULMONIC[33].1), // ŋ̊
+    "\u{14B}" => Description::Consonant(PULMONIC[34].1), // ŋ
+    "\u{274}\u{325}" => Description::Consonant(PULMONIC[35].1), // ɴ̥
+    "\u{274}" => Description::Consonant(PULMONIC[36].1), // ɴ
+    "\u{299}\u{325}" => Description::Consonant(PULMONIC[37].1), // ʙ̥
+    "\u{299}" => Description::Consonant(PULMONIC[38].1), // ʙ
+    "\u{72}\u{325}" => Description::Consonant(PULMONIC[39].1), // r̥
+    "\u{72}" => Description::Consonant(PULMONIC[40].1), // r
+    "\u{280}\u{325}" => Description::Consonant(PULMONIC[41].1), // ʀ̥
+    "\u{280}" => Description::Consonant(PULMONIC[42].1), // ʀ
+    "\u{2C71}\u{31F}" => Description::Consonant(PULMONIC[43].1), // ⱱ̟
+    "\u{2C71}" => Description::Consonant(PULMONIC[44].1), // ⱱ
+    "\u{27E}\u{325}" => Description::Consonant(PULMONIC[45].1), // ɾ̥
+    "\u{27E}" => Description::Consonant(PULMONIC[46].1), // ɾ
+    "\u{27D}" => Description::Consonant(PULMONIC[47].1), // ɽ
+    "\u{278}" => Description::Consonant(PULMONIC[48].1), // ɸ
+    "\u{3B2}" => Description::Consonant(PULMONIC[49].1), // β
+    "\u{66}" => Description::Consonant(PULMONIC[50].1), // f
+    "\u{76}" => Description::Consonant(PULMONIC[51].1), // v
+    "\u{3B8}" => Description::Consonant(PULMONIC[52].1), // θ
+    "\u{F0}" => Description::Consonant(PULMONIC[53].1), // ð
+    "\u{73}" => Description::Consonant(PULMONIC[54].1), // s
+    "\u{7A}" => Description::Consonant(PULMONIC[55].1), // z
+    "\u{283}" => Description::Consonant(PULMONIC[56].1), // ʃ
+    "\u{292}" => Description::Consonant(PULMONIC[57].1), // ʒ
+    "\u{282}" => Description::Consonant(PULMONIC[58].1), // ʂ
+    "\u{290}" => Description::Consonant(PULMONIC[59].1), // ʐ
+    "\u{255}" => Description::Consonant(PULMONIC[60].1), // ɕ
+    "\u{291}" => Description::Consonant(PULMONIC[61].1), // ʑ
+    "\u{E7}" => Description::Consonant(PULMONIC[62].1), // ç
+    "\u{29D}" => Description::Consonant(PULMONIC[63].1), // ʝ
+    "\u{78}" => Description::Consonant(PULMONIC[64].1), // x
+    "\u{263}" => Description::Consonant(PULMONIC[65].1), // ɣ
+    "\u{3C7}" => Description::Consonant(PULMONIC[66].1), // χ
+    "\u{281}" => Description::Consonant(PULMONIC[67].1), // ʁ
+    "\u{127}" => Description::Consonant(PULMONIC[68].1), // ħ
+    "\u{295}" => Description::Consonant(PULMONIC[69].1), // ʕ
+    "\u{29C}" => Description::Consonant(PULMONIC[70].1), // ʜ
+    "\u{2A2}" => Description::Consonant(PULMONIC[71].1), // ʢ
+    "\u{68}" => Description::Consonant(PULMONIC[72].1), // h
+    "\u{266}" => Description::Consonant(PULMONIC[73].1), // ɦ
+    "\u{26C}" => Description::Consonant(PULMONIC[74].1), // ɬ
+    "\u{26E}" => Description::Consonant(PULMONIC[75].1), // ɮ
+    "\u{28B}" => Description::Consonant(PULMONIC[76].1), // ʋ
+    "\u{279}" => Description::Consonant(PULMONIC[77].1), // ɹ
+    "\u{27B}" => Description::Consonant(PULMONIC[78].1), // ɻ
+    "\u{6A}\u{30A}" => Description::Consonant(PULMONIC[79].1), // j̊
+    "\u{6A}" => Description::Consonant(PULMONIC[80].1), // j
+    "\u{270}" => Description::Consonant(PULMONIC[81].1), // ɰ
+    "\u{6C}\u{325}" => Description::Consonant(PULMONIC[82].1), // l̥
+    "\u{6C}" => Description::Consonant(PULMONIC[83].1), // l
+    "\u{26D}" => Description::Consonant(PULMONIC[84].1), // ɭ
+    "\u{28E}\u{325}" => Description::Consonant(PULMONIC[85].1), // ʎ̥
+    "\u{28E}" => Description::Consonant(PULMONIC[86].1), // ʎ
+    "\u{29F}" => Description::Consonant(PULMONIC[87].1), // ʟ
+    "\u{27A}" => Description::Consonant(PULMONIC[88].1), // ɺ
+    "\u{298}" => Description::Consonant(CLICKS[0]), // ʘ
+    "\u{1C0}" => Description::Consonant(CLICKS[1]), // ǀ
+    "\u{1C3}" => Description::Consonant(CLICKS[2]), // ǃ
+    "\u{1C2}" => Description::Consonant(CLICKS[3]), // ǂ
+    "\u{1C1}" => Description::Consonant(CLICKS[4]), // ǁ
+    "\u{253}" => Description::Consonant(IMPLOSIVES[0]), // ɓ
+    "\u{257}" => Description::Consonant(IMPLOSIVES[1]), // ɗ
+    "\u{284}" => Description::Consonant(IMPLOSIVES[2]), // ʄ
+    "\u{260}" => Description::Consonant(IMPLOSIVES[3]), // ɠ
+    "\u{29B}" => Description::Consonant(IMPLOSIVES[4]), // ʛ
+    "\u{77}" => Description::Consonant(COARTICULATED[0]), // w
+    "\u{28D}" => Description::Consonant(COARTICULATED[1]), // ʍ
+    "\u{265}" => Description::Consonant(COARTICULATED[2]), // ɥ
+    "\u{267}" => Description::Consonant(COARTICULATED[3]), // ɧ
+    "\u{69}" => Description::Vowel(VOWELS[0].1), // i
+    "\u{79}" => Description::Vowel(VOWELS[1].1), // y
+    "\u{268}" => Description::Vowel(VOWELS[2].1), // ɨ
+    "\u{289}" => Description::Vowel(VOWELS[3].1), // ʉ
+    "\u{26F}" => Description::Vowel(VOWELS[4].1), // ɯ
+    "\u{75}" => Description::Vowel(VOWELS[5].1), // u
+    "\u{26A}" => Description::Vowel(VOWELS[6].1), // ɪ
+    "\u{28F}" => Description::Vowel(VOWELS[7].1), // ʏ
+    "\u{28A}" => Description::Vowel(VOWELS[8].1), // ʊ
+    "\u{65}" => Description::Vowel(VOWELS[9].1), // e
+    "\u{F8}" => Description::Vowel(VOWELS[10].1), // ø
+    "\u{258}" => Description::Vowel(VOWELS[11].1), // ɘ
+    "\u{275}" => Description::Vowel(VOWELS[12].1), // ɵ
+    "\u{264}" => Description::Vowel(VOWELS[13].1), // ɤ
+    "\u{6F}" => Description::Vowel(VOWELS[14].1), // o
+    "\u{65}\u{31E}" => Description::Vowel(VOWELS[15].1), // e̞
+    "\u{F8}\u{31E}" => Description::Vowel(VOWELS[16].1), // ø̞
+    "\u{259}" => Description::Vowel(VOWELS[17].1), // ə
+    "\u{264}\u{31E}" => Description::Vowel(VOWELS[18].1), // ɤ̞
+    "\u{6F}\u{31E}" => Description::Vowel(VOWELS[19].1), // o̞
+    "\u{25B}" => Description::Vowel(VOWELS[20].1), // ɛ
+    "\u{153}" => Description::Vowel(VOWELS[21].1), // œ
+    "\u{25C}" => Description::Vowel(VOWELS[22].1), // ɜ
+    "\u{25E}" => Description::Vowel(VOWELS[23].1), // ɞ
+    "\u{28C}" => Description::Vowel(VOWELS[24].1), // ʌ
+    "\u{254}" => Description::Vowel(VOWELS[25].1), // ɔ
+    "\u{E6}" => Description::Vowel(VOWELS[26].1), // æ
+    "\u{250}" => Description::Vowel(VOWELS[27].1), // ɐ
+    "\u{61}" => Description::Vowel(VOWELS[28].1), // a
+    "\u{276}" => Description::Vowel(VOWELS[29].1), // ɶ
+    "\u{61}\u{308}" => Description::Vowel(VOWELS[30].1), // ä
+    "\u{251}" => Description::Vowel(VOWELS[31].1), // ɑ
+    "\u{252}" => Description::Vowel(VOWELS[32].1), // ɒ
+};
@@ -0,0 +1,492 @@
+//! Japanese kana and romaji, and their IPA.
+//!
+//! Hiragana, katakana and Hepburn romaji (with the common Kunrei-shiki
+//! spellings such as `si` and `tya`) are read mora by mora into a broad
+//! phonetic transcription, with:
+//!
+//! * the moraic nasal `ん` (`n`, `n'`) taking the place of what follows:
+//!   `m` before labials, `n` before alveolars, `ɲ` before `t͡ɕ`, `d͡ʑ`
+//!   and `ɲ`, `ŋ` before velars, and `ɴ` elsewhere;
+//! * the small `っ` (a doubled consonant in romaji) doubling the next
+//!   consonant, or a glottal stop where none follows;
+//! * long vowels, whether written `ー`, with a macron (`ō`), or as a
+//!   vowel followed by the same vowel, `o` by `u` or `e` by `i`, as
+//!   `ː`.
+//!
+//! Kana spellings of sounds are not always how a word is said, so the
+//! result is best effort: `ou` is always `oː`, though the verb `omou`
+//! has a plain `o` and `u`.
+//!
+//! ```
+//! use allo::japanese::{self, Script};
+//!
+//! assert_eq!(japanese::to_ipa("とうきょう").unwrap(), "toːkʲoː");
+//! assert_eq!(japanese::to_ipa("コーヒー").unwrap(), "koːçiː");
+//! assert_eq!(japanese::to_ipa("shimbun").unwrap(), "ɕimbɯɴ");
+//! assert_eq!(japanese::to_ipa("きって").unwrap(), "kʲitte");
+//! assert_eq!(japanese::from_ipa("sakana", Script::Katakana).unwrap(), "サカナ");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::notation::UnknownSymbol;
+use crate::parser;
+use crate::reading::{self, Reader};
+use crate::segment::{ParseSegmentError, Segment};
+
+/// The kana script `from_ipa` writes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Script {
+    #[default]
+    Hiragana,
+    Katakana,
+}
+
+/// The morae: their Hepburn romaji, hiragana and IPA. Where several
+/// morae have the same romaji or IPA, the first listed is the one read
+/// from romaji or written from IPA.
+pub const MORAE: &[(&str, &str, &str)] = &[
+    ("a", "あ", "a"),
+    ("i", "い", "i"),
+    ("u", "う", "ɯ"),
+    ("e", "え", "e"),
+    ("o", "お", "o"),
+    ("ka", "か", "ka"),
+    ("ki", "き", "kʲi"),
+    ("ku", "く", "kɯ"),
+    ("ke", "け", "ke"),
+    ("ko", "こ", "ko"),
+    ("kya", "きゃ", "kʲa"),
+    ("kyu", "きゅ", "kʲɯ"),
+    ("kyo", "きょ", "kʲo"),
+    ("ga", "が", "ɡa"),
+    ("gi", "ぎ", "ɡʲi"),
+    ("gu", "ぐ", "ɡɯ"),
+    ("ge", "げ", "ɡe"),
+    ("go", "ご", "ɡo"),
+    ("gya", "ぎゃ", "ɡʲa"),
+    ("gyu", "ぎゅ", "ɡʲɯ"),
+    ("gyo", "ぎょ", "ɡʲo"),
+    ("sa", "さ", "sa"),
+    ("shi", "し", "ɕi"),
+    ("su", "す", "sɯ"),
+    ("se", "せ", "se"),
+    ("so", "そ", "so"),
+    ("sha", "しゃ", "ɕa"),
+    ("shu", "しゅ", "ɕɯ"),
+    ("sho", "しょ", "ɕo"),
+    ("she", "しぇ", "ɕe"),
+    ("za", "ざ", "za"),
+    ("ji", "じ", "d͡ʑi"),
+    ("zu", "ず", "zɯ"),
+    ("ze", "ぜ", "ze"),
+    ("zo", "ぞ", "zo"),
+    ("ja", "じゃ", "d͡ʑa"),
+    ("ju", "じゅ", "d͡ʑɯ"),
+    ("jo", "じょ", "d͡ʑo"),
+    ("je", "じぇ", "d͡ʑe"),
+    ("ta", "た", "ta"),
+    ("chi", "ち", "t͡ɕi"),
+    ("tsu", "つ", "t͡sɯ"),
+    ("te", "て", "te"),
+    ("to", "と", "to"),
+    ("cha", "ちゃ", "t͡ɕa"),
+    ("chu", "ちゅ", "t͡ɕɯ"),
+    ("cho", "ちょ", "t͡ɕo"),
+    ("che", "ちぇ", "t͡ɕe"),
+    ("ti", "てぃ", "ti"),
+    ("tu", "とぅ", "tɯ"),
+    ("tsa", "つぁ", "t͡sa"),
+    ("tse", "つぇ", "t͡se"),
+    ("tso", "つぉ", "t͡so"),
+    ("da", "だ", "da"),
+    ("ji", "ぢ", "d͡ʑi"),
+    ("ja", "ぢゃ", "d͡ʑa"),
+    ("ju", "ぢゅ", "d͡ʑɯ"),
+    ("jo", "ぢょ", "d͡ʑo"),
+    ("zu", "づ", "zɯ"),
+    ("de", "で", "de"),
+    ("do", "ど", "do"),
+    ("di", "でぃ", "di"),
+    ("du", "どぅ", "dɯ"),
+    ("na", "な", "na"),
+    ("ni", "に", "ɲi"),
+    ("nu", "ぬ", "nɯ"),
+    ("ne", "ね", "ne"),
+    ("no", "の", "no"),
+    ("nya", "にゃ", "ɲa"),
+    ("nyu", "にゅ", "ɲɯ"),
+    ("nyo", "にょ", "ɲo"),
+    ("ha", "は", "ha"),
+    ("hi", "ひ", "çi"),
+    ("fu", "ふ", "ɸɯ"),
+    ("he", "へ", "he"),
+    ("ho", "ほ", "ho"),
+    ("hya", "ひゃ", "ça"),
+    ("hyu", "ひゅ", "çɯ"),
+    ("hyo", "ひょ", "ço"),
+    ("fa", "ふぁ", "ɸa"),
+    ("fi", "ふぃ", "ɸi"),
+    ("fe", "ふぇ", "ɸe"),
+    ("fo", "ふぉ", "ɸo"),
+    ("ba", "ば", "ba"),
+    ("bi", "び", "bʲi"),
+    ("bu", "ぶ", "bɯ"),
+    ("be", "べ", "be"),
+    ("bo", "ぼ", "bo"),
+    ("bya", "びゃ", "bʲa"),
+    ("byu", "びゅ", "bʲɯ"),
+    ("byo", "びょ", "bʲo"),
+    ("pa", "ぱ", "pa"),
+    ("pi", "ぴ", "pʲi"),
+    ("pu", "ぷ", "pɯ"),
+    ("pe", "ぺ", "pe"),
+    ("po", "ぽ", "po"),
+    ("pya", "ぴゃ", "pʲa"),
+    ("pyu", "ぴゅ", "pʲɯ"),
+    ("pyo", "ぴょ", "pʲo"),
+    ("ma", "ま", "ma"),
+    ("mi", "み", "mʲi"),
+    ("mu", "む", "mɯ"),
+    ("me", "め", "me"),
+    ("mo", "も", "mo"),
+    ("mya", "みゃ", "mʲa"),
+    ("myu", "みゅ", "mʲɯ"),
+    ("myo", "みょ", "mʲo"),
+    ("ya", "や", "ja"),
+    ("yu", "ゆ", "jɯ"),
+    ("yo", "よ", "jo"),
+    ("ye", "いぇ", "je"),
+    ("ra", "ら", "ɾa"),
+    ("ri", "り", "ɾʲi"),
+    ("ru", "る", "ɾɯ"),
+    ("re", "れ", "ɾe"),
+    ("ro", "ろ", "ɾo"),
+    ("rya", "りゃ", "ɾʲa"),
+    ("ryu", "りゅ", "ɾʲɯ"),
+    ("ryo", "りょ", "ɾʲo"),
+    ("wa", "わ", "ɰa"),
+    ("wi", "うぃ", "ɰi"),
+    ("we", "うぇ", "ɰe"),
+    ("wo", "を", "o"),
+    ("wi", "ゐ", "i"),
+    ("we", "ゑ", "e"),
+    ("wo", "うぉ", "ɰo"),
+    ("va", "ゔぁ", "va"),
+    ("vi", "ゔぃ", "vi"),
+    ("vu", "ゔ", "vɯ"),
+    ("ve", "ゔぇ", "ve"),
+    ("vo", "ゔぉ", "vo"),
+    // small kana written alone
+    ("a", "ぁ", "a"),
+    ("i", "ぃ", "i"),
+    ("u", "ぅ", "ɯ"),
+    ("e", "ぇ", "e"),
+    ("o", "ぉ", "o"),
+    ("ya", "ゃ", "ja"),
+    ("yu", "ゅ", "jɯ"),
+    ("yo", "ょ", "jo"),
+];
+
+/// Kunrei-shiki and other romaji spellings and the Hepburn they stand
+/// for.
+const ALIASES: [(&str, &str); 17] = [
+    ("si", "shi"),
+    ("zi", "ji"),
+    ("hu", "fu"),
+    ("sya", "sha"),
+    ("syu", "shu"),
+    ("syo", "sho"),
+    ("zya", "ja"),
+    ("zyu", "ju"),
+    ("zyo", "jo"),
+    ("jya", "ja"),
+    ("jyu", "ju"),
+    ("jyo", "jo"),
+    ("tya", "cha"),
+    ("tyu", "chu"),
+    ("tyo", "cho"),
+    ("tsi", "chi"),
+    ("la", "ra"),
+];
+
+/// Vowels with a macron or circumflex, as romaji writes long vowels.
+const LONG: [(char, char); 10] = [
+    ('ā', 'a'),
+    ('ī', 'i'),
+    ('ū', 'u'),
+    ('ē', 'e'),
+    ('ō', 'o'),
+    ('â', 'a'),
+    ('î', 'i'),
+    ('û', 'u'),
+    ('ê', 'e'),
+    ('ô', 'o'),
+];
+
+const VOWELS: [char; 6] = ['a', 'i', 'ɯ', 'u', 'e', 'o'];
+
+/// What a kana or romaji text reads as, before it is transcribed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Unit {
+    /// A mora, by its index in `MORAE`.
+    Mora(usize),
+    /// The moraic nasal.
+    N,
+    /// The first half of a doubled consonant.
+    Q,
+    /// A long vowel mark.
+    Long,
+    /// Anything copied through, as whitespace.
+    Other(char),
+}
+
+/// Converts kana or romaji, or a mix of the two, to IPA. Whitespace is
+/// copied through; an apostrophe only separates morae.
+pub fn to_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    Ok(transcribe(&units(s)?))
+}
+
+/// Reads kana or romaji directly into segments.
+pub fn to_segments(s: &str) -> Result<Vec<Segment>, ParseSegmentError> {
+    parser::parse(&to_ipa(s)?)
+}
+
+fn units(s: &str) -> Result<Vec<Unit>, UnknownSymbol> {
+    // a vowel with a macron is read as the vowel and `ー`
+    let chars: Vec<(usize, char)> = s
+        .char_indices()
+        .map(|(offset, c)| (offset, hiragana(c.to_lowercase().next().unwrap_or(c))))
+        .flat_map(
+            |(offset, c)| match LONG.iter().find(|(long, _)| *long == c) {
+                Some(&(_, vowel)) => [Some((offset, vowel)), Some((offset, 'ー'))],
+                None => [Some((offset, c)), None],
+            },
+        )
+        .flatten()
+        .collect();
+    let mut units = Vec::new();
+    let mut i = 0;
+    while let Some(&(offset, c)) = chars.get(i) {
+        let next = chars.get(i + 1).map(|&(_, c)| c);
+        let error = UnknownSymbol {
+            offset,
+            symbol: s[offset..].chars().next().unwrap_or(c),
+        };
+        i += 1;
+        match c {
+            c if c.is_whitespace() => units.push(Unit::Other(c)),
+            '\'' => {}
+            'ー' => units.push(Unit::Long),
+            'っ' => units.push(Unit::Q),
+            'ん' => units.push(Unit::N),
+            'n' if !matches!(next, Some('a' | 'i' | 'u' | 'e' | 'o' | 'y')) => units.push(Unit::N),
+            'm' if matches!(next, Some('b' | 'p' | 'm')) => units.push(Unit::N),
+            'b'..='z' if next == Some(c) && !matches!(c, 'e' | 'i' | 'o' | 'u') => {
+                units.push(Unit::Q)
+            }
+            't' if next == Some('c') => units.push(Unit::Q),
+            '\u{3041}'..='\u{3096}' => {
+                let two: String = chars[i - 1..chars.len().min(i + 1)]
+                    .iter()
+                    .map(|&(_, c)| c)
+                    .collect();
+                let (mora, len) = kana(&two)
+                    .map(|m| (m, 2))
+                    .or_else(|| kana(&c.to_string()).map(|m| (m, 1)))
+                    .ok_or(error)?;
+                units.push(Unit::Mora(mora));
+                i += len - 1;
+            }
+            _ => {
+                let (mora, len) = (1..=3)
+                    .rev()
+                    .find_map(|len| {
+                        let spelled: String = chars
+                            .get(i - 1..i - 1 + len)?
+                            .iter()
+                            .map(|&(_, c)| c)
+                            .collect();
+                        Some((romaji(&spelled)?, len))
+                    })
+                    .ok_or(error)?;
+                units.push(Unit::Mora(mora));
+                i += len - 1;
+            }
+        }
+    }
+    Ok(units)
+}
+
+/// Katakana as the hiragana for the same mora; other characters are
+/// returned unchanged.
+fn hiragana(c: char) -> char {
+    match c {
+        '\u{30A1}'..='\u{30F6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        c => c,
+    }
+}
+
+fn kana(s: &str) -> Option<usize> {
+    MORAE.iter().position(|(_, kana, _)| *kana == s)
+}
+
+fn romaji(s: &str) -> Option<usize> {
+    let s = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == s)
+        .map_or(s, |(_, hepburn)| hepburn);
+    MORAE.iter().position(|(romaji, ..)| *romaji == s)
+}
+
+fn transcribe(units: &[Unit]) -> String {
+    let ipa = |unit: Option<&Unit>| match unit {
+        Some(Unit::Mora(m)) => MORAE[*m].2,
+        _ => "",
+    };
+    let mut out = String::new();
+    for (i, unit) in units.iter().enumerate() {
+        let next = ipa(units.get(i + 1));
+        match unit {
+            Unit::Mora(m) => {
+                let (romaji, _, ipa) = MORAE[*m];
+                let previous = match i.checked_sub(1).map(|i| &units[i]) {
+                    Some(Unit::Mora(p)) => MORAE[*p].0.chars().last(),
+                    _ => None,
+                };
+                match (previous, romaji) {
+                    (Some(p), _) if romaji.len() == 1 && p.to_string() == romaji => out.push('ː'),
+                    (Some('o'), "u") | (Some('e'), "i") => out.push('ː'),
+                    _ => out.push_str(ipa),
+                }
+            }
+            Unit::N => out.push_str(match next {
+                _ if next.starts_with("t͡ɕ") || next.starts_with("d͡ʑ") => "ɲ",
+                _ if next.starts_with(['p', 'b', 'm']) => "m",
+                _ if next.starts_with(['t', 'd', 'n', 'ɾ', 'z']) => "n",
+                _ if next.starts_with('ɲ') => "ɲ",
+                _ if next.starts_with(['k', 'ɡ']) => "ŋ",
+                _ => "ɴ",
+            }),
+            Unit::Q => match next.chars().next() {
+                Some(c) if !VOWELS.contains(&c) && !matches!(c, 'j' | 'ɰ') => out.push(c),
+                _ => out.push('ʔ'),
+            },
+            Unit::Long => out.push('ː'),
+            Unit::Other(c) => out.push(*c),
+        }
+    }
+    out
+}
+
+/// Writes IPA in kana, mora by mora, as best it can: the longest mora
+/// of `MORAE` is matched first, a doubled consonant or one marked long
+/// before a vowel is written with `っ` (but a long nasal with `ん`, as
+/// `onːa` is `おんな`), a nasal before anything but a vowel with `ん`,
+/// and `ː` with `ー` in katakana and the vowel's kana in hiragana (`う`
+/// after `o`, `い` after `e`). A consonant before `i`
+/// may be written without its palatalization, as `ki`. Stress marks,
+/// syllable breaks and the voiceless mark of devoiced vowels are left
+/// out, and `u`, `w`, `r`, `g`, `ʃ` and `ʒ` are read as `ɯ`, `ɰ`, `ɾ`,
+/// `ɡ`, `ɕ` and `ʑ`.
+pub fn from_ipa(s: &str, script: Script) -> Result<String, UnknownSymbol> {
+    let mut table: Vec<(String, String)> = Vec::new();
+    for (_, kana, ipa) in MORAE {
+        table.push((ipa.to_string(), kana.to_string()));
+    }
+    // consonants before `i` written without their palatalization
+    for (_, kana, ipa) in MORAE {
+        if ipa.ends_with("ʲi") || *ipa == "ɲi" {
+            let plain = ipa.replace('ʲ', "").replace('ɲ', "n");
+            table.push((plain, kana.to_string()));
+        }
+    }
+    for (_, kana, ipa) in MORAE {
+        let consonant = ipa.find(VOWELS).unwrap_or(ipa.len());
+        let first = ipa.chars().next().unwrap();
+        if consonant == 0 || matches!(first, 'm' | 'n' | 'ɲ' | 'j' | 'ɰ') {
+            continue;
+        }
+        let kana = ["っ", kana].concat();
+        table.push(([&first.to_string(), *ipa].concat(), kana.clone()));
+        table.push(([&ipa[..consonant], "ː", &ipa[consonant..]].concat(), kana));
+    }
+    // a long nasal before a vowel as `ん` and the nasal's mora
+    let nasals: Vec<(String, String)> = table
+        .iter()
+        .filter(|(ipa, _)| ipa.starts_with(['m', 'n', 'ɲ']))
+        .filter_map(|(ipa, kana)| {
+            let consonant = ipa.find(VOWELS)?;
+            let long = [&ipa[..consonant], "ː", &ipa[consonant..]].concat();
+            Some((long, ["ん", kana].concat()))
+        })
+        .collect();
+    table.extend(nasals);
+    for nasal in ["ɴ", "m", "n", "ŋ", "ɲ"] {
+        table.push((nasal.to_string(), "ん".to_string()));
+    }
+    table.push(("ʔ".to_string(), "っ".to_string()));
+
+    // devoicing and downstep are not written in kana
+    let mut reader = Reader::new(s, &['\u{325}', 'ꜜ'], |c| match c {
+        'u' => 'ɯ',
+        'w' => 'ɰ',
+        'r' => 'ɾ',
+        'g' => 'ɡ',
+        'ʃ' => 'ɕ',
+        'ʒ' => 'ʑ',
+        c => c,
+    });
+    let mut out = String::new();
+    let mut vowel = None;
+    while let Some(c) = reader.peek() {
+        if c.is_whitespace() || c == 'ː' {
+            out.push_str(&match c {
+                'ː' => long(vowel.ok_or_else(|| reader.error())?, script),
+                c => c.to_string(),
+            });
+            reader.advance(c.len_utf8());
+            continue;
+        }
+        let rest = reader.rest();
+        let (len, kana) = reader.longest(
+            table
+                .iter()
+                .filter(|(ipa, _)| rest.starts_with(ipa.as_str()))
+                .map(|(ipa, kana)| (ipa.len(), kana)),
+        )?;
+        vowel = rest[..len].chars().last().filter(|c| VOWELS.contains(c));
+        reader.advance(len);
+        out.extend(kana.chars().map(|c| match script {
+            Script::Hiragana => c,
+            Script::Katakana => katakana(c),
+        }));
+    }
+    Ok(out)
+}
+
+/// Writes `segments` in kana.
+pub fn from_segments(segments: &[Segment], script: Script) -> Result<String, UnknownSymbol> {
+    from_ipa(&reading::ipa(segments), script)
+}
+
+/// The kana lengthening a mora ending in `vowel`.
+fn long(vowel: char, script: Script) -> String {
+    let kana = match (vowel, script) {
+        (_, Script::Katakana) => 'ー',
+        ('a', _) => 'あ',
+        ('i' | 'e', _) => 'い',
+        _ => 'う',
+    };
+    kana.to_string()
+}
+
+fn katakana(c: char) -> char {
+    match c {
+        '\u{3041}'..='\u{3096}' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+        c => c,
+    }
+}
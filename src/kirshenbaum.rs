@@ -0,0 +1,151 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::notation::{self, Table, UnknownSymbol};
+use crate::parser;
+use crate::segment::{ParseSegmentError, Segment};
+
+/// Kirshenbaum (ASCII-IPA) symbols and their IPA equivalents, as used
+/// by espeak and the `sci.lang` ASCII-IPA convention.
+///
+/// Where several spellings map to the same IPA symbol, the first
+/// listed is the one produced by `from_ipa`.
+pub const TABLE: &Table = &[
+    // consonants
+    ("p", "p"),
+    ("b", "b"),
+    ("t", "t"),
+    ("d", "d"),
+    ("t.", "ʈ"),
+    ("d.", "ɖ"),
+    ("c", "c"),
+    ("J", "ɟ"),
+    ("k", "k"),
+    ("g", "ɡ"),
+    ("q", "q"),
+    ("G", "ɢ"),
+    ("?", "ʔ"),
+    ("m", "m"),
+    ("M", "ɱ"),
+    ("n", "n"),
+    ("n.", "ɳ"),
+    ("n^", "ɲ"),
+    ("N", "ŋ"),
+    ("n\"", "ɴ"),
+    ("b<trl>", "ʙ"),
+    ("r<trl>", "r"),
+    ("r\"", "ʀ"),
+    ("*", "ɾ"),
+    ("*.", "ɽ"),
+    ("P", "ɸ"),
+    ("B", "β"),
+    ("f", "f"),
+    ("v", "v"),
+    ("T", "θ"),
+    ("D", "ð"),
+    ("s", "s"),
+    ("z", "z"),
+    ("S", "ʃ"),
+    ("Z", "ʒ"),
+    ("s.", "ʂ"),
+    ("z.", "ʐ"),
+    ("C", "ç"),
+    ("C<vcd>", "ʝ"),
+    ("x", "x"),
+    ("Q", "ɣ"),
+    ("X", "χ"),
+    ("g\"", "ʁ"),
+    ("H", "ħ"),
+    ("H<vcd>", "ʕ"),
+    ("h", "h"),
+    ("h<?>", "ɦ"),
+    ("s<lat>", "ɬ"),
+    ("z<lat>", "ɮ"),
+    ("r<lbd>", "ʋ"),
+    ("r", "ɹ"),
+    ("r.", "ɻ"),
+    ("j", "j"),
+    ("j<vel>", "ɰ"),
+    ("l", "l"),
+    ("l.", "ɭ"),
+    ("l^", "ʎ"),
+    ("L", "ʟ"),
+    ("w", "w"),
+    ("w<vls>", "ʍ"),
+    ("j<rnd>", "ɥ"),
+    ("b`", "ɓ"),
+    ("d`", "ɗ"),
+    ("g`", "ɠ"),
+    ("p!", "ʘ"),
+    ("t!", "ǀ"),
+    ("c!", "ǃ"),
+    ("l!", "ǁ"),
+    // vowels
+    ("i", "i"),
+    ("y", "y"),
+    ("i\"", "ɨ"),
+    ("u\"", "ʉ"),
+    ("u-", "ɯ"),
+    ("u", "u"),
+    ("I", "ɪ"),
+    ("I.", "ʏ"),
+    ("U", "ʊ"),
+    ("e", "e"),
+    ("Y", "ø"),
+    ("@<umd>", "ɘ"),
+    ("@.", "ɵ"),
+    ("o-", "ɤ"),
+    ("o", "o"),
+    ("@", "ə"),
+    ("E", "ɛ"),
+    ("W", "œ"),
+    ("V\"", "ɜ"),
+    ("O\"", "ɞ"),
+    ("V", "ʌ"),
+    ("O", "ɔ"),
+    ("&", "æ"),
+    ("a", "a"),
+    ("a.", "ɶ"),
+    ("A", "ɑ"),
+    ("A.", "ɒ"),
+    // suprasegmentals and diacritics
+    ("'", "ˈ"),
+    (",", "ˌ"),
+    (":", "ː"),
+    ("`", "ʼ"),
+    ("~", "\u{303}"),
+    ("<h>", "ʰ"),
+    ("<o>", "\u{325}"),
+    ("<v>", "\u{32C}"),
+    ("<w>", "ʷ"),
+    ("<j>", "ʲ"),
+    ("<r>", "\u{2DE}"),
+    ("-", "\u{329}"),
+    // tied affricates are written unbroken, as `tS`
+    ("", "\u{361}"),
+    ("", "\u{35C}"),
+];
+
+/// Converts a Kirshenbaum transcription to IPA, e.g. `"S@'nEl"` to
+/// `"ʃəˈnɛl"`.
+pub fn to_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    notation::to_ipa(s, TABLE)
+}
+
+/// Converts an IPA transcription to Kirshenbaum.
+pub fn from_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    notation::from_ipa(s, TABLE)
+}
+
+/// Parses a Kirshenbaum transcription directly into segments.
+pub fn to_segments(s: &str) -> Result<Vec<Segment>, ParseSegmentError> {
+    parser::parse(&to_ipa(s)?)
+}
+
+/// Writes `segments` in Kirshenbaum notation.
+pub fn from_segments(segments: &[Segment]) -> Result<String, UnknownSymbol> {
+    let ipa: String = segments.iter().map(Segment::to_string).collect();
+    from_ipa(&ipa)
+}
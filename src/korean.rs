@@ -0,0 +1,584 @@
+//! Korean Hangul, and its IPA.
+//!
+//! Each syllable block is split into its initial, vowel and final jamo,
+//! and a word is read with the positional rules of the standard
+//! pronunciation, which change one phoneme for another:
+//!
+//! * liaison: a final moves to a following syllable with no initial
+//!   (`ㅇ`), the second of a double final moving alone, and `ㄷ` and
+//!   `ㅌ` moved before `ㅣ` become `t͡ɕ` and `t͡ɕʰ`;
+//! * `ㅎ` aspirates a stop next to it, `ㄷ` before `ㅣ` becoming
+//!   `t͡ɕʰ`, and drops before a vowel;
+//! * coda neutralization: a final with nothing after it to move to is
+//!   `k`, `n`, `t`, `l`, `m`, `p` or `ŋ`, a double final keeping one of
+//!   its two;
+//! * a stop final becomes a nasal before a nasal, `ㄹ` is `n` after any
+//!   final but `ㄹ` and `ㄴ`, and `ㄴ` next to `ㄹ` is `l`;
+//! * a lax initial after an obstruent final is tense, also when a
+//!   double final keeps its other consonant (`앉다` is `ant͈a`).
+//!
+//! Syllables in different words, split by whitespace, are read apart.
+//! The phonetic transcription, the default, adds the allophones: lax
+//! stops and `ㅎ` voiced between voiced sounds (`ɡ`, `d`, `b`, `d͡ʑ`,
+//! `ɦ`), stop finals unreleased (`k̚`), `ㄹ` a tap but after `ㄹ`, and
+//! `ㅅ` and `ㅆ` palatal before `i` and `j`. Tense consonants are
+//! written with the extIPA strong articulation mark, as `k͈`.
+//!
+//! ```
+//! use allo::korean::{self, Transcription};
+//!
+//! assert_eq!(korean::to_ipa("한국어", Transcription::Phonetic).unwrap(), "hanɡuɡʌ");
+//! assert_eq!(korean::to_ipa("한국어", Transcription::Phonemic).unwrap(), "hankukʌ");
+//! assert_eq!(korean::to_ipa("국물", Transcription::Phonemic).unwrap(), "kuŋmul");
+//! assert_eq!(korean::to_ipa("같이", Transcription::Phonemic).unwrap(), "kat͡ɕʰi");
+//! assert_eq!(korean::from_ipa("saɾaŋ").unwrap(), "사랑");
+//! ```
+
+use alloc::{string::String, vec::Vec};
+
+use crate::notation::UnknownSymbol;
+use crate::parser;
+use crate::reading::{self, Reader};
+use crate::segment::{ParseSegmentError, Segment};
+
+/// Which transcription `to_ipa` writes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Transcription {
+    /// The phonemes of the standard pronunciation, positional rules
+    /// applied.
+    Phonemic,
+    /// The phonemes as their positional allophones.
+    #[default]
+    Phonetic,
+}
+
+/// A consonant jamo, as an initial or one of a final.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Jamo {
+    K,
+    Kk,
+    Kh,
+    N,
+    T,
+    Tt,
+    Th,
+    L,
+    M,
+    P,
+    Pp,
+    Ph,
+    S,
+    Ss,
+    Ng,
+    C,
+    Cc,
+    Ch,
+    H,
+}
+
+/// The initials in Unicode's order; `ㅇ` is no consonant.
+const INITIALS: [Option<Jamo>; 19] = [
+    Some(Jamo::K),
+    Some(Jamo::Kk),
+    Some(Jamo::N),
+    Some(Jamo::T),
+    Some(Jamo::Tt),
+    Some(Jamo::L),
+    Some(Jamo::M),
+    Some(Jamo::P),
+    Some(Jamo::Pp),
+    Some(Jamo::S),
+    Some(Jamo::Ss),
+    None,
+    Some(Jamo::C),
+    Some(Jamo::Cc),
+    Some(Jamo::Ch),
+    Some(Jamo::Kh),
+    Some(Jamo::Th),
+    Some(Jamo::Ph),
+    Some(Jamo::H),
+];
+
+/// The vowels' IPA, in Unicode's order.
+const VOWELS: [&str; 21] = [
+    "a", "ɛ", "ja", "jɛ", "ʌ", "e", "jʌ", "je", "o", "wa", "wɛ", "ø", "jo", "u", "wʌ", "we", "y",
+    "ju", "ɯ", "ɰi", "i",
+];
+
+/// `ㅏ`.
+const A: usize = 0;
+
+/// `ㅓ`.
+const EO: usize = 4;
+
+/// `ㅜ`.
+const U: usize = 13;
+
+/// `ㅢ`, read `i` after a consonant.
+const UI: usize = 19;
+
+/// `ㅣ`.
+const I: usize = 20;
+
+/// The finals in Unicode's order, after none: one consonant, or the two
+/// of a double final.
+const FINALS: [(Option<Jamo>, Option<Jamo>); 28] = {
+    use Jamo::*;
+    [
+        (None, None),
+        (Some(K), None),
+        (Some(Kk), None),
+        (Some(K), Some(S)),
+        (Some(N), None),
+        (Some(N), Some(C)),
+        (Some(N), Some(H)),
+        (Some(T), None),
+        (Some(L), None),
+        (Some(L), Some(K)),
+        (Some(L), Some(M)),
+        (Some(L), Some(P)),
+        (Some(L), Some(S)),
+        (Some(L), Some(Th)),
+        (Some(L), Some(Ph)),
+        (Some(L), Some(H)),
+        (Some(M), None),
+        (Some(P), None),
+        (Some(P), Some(S)),
+        (Some(S), None),
+        (Some(Ss), None),
+        (Some(Ng), None),
+        (Some(C), None),
+        (Some(Ch), None),
+        (Some(Kh), None),
+        (Some(Th), None),
+        (Some(Ph), None),
+        (Some(H), None),
+    ]
+};
+
+impl Jamo {
+    fn ipa(self) -> &'static str {
+        match self {
+            Jamo::K => "k",
+            Jamo::Kk => "k͈",
+            Jamo::Kh => "kʰ",
+            Jamo::N => "n",
+            Jamo::T => "t",
+            Jamo::Tt => "t͈",
+            Jamo::Th => "tʰ",
+            Jamo::L => "l",
+            Jamo::M => "m",
+            Jamo::P => "p",
+            Jamo::Pp => "p͈",
+            Jamo::Ph => "pʰ",
+            Jamo::S => "s",
+            Jamo::Ss => "s͈",
+            Jamo::Ng => "ŋ",
+            Jamo::C => "t͡ɕ",
+            Jamo::Cc => "t͡ɕ͈",
+            Jamo::Ch => "t͡ɕʰ",
+            Jamo::H => "h",
+        }
+    }
+
+    /// The stop a final is read as, if it is an obstruent.
+    fn neutral(self) -> Option<Jamo> {
+        match self {
+            Jamo::K | Jamo::Kk | Jamo::Kh => Some(Jamo::K),
+            Jamo::P | Jamo::Pp | Jamo::Ph => Some(Jamo::P),
+            Jamo::N | Jamo::L | Jamo::M | Jamo::Ng => None,
+            _ => Some(Jamo::T),
+        }
+    }
+
+    fn aspirated(self) -> Jamo {
+        match self {
+            Jamo::K | Jamo::Kk | Jamo::Kh => Jamo::Kh,
+            Jamo::P | Jamo::Pp | Jamo::Ph => Jamo::Ph,
+            Jamo::C | Jamo::Cc | Jamo::Ch => Jamo::Ch,
+            _ => Jamo::Th,
+        }
+    }
+
+    fn tense(self) -> Jamo {
+        match self {
+            Jamo::K => Jamo::Kk,
+            Jamo::T => Jamo::Tt,
+            Jamo::P => Jamo::Pp,
+            Jamo::S => Jamo::Ss,
+            Jamo::C => Jamo::Cc,
+            other => other,
+        }
+    }
+
+    fn nasal(self) -> Jamo {
+        match self {
+            Jamo::K => Jamo::Ng,
+            Jamo::P => Jamo::M,
+            Jamo::T => Jamo::N,
+            other => other,
+        }
+    }
+
+    const fn is_lax(self) -> bool {
+        matches!(self, Jamo::K | Jamo::T | Jamo::P | Jamo::S | Jamo::C)
+    }
+}
+
+/// A syllable block, as its jamo and then as the rules leave it.
+#[derive(Copy, Clone, Debug)]
+struct Syllable {
+    initial: Option<Jamo>,
+    vowel: usize,
+    coda: Option<Jamo>,
+    /// The second consonant of a double final.
+    cluster: Option<Jamo>,
+}
+
+/// Splits a Hangul syllable block into its jamo.
+fn decompose(c: char) -> Option<Syllable> {
+    let index = (c as u32).checked_sub(0xAC00).filter(|&i| i < 11172)? as usize;
+    let (coda, cluster) = FINALS[index % 28];
+    Some(Syllable {
+        initial: INITIALS[index / 588],
+        vowel: index % 588 / 28,
+        coda,
+        cluster,
+    })
+}
+
+/// Converts Hangul to IPA. Whitespace is copied through, and separates
+/// words.
+pub fn to_ipa(hangul: &str, transcription: Transcription) -> Result<String, UnknownSymbol> {
+    let mut out = String::new();
+    let mut word = Vec::new();
+    for (offset, c) in hangul.char_indices() {
+        if c.is_whitespace() {
+            out.push_str(&pronounce(&mut word, transcription));
+            word.clear();
+            out.push(c);
+        } else {
+            word.push(decompose(c).ok_or(UnknownSymbol { offset, symbol: c })?);
+        }
+    }
+    out.push_str(&pronounce(&mut word, transcription));
+    Ok(out)
+}
+
+/// Reads Hangul directly into segments.
+pub fn to_segments(
+    hangul: &str,
+    transcription: Transcription,
+) -> Result<Vec<Segment>, ParseSegmentError> {
+    parser::parse(&to_ipa(hangul, transcription)?)
+}
+
+/// Applies the positional rules to a word's syllables, then writes them.
+fn pronounce(word: &mut [Syllable], transcription: Transcription) -> String {
+    for i in 0..word.len() {
+        let (this, rest) = word[i..].split_first_mut().unwrap();
+        let next = rest.first_mut();
+        rules(this, next);
+    }
+    let mut out = String::new();
+    for (i, syllable) in word.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|i| word[i].coda);
+        out.push_str(&match transcription {
+            Transcription::Phonemic => phoneme(syllable),
+            Transcription::Phonetic => allophone(syllable, previous),
+        });
+    }
+    out
+}
+
+fn rules(this: &mut Syllable, mut next: Option<&mut Syllable>) {
+    if this.initial.is_some() && this.vowel == UI {
+        this.vowel = I;
+    }
+    // `ㅎ` with the initial after it
+    if let Some(next) = next.as_deref_mut() {
+        let final_h = this.cluster == Some(Jamo::H) || this.coda == Some(Jamo::H);
+        match next.initial {
+            Some(Jamo::K | Jamo::T | Jamo::C | Jamo::S | Jamo::N) | None if final_h => {
+                let initial = next.initial;
+                next.initial = match initial {
+                    Some(Jamo::S) => Some(Jamo::Ss),
+                    Some(Jamo::N) | None => initial,
+                    Some(stop) => Some(stop.aspirated()),
+                };
+                if this.cluster.take().is_none() {
+                    this.coda = (initial == Some(Jamo::N)).then_some(Jamo::N);
+                }
+            }
+            Some(Jamo::H) => {
+                let last = this
+                    .cluster
+                    .or(this.coda)
+                    .filter(|c| *c != Jamo::H && c.neutral().is_some());
+                if let Some(last) = last {
+                    // `ㄷ` aspirated before `ㅣ` is palatal too, as in `닫히다`
+                    next.initial = Some(match last.aspirated() {
+                        Jamo::Th if next.vowel == I => Jamo::Ch,
+                        aspirated => aspirated,
+                    });
+                    if this.cluster.take().is_none() {
+                        this.coda = None;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    // liaison
+    if let Some(next) = next.as_deref_mut().filter(|n| n.initial.is_none()) {
+        let moved = match this.cluster.take() {
+            Some(second) => Some(second),
+            None => this.coda.take_if(|c| *c != Jamo::Ng),
+        };
+        next.initial = match moved {
+            Some(Jamo::T) if next.vowel == I => Some(Jamo::C),
+            Some(Jamo::Th) if next.vowel == I => Some(Jamo::Ch),
+            moved => moved,
+        };
+    }
+    // a final with an obstruent tenses a lax initial, even if a double
+    // final keeps the other, as in `앉다`
+    let obstruent = [this.coda, this.cluster]
+        .into_iter()
+        .flatten()
+        .any(|c| c.neutral().is_some());
+    // a double final keeps one of its two; `ㄼ` keeps `ㅂ` in `밟-` and
+    // in `넓죽-` and `넓둥-`
+    let keeps_p = match (this.initial, next.as_deref()) {
+        (Some(Jamo::P), _) => this.vowel == A,
+        (Some(Jamo::N), Some(next)) => {
+            this.vowel == EO
+                && next.vowel == U
+                && matches!(
+                    (next.initial, next.coda),
+                    (Some(Jamo::C), Some(Jamo::K)) | (Some(Jamo::T), Some(Jamo::Ng))
+                )
+        }
+        _ => false,
+    };
+    if let (Some(first), Some(second)) = (this.coda, this.cluster.take()) {
+        let keeps_second = first == Jamo::L
+            && (matches!(second, Jamo::K | Jamo::M | Jamo::Ph) || second == Jamo::P && keeps_p);
+        this.coda = Some(if keeps_second { second } else { first });
+    }
+    this.coda = this.coda.map(|c| c.neutral().unwrap_or(c));
+    let (Some(coda), Some(next)) = (this.coda, next) else {
+        return;
+    };
+    let Some(initial) = next.initial else {
+        return;
+    };
+    let stop = coda.neutral().is_some();
+    match (coda, initial) {
+        (Jamo::L, Jamo::L) => {}
+        (Jamo::N, Jamo::L) => this.coda = Some(Jamo::L),
+        (Jamo::L, Jamo::N) => next.initial = Some(Jamo::L),
+        (_, Jamo::L) => next.initial = Some(Jamo::N),
+        _ => {}
+    }
+    if stop && matches!(next.initial, Some(Jamo::N | Jamo::M)) {
+        this.coda = Some(coda.nasal());
+    } else if obstruent && initial.is_lax() {
+        next.initial = Some(initial.tense());
+    }
+}
+
+fn phoneme(syllable: &Syllable) -> String {
+    [
+        syllable.initial.map_or("", Jamo::ipa),
+        VOWELS[syllable.vowel],
+        syllable.coda.map_or("", Jamo::ipa),
+    ]
+    .concat()
+}
+
+/// `previous` is the final of the syllable before, if there is one.
+fn allophone(syllable: &Syllable, previous: Option<Option<Jamo>>) -> String {
+    let vowel = VOWELS[syllable.vowel];
+    let voiced = matches!(
+        previous,
+        Some(None | Some(Jamo::N | Jamo::L | Jamo::M | Jamo::Ng))
+    );
+    let palatal = vowel.starts_with(['i', 'j']);
+    let initial = match syllable.initial {
+        Some(Jamo::K) if voiced => "ɡ",
+        Some(Jamo::T) if voiced => "d",
+        Some(Jamo::P) if voiced => "b",
+        Some(Jamo::C) if voiced => "d͡ʑ",
+        Some(Jamo::H) if voiced => "ɦ",
+        Some(Jamo::L) if previous != Some(Some(Jamo::L)) => "ɾ",
+        Some(Jamo::S) if palatal => "ɕ",
+        Some(Jamo::Ss) if palatal => "ɕ͈",
+        initial => initial.map_or("", Jamo::ipa),
+    };
+    let coda = match syllable.coda {
+        Some(Jamo::K) => "k̚",
+        Some(Jamo::T) => "t̚",
+        Some(Jamo::P) => "p̚",
+        coda => coda.map_or("", Jamo::ipa),
+    };
+    [initial, vowel, coda].concat()
+}
+
+/// The IPA read as each initial, phonemic and phonetic.
+const READ_INITIALS: [(&str, usize); 33] = [
+    ("k", 0),
+    ("ɡ", 0),
+    ("k͈", 1),
+    ("ɡ͈", 1),
+    ("n", 2),
+    ("t", 3),
+    ("d", 3),
+    ("t͈", 4),
+    ("d͈", 4),
+    ("ɾ", 5),
+    ("l", 5),
+    ("m", 6),
+    ("p", 7),
+    ("b", 7),
+    ("p͈", 8),
+    ("b͈", 8),
+    ("s", 9),
+    ("ɕ", 9),
+    ("s͈", 10),
+    ("z͈", 10),
+    ("ɕ͈", 10),
+    ("ʑ͈", 10),
+    ("t͡ɕ", 12),
+    ("d͡ʑ", 12),
+    ("t͡ɕ͈", 13),
+    ("t͡ʑ͈", 13),
+    ("d͡ʑ͈", 13),
+    ("t͡ɕʰ", 14),
+    ("kʰ", 15),
+    ("tʰ", 16),
+    ("pʰ", 17),
+    ("h", 18),
+    ("ɦ", 18),
+];
+
+/// The IPA read as each final with a single consonant.
+const READ_FINALS: [(&str, usize); 20] = [
+    ("k", 1),
+    ("ɡ", 1),
+    ("k͈", 2),
+    ("ɡ͈", 2),
+    ("n", 4),
+    ("t", 7),
+    ("l", 8),
+    ("ɾ", 8),
+    ("m", 16),
+    ("p", 17),
+    ("s", 19),
+    ("s͈", 20),
+    ("z͈", 20),
+    ("ŋ", 21),
+    ("t͡ɕ", 22),
+    ("t͡ɕʰ", 23),
+    ("kʰ", 24),
+    ("tʰ", 25),
+    ("pʰ", 26),
+    ("h", 27),
+];
+
+/// What `from_ipa` reads a stretch of IPA as.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Sound {
+    Consonant(&'static str),
+    Vowel(usize),
+    Other(char),
+}
+
+/// Writes IPA in Hangul as it sounds, syllable by syllable: a consonant
+/// before a vowel is its initial, and any other is the final of the
+/// syllable before, so the spelling rules are not undone (`hanɡuɡʌ` is
+/// written `한구거`). Phonemic and phonetic transcriptions are both read;
+/// the unreleased mark, stress marks and syllable breaks are left out,
+/// and `g` and `r` are read as `ɡ` and `ɾ`.
+pub fn from_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    let mut reader = Reader::new(s, &['\u{31A}'], |c| match c {
+        'g' => 'ɡ',
+        'r' => 'ɾ',
+        c => c,
+    });
+    // each sound with its position in the reader
+    let mut sounds = Vec::new();
+    while let Some(c) = reader.peek() {
+        let position = reader.position();
+        if c.is_whitespace() {
+            sounds.push((position, Sound::Other(c)));
+            reader.advance(c.len_utf8());
+            continue;
+        }
+        let rest = reader.rest();
+        let consonants = READ_INITIALS
+            .iter()
+            .map(|(ipa, _)| *ipa)
+            .chain(READ_FINALS.iter().map(|(ipa, _)| *ipa))
+            .map(|ipa| (ipa, Sound::Consonant(ipa)));
+        let vowels = VOWELS
+            .iter()
+            .enumerate()
+            .map(|(i, ipa)| (*ipa, Sound::Vowel(i)));
+        let (len, sound) = reader.longest(
+            consonants
+                .chain(vowels)
+                .filter(|(ipa, _)| rest.starts_with(ipa))
+                .map(|(ipa, sound)| (ipa.len(), sound)),
+        )?;
+        sounds.push((position, sound));
+        reader.advance(len);
+    }
+
+    // syllables as the indices of their jamo, or characters copied
+    let mut blocks: Vec<Result<(usize, usize, usize), char>> = Vec::new();
+    for (i, &(position, sound)) in sounds.iter().enumerate() {
+        match sound {
+            Sound::Other(c) => blocks.push(Err(c)),
+            Sound::Vowel(vowel) => {
+                let initial = match i.checked_sub(1).map(|i| sounds[i].1) {
+                    Some(Sound::Consonant(ipa)) => read(&READ_INITIALS, ipa),
+                    _ => None,
+                };
+                blocks.push(Ok((initial.unwrap_or(11), vowel, 0)));
+            }
+            Sound::Consonant(ipa) => {
+                let before_vowel = matches!(sounds.get(i + 1), Some((_, Sound::Vowel(_))));
+                if before_vowel && read(&READ_INITIALS, ipa).is_some() {
+                    continue;
+                }
+                let open = matches!(blocks.last(), Some(Ok((_, _, 0))));
+                let final_ = read(&READ_FINALS, ipa)
+                    .filter(|_| open)
+                    .ok_or_else(|| reader.error_at(position))?;
+                if let Some(Ok((_, _, coda))) = blocks.last_mut() {
+                    *coda = final_;
+                }
+            }
+        }
+    }
+    Ok(blocks
+        .into_iter()
+        .map(|block| match block {
+            Ok((initial, vowel, coda)) => {
+                char::from_u32(0xAC00 + (initial * 588 + vowel * 28 + coda) as u32)
+                    .unwrap_or_default()
+            }
+            Err(c) => c,
+        })
+        .collect())
+}
+
+fn read(table: &[(&str, usize)], ipa: &str) -> Option<usize> {
+    table.iter().find(|(read, _)| *read == ipa).map(|(_, i)| *i)
+}
+
+/// Writes `segments` in Hangul.
+pub fn from_segments(segments: &[Segment]) -> Result<String, UnknownSymbol> {
+    from_ipa(&reading::ipa(segments))
+}
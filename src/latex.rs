@@ -0,0 +1,252 @@
+//! Rendering IPA as LaTeX with the TIPA package's macros, e.g. `ʃ` as
+//! `\textesh`.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use crate::notation::{Table, UnknownSymbol};
+use crate::parser;
+use crate::segment::Segment;
+use crate::tone::{Contour, Level};
+
+/// TIPA macros for IPA symbols that are not plain ASCII letters. ASCII
+/// letters are written as themselves.
+pub const TABLE: &Table = &[
+    // pulmonic consonants
+    (r"\textscb", "ʙ"),
+    (r"\textphi", "ɸ"),
+    (r"\textbeta", "β"),
+    (r"\textltailm", "ɱ"),
+    (r"\textscriptv", "ʋ"),
+    (r"\texttheta", "θ"),
+    (r"\dh", "ð"),
+    (r"\textfishhookr", "ɾ"),
+    (r"\textturnr", "ɹ"),
+    (r"\textbeltl", "ɬ"),
+    (r"\textlyoghlig", "ɮ"),
+    (r"\textturnlonglegr", "ɺ"),
+    (r"\textesh", "ʃ"),
+    (r"\textyogh", "ʒ"),
+    (r"\textrtailt", "ʈ"),
+    (r"\textrtaild", "ɖ"),
+    (r"\textrtailn", "ɳ"),
+    (r"\textrtailr", "ɽ"),
+    (r"\textrtails", "ʂ"),
+    (r"\textrtailz", "ʐ"),
+    (r"\textturnrrtail", "ɻ"),
+    (r"\textrtaill", "ɭ"),
+    (r"\textbardotlessj", "ɟ"),
+    (r"\textltailn", "ɲ"),
+    (r"\c{c}", "ç"),
+    (r"\textctj", "ʝ"),
+    (r"\textturny", "ʎ"),
+    (r"\textctc", "ɕ"),
+    (r"\textctz", "ʑ"),
+    (r"\textscriptg", "ɡ"),
+    (r"\ng", "ŋ"),
+    (r"\textgamma", "ɣ"),
+    (r"\textturnmrleg", "ɰ"),
+    (r"\textscl", "ʟ"),
+    (r"\textscg", "ɢ"),
+    (r"\textscn", "ɴ"),
+    (r"\textscr", "ʀ"),
+    (r"\textchi", "χ"),
+    (r"\textinvscr", "ʁ"),
+    (r"\textcrh", "ħ"),
+    (r"\textrevglotstop", "ʕ"),
+    (r"\textsch", "ʜ"),
+    (r"\textbarrevglotstop", "ʢ"),
+    (r"\textbarglotstop", "ʡ"),
+    (r"\textglotstop", "ʔ"),
+    (r"\texthth", "ɦ"),
+    // other consonants
+    (r"\textturnw", "ʍ"),
+    (r"\textturnh", "ɥ"),
+    (r"\texththeng", "ɧ"),
+    // clicks and implosives
+    (r"\textbullseye", "ʘ"),
+    (r"\textpipe", "ǀ"),
+    (r"\textdoublebarpipe", "ǂ"),
+    (r"\textdoublepipe", "ǁ"),
+    (r"\textexclam", "ǃ"),
+    (r"\texthtb", "ɓ"),
+    (r"\texthtd", "ɗ"),
+    (r"\texthtbardotlessj", "ʄ"),
+    (r"\texthtg", "ɠ"),
+    (r"\texthtscg", "ʛ"),
+    // vowels
+    (r"\textbari", "ɨ"),
+    (r"\textbaru", "ʉ"),
+    (r"\textturnm", "ɯ"),
+    (r"\textsci", "ɪ"),
+    (r"\textscy", "ʏ"),
+    (r"\textupsilon", "ʊ"),
+    (r"\o", "ø"),
+    (r"\textreve", "ɘ"),
+    (r"\textbaro", "ɵ"),
+    (r"\textramshorns", "ɤ"),
+    (r"\textschwa", "ə"),
+    (r"\textepsilon", "ɛ"),
+    (r"\oe", "œ"),
+    (r"\textrevepsilon", "ɜ"),
+    (r"\textcloserevepsilon", "ɞ"),
+    (r"\textturnv", "ʌ"),
+    (r"\textopeno", "ɔ"),
+    (r"\ae", "æ"),
+    (r"\textturna", "ɐ"),
+    (r"\textscoelig", "ɶ"),
+    (r"\textscripta", "ɑ"),
+    (r"\textturnscripta", "ɒ"),
+    // spacing modifiers
+    (r"\textsuperscript{h}", "ʰ"),
+    (r"\textsuperscript{w}", "ʷ"),
+    (r"\textsuperscript{j}", "ʲ"),
+    (r"\textsuperscript{\textgamma}", "ˠ"),
+    (r"\textsuperscript{\textrevglotstop}", "ˤ"),
+    (r"\textsuperscript{\textglotstop}", "ˀ"),
+    (r"\textsuperscript{n}", "ⁿ"),
+    (r"\textsuperscript{l}", "ˡ"),
+    (r"\textrhoticity", "˞"),
+    (r"\textcorner", "̚"),
+    ("'", "ʼ"),
+    // suprasegmentals
+    (r"\textprimstress", "ˈ"),
+    (r"\textsecstress", "ˌ"),
+    (r"\textlengthmark", "ː"),
+    (r"\texthalflength", "ˑ"),
+    (".", "."),
+    (r"\textvertline", "|"),
+    (r"\textdoublevertline", "‖"),
+    (r"\textbottomtiebar", "‿"),
+];
+
+/// TIPA accent macros for combining diacritics, each taking the
+/// symbol it marks as its argument: `n̥` is `\textsubring{n}`.
+pub const DIACRITICS: &[(char, &str)] = &[
+    ('\u{325}', r"\textsubring"),
+    ('\u{30A}', r"\r"),
+    ('\u{32C}', r"\textsubwedge"),
+    ('\u{324}', r"\textsubumlaut"),
+    ('\u{330}', r"\textsubtilde"),
+    ('\u{33C}', r"\textseagull"),
+    ('\u{32A}', r"\textsubbridge"),
+    ('\u{33A}', r"\textinvsubbridge"),
+    ('\u{33B}', r"\textsubsquare"),
+    ('\u{339}', r"\textsubrhalfring"),
+    ('\u{31C}', r"\textsublhalfring"),
+    ('\u{31F}', r"\textsubplus"),
+    ('\u{320}', r"\textsubbar"),
+    ('\u{308}', "\\\""),
+    ('\u{33D}', r"\textovercross"),
+    ('\u{329}', r"\textsyllabic"),
+    ('\u{32F}', r"\textsubarch"),
+    ('\u{303}', r"\~"),
+    ('\u{334}', r"\textsuperimposetilde"),
+    ('\u{31D}', r"\textraising"),
+    ('\u{31E}', r"\textlowering"),
+    ('\u{318}', r"\textadvancing"),
+    ('\u{319}', r"\textretracting"),
+    ('\u{306}', r"\u"),
+    ('\u{30B}', r"\H"),
+    ('\u{301}', r"\'"),
+    ('\u{304}', r"\="),
+    ('\u{300}', r"\`"),
+    ('\u{30F}', r"\textdoublegrave"),
+    ('\u{30C}', r"\v"),
+    ('\u{302}', r"\^"),
+];
+
+/// Renders an IPA transcription as TIPA, e.g. `"ʃɪp"` as
+/// `"\textesh\textsci{}p"`. Diacritics wrap the symbol they mark, tied
+/// pairs are written with `\t`, and runs of tone letters with `\tone`.
+///
+/// The output uses TIPA's text-mode macros, so it needs only
+/// `\usepackage{tipa}` and no `\textipa` environment.
+pub fn from_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            join(&mut out, c.encode_utf8(&mut [0; 4]));
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let (piece, len) = cluster(s, rest)?;
+        join(&mut out, &piece);
+        rest = &rest[len..];
+    }
+    Ok(out)
+}
+
+/// Renders segments as TIPA; see `from_ipa`.
+pub fn from_segments(segments: &[Segment]) -> Result<String, UnknownSymbol> {
+    from_ipa(&segments.iter().map(Segment::to_string).collect::<String>())
+}
+
+/// Renders the symbol at the start of `rest` with its diacritics,
+/// returning it and the number of bytes it took up. `s` is the whole
+/// input, for error offsets.
+fn cluster(s: &str, rest: &str) -> Result<(String, usize), UnknownSymbol> {
+    let unknown = |at: &str, symbol| UnknownSymbol {
+        offset: s.len() - at.len(),
+        symbol,
+    };
+    let c = rest.chars().next().unwrap();
+
+    let tones: String = rest
+        .chars()
+        .take_while(|c| Level::from_letter(*c).is_some())
+        .collect();
+    if let Some(contour) = Contour::parse(&tones) {
+        return Ok((format!(r"\tone{{{}}}", contour.chao()), tones.len()));
+    }
+
+    let (mut piece, mut len) = match TABLE
+        .iter()
+        .filter(|(_, ipa)| rest.starts_with(ipa))
+        .max_by_key(|(_, ipa)| ipa.len())
+    {
+        Some((tipa, ipa)) => (tipa.to_string(), ipa.len()),
+        None if c.is_ascii_alphabetic() => (c.to_string(), 1),
+        None => return Err(unknown(rest, c)),
+    };
+    while let Some(mark) = rest[len..].chars().next() {
+        if mark == '\u{361}' || mark == '\u{35C}' {
+            let after = &rest[len + mark.len_utf8()..];
+            if after.is_empty() {
+                return Err(unknown(&rest[len..], mark));
+            }
+            let (next, next_len) = cluster(s, after)?;
+            let mut tied = piece;
+            join(&mut tied, &next);
+            piece = format!(r"\t{{{}}}", tied);
+            len += mark.len_utf8() + next_len;
+        } else if let Some((_, accent)) = DIACRITICS.iter().find(|(d, _)| *d == mark) {
+            piece = format!("{}{{{}}}", accent, piece);
+            len += mark.len_utf8();
+        } else if parser::is_combining(mark) {
+            // combining marks TIPA writes as a spacing symbol, like `̚`
+            let spacing = TABLE.iter().find(|(_, ipa)| ipa.chars().eq([mark]));
+            let (tipa, _) = spacing.ok_or_else(|| unknown(&rest[len..], mark))?;
+            join(&mut piece, tipa);
+            len += mark.len_utf8();
+        } else {
+            break;
+        }
+    }
+    Ok((piece, len))
+}
+
+/// Appends `piece` to `out`, ending a control word in `out` with `{}`
+/// where TeX would otherwise run it into `piece` or swallow a space.
+fn join(out: &mut String, piece: &str) {
+    let word = out.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let ends_in_control_word = word.len() < out.len() && word.ends_with('\\');
+    let next = piece.chars().next();
+    if ends_in_control_word && next.is_some_and(|c| c.is_ascii_alphabetic() || c.is_whitespace()) {
+        out.push_str("{}");
+    }
+    out.push_str(piece);
+}
@@ -0,0 +1,250 @@
+//! Importing sound changes written for Lexurgy, a sound-change applier
+//! for conlangers, from its `.lsc` format:
+//!
+//! ```text
+//! Class vowel {a, e, i, o, u}
+//!
+//! intervocalic-voicing:
+//!     {p, t, k} => {b, d, ɡ} / @vowel _ @vowel
+//!
+//! romanizer-old:
+//!     ...
+//!
+//! final-devoicing:
+//!     [-son] => [-voice] / _ $
+//! ```
+//!
+//! Classes, rules and intermediate romanizers, which divide the file
+//! into stages, are read into this crate's `rules::Rule`s. The
+//! deromanizer, romanizers and syllable definitions are skipped, since
+//! the words are taken to be in IPA already, and `Feature`, `Symbol`
+//! and `Diacritic` declarations are skipped in favour of the crate's
+//! own features. Deletion (`x => *`) and insertion (`* => x`) are
+//! supported, but rule modifiers, exceptions (`//`) and targets longer
+//! than one segment are not.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::rules::{self, ImportError, ImportErrorKind, Item, ParseRuleError, Rule};
+use crate::segment::Segment;
+
+/// A rule block: expressions applied together, each segment changed
+/// by the first expression that matches it.
+#[derive(Clone, Debug)]
+pub struct Block {
+    pub name: String,
+    pub rules: Vec<Rule>,
+}
+
+impl Block {
+    /// Applies the block simultaneously, as `rules::apply_together`
+    /// does.
+    pub fn apply(&self, word: &[Segment]) -> Vec<Segment> {
+        rules::apply_together(&self.rules, word)
+    }
+}
+
+/// The blocks between two intermediate romanizers, in order. The
+/// first stage has no name; each later one is named after the
+/// romanizer that starts it.
+#[derive(Clone, Debug, Default)]
+pub struct Stage {
+    pub name: Option<String>,
+    pub blocks: Vec<Block>,
+}
+
+impl Stage {
+    pub fn apply(&self, word: &[Segment]) -> Vec<Segment> {
+        self.blocks
+            .iter()
+            .fold(word.to_vec(), |word, block| block.apply(&word))
+    }
+}
+
+/// A Lexurgy sound-change file.
+///
+/// ```
+/// use allo::lexurgy::SoundChanges;
+/// use allo::parser;
+///
+/// let changes: SoundChanges = "
+/// Class vowel {a, i, u}
+/// lenition:
+///     {p, t, k} => {b, d, ɡ} / @vowel _ @vowel
+/// ".parse().unwrap();
+/// let word = changes.apply(&parser::parse("apataka").unwrap());
+/// assert_eq!(word.iter().map(|s| s.to_string()).collect::<String>(), "abadaɡa");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SoundChanges {
+    classes: Vec<(String, Vec<Segment>)>,
+    stages: Vec<Stage>,
+}
+
+impl SoundChanges {
+    /// The classes, by name without the `@`, in the order defined.
+    pub fn classes(&self) -> &[(String, Vec<Segment>)] {
+        &self.classes
+    }
+
+    pub fn stages(&self) -> &[Stage] {
+        &self.stages
+    }
+
+    /// Runs every stage on `word`.
+    pub fn apply(&self, word: &[Segment]) -> Vec<Segment> {
+        self.stages
+            .iter()
+            .fold(word.to_vec(), |word, stage| stage.apply(&word))
+    }
+
+    /// The segments in a class or a list such as `{p, t, @nasal}`.
+    fn members(&self, s: &str) -> Result<Vec<Segment>, ImportErrorKind> {
+        let s = s.trim();
+        if let Some(name) = s.strip_prefix('@') {
+            return self
+                .classes
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, segments)| segments.clone())
+                .ok_or_else(|| ImportErrorKind::UnknownClass(name.to_string()));
+        }
+        match s.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(list) => {
+                let mut members = Vec::new();
+                for item in list.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+                    match item.starts_with('@') {
+                        true => members.extend(self.members(item)?),
+                        false => members.push(item.parse()?),
+                    }
+                }
+                Ok(members)
+            }
+            None => Ok(Vec::from([s.parse()?])),
+        }
+    }
+
+    /// The rules for one expression, `target => result / environment`.
+    fn expression(&self, line: &str) -> Result<Vec<Rule>, ImportErrorKind> {
+        let unsupported = || ImportErrorKind::Unsupported(line.to_string());
+        if line.contains("//") {
+            return Err(unsupported());
+        }
+        let (target, rest) = line.split_once("=>").ok_or_else(unsupported)?;
+        let (result, environment) = rest.split_once('/').unwrap_or((rest, ""));
+        let [target, result] = [target, result].map(|s| match s.trim() {
+            "*" => "\u{2205}",
+            s => s,
+        });
+        if [target, result].iter().any(|s| s.contains('*')) {
+            return Err(unsupported());
+        }
+
+        let environment = rules::environment(environment.trim(), |c, rest| {
+            Ok::<_, ImportErrorKind>(match c {
+                '{' => {
+                    let len = rest.find('}').ok_or(ParseRuleError::Unbalanced('{'))? + 1;
+                    Item::Class(len, self.members(&rest[..len])?)
+                }
+                '@' => {
+                    let len = rest
+                        .find(|c: char| c.is_whitespace() || "#[]()_".contains(c))
+                        .unwrap_or(rest.len());
+                    Item::Class(len, self.members(&rest[..len])?)
+                }
+                '$' => Item::Written(1, "#"),
+                _ => Item::Same,
+            })
+        })?;
+        let members = |s: &str| match s.starts_with(['{', '@']) {
+            true => self.members(s).map(Some),
+            false => Ok(None),
+        };
+        rules::imported(
+            line,
+            (target, members(target)?),
+            (result, members(result)?),
+            &environment,
+        )
+    }
+}
+
+impl core::str::FromStr for SoundChanges {
+    type Err = ImportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut changes = SoundChanges::default();
+        let mut stage = Stage::default();
+        // The block being read, or `None` inside a skipped one.
+        let mut block: Option<Block> = None;
+        for (n, line) in s.lines().enumerate() {
+            let error = |kind| ImportError { line: n + 1, kind };
+            let line = line.split('#').next().unwrap_or("");
+            let indented = line.starts_with(char::is_whitespace);
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(class) = line.strip_prefix("Class ") {
+                let (name, list) = class.split_once(char::is_whitespace).unwrap_or((class, ""));
+                let members = changes.members(list).map_err(error)?;
+                changes.classes.push((name.to_string(), members));
+                continue;
+            }
+            if ["Feature ", "Symbol ", "Diacritic "]
+                .iter()
+                .any(|k| line.starts_with(k))
+            {
+                continue;
+            }
+            if indented && line == "Then:" {
+                if let Some(b) = block.take() {
+                    let name = b.name.clone();
+                    stage.blocks.push(b);
+                    block = Some(Block {
+                        name,
+                        rules: Vec::new(),
+                    });
+                }
+                continue;
+            }
+            if !indented {
+                let (header, expression) = line
+                    .split_once(':')
+                    .ok_or_else(|| error(ImportErrorKind::Unsupported(line.to_string())))?;
+                stage.blocks.extend(block.take());
+                let header = header.trim();
+                if header.contains(char::is_whitespace) {
+                    return Err(error(ImportErrorKind::Unsupported(line.to_string())));
+                }
+                let lower = header.to_lowercase();
+                if let Some(name) = lower.strip_prefix("romanizer-") {
+                    changes.stages.push(core::mem::take(&mut stage));
+                    stage.name = Some(name.to_string());
+                } else if !matches!(lower.as_str(), "deromanizer" | "romanizer" | "syllables") {
+                    block = Some(Block {
+                        name: header.to_string(),
+                        rules: Vec::new(),
+                    });
+                }
+                if expression.trim().is_empty() {
+                    continue;
+                }
+            }
+            let expression = match indented {
+                true => line,
+                false => line.split_once(':').map_or("", |(_, e)| e.trim()),
+            };
+            if let Some(b) = block.as_mut() {
+                b.rules
+                    .extend(changes.expression(expression).map_err(error)?);
+            }
+        }
+        stage.blocks.extend(block);
+        changes.stages.push(stage);
+        Ok(changes)
+    }
+}
@@ -1,5 +1,117 @@
+//! Without the default `std` feature, the crate is `no_std`: the
+//! enums, symbol tables and lookups such as `ipa::describe` and
+//! `features::of` need only `core`. The `alloc` feature adds parsing
+//! and everything else that builds strings or collections.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod affricate;
+#[cfg(feature = "alloc")]
+pub mod align;
+#[cfg(feature = "alloc")]
+pub mod americanist;
+#[cfg(feature = "alloc")]
+pub mod arpabet;
+#[cfg(feature = "alloc")]
+pub mod asjp;
+#[cfg(feature = "alloc")]
+pub mod chart;
+#[cfg(feature = "alloc")]
+pub mod classes;
+#[cfg(feature = "alloc")]
+pub mod cmudict;
+#[cfg(feature = "alloc")]
+pub mod cognates;
+#[cfg(feature = "alloc")]
+pub mod cxs;
+#[cfg(feature = "alloc")]
+pub mod delimited;
+pub mod diacritics;
+pub mod dolgopolsky;
+#[cfg(feature = "alloc")]
+pub mod english;
+pub mod error;
+#[cfg(feature = "alloc")]
+pub mod espeak;
+#[cfg(feature = "alloc")]
+pub mod extipa;
+pub mod features;
+pub mod formants;
+#[cfg(feature = "alloc")]
+pub mod g2p;
+#[cfg(feature = "alloc")]
+pub mod generator;
+pub mod geometry;
 pub mod graphemes;
+#[cfg(feature = "alloc")]
+pub mod inventory;
 pub mod ipa;
+#[cfg(feature = "alloc")]
+pub mod japanese;
+#[cfg(feature = "alloc")]
+pub mod kirshenbaum;
+#[cfg(feature = "alloc")]
+pub mod korean;
+#[cfg(feature = "alloc")]
+pub mod latex;
+#[cfg(feature = "alloc")]
+pub mod lexurgy;
+pub mod lingpy;
+mod macros;
+#[cfg(feature = "alloc")]
+pub mod mandarin;
+#[cfg(feature = "alloc")]
+pub mod metrical;
+#[cfg(feature = "alloc")]
+pub mod names;
+pub mod normalize;
+#[cfg(feature = "alloc")]
+pub mod notation;
+#[cfg(feature = "alloc")]
+pub mod orthography;
+pub mod parser;
+#[cfg(feature = "alloc")]
+pub mod pattern;
+#[cfg(feature = "phoible")]
+pub mod phoible;
+#[cfg(feature = "alloc")]
+pub mod phonotactics;
+#[cfg(feature = "alloc")]
+pub mod plot;
+pub mod random;
+#[cfg(feature = "alloc")]
+mod reading;
+#[cfg(feature = "alloc")]
+pub mod rules;
+#[cfg(feature = "alloc")]
+pub mod sampa;
+#[cfg(feature = "alloc")]
+pub mod sca;
+pub mod segment;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod suprasegmentals;
+#[cfg(feature = "alloc")]
+pub mod syllable;
+pub mod tone;
+#[cfg(feature = "alloc")]
+pub mod typology;
+#[cfg(feature = "alloc")]
+pub mod upa;
+#[cfg(feature = "alloc")]
+pub mod voqs;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "alloc")]
+pub mod xsampa;
+#[cfg(feature = "alloc")]
+pub mod zsampa;
+
+pub use error::Error;
+pub use segment::Segment;
 
 #[cfg(test)]
 mod tests {
@@ -12,4 +124,2647 @@ mod tests {
         assert!(voiceless_lateral_fricative == graphemes::LAT_FRICATIVES[0]);
         assert!(voiced_lateral_fricative == graphemes::LAT_FRICATIVES[1]);
     }
+
+    #[test]
+    fn vowel_rows() {
+        assert_eq!(graphemes::CLOSE_VOWELS, ["i", "y", "ɨ", "ʉ", "ɯ", "u"]);
+        assert_eq!(graphemes::MID_VOWELS, ["ə"]);
+        let schwa = ipa::Vowel::new(
+            ipa::Height::Mid,
+            ipa::Backness::Central,
+            ipa::Roundedness::Unrounded,
+        );
+        assert!(!schwa.is_rounded());
+    }
+
+    #[test]
+    fn non_pulmonic() {
+        assert_eq!(graphemes::ejective("k"), "kʼ");
+        assert_eq!(graphemes::ejective("t"), graphemes::EJECTIVES[1]);
+        assert_eq!(ipa::CLICKS[0].articulation(), ipa::Articulation::Bilabial);
+        assert!(!ipa::IMPLOSIVES[3].airstream().is_pulmonic());
+    }
+
+    #[test]
+    fn attach_diacritics() {
+        use diacritics::{attach, Diacritic, Diacritics};
+        let voiceless = Diacritics::new().with(Diacritic::Voiceless);
+        assert_eq!(attach("n", voiceless), "n̥");
+        assert_eq!(attach("ŋ", voiceless), "ŋ̊");
+        let set: Diacritics = [Diacritic::Aspirated, Diacritic::Dental]
+            .into_iter()
+            .collect();
+        assert_eq!(attach("t", set), "t̪ʰ");
+        assert_eq!(Diacritic::from_char('\u{30A}'), Some(Diacritic::Voiceless));
+    }
+
+    #[test]
+    fn suprasegmental_symbols() {
+        use suprasegmentals::*;
+        assert_eq!(
+            Suprasegmental::from_char('ˈ'),
+            Some(Suprasegmental::Stress(Stress::Primary))
+        );
+        assert_eq!(Suprasegmental::Length(Length::Long).to_string(), "ː");
+        assert!(Suprasegmental::from_char('a').is_none());
+    }
+
+    #[test]
+    fn tone_styles() {
+        use tone::{Contour, Level, Style};
+        let dipping = Contour::parse("214").unwrap();
+        assert_eq!(dipping.letters(), "˨˩˦");
+        assert_eq!(Contour::parse("˨˩˦"), Some(dipping));
+        let rising = Contour::new(&[Level::ExtraLow, Level::ExtraHigh]).unwrap();
+        assert!(rising.is_rising());
+        assert_eq!(rising.render("a", Style::Diacritic), "a\u{30C}");
+        assert_eq!(dipping.render("ma", Style::Diacritic), "ma˨˩˦");
+        let (base, high) = Contour::split("ma\u{301}");
+        assert_eq!(base, "ma");
+        assert_eq!(high, Some(Contour::level(Level::High)));
+    }
+
+    #[test]
+    fn display_segments() {
+        use diacritics::Diacritic;
+        use ipa::*;
+        let lateral = ipa::PULMONIC
+            .iter()
+            .find(|(g, _)| *g == graphemes::LAT_FRICATIVES[1])
+            .map(|(_, c)| *c)
+            .unwrap();
+        assert_eq!(Segment::from(lateral).to_string(), "ɮ");
+        let k = Consonant::new(
+            Articulation::Velar.into(),
+            Manner::Plosive,
+            Phonation::Voiceless,
+        );
+        assert_eq!(k.ejective().to_string(), "kʼ");
+        assert_eq!(k.with_diacritic(Diacritic::Aspirated).to_string(), "kʰ");
+        let glottal_voiced = Consonant::new(
+            Articulation::Glottal.into(),
+            Manner::Plosive,
+            Phonation::Voiced,
+        );
+        assert_eq!(glottal_voiced.to_string(), "ʔ̬");
+        let high = Vowel::new(Height::Close, Backness::Front, Roundedness::Unrounded);
+        assert_eq!(Segment::Vowel(high).to_string(), "i");
+    }
+
+    #[test]
+    fn forward_lookup() {
+        use ipa::*;
+        assert_eq!(
+            grapheme_for(
+                Articulation::Velar.into(),
+                Manner::Fricative { sibilant: false },
+                Phonation::Voiced
+            ),
+            Some("ɣ")
+        );
+        assert_eq!(
+            grapheme_for(
+                Articulation::Pharyngeal.into(),
+                Manner::Plosive,
+                Phonation::Voiced
+            ),
+            None
+        );
+        assert_eq!(
+            vowel_grapheme_for(Height::CloseMid, Backness::Front, Roundedness::Rounded),
+            Some("ø")
+        );
+    }
+
+    #[test]
+    fn reverse_lookup() {
+        use diacritics::Diacritic;
+        use ipa::*;
+        let voiced_velar_fricative = describe("ɣ").unwrap();
+        assert_eq!(voiced_velar_fricative.place(), Some(Place::Dorsal));
+        assert_eq!(
+            voiced_velar_fricative.articulation(),
+            Some(Articulation::Velar)
+        );
+        assert_eq!(voiced_velar_fricative.phonation(), Phonation::Voiced);
+        assert_eq!(
+            describe("n̪").and_then(|d| d.articulation()),
+            Some(Articulation::Dental)
+        );
+        assert_eq!(describe("d̥").unwrap().phonation(), Phonation::Voiceless);
+        let aspirated = describe("tʰ").unwrap();
+        assert!(aspirated.diacritics().contains(Diacritic::Aspirated));
+        assert!(matches!(
+            describe("kʼ"),
+            Some(Description::Consonant(c)) if c.airstream() == Airstream::GlottalicEgressive
+        ));
+        assert!(describe("ʘ").is_some());
+        assert!(describe("Q").is_none());
+    }
+
+    #[test]
+    fn tokenize_clusters() {
+        use parser::tokenize;
+        for nasal in graphemes::NASALS {
+            assert_eq!(tokenize(nasal), [nasal]);
+        }
+        assert_eq!(tokenize("t͡ʃʰaːˈn̪a˨˩˦"), ["t͡ʃʰ", "aː", "ˈ", "n̪", "a", "˨˩˦"]);
+        assert_eq!(tokenize("kʷʼ ɛ"), ["kʷʼ", "ɛ"]);
+    }
+
+    #[test]
+    fn parse_segments() {
+        use ipa::*;
+        use segment::ParseSegmentError;
+        let lateral: Consonant = "ɮ".parse().unwrap();
+        assert_eq!(lateral.manner(), Manner::LatFric);
+        assert_eq!(lateral.articulation(), Articulation::Alveolar);
+        assert_eq!(lateral.phonation(), Phonation::Voiced);
+        assert!(matches!("ɮ".parse::<Segment>(), Ok(Segment::Consonant(c)) if c == lateral));
+        assert!(matches!(
+            Segment::try_from("ˈ"),
+            Ok(Segment::Suprasegmental(_))
+        ));
+        assert_eq!(
+            "a".parse::<Consonant>(),
+            Err(ParseSegmentError::NotConsonant("a".into()))
+        );
+        assert_eq!(
+            "ʬ".parse::<Segment>(),
+            Err(ParseSegmentError::Invalid(Error::UnknownSymbol {
+                offset: 0,
+                symbol: 'ʬ'
+            }))
+        );
+        assert!(Vowel::try_from("œ").is_ok());
+    }
+
+    #[test]
+    fn xsampa_round_trip() {
+        assert_eq!(xsampa::to_ipa("S").unwrap(), "ʃ");
+        assert_eq!(xsampa::to_ipa("t_h").unwrap(), "tʰ");
+        assert_eq!(xsampa::to_ipa("\"t_SE:r\\`").unwrap(), "ˈt͡ʃɛːɻ");
+        assert_eq!(xsampa::from_ipa("ʃ").unwrap(), "S");
+        assert_eq!(xsampa::from_ipa("ˈt͡ʃɛːɻ").unwrap(), "\"t_SE:r\\`");
+        for (x, ipa) in xsampa::TABLE {
+            assert_eq!(xsampa::to_ipa(x).unwrap(), *ipa);
+            let back = xsampa::from_ipa(ipa).unwrap();
+            assert_eq!(xsampa::to_ipa(&back).unwrap(), *ipa);
+        }
+        for ipa in ["ǀǀ", "ɹ˞", "ə˞l\u{334}"] {
+            let back = xsampa::from_ipa(ipa).unwrap();
+            assert_eq!(xsampa::to_ipa(&back).unwrap(), ipa);
+            assert!(parser::parse(&xsampa::to_ipa(&back).unwrap()).is_ok());
+        }
+        assert_eq!(xsampa::from_ipa("ǀǀ").unwrap(), "|\\-|\\");
+        assert_eq!(xsampa::from_ipa("ɹ˞").unwrap(), "r\\-`");
+        assert!(parser::parse(&xsampa::to_ipa("@`5").unwrap()).is_ok());
+        let err = xsampa::from_ipa("aʬ").unwrap_err();
+        assert_eq!(err.offset, 1);
+    }
+
+    #[test]
+    fn kirshenbaum_segments() {
+        assert_eq!(kirshenbaum::to_ipa("S@'nEl").unwrap(), "ʃəˈnɛl");
+        let segments = kirshenbaum::to_segments("n^a<o>").unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].to_string(), "ɲ");
+        assert_eq!(kirshenbaum::from_segments(&segments).unwrap(), "n^a<o>");
+        let affricate = parser::parse("t\u{361}\u{283}a").unwrap();
+        let written = kirshenbaum::from_segments(&affricate).unwrap();
+        assert_eq!(written, "tSa");
+        assert_eq!(
+            kirshenbaum::to_segments("Sa%").unwrap_err().offset(),
+            Some(2)
+        );
+        let read = kirshenbaum::to_segments(&written).unwrap();
+        assert_eq!(kirshenbaum::from_segments(&read).unwrap(), written);
+        assert_eq!(
+            notation::convert(
+                "t_S@",
+                notation::Notation::XSampa,
+                notation::Notation::Kirshenbaum
+            )
+            .unwrap(),
+            "tS@"
+        );
+    }
+
+    #[test]
+    fn arpabet_phones() {
+        assert_eq!(arpabet::to_ipa("HH AH0 L OW1").unwrap(), "həlˈoʊ");
+        let segments = arpabet::to_segments("CH ER1 CH").unwrap();
+        assert_eq!(arpabet::from_segments(&segments).unwrap(), "CH ER1 CH");
+        assert!(arpabet::to_segments("XX").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_schema() {
+        let segment: Segment = "ɣ".parse().unwrap();
+        let json = serde_json::to_value(segment).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "consonant",
+                "place": "dorsal",
+                "articulation": "velar",
+                "manner": "fricative",
+                "voicing": "voiced",
+                "airstream": "pulmonic_egressive",
+            })
+        );
+        assert_eq!(serde_json::from_value::<Segment>(json).unwrap(), segment);
+        let tone: Segment = "˨˩˦".parse().unwrap();
+        let json = serde_json::to_string(&tone).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"tone","levels":["low","extra_low","high"]}"#
+        );
+    }
+
+    #[test]
+    fn distinctive_features() {
+        use features::{Feature, FeatureBundle};
+        let features = |s: &str| s.parse::<Segment>().unwrap().features().unwrap();
+        let (p, b, s) = (features("p"), features("b"), features("s"));
+        assert!(p.is(Feature::Labial, true));
+        assert_eq!(p.differences(&b).collect::<Vec<_>>(), [Feature::Voice]);
+        assert!(s.is(Feature::Strident, true) && s.is(Feature::Anterior, true));
+        let voiced_obstruent = FeatureBundle::new()
+            .with(Feature::Voice, true)
+            .with(Feature::Sonorant, false);
+        assert!(voiced_obstruent.subsumes(&b));
+        assert!(!voiced_obstruent.subsumes(&p));
+        assert!(voiced_obstruent.unify(&p).is_none());
+        assert_eq!(voiced_obstruent.to_string(), "[-sonorant, +voice]");
+        assert!(features("i").is(Feature::High, true));
+    }
+
+    #[test]
+    fn natural_classes() {
+        use classes::*;
+        let render = |class: Class| -> Vec<String> {
+            Segments::all()
+                .filter(class)
+                .map(|s| s.to_string())
+                .collect()
+        };
+        assert_eq!(
+            render(voiceless() & plosive() & coronal()),
+            ["t̼", "t̪", "t", "ʈ", "ǀ", "ǃ", "ǁ"]
+        );
+        assert_eq!(render(sibilant() & voiced()), ["z", "ʒ", "ʐ", "ʑ"]);
+        assert!(render(vowel() & !voiced()).is_empty());
+    }
+
+    #[test]
+    fn sound_change_rules() {
+        use classes::vowel;
+        use features::{Feature, FeatureBundle};
+        use rules::{Pattern, Rule};
+        let word = parser::parse("atakad").unwrap();
+        let render = |w: &[Segment]| w.iter().map(|s| s.to_string()).collect::<String>();
+        let voicing = Rule::new(
+            FeatureBundle::new().with(Feature::Sonorant, false),
+            FeatureBundle::new().with(Feature::Voice, true),
+        )
+        .with_environment([vowel().into()], [vowel().into()]);
+        assert_eq!(render(&voicing.apply(&word)), "adaɡad");
+        let final_devoicing = Rule::new(
+            FeatureBundle::new().with(Feature::Sonorant, false),
+            FeatureBundle::new().with(Feature::Voice, false),
+        )
+        .with_environment([], [Pattern::Boundary]);
+        assert_eq!(render(&final_devoicing.apply(&word)), "atakat");
+        let t: Segment = "t".parse().unwrap();
+        let s: Segment = "s".parse().unwrap();
+        let initial = Rule::new(t, s).with_environment([Pattern::Boundary], []);
+        assert_eq!(
+            render(&initial.apply(&parser::parse("tat").unwrap())),
+            "sat"
+        );
+    }
+
+    #[test]
+    fn syllabification() {
+        use syllable::{syllabify, syllabify_with, Phonotactics, Sonority};
+        let syllables = |s: &str| {
+            syllabify(&parser::parse(s).unwrap())
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .join(".")
+        };
+        assert_eq!(syllables("kamta"), "kam.ta");
+        assert_eq!(syllables("aplo"), "a.plo");
+        assert_eq!(syllables("asplat"), "as.plat");
+        assert_eq!(syllables("ai̯ta"), "ai̯.ta");
+        assert_eq!(syllables("pn̩"), "pn̩");
+        assert_eq!(syllables("a.pla"), "a.pla");
+        assert_eq!(syllables("ap.la"), "ap.la");
+
+        struct SClusters;
+        impl Phonotactics for SClusters {
+            fn is_onset(&self, cluster: &[Segment]) -> bool {
+                let s: Segment = "s".parse().unwrap();
+                match cluster.split_first() {
+                    Some((first, rest)) if *first == s => Sonority.is_onset(rest),
+                    _ => Sonority.is_onset(cluster),
+                }
+            }
+        }
+        let word = parser::parse("asplat").unwrap();
+        let split: Vec<_> = syllabify_with(&word, &SClusters)
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(split, ["a", "splat"]);
+        let first = &syllabify(&word)[0];
+        assert!(!first.is_open());
+        assert_eq!(first.nucleus.len(), 1);
+    }
+
+    #[test]
+    fn phonotactic_validation() {
+        use classes::{lateral, obstruent, sonorant};
+        use phonotactics::{Phonotactics, Template, ViolationKind};
+        use rules::Pattern;
+        let ŋ: Segment = "ŋ".parse().unwrap();
+        let p = Phonotactics::new()
+            .with_template(Template::parse("(C)(C)V(C)(C)").unwrap())
+            .with_onset([obstruent().into(), lateral().into()])
+            .with_coda([sonorant().into(), obstruent().into()])
+            .with_banned([ŋ.into(), Pattern::Boundary]);
+        let check = |s: &str| p.validate(&parser::parse(s).unwrap());
+        assert_eq!(check("plan"), Ok(()));
+        assert_eq!(check("ˈplan.ta"), Ok(()));
+        assert_eq!(check("ˈtaplant"), Ok(()));
+        let err = check("ˈpran").unwrap_err();
+        assert_eq!((err.position, err.kind), (1, ViolationKind::Onset));
+        let err = check("taŋ").unwrap_err();
+        assert_eq!((err.position, err.kind), (2, ViolationKind::Banned(0)));
+        let kinds: Vec<_> = p
+            .violations(&parser::parse("ˈtaplants").unwrap())
+            .iter()
+            .map(|v| (v.position, v.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [(3, ViolationKind::Template), (6, ViolationKind::Coda)]
+        );
+        let cv = Phonotactics::new().with_template(Template::parse("CV").unwrap());
+        assert_eq!(cv.validate(&parser::parse("tata").unwrap()), Ok(()));
+        let err = cv.validate(&parser::parse("tatan").unwrap()).unwrap_err();
+        assert_eq!((err.position, err.kind), (2, ViolationKind::Template));
+        assert_eq!(Template::parse("(CV"), None);
+    }
+
+    #[test]
+    fn inventories() {
+        use features::{Feature, FeatureBundle};
+        use inventory::Inventory;
+        let parse = |s: &str| -> Inventory { parser::parse(s).unwrap().into_iter().collect() };
+        let render = |i: &Inventory| i.iter().map(|s| s.to_string()).collect::<String>();
+        let swahili = parse("aɛiɔumnɲŋptkbdɡfvszʃhlrj");
+        assert_eq!(render(&swahili), "pbmfvtdnrszlʃɲjkɡŋhiuɛɔa");
+        let mut hawaiian = parse("aeioupkʔmnhl");
+        assert!(!hawaiian.insert_grapheme("p").unwrap());
+        assert_eq!(
+            hawaiian.insert_features(
+                &FeatureBundle::new()
+                    .with(Feature::Consonantal, true)
+                    .with(Feature::Continuant, false)
+                    .with(Feature::Nasal, false)
+                    .with(Feature::Voice, false)
+                    .with(Feature::Anterior, true)
+                    .with(Feature::Coronal, true)
+                    .with(Feature::Lateral, false)
+            ),
+            Some("t".parse().unwrap())
+        );
+        assert_eq!(render(&swahili.intersection(&hawaiian)), "pmtnlkhiua");
+        assert_eq!(render(&hawaiian.difference(&swahili)), "ʔeo");
+        assert_eq!(swahili.union(&hawaiian).len(), swahili.len() + 3);
+        assert!(hawaiian.intersection(&swahili).is_subset(&swahili));
+        assert_eq!(render(&parse("a\u{303} a")), "aa\u{303}");
+    }
+
+    #[cfg(feature = "phoible")]
+    #[test]
+    fn phoible_inventories() {
+        use inventory::Inventory;
+        let swahili = Inventory::from_phoible("swa").unwrap();
+        let implosives: Vec<_> = swahili
+            .consonants()
+            .filter(|s| {
+                s.as_consonant()
+                    .is_some_and(|c| !c.airstream().is_pulmonic())
+            })
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(implosives, ["ɓ", "ɗ", "ʄ", "ɠ"]);
+        assert_eq!(swahili.vowels().count(), 5);
+        assert!(phoible::phonemes("swa").unwrap().any(|p| p == "tʃ"));
+        assert!(phoible::languages().any(|(code, name)| code == "zul" && name == "Zulu"));
+        assert_eq!(Inventory::from_phoible("xxx"), None);
+    }
+
+    #[test]
+    fn segment_distance() {
+        use features::{Feature, Weights};
+        let s = |g: &str| g.parse::<Segment>().unwrap();
+        assert_eq!(s("p").distance(&s("p")), 0.0);
+        assert_eq!(s("p").distance(&s("b")), 1.0);
+        assert!(s("p").distance(&s("b")) < s("p").distance(&s("ŋ")));
+        assert!(s("i").distance(&s("e")) < s("i").distance(&s("a")));
+        let voice_heavy = Weights::default().with(Feature::Voice, 5.0);
+        assert_eq!(s("p").distance_with(&s("b"), &voice_heavy), 5.0);
+        let stress = s("ˈ");
+        assert_eq!(stress.distance(&stress), 0.0);
+        assert_eq!(stress.distance(&s("p")), Weights::default().total());
+    }
+
+    #[test]
+    fn phonetic_alignment() {
+        use align::align;
+        let word = |s: &str| parser::parse(s).unwrap();
+        let show = |pairs: &[(Option<Segment>, Option<Segment>)]| {
+            pairs
+                .iter()
+                .map(|(a, b)| {
+                    let side = |s: &Option<Segment>| s.map_or("-".to_string(), |s| s.to_string());
+                    format!("{}{}", side(a), side(b))
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let alignment = align(&word("pater"), &word("fadar"));
+        assert_eq!(show(&alignment.pairs), "pf aa td ea rr");
+        assert_eq!(alignment.gaps(), 0);
+        let alignment = align(&word("kat"), &word("kast"));
+        assert_eq!(show(&alignment.pairs), "kk aa -s tt");
+        assert_eq!(alignment.cost, 3.0);
+        assert_eq!(alignment.matches().count(), 3);
+        assert_eq!(align(&[], &word("ab")).gaps(), 2);
+        let costs = align::Costs {
+            insertion: 1.0,
+            ..align::Costs::default()
+        };
+        assert_eq!(
+            align::align_with(&word("kast"), &word("kat"), &costs).cost,
+            3.0
+        );
+        assert_eq!(
+            align::align_with(&word("kat"), &word("kast"), &costs).cost,
+            1.0
+        );
+    }
+
+    #[test]
+    fn notation_conversion() {
+        use notation::{convert, Notation};
+        let xsampa: Notation = "xsampa".parse().unwrap();
+        assert_eq!(convert("tS", xsampa, Notation::Ipa).unwrap(), "tʃ");
+        assert_eq!(
+            convert("HH AH0 L OW1", Notation::Arpabet, xsampa).unwrap(),
+            "h@l\"oU"
+        );
+        assert!("morse".parse::<Notation>().is_err());
+    }
+
+    #[test]
+    fn consonant_chart() {
+        use chart::Chart;
+        let chart = Chart::consonants();
+        assert_eq!(chart.rows()[0], "plosive");
+        let velar = chart.columns().iter().position(|c| *c == "velar").unwrap();
+        assert_eq!(chart.cell(0, velar), [Some("k"), Some("ɡ")]);
+        let palatal = chart
+            .columns()
+            .iter()
+            .position(|c| *c == "palatal")
+            .unwrap();
+        assert_eq!(chart.cell(4, palatal), [Some("ç"), Some("ʝ")]);
+        let text = chart.render_ascii();
+        let fricatives = text.lines().find(|l| l.starts_with("fricative")).unwrap();
+        assert!(fricatives.contains("| s      z |"));
+        assert!(text.lines().all(|l| !l.ends_with(' ')));
+    }
+
+    #[test]
+    fn html_charts() {
+        use chart::Chart;
+        let vowels = Chart::vowels();
+        assert_eq!(vowels.cell(0, 0), [Some("i"), Some("y")]);
+        assert_eq!(vowels.cell(3, 2), [Some("ə"), None]);
+        let html = vowels.render_html();
+        assert!(html.starts_with("<table class=\"ipa-chart ipa-vowels\">"));
+        assert!(html.contains(
+            "<td class=\"close front\"><span class=\"unrounded\">i</span><span class=\"rounded\">y</span></td>"
+        ));
+        assert!(html.contains("<td class=\"close near-front empty\"></td>"));
+        let html = Chart::consonants().render_html();
+        assert!(html.contains("<th scope=\"row\" class=\"tap-or-flap\">tap or flap</th>"));
+        assert!(html.contains("<span class=\"voiced\">ɡ</span>"));
+    }
+
+    #[test]
+    fn tipa_output() {
+        assert_eq!(latex::from_ipa("ʃ").unwrap(), r"\textesh");
+        assert_eq!(latex::from_ipa("ʃɪp").unwrap(), r"\textesh\textsci{}p");
+        assert_eq!(
+            latex::from_ipa("ˈʃɪp ʃɪps").unwrap(),
+            r"\textprimstress\textesh\textsci{}p \textesh\textsci{}ps"
+        );
+        assert_eq!(latex::from_ipa("n̥a").unwrap(), r"\textsubring{n}a");
+        assert_eq!(
+            latex::from_ipa("t͡ʃʰ").unwrap(),
+            r"\t{t\textesh}\textsuperscript{h}"
+        );
+        assert_eq!(latex::from_ipa("ma˨˩˦").unwrap(), r"ma\tone{214}");
+        assert_eq!(latex::from_ipa("ɲ̊").unwrap(), r"\r{\textltailn}");
+        assert_eq!(latex::from_ipa("at\u{31A}").unwrap(), r"at\textcorner");
+        let word = parser::parse("ðə").unwrap();
+        assert_eq!(latex::from_segments(&word).unwrap(), r"\dh\textschwa");
+        let err = latex::from_ipa("a☃").unwrap_err();
+        assert_eq!((err.offset, err.symbol), (1, '☃'));
+    }
+
+    #[test]
+    fn vowel_formants() {
+        use formants::Speaker;
+        let v = |s: &str| s.parse::<ipa::Vowel>().unwrap();
+        let i = v("i").formants();
+        assert_eq!((i.f1, i.f2, i.f3), (280.0, 2250.0, 2900.0));
+        assert!(v("u").formants().f2 < v("ɯ").formants().f2);
+        assert!(v("a").formants().f1 > v("e").formants().f1);
+        let lax = v("ɪ").formants();
+        assert!(lax.f2 < i.f2 && lax.f2 > v("ɨ").formants().f2);
+        let child = v("i").formants_for(Speaker::Child);
+        assert!(child.f1 > i.f1 && child.f2 > i.f2);
+        assert_eq!(v("i").formants_for(Speaker::default()), i);
+    }
+
+    #[test]
+    fn vowel_plot() {
+        use formants::Formants;
+        use plot::VowelPlot;
+        let v = |s: &str| s.parse::<ipa::Vowel>().unwrap();
+        let svg = VowelPlot::new()
+            .vowels([v("i"), v("y"), v("a"), v("u")])
+            .point(
+                "<x>",
+                Formants {
+                    f1: 400.0,
+                    f2: 1500.0,
+                    f3: 2500.0,
+                },
+            )
+            .render_svg();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<circle").count(), 5);
+        assert!(svg.contains(r#"<polygon class="quadrilateral" points="30.0,30.0 430.0,30.0 430.0,330.0 230.0,330.0""#));
+        // i at the top-left corner, y just to its right, u at the
+        // top-right corner, a at the open front corner
+        assert!(svg.contains(r#"<circle class="vowel" cx="30.0" cy="30.0" r="3"/>"#));
+        assert!(svg.contains(r#"<circle class="vowel" cx="37.0" cy="30.0" r="3"/>"#));
+        assert!(svg.contains(r#"<circle class="vowel" cx="430.0" cy="30.0" r="3"/>"#));
+        assert!(svg.contains(r#"<circle class="vowel" cx="230.0" cy="330.0" r="3"/>"#));
+        assert!(svg.contains(">&lt;x&gt;</text>"));
+    }
+
+    #[test]
+    fn segment_names() {
+        let name = |s: &str| s.parse::<Segment>().unwrap().name();
+        assert_eq!(name("ɬ"), "voiceless alveolar lateral fricative");
+        assert_eq!(name("ɕ"), "voiceless alveolo-palatal fricative");
+        assert_eq!(name("ʔ"), "glottal plosive");
+        assert_eq!(name("ǁ"), "alveolar lateral click");
+        assert_eq!(name("ɪ"), "near-close near-front unrounded vowel");
+        assert_eq!(name("tʰ"), "aspirated voiceless alveolar plosive");
+        assert_eq!(name("kʼ"), "velar ejective");
+        assert_eq!(name("sʼ"), "alveolar ejective fricative");
+        assert_eq!(name("a\u{303}"), "nasalized open front unrounded vowel");
+        assert_eq!(name("dⁿ"), "voiced alveolar plosive with nasal release");
+        assert_eq!(name("ː"), "long");
+        assert_eq!(name("˨˩˦"), "tone 214");
+        assert_eq!(name("˩˥"), "rising tone");
+        assert_eq!(name("˥"), "extra high tone");
+        // every table symbol has a name of its own
+        for (grapheme, expected) in names::NAMES {
+            assert_eq!(&name(grapheme), expected, "{}", grapheme);
+        }
+
+        let lateral: Vec<String> = names::containing("Lateral")
+            .iter()
+            .map(Segment::to_string)
+            .collect();
+        assert_eq!(
+            lateral,
+            ["ɬ", "ɮ", "l̥", "l", "ɭ", "ʎ̥", "ʎ", "ʟ", "ɺ", "t͡ɬ", "d͡ɮ", "ǁ"]
+        );
+    }
+
+    #[test]
+    fn segments_from_names() {
+        let find = |name: &str| Segment::from_name(name).map(|s| s.to_string());
+        assert_eq!(find("voiced velar nasal").as_deref(), Some("ŋ"));
+        assert_eq!(find("Nasal velar voiced").as_deref(), Some("ŋ"));
+        assert_eq!(find("voiceless alveolar stop").as_deref(), Some("t"));
+        assert_eq!(find("voiced alveolar flap").as_deref(), Some("ɾ"));
+        assert_eq!(find("retroflex tap").as_deref(), Some("ɽ"));
+        assert_eq!(find("unvoiced alveolar sibilant").as_deref(), Some("s"));
+        assert_eq!(find("close-mid front rounded").as_deref(), Some("ø"));
+        assert_eq!(find("velar ejective stop").as_deref(), Some("kʼ"));
+        assert_eq!(find("alveolar ejective").as_deref(), Some("tʼ"));
+        assert_eq!(find("alveolar ejective fricative").as_deref(), Some("sʼ"));
+        assert_eq!(find("glottal stop").as_deref(), Some("ʔ"));
+        assert_eq!(find("primary stress").as_deref(), Some("ˈ"));
+        assert_eq!(find("high tone").as_deref(), Some("˦"));
+        assert_eq!(find("voiced glottal stop"), None);
+        for (grapheme, name) in names::NAMES {
+            assert_eq!(find(name).as_deref(), Some(*grapheme));
+        }
+    }
+
+    #[test]
+    fn secondary_articulation() {
+        use ipa::SecondaryArticulation;
+        let c = |s: &str| s.parse::<ipa::Consonant>().unwrap();
+        assert_eq!(c("kʷ").secondary(), Some(SecondaryArticulation::Labialized));
+        assert!(c("kʷ").diacritics().is_empty());
+        assert_eq!(
+            c("tʲ").secondary(),
+            Some(SecondaryArticulation::Palatalized)
+        );
+        assert_eq!(c("l\u{334}").secondary(), None);
+        assert_eq!(c("lˠ").secondary(), Some(SecondaryArticulation::Velarized));
+        assert_eq!(
+            c("sˤ").secondary(),
+            Some(SecondaryArticulation::Pharyngealized)
+        );
+        assert_eq!(
+            c("mˀ").secondary(),
+            Some(SecondaryArticulation::Glottalized)
+        );
+        for s in ["kʷ", "tʲ", "lˠ", "sˤ", "mˀ", "kʷʼ", "tʰʷ", "n̪ʲ"] {
+            assert_eq!(c(s).to_string(), s);
+        }
+        assert_eq!(
+            c("kʷʼ"),
+            c("kʼ").with_secondary(Some(SecondaryArticulation::Labialized))
+        );
+        // the diacritic and the field are the same thing on consonants
+        let k = c("k");
+        assert_eq!(k.with_diacritic(diacritics::Diacritic::Labialized), c("kʷ"));
+        // vowels keep the diacritic
+        let a = "aˤ".parse::<ipa::Vowel>().unwrap();
+        assert!(a
+            .diacritics()
+            .contains(diacritics::Diacritic::Pharyngealized));
+        assert!("aˀ".parse::<Segment>().is_err());
+
+        let s = |s: &str| s.parse::<Segment>().unwrap();
+        assert_eq!(
+            s("sˤ").name(),
+            "pharyngealized voiceless alveolar fricative"
+        );
+        let voice = features::FeatureBundle::new().with(features::Feature::Voice, true);
+        let unround = features::FeatureBundle::new().with(features::Feature::Round, false);
+        assert_eq!(features::change(&s("kʷ"), &voice), Some(s("ɡʷ")));
+        assert_eq!(features::change(&s("kʷ"), &unround), Some(s("k")));
+        assert_eq!(features::change(&s("tʲ"), &voice), Some(s("dʲ")));
+        assert!(s("tʲ")
+            .features()
+            .unwrap()
+            .is(features::Feature::High, true));
+    }
+
+    #[test]
+    fn affricates() {
+        use affricate::Affricate;
+        let tied: Affricate = "t͡ʃ".parse().unwrap();
+        assert_eq!(tied.to_string(), "t\u{361}ʃ");
+        assert_eq!("tʃ".parse::<Affricate>(), Ok(tied));
+        assert_eq!("t\u{35C}ʃ".parse::<Affricate>(), Ok(tied));
+        assert_eq!(tied.stop().to_string(), "t");
+        assert_eq!(tied.fricative().to_string(), "ʃ");
+        assert!("ʃt".parse::<Affricate>().is_err());
+        assert!("ta".parse::<Affricate>().is_err());
+        for g in graphemes::AFFRICATES {
+            assert_eq!(g.parse::<Affricate>().unwrap().to_string(), g);
+        }
+        // aspiration belongs to the release
+        assert_eq!("tʰʃ".parse::<Affricate>().unwrap().to_string(), "t͡ʃʰ");
+
+        let s = |s: &str| s.parse::<Segment>().unwrap();
+        assert_eq!(s("tʃ"), Segment::Affricate(tied));
+        assert_eq!(s("t͡sʼ").name(), "alveolar ejective affricate");
+        assert_eq!(s("d͡ʒ").name(), "voiced postalveolar affricate");
+        assert_eq!(
+            Segment::from_name("voiceless alveolar affricate"),
+            Some(s("ts"))
+        );
+        assert_eq!(
+            parser::parse("t͡ʃip tʃ").unwrap(),
+            [s("t͡ʃ"), s("i"), s("p"), s("t"), s("ʃ")]
+        );
+        assert!("ta".parse::<Segment>().is_err());
+
+        let f = s("t͡ʃ").features().unwrap();
+        assert!(f.is(features::Feature::DelayedRelease, true));
+        assert!(f.is(features::Feature::Continuant, false));
+        assert!(f.is(features::Feature::Anterior, false));
+        let voice = features::FeatureBundle::new().with(features::Feature::Voice, true);
+        let continuant = features::FeatureBundle::new().with(features::Feature::Continuant, true);
+        assert_eq!(features::change(&s("t͡ʃ"), &voice), Some(s("d͡ʒ")));
+        assert_eq!(features::change(&s("t͡ʃ"), &continuant), Some(s("ʃ")));
+
+        assert_eq!(arpabet::to_ipa("CH IY1 Z").unwrap(), "t͡ʃˈiz");
+        let untied = parser::parse("tʃiz").unwrap();
+        assert_eq!(arpabet::from_segments(&untied).unwrap(), "CH IY0 Z");
+    }
+
+    #[test]
+    fn double_articulation() {
+        use crate::features::Feature;
+        use crate::ipa::{Articulation, Manner, Phonation, Place};
+
+        let kp: Segment = "k͡p".parse().unwrap();
+        let c = *kp.as_consonant().unwrap();
+        assert_eq!(c.articulation(), Articulation::Bilabial);
+        assert_eq!(c.coarticulation(), Some(Articulation::Velar));
+        assert_eq!(c.manner(), Manner::Plosive);
+        assert_eq!(kp.to_string(), "k͡p");
+        assert_eq!("k͜p".parse::<Segment>(), Ok(kp));
+        assert_eq!(kp.name(), "voiceless labial-velar plosive");
+        assert_eq!(Segment::from_name("unvoiced labial-velar stop"), Some(kp));
+        let features = kp.features().unwrap();
+        assert!(features.is(Feature::Labial, true));
+        assert!(features.is(Feature::Dorsal, true));
+        assert!(features.is(Feature::Round, false));
+
+        let gb: Segment = "ɡ͡b".parse().unwrap();
+        assert_eq!(gb.as_consonant().unwrap().phonation(), Phonation::Voiced);
+        assert_eq!("ŋ͡m".parse::<Segment>().unwrap().to_string(), "ŋ͡m");
+        assert_eq!("ɠ͡ɓ".parse::<Segment>().unwrap().to_string(), "ɠ͡ɓ");
+        assert_eq!("k͡pʼ".parse::<Segment>().unwrap().to_string(), "k͡pʼ");
+        assert!("k͡a".parse::<Segment>().is_err());
+        assert!("p͡p".parse::<Segment>().is_err());
+
+        let w: Segment = "w".parse().unwrap();
+        assert_eq!(w.as_consonant().unwrap().place(), Place::Labial);
+        assert_eq!(
+            w.as_consonant().unwrap().coarticulation(),
+            Some(Articulation::Velar)
+        );
+        assert_eq!(w.name(), "voiced labial-velar approximant");
+        assert!(w.features().unwrap().is(Feature::Round, true));
+        assert_eq!("wʰ".parse::<Segment>().unwrap().to_string(), "wʰ");
+        for symbol in ["ʍ", "ɥ", "ɧ"] {
+            assert_eq!(symbol.parse::<Segment>().unwrap().to_string(), symbol);
+        }
+        assert_eq!(
+            "ɥ".parse::<Segment>().unwrap().name(),
+            "voiced labial-palatal approximant"
+        );
+        assert!("t͡ʃ".parse::<Segment>().unwrap().as_affricate().is_some());
+    }
+
+    #[test]
+    fn phonation_types() {
+        use crate::features::{Feature, FeatureBundle};
+        use crate::ipa::Phonation;
+
+        let bh: Segment = "b̤".parse().unwrap();
+        assert_eq!(bh.as_consonant().unwrap().phonation(), Phonation::Breathy);
+        assert!(bh.diacritics().is_empty());
+        assert_eq!("bʱ".parse::<Segment>(), Ok(bh));
+        assert_eq!(bh.to_string(), "b̤");
+        assert_eq!(bh.name(), "breathy-voiced bilabial plosive");
+        assert_eq!(Segment::from_name("murmured bilabial stop"), Some(bh));
+        let features = bh.features().unwrap();
+        assert!(features.is(Feature::Voice, true));
+        assert!(features.is(Feature::SpreadGlottis, true));
+        assert_eq!(
+            features::change(
+                &"b".parse().unwrap(),
+                &FeatureBundle::new().with(Feature::SpreadGlottis, true)
+            ),
+            Some(bh)
+        );
+
+        let creaky: Segment = "ŋ̰".parse().unwrap();
+        assert_eq!(
+            creaky.as_consonant().unwrap().phonation(),
+            Phonation::Creaky
+        );
+        assert!(creaky
+            .features()
+            .unwrap()
+            .is(Feature::ConstrictedGlottis, true));
+        assert_eq!(
+            Segment::from_name("creaky voiced velar nasal"),
+            Some(creaky)
+        );
+        for (symbol, phonation) in [("d͉", Phonation::Slack), ("ɡ͈", Phonation::Stiff)] {
+            let segment: Segment = symbol.parse().unwrap();
+            assert_eq!(segment.as_consonant().unwrap().phonation(), phonation);
+            assert_eq!(segment.to_string(), symbol);
+        }
+        assert_eq!("d̤ʰ".parse::<Segment>().unwrap().to_string(), "d̤ʰ");
+        assert_eq!("ɡ̤".parse::<Segment>().unwrap().to_string(), "ɡ̤");
+        assert_eq!(ipa::describe("a̤").unwrap().phonation(), Phonation::Breathy);
+    }
+
+    #[test]
+    fn extipa_transcription() {
+        use crate::extipa::{self, Pause, Unit};
+
+        let units = extipa::parse("ʩa(ʃ)(..)p͆ʰ↓").unwrap();
+        assert_eq!(units.len(), 5);
+        let Unit::Sound(vpf) = &units[0] else {
+            panic!("expected a sound");
+        };
+        assert_eq!(vpf.segment(), None);
+        assert_eq!(vpf.name(), "velopharyngeal fricative");
+        let Unit::Silent(silent) = &units[2] else {
+            panic!("expected silent articulation");
+        };
+        assert_eq!(silent[0].segment(), "ʃ".parse().ok());
+        assert_eq!(units[3], Unit::Pause(Pause::Medium));
+        let Unit::Sound(p) = &units[4] else {
+            panic!("expected a sound");
+        };
+        assert_eq!(p.segment(), "pʰ".parse().ok());
+        assert_eq!(p.marks(), ['\u{346}', '↓']);
+        assert_eq!(
+            p.name(),
+            "dentolabial aspirated voiceless bilabial plosive with ingressive airflow"
+        );
+        let written: String = units.iter().map(Unit::to_string).collect();
+        assert_eq!(written, "ʩa(ʃ)(..)p͆ʰ↓");
+
+        let strong = extipa::parse("p͈").unwrap();
+        let Unit::Sound(strong) = &strong[0] else {
+            panic!("expected a sound");
+        };
+        assert_eq!(strong.segment(), "p".parse().ok());
+        assert_eq!(extipa::mark_name('\u{34A}'), Some("denasal"));
+        assert!(extipa::parse("()").is_err());
+        assert!(extipa::parse("ʩq̃ʘ?").is_err());
+    }
+
+    #[test]
+    fn voice_quality_spans() {
+        use crate::voqs::{Annotation, Transcription, VoiceQuality};
+
+        let t: Transcription = "{3F haɪ {W ðɛə W} 3F} naʊ".parse().unwrap();
+        assert_eq!(t.segments().len(), 9);
+        assert_eq!(t.annotations().len(), 2);
+        let outer = t.qualities_at(3);
+        assert_eq!(outer[0].quality(), VoiceQuality::Falsetto);
+        assert_eq!(outer[0].degree(), Some(3));
+        assert_eq!(outer[1].quality(), VoiceQuality::Whisper);
+        assert_eq!(outer[1].range(), 3..6);
+        assert!(t.qualities_at(7).is_empty());
+        assert_eq!(t.to_string(), "{3F haɪ {W ðɛə W} 3F} naʊ");
+
+        let harsh: Transcription = "{V‼ a V!!}".parse().unwrap();
+        assert_eq!(harsh.annotations()[0].quality(), VoiceQuality::Ventricular);
+        assert!("{V! a".parse::<Transcription>().is_err());
+        assert!("{W a F}".parse::<Transcription>().is_err());
+        assert!("{4F a 4F}".parse::<Transcription>().is_err());
+        assert!("{W W}".parse::<Transcription>().is_err());
+
+        let mut t = Transcription::new(parser::parse("ɑ").unwrap());
+        assert!(!t.annotate(Annotation::new(VoiceQuality::Creak, 0..2)));
+        assert!(t.annotate(Annotation::new(VoiceQuality::Creak, 0..1)));
+        assert_eq!(t.to_string(), "{C ɑ C}");
+    }
+
+    #[test]
+    fn enum_variant_lists() {
+        use crate::ipa::{
+            Airstream, Articulation, Backness, Height, Manner, Phonation, Place, Roundedness,
+        };
+
+        assert!(Articulation::ALL.windows(2).all(|w| w[0] < w[1]));
+        assert!(Height::ALL.windows(2).all(|w| w[0] < w[1]));
+        assert!(Backness::ALL.windows(2).all(|w| w[0] < w[1]));
+        assert!(Place::ALL.windows(2).all(|w| w[0] < w[1]));
+        assert!(Manner::ALL.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(Roundedness::ALL.len(), 2);
+        assert_eq!(Airstream::ALL[0], Airstream::default());
+        for (_, c) in ipa::PULMONIC {
+            assert!(Articulation::ALL.contains(&c.articulation()));
+            assert!(Manner::ALL.contains(&c.manner()));
+            assert!(Place::ALL.contains(&c.place()));
+            assert!(Phonation::ALL.contains(&c.phonation()));
+        }
+        let mut phonations = Phonation::ALL.to_vec();
+        phonations.sort();
+        phonations.dedup();
+        assert_eq!(phonations.len(), Phonation::ALL.len());
+        for (_, v) in ipa::VOWELS {
+            assert!(Height::ALL.contains(&v.height()));
+            assert!(Backness::ALL.contains(&v.backness()));
+        }
+    }
+
+    #[test]
+    fn every_consonant() {
+        let all: Vec<ipa::Consonant> = ipa::consonants().collect();
+        assert_eq!(all.len(), ipa::PULMONIC.len() + 14);
+        let unique: std::collections::BTreeSet<_> = all.iter().collect();
+        assert_eq!(unique.len(), all.len());
+        for c in all {
+            let segment = Segment::Consonant(c);
+            assert_eq!(segment.to_string().parse::<Segment>(), Ok(segment));
+            assert!(segment.features().is_some());
+        }
+    }
+
+    #[test]
+    fn symbol_map_matches_tables() {
+        let symbols: Vec<_> = ipa::symbols().collect();
+        assert_eq!(ipa::SYMBOLS.len(), symbols.len());
+        for (grapheme, description) in symbols {
+            assert_eq!(
+                ipa::SYMBOLS.get(grapheme),
+                Some(&description),
+                "{}",
+                grapheme
+            );
+        }
+    }
+
+    #[test]
+    fn const_lookups() {
+        use ipa::{Articulation, Description, Manner, Phonation, PoA};
+        const SH: Option<&str> = ipa::grapheme_for(
+            PoA::new(ipa::Place::Corona, Articulation::Postalveolar),
+            Manner::Fricative { sibilant: true },
+            Phonation::Voiceless,
+        );
+        const PHONEMES: [Option<Description>; 3] =
+            [ipa::lookup("ʃ"), ipa::lookup("ɓ"), ipa::lookup("ʃʷ")];
+        assert_eq!(SH, Some("ʃ"));
+        assert_eq!(PHONEMES[0], ipa::describe("ʃ"));
+        assert_eq!(PHONEMES[1], ipa::describe("ɓ"));
+        assert_eq!(PHONEMES[2], None);
+        assert!(ipa::describe("ʃʷ").is_some());
+        const E: Option<&str> = ipa::vowel_grapheme_for(
+            ipa::Height::CloseMid,
+            ipa::Backness::Front,
+            ipa::Roundedness::Unrounded,
+        );
+        assert_eq!(E, Some("e"));
+        const BREATHY: Option<Phonation> = Phonation::from_mark('\u{324}');
+        assert_eq!(BREATHY, Some(Phonation::Breathy));
+        for (g, d) in ipa::symbols() {
+            assert_eq!(ipa::lookup(g), Some(d), "{}", g);
+        }
+    }
+
+    #[test]
+    fn segment_literals() {
+        const CH: Segment = ipa!("t͡ʃʰ");
+        assert_eq!(CH, "t͡ʃʰ".parse().unwrap());
+        assert_eq!(ipa!("k͡p"), "k͡p".parse().unwrap());
+        assert_eq!(ipa!("ˈ"), "ˈ".parse().unwrap());
+        assert_eq!(ipa!("˨˩˦"), "˨˩˦".parse().unwrap());
+        for (g, _) in ipa::symbols() {
+            for marks in ["", "\u{325}", "ʷ", "ʼ", "\u{324}", "\u{32A}ʰ"] {
+                let s = format!("{}{}", g, marks);
+                assert_eq!(Segment::parse_const(&s), s.parse().ok(), "{}", s);
+            }
+        }
+        assert_eq!(Segment::parse_const("t͡ʃ7"), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        use segment::ParseSegmentError;
+        assert_eq!(
+            ipa::try_describe("t7"),
+            Err(Error::UnknownSymbol {
+                offset: 1,
+                symbol: '7'
+            })
+        );
+        assert_eq!(
+            ipa::try_describe("ʰa"),
+            Err(Error::DanglingDiacritic {
+                offset: 0,
+                mark: 'ʰ'
+            })
+        );
+        assert_eq!(
+            ipa::try_describe("ǀʼ"),
+            Err(Error::InvalidCombination {
+                offset: 2,
+                mark: 'ʼ'
+            })
+        );
+        assert_eq!(
+            ipa::try_describe("k\u{361}a"),
+            Err(Error::InvalidCombination {
+                offset: 1,
+                mark: '\u{361}'
+            })
+        );
+        assert_eq!(ipa::try_describe(""), Err(Error::Empty));
+        assert_eq!(ipa::try_describe("ɡ͡b").ok(), ipa::describe("ɡ͡b"));
+        let e = parser::parse("pat t7").unwrap_err();
+        assert_eq!(
+            e,
+            ParseSegmentError::Invalid(Error::UnknownSymbol {
+                offset: 5,
+                symbol: '7'
+            })
+        );
+        assert_eq!(e.to_string(), "unknown IPA symbol `7` at byte 5");
+        assert_eq!(
+            "t͡ʃ\u{350}".parse::<Segment>(),
+            Err(ParseSegmentError::Invalid(Error::UnknownSymbol {
+                offset: 5,
+                symbol: '\u{350}'
+            }))
+        );
+    }
+
+    #[test]
+    fn lenient_parsing() {
+        use parser::Correction;
+        let (segments, corrections) = parser::parse_lenient("gak'!?").unwrap();
+        assert_eq!(segments, parser::parse("ɡakʼǃʔ").unwrap());
+        assert_eq!(
+            corrections[1],
+            Correction {
+                offset: 3,
+                found: '\'',
+                replacement: 'ʼ'
+            }
+        );
+        assert_eq!(corrections.len(), 4);
+        assert!(parser::parse("ga").is_err());
+        // The error is at the `ʬ` as written, not in the corrected text.
+        let e = parser::parse_lenient("gʬ").unwrap_err();
+        assert_eq!(e.offset(), Some(1));
+        // `’` is corrected to the narrower `ʼ`
+        let e = parser::parse_lenient("k\u{2019}\u{2AC}").unwrap_err();
+        assert_eq!(e.offset(), Some(4));
+    }
+
+    #[test]
+    fn unicode_normalization() {
+        use crate::normalize::normalize_ipa;
+        use crate::tone::Contour;
+        assert_eq!(ipa::describe("\u{E3}"), ipa::describe("a\u{303}"));
+        assert_eq!(
+            "c\u{327}".parse::<Segment>().unwrap(),
+            "\u{E7}".parse::<Segment>().unwrap()
+        );
+        assert_eq!(Segment::parse_const("\u{E3}"), "a\u{303}".parse().ok());
+        assert_eq!(normalize_ipa("n\u{32A}\u{30A}"), "n\u{30A}\u{32A}");
+        assert_eq!(normalize_ipa("\u{E3}c\u{327}"), "a\u{303}\u{E7}");
+        assert_eq!(xsampa::from_ipa("c\u{327}").unwrap(), "C");
+        let (base, tone) = Contour::split("m\u{E1}");
+        assert_eq!(
+            (base.as_str(), tone),
+            ("ma", Contour::from_diacritic('\u{301}'))
+        );
+        let high = Segment::Tone(Contour::from_diacritic('\u{301}').unwrap());
+        let a: Segment = "a".parse().unwrap();
+        assert_eq!(parser::parse("\u{E1}").unwrap(), vec![a, high]);
+        assert_eq!(parser::parse("e\u{301}").unwrap()[1], high);
+        assert_eq!(
+            parser::parse("\u{1CE}").unwrap(),
+            vec![
+                a,
+                Segment::Tone(Contour::from_diacritic('\u{30C}').unwrap())
+            ]
+        );
+        assert_eq!(
+            parser::parse("\u{E3}\u{301}").unwrap(),
+            vec!["a\u{303}".parse().unwrap(), high]
+        );
+        assert_eq!(
+            parser::parse("t\u{E1}\u{20DD}").unwrap_err().offset(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn transcription_diagnostics() {
+        use crate::notation::Notation;
+        use crate::parser::{Diagnostic, Problem};
+        assert_eq!(parser::validate("/ˈt͡ʃɪp/ [tʰa]"), Ok(()));
+        let diagnostics = parser::validate("[ʰat͡ ʃ§] E)").unwrap_err();
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    range: 1..3,
+                    problem: Problem::DanglingDiacritic('\u{2B0}'),
+                },
+                Diagnostic {
+                    range: 5..7,
+                    problem: Problem::UnmatchedTieBar('\u{361}'),
+                },
+                Diagnostic {
+                    range: 10..12,
+                    problem: Problem::UnknownSymbol('§'),
+                },
+                Diagnostic {
+                    range: 14..15,
+                    problem: Problem::MixedNotation {
+                        symbol: 'E',
+                        notation: Notation::XSampa,
+                    },
+                },
+                Diagnostic {
+                    range: 15..16,
+                    problem: Problem::UnmatchedBracket(')'),
+                },
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "`\u{25CC}\u{2B0}` has no symbol to attach to at bytes 1..3"
+        );
+    }
+
+    #[test]
+    fn streaming_tokens() {
+        use crate::parser::IpaTokens;
+        let text = "ˈt͡ʃʰa ˥˩ k͡pə.";
+        let segments: Vec<Segment> = IpaTokens::new(text).map(|t| t.unwrap().segment).collect();
+        assert_eq!(segments, parser::parse(text).unwrap());
+        let mut tokens = IpaTokens::new("a§ ʰo");
+        assert_eq!(tokens.next().unwrap().unwrap().offset, 0);
+        assert_eq!(
+            tokens.next(),
+            Some(Err(Error::UnknownSymbol {
+                offset: 1,
+                symbol: '§'
+            }))
+        );
+        assert!(matches!(
+            tokens.next(),
+            Some(Err(Error::DanglingDiacritic { offset: 4, .. }))
+        ));
+        assert_eq!(tokens.next().unwrap().unwrap().text, "o");
+        assert_eq!(tokens.next(), None);
+    }
+
+    #[test]
+    fn release_modifiers() {
+        use ipa::{Airstream, Release};
+        let c = |s: &str| s.parse::<ipa::Consonant>().unwrap();
+        assert_eq!(parser::tokenize("tʰatⁿ t̚kʼ"), ["tʰ", "a", "tⁿ", "t̚", "kʼ"]);
+        assert_eq!(c("tʰ").release(), Some(Release::Aspirated));
+        assert_eq!(c("dⁿ").release(), Some(Release::Nasal));
+        assert_eq!(c("tˡ").release(), Some(Release::Lateral));
+        assert_eq!(c("p\u{31A}").release(), Some(Release::Unreleased));
+        assert_eq!(c("kʼ").release(), None);
+        assert_eq!(c("kʼ").airstream(), Airstream::GlottalicEgressive);
+        assert_eq!(Release::from_char('\u{2E1}'), Some(Release::Lateral));
+        assert_eq!(c("tʰ").with_release(Some(Release::Nasal)).to_string(), "tⁿ");
+        assert_eq!(c("tʰ").with_release(None), c("t"));
+        assert_eq!(c("tʰ\u{32A}ʷ").to_string(), "t\u{32A}ʰʷ");
+    }
+
+    #[test]
+    fn segment_length() {
+        use crate::suprasegmentals::{Gemination, Length, Suprasegmental};
+        let length = |s: &str| s.parse::<Segment>().unwrap().length();
+        assert_eq!(length("a"), Some(Length::Short));
+        assert_eq!(length("aː"), Some(Length::Long));
+        assert_eq!(length("eˑ"), Some(Length::HalfLong));
+        assert_eq!(length("ĕ"), Some(Length::ExtraShort));
+        assert_eq!(length("sːː"), Some(Length::ExtraLong));
+        assert_eq!(length("t͡ʃː"), Some(Length::Long));
+        assert_eq!(length("ː"), None);
+        for s in ["aː", "tʰː", "kʼː", "t͡ʃː", "e\u{306}"] {
+            assert_eq!(s.parse::<Segment>().unwrap().to_string(), s);
+        }
+        let long_a: Segment = "aː".parse().unwrap();
+        assert_eq!(long_a.name(), "long open front unrounded vowel");
+        assert_eq!(
+            Segment::from_name("long open front unrounded vowel"),
+            Some(long_a)
+        );
+        assert_eq!(
+            Segment::from_name("long"),
+            Some(Segment::Suprasegmental(Suprasegmental::Length(
+                Length::Long
+            )))
+        );
+
+        let geminate = parser::parse_with("katta", Gemination::Geminate).unwrap();
+        assert_eq!(geminate, parser::parse("katːa").unwrap());
+        assert_eq!(
+            parser::parse_with("katta", Gemination::Long).unwrap().len(),
+            5
+        );
+        assert_eq!(Gemination::Geminate.write(&geminate), "katta");
+        assert_eq!(Gemination::Long.write(&geminate), "katːa");
+    }
+
+    #[test]
+    fn word_stress() {
+        use crate::suprasegmentals::Stress;
+        use syllable::Word;
+        let word: Word = "[ˌfəʊ̯.nəˈlɒ.dʒi]".parse().unwrap();
+        let stresses: Vec<_> = word.syllables().iter().map(|s| s.stress).collect();
+        assert_eq!(
+            stresses,
+            [Some(Stress::Secondary), None, Some(Stress::Primary), None]
+        );
+        assert_eq!(word.stressed_syllable().unwrap().to_string(), "ˈlɒ");
+        assert_eq!(word.to_string(), "ˌfəʊ̯.nəˈlɒ.dʒi");
+        // a stress mark is a syllable boundary of its own
+        let word: Word = "əˈstrɒŋ".parse().unwrap();
+        assert_eq!(word.to_string(), "əˈstrɒŋ");
+        assert_eq!("kata".parse::<Word>().unwrap().stressed_syllable(), None);
+        let word: Word = "/'fəʊ.nɪm/".parse().unwrap();
+        assert_eq!(word.syllables().len(), 2);
+        assert_eq!(word.stressed_syllable().unwrap().to_string(), "ˈfəʊ");
+        let word: Word = "ˈfəʊ.nɪm".parse().unwrap();
+        assert_eq!(word.stressed_syllable().unwrap().to_string(), "ˈfəʊ");
+        let error = "/'fə§/".parse::<Word>().unwrap_err();
+        assert_eq!(error.offset(), Some(5));
+    }
+
+    #[test]
+    fn syllable_weight() {
+        use syllable::{syllabify, Weight, WeightCriteria};
+        let syllables = syllabify(&parser::parse("ta.taː.tai̯.tat.taːt").unwrap());
+        let rime: Vec<String> = syllables[3].rime().map(|s| s.to_string()).collect();
+        assert_eq!(rime, ["a", "t"]);
+        let weights = |criteria| {
+            syllables
+                .iter()
+                .map(|s| s.weight(&criteria))
+                .collect::<Vec<_>>()
+        };
+        use Weight::*;
+        assert_eq!(
+            weights(WeightCriteria::LATIN),
+            [Light, Heavy, Heavy, Heavy, Superheavy]
+        );
+        assert_eq!(
+            weights(WeightCriteria::NUCLEUS_ONLY),
+            [Light, Heavy, Heavy, Light, Heavy]
+        );
+        let morae: Vec<usize> = syllables
+            .iter()
+            .map(|s| s.morae(&WeightCriteria::LATIN))
+            .collect();
+        assert_eq!(morae, [1, 2, 2, 2, 3]);
+    }
+
+    #[test]
+    fn sonority_scale() {
+        use classes::{fricative, voiced};
+        use syllable::{syllabify_with, Phonotactics, Scale, Sonority};
+        let seg = |s: &str| s.parse::<Segment>().unwrap();
+        assert_eq!(seg("t").sonority(), Some(1));
+        assert_eq!(seg("s").sonority(), Some(2));
+        assert_eq!(seg("n").sonority(), Some(3));
+        assert_eq!(seg("l").sonority(), Some(4));
+        assert_eq!(seg("j").sonority(), Some(5));
+        assert_eq!(seg("a").sonority(), Some(6));
+        assert_eq!(seg("ˈ").sonority(), None);
+
+        let scale = Scale::new().with(voiced() & fricative(), 3);
+        assert_eq!(scale.sonority(&seg("z")), Some(3));
+        assert_eq!(scale.sonority(&seg("s")), Some(2));
+        let word = parser::parse("azma").unwrap();
+        assert_eq!(syllabify_with(&word, &Sonority)[0].to_string(), "a");
+        assert_eq!(syllabify_with(&word, &scale)[0].to_string(), "az");
+        let language = phonotactics::Phonotactics::new().with_scale(scale);
+        assert!(!Phonotactics::is_onset(
+            &language,
+            &parser::parse("zm").unwrap()
+        ));
+    }
+
+    #[test]
+    fn syllabification_policy() {
+        use classes::{consonant, nasal, vowel};
+        use rules::Pattern;
+        use syllable::{syllabify_with, Policy, Split, Word};
+        let split = |word: &str, policy: &Policy| {
+            let syllables = syllabify_with(&parser::parse(word).unwrap(), policy);
+            Word::new(syllables).to_string()
+        };
+        assert_eq!(split("apla", &Policy::new()), "a.pla");
+        let coda_first = Policy::new().with_split(Split::MaximalCoda);
+        assert_eq!(split("apla", &coda_first), "ap.la");
+        assert_eq!(split("aksa", &coda_first), "ak.sa");
+
+        let japanese = Policy::new()
+            .with_onset([Pattern::Class(consonant())])
+            .with_coda([Pattern::Class(nasal())])
+            .with_split(Split::MaximalCoda);
+        assert_eq!(split("kanta", &japanese), "kan.ta");
+        assert_eq!(split("kata", &japanese), "ka.ta");
+
+        let english = Policy::new().with_ambisyllabic(vowel(), consonant());
+        let syllables = syllabify_with(&parser::parse("hæpi").unwrap(), &english);
+        assert_eq!(syllables[0].coda, syllables[1].onset);
+        assert!(syllables[1].ambisyllabic && !syllables[0].ambisyllabic);
+        assert_eq!(Word::new(syllables).to_string(), "hæ.pi");
+        assert_eq!(split("hæmpi", &english), "hæm.pi");
+    }
+
+    #[test]
+    fn metrical_stress() {
+        use metrical::{Edge, Foot, Meter};
+        let stress =
+            |meter: &Meter, word: &str| meter.stress(&parser::parse(word).unwrap()).to_string();
+        assert_eq!(stress(&Meter::LATIN, "amiːkus"), "aˈmiː.kus");
+        assert_eq!(stress(&Meter::LATIN, "fakilis"), "ˈfa.ki.lis");
+        assert_eq!(stress(&Meter::LATIN, "pater"), "ˈpa.ter");
+        assert_eq!(stress(&Meter::LATIN, "kor"), "ˈkor");
+        assert_eq!(stress(&Meter::WARLPIRI, "pakatiwa"), "ˈpa.kaˌti.wa");
+        assert_eq!(stress(&Meter::WARLPIRI, "pakati"), "ˈpa.ka.ti");
+        let iambs = Meter::new(Foot::Iamb, Edge::Left);
+        assert_eq!(stress(&iambs, "pakati"), "paˈka.ti");
+        let iambs = Meter {
+            degenerate: true,
+            main: Edge::Right,
+            ..iambs
+        };
+        assert_eq!(stress(&iambs, "pakati"), "paˌkaˈti");
+        let word = syllable::syllabify(&parser::parse("patakalama").unwrap());
+        assert_eq!(Meter::LATIN.feet(&word), [0..2, 2..4]);
+        let word = syllable::syllabify(&parser::parse("patanpala").unwrap());
+        assert_eq!(Meter::LATIN.feet(&word), vec![1..2]);
+    }
+
+    #[test]
+    fn minimal_pairs() {
+        use inventory::Inventory;
+        let seg = |s: &str| s.parse::<Segment>().unwrap();
+        let inventory: Inventory = parser::parse("pbtdaiiː").unwrap().into_iter().collect();
+        let words = ["pat", "bat", "bad", "pit", "piːt", "tab", "pab"];
+        let pairs = inventory.find_minimal_pairs(&words, &seg("p"), &seg("b"));
+        assert_eq!(pairs.unwrap(), [("pat", "bat")]);
+        let pairs = inventory.find_minimal_pairs(&words, &seg("i"), &seg("iː"));
+        assert_eq!(pairs.unwrap(), [("pit", "piːt")]);
+        let pairs = inventory.find_minimal_pairs(&words, &seg("p"), &seg("k"));
+        assert!(pairs.unwrap().is_empty());
+        assert!(inventory
+            .find_minimal_pairs(&["p§"], &seg("p"), &seg("b"))
+            .is_err());
+    }
+
+    #[test]
+    fn functional_load() {
+        use inventory::Inventory;
+        let seg = |s: &str| s.parse::<Segment>().unwrap();
+        let inventory: Inventory = parser::parse("pbtai").unwrap().into_iter().collect();
+        let lexicon = ["pa", "ba", "ta", "pi"];
+        let load = inventory
+            .functional_load(&seg("p"), &seg("b"), &lexicon)
+            .unwrap();
+        // Merging p and b leaves {pa: 2, ta: 1, pi: 1}, losing half a
+        // bit of the lexicon's two.
+        assert!((load - 0.25).abs() < 1e-6);
+        let load = inventory.functional_load(&seg("b"), &seg("i"), &lexicon);
+        assert_eq!(load.unwrap(), 0.0);
+        let load = inventory.functional_load(&seg("p"), &seg("k"), &lexicon);
+        assert_eq!(load.unwrap(), 0.0);
+    }
+
+    #[test]
+    fn generated_inventories() {
+        use inventory::{Inventory, Profile};
+        use random::SplitMix64;
+        let seg = |s: &str| s.parse::<Segment>().unwrap();
+        for seed in 0..50 {
+            let inventory = Inventory::generate(&mut SplitMix64::new(seed), &Profile::AVERAGE);
+            assert!((19..=25).contains(&inventory.consonants().count()));
+            assert!((5..=6).contains(&inventory.vowels().count()));
+            for (implying, implied) in [("ɸ", "p"), ("ʒ", "ʃ"), ("o", "u"), ("ɖ", "ʈ")] {
+                assert!(!inventory.contains(&seg(implying)) || inventory.contains(&seg(implied)));
+            }
+        }
+        let generate = || Inventory::generate(&mut SplitMix64::new(42), &Profile::LARGE);
+        assert_eq!(generate(), generate());
+        let b = seg("p")
+            .as_consonant()
+            .unwrap()
+            .with_phonation(ipa::Phonation::Voiced);
+        assert_eq!(Segment::Consonant(b), seg("b"));
+    }
+
+    #[test]
+    fn word_generator() {
+        use generator::Generator;
+        use phonotactics::{Phonotactics, Template};
+        use random::SplitMix64;
+        let seg = |s: &str| s.parse::<Segment>().unwrap();
+        let inventory: inventory::Inventory =
+            parser::parse("ptkmnsaiu").unwrap().into_iter().collect();
+        let phonotactics = Phonotactics::new()
+            .with_template(Template::parse("CV").unwrap())
+            .with_template(Template::parse("CVC").unwrap())
+            .with_banned([rules::Pattern::Segment(seg("k")), rules::Pattern::Boundary]);
+        let generator = Generator::new(inventory, phonotactics.clone())
+            .with_frequency(seg("u"), 0)
+            .with_syllables(2..=2);
+        let mut rng = SplitMix64::new(3);
+        for _ in 0..20 {
+            let word = generator.word(&mut rng).unwrap();
+            assert!(phonotactics.validate(&word).is_ok());
+            assert!(!word.contains(&seg("u")));
+            assert!((4..=6).contains(&word.len()));
+        }
+        let ipa = |seed| generator.ipa(&mut SplitMix64::new(seed)).unwrap();
+        assert_eq!(ipa(9), ipa(9));
+    }
+
+    #[test]
+    fn markov_generator() {
+        use generator::Markov;
+        use random::SplitMix64;
+        let words = ["tata", "tatata", "tatatata"];
+        let model = Markov::train(&words, 1).unwrap();
+        let mut rng = SplitMix64::new(11);
+        for _ in 0..20 {
+            let word = model.ipa(&mut rng);
+            // Every `t` is followed by `a`, and every word ends in `a`.
+            assert!(word.starts_with('t') && word.ends_with('a'));
+            assert!(!word.contains("tt") && !word.contains("aa"));
+        }
+        let smoothed = model.clone().with_smoothing(1.0);
+        let mut rng = SplitMix64::new(11);
+        assert!((0..50).any(|_| smoothed.ipa(&mut rng).contains("aa")));
+        assert!(Markov::train(&["t§"], 2).is_err());
+        let model = Markov::train(&words, 2).unwrap();
+        assert_eq!(
+            model.ipa(&mut SplitMix64::new(4)),
+            model.ipa(&mut SplitMix64::new(4))
+        );
+    }
+
+    #[test]
+    fn allophones() {
+        use classes::vowel;
+        use rules::{Allophone, Pattern};
+        let seg = |s: &str| s.parse::<Segment>().unwrap();
+        let render = |w: &[Segment]| w.iter().map(|s| s.to_string()).collect::<String>();
+        let t = Allophone::new(seg("t"))
+            .with_realization(seg("ɾ"), [vowel().into()], [vowel().into()])
+            .with_realization(seg("tʰ"), [Pattern::Boundary], [])
+            .with_realization(seg("t̚"), [], [Pattern::Boundary]);
+        let word = parser::parse("tatat").unwrap();
+        assert_eq!(render(&t.realize(&word)), "tʰaɾat̚");
+        assert_eq!(t.realize_at(&word, 1), None);
+        assert_eq!(t.realize_at(&word, 0), Some(seg("tʰ")));
+        let t = t.with_elsewhere(seg("ʔ"));
+        assert_eq!(render(&t.realize(&parser::parse("atsa").unwrap())), "aʔsa");
+        assert_eq!(t.realizations().len(), 3);
+    }
+
+    #[test]
+    fn phonetic_derivation() {
+        use classes::vowel;
+        use inventory::{Firing, Inventory, PhonemicWord};
+        use rules::{Allophone, Pattern};
+        let seg = |s: &str| s.parse::<Segment>().unwrap();
+        let mut inventory = Inventory::new();
+        inventory.add_allophone(Allophone::new(seg("d")).with_realization(
+            seg("t"),
+            [],
+            [Pattern::Boundary],
+        ));
+        inventory.add_allophone(Allophone::new(seg("t")).with_realization(
+            seg("ɾ"),
+            [vowel().into()],
+            [vowel().into()],
+        ));
+        inventory.add_allophone(Allophone::new(seg("a")).with_elsewhere(seg("ɐ")));
+        assert_eq!(inventory.len(), 3);
+        let word: PhonemicWord = "/tad/".parse().unwrap();
+        assert_eq!(word.to_string(), "/tad/");
+        let (phonetic, firings) = inventory.trace(&word);
+        assert_eq!(phonetic.to_string(), "[tɐt]");
+        assert_eq!(
+            firings,
+            [
+                Firing {
+                    position: 2,
+                    allophone: 0,
+                    realization: Some(0)
+                },
+                Firing {
+                    position: 1,
+                    allophone: 2,
+                    realization: None
+                },
+            ]
+        );
+        let word: PhonemicWord = "tada".parse().unwrap();
+        assert_eq!(inventory.realize(&word).to_string(), "[tɐdɐ]");
+        assert_eq!(
+            inventory.realize(&"tata".parse().unwrap()).to_string(),
+            "[tɐɾɐ]"
+        );
+    }
+
+    #[test]
+    fn rule_notation() {
+        use classes::nasal;
+        use rules::{ParseRuleError, Rule};
+        let render = |w: &[Segment]| w.iter().map(|s| s.to_string()).collect::<String>();
+        let apply = |rule: &str, word: &str| {
+            let rule: Rule = rule.parse().unwrap();
+            render(&rule.apply(&parser::parse(word).unwrap()))
+        };
+        assert_eq!(apply("s → z / V _ V", "asasta"), "azasta");
+        assert_eq!(apply("s->z/V_V", "asa"), "aza");
+        assert_eq!(apply("[-voice] → [+voice] / _ [+voice]", "akda"), "aɡda");
+        assert_eq!(apply("d > t / _ #", "dad"), "dat");
+        assert_eq!(apply("[-son] → [+voice] / V _ (C) V", "asta"), "azta");
+        assert_eq!(apply("[-son] → [+voice] / V _ (C) V", "apa"), "aba");
+        assert_eq!(apply("a → ə", "tata"), "tətə");
+        assert_eq!(apply("k → x / # (V) _", "kak"), "xak");
+        assert_eq!(apply("k → x / # (V) _", "akak"), "axak");
+
+        let rule = Rule::parse_with("p → m / _ N", &[("N", nasal())]).unwrap();
+        assert_eq!(render(&rule.apply(&parser::parse("apna").unwrap())), "amna");
+
+        let error = |rule: &str| rule.parse::<Rule>().unwrap_err();
+        assert_eq!(error("s z"), ParseRuleError::MissingArrow);
+        assert_eq!(error("s → z / V V"), ParseRuleError::MissingFocus);
+        assert_eq!(
+            error("[+foo] → z"),
+            ParseRuleError::UnknownFeature("+foo".into())
+        );
+        assert_eq!(error("s → z / _ (V"), ParseRuleError::Unbalanced('('));
+        assert_eq!(
+            error("s t → z"),
+            ParseRuleError::InvalidTarget("s t".into())
+        );
+        assert!(matches!(error("s → z / §_"), ParseRuleError::Segment(_)));
+    }
+
+    #[test]
+    fn lexurgy_import() {
+        use lexurgy::SoundChanges;
+        use rules::{ImportError, ImportErrorKind};
+        let changes: SoundChanges = "
+# An example project.
+Feature +voice
+Class vowel {a, e, i, o, u}
+Class stop {p, t, k}
+Class obstruent {@stop, s}
+
+Deromanizer:
+    c => k
+
+lenition:
+    @stop => {b, d, ɡ} / @vowel _ @vowel
+    s => z / @vowel _ @vowel
+    Then:
+    {e, o} => ə / _ $
+
+romanizer-old:
+    k => c
+
+final-devoicing: [-son] => [-voice] / _ $
+"
+        .parse()
+        .unwrap();
+        let (name, obstruents) = &changes.classes()[2];
+        assert_eq!((name.as_str(), obstruents.len()), ("obstruent", 4));
+        // the romanizer divides the file into two stages, and `Then:`
+        // the lenition block into two blocks
+        let stages = changes.stages();
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[1].name.as_deref(), Some("old"));
+        let names: Vec<&str> = stages[0].blocks.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["lenition", "lenition"]);
+        assert_eq!(stages[0].blocks[0].rules.len(), 4);
+        assert_eq!(stages[1].blocks[0].name, "final-devoicing");
+
+        for (word, changed) in [("atasake", "adazaɡə"), ("ikod", "iɡot"), ("tab", "tap")] {
+            let segments = changes.apply(&parser::parse(word).unwrap());
+            assert_eq!(segments, parser::parse(changed).unwrap());
+        }
+        let stage = stages[0].apply(&parser::parse("ikod").unwrap());
+        assert_eq!(stage, parser::parse("iɡod").unwrap());
+
+        let apocope: SoundChanges = "apocope:\n    {e, o} => * / _ $\n    * => ə / $ _ s"
+            .parse()
+            .unwrap();
+        let stomo = apocope.apply(&parser::parse("stomo").unwrap());
+        assert_eq!(stomo, parser::parse("əstom").unwrap());
+
+        assert_eq!(
+            "rule:\n    p => b / @vowel _"
+                .parse::<SoundChanges>()
+                .unwrap_err(),
+            ImportError {
+                line: 2,
+                kind: ImportErrorKind::UnknownClass("vowel".into())
+            }
+        );
+        for unsupported in ["rule propagate:\n    p => b", "rule:\n    p => b // a _"] {
+            let error = unsupported.parse::<SoundChanges>().unwrap_err();
+            assert!(matches!(error.kind, ImportErrorKind::Unsupported(_)));
+        }
+    }
+
+    #[test]
+    fn sca_import() {
+        use rules::ImportErrorKind;
+        use sca::SoundChanges;
+        // categories redefined later replace the earlier ones, and `→`
+        // may separate the fields
+        let changes: SoundChanges = "
+V=aeiou
+P=ptk
+B=bdɡ
+* Lenition, then reduction.
+P/B/V_V
+s→z/V_(P)V
+e/ə/_#
+k/x/[iu]_
+V=ai
+"
+        .parse()
+        .unwrap();
+        let categories: String = changes.categories().iter().map(|(c, _)| *c).collect();
+        assert_eq!(categories, "PBV");
+        assert_eq!(changes.categories()[2].1.len(), 2);
+        let sizes: Vec<usize> = changes.rules().iter().map(Vec::len).collect();
+        assert_eq!(sizes, [3, 1, 1, 1]);
+
+        let apply = |word: &str| {
+            let changed = changes.apply(&parser::parse(word).unwrap());
+            changed.iter().map(|s| s.to_string()).collect::<String>()
+        };
+        assert_eq!(apply("atakase"), "adaɡazə");
+        assert_eq!(apply("astake"), "aztaɡə");
+        assert_eq!(apply("iktuk"), "ixtux");
+
+        // an empty replacement deletes, an empty target inserts
+        let edges: SoundChanges = "V=aeiou\nV//_#\n/ə/#_s".parse().unwrap();
+        let stomo = edges.apply(&parser::parse("stomo").unwrap());
+        assert_eq!(stomo, parser::parse("əstom").unwrap());
+
+        let error = "P=ptk\nB=bd\nP/B/_".parse::<SoundChanges>().unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.kind, ImportErrorKind::Mismatched("P/B/_".into()));
+        assert_eq!(
+            error.to_string(),
+            "line 3: targets and results do not pair up in `P/B/_`"
+        );
+        for unsupported in ["//_#", "ch|č", "a/e/_…i", "PV=x"] {
+            let error = unsupported.parse::<SoundChanges>().unwrap_err();
+            assert!(matches!(error.kind, ImportErrorKind::Unsupported(_)));
+        }
+    }
+
+    #[test]
+    fn rule_interactions() {
+        use rules::{Interaction, InteractionKind::*, Rule};
+        let rule = |s: &str| s.parse::<Rule>().unwrap();
+        let voicing = rule("t → d / V _ V");
+        let raising = rule("a → ə / _ d");
+        let assibilation = rule("t → s / _ a");
+        let kinds = |rules: &[Rule], lexicon: &[&str]| {
+            let found = rules::interactions(rules, lexicon).unwrap();
+            found.iter().map(|f| f.kind).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            kinds(&[voicing.clone(), raising.clone()], &["atad"]),
+            [Feeding]
+        );
+        assert_eq!(
+            kinds(&[raising, voicing.clone()], &["atad"]),
+            [Counterfeeding]
+        );
+        assert_eq!(
+            kinds(&[assibilation.clone(), voicing.clone()], &["ata"]),
+            [Bleeding, Counterbleeding]
+        );
+        assert_eq!(
+            rules::interactions(&[voicing, rule("a → e / t _")], &["pi", "ata", "itai"]).unwrap(),
+            [Interaction {
+                earlier: 0,
+                later: 1,
+                kind: Bleeding,
+                words: vec![1, 2],
+            }]
+        );
+    }
+
+    #[test]
+    fn segment_patterns() {
+        use classes::sibilant;
+        use pattern::Pattern;
+        use rules::ParseRuleError;
+        let word = |s: &str| parser::parse(s).unwrap();
+        let pattern = |s: &str| s.parse::<Pattern>().unwrap();
+
+        let cluster = pattern("[+nasal] [−voice]* V");
+        assert_eq!(cluster.find(&word("tamsta")), Some(2..6));
+        assert_eq!(cluster.find(&word("tama")), Some(2..4));
+        assert_eq!(cluster.match_at(&word("tamsta"), 1), None);
+        assert_eq!(cluster.find(&word("tamd")), None);
+
+        assert_eq!(pattern("C V+").find_all(&word("taamuai")), vec![0..3, 3..7]);
+        assert_eq!(
+            pattern("(p|t|k) a?").find_all(&word("pkat")),
+            vec![0..1, 1..3, 3..4]
+        );
+        assert_eq!(pattern("s t # ").find(&word("stast")), Some(3..5));
+        assert!(pattern("# (C V)+ #").matches(&word("tamaka")));
+        assert!(!pattern("(C V)+").matches(&word("tamak")));
+        assert!(pattern("a (b | c d)* e").matches(&word("abcdbe")));
+
+        let s = Pattern::parse_with("S V", &[("S", sibilant())]).unwrap();
+        assert_eq!(s.find(&word("tasa")), Some(2..4));
+
+        let error = |s: &str| s.parse::<Pattern>().unwrap_err();
+        assert_eq!(error("* a"), ParseRuleError::Dangling('*'));
+        assert_eq!(error("(a b"), ParseRuleError::Unbalanced('('));
+        assert_eq!(error("a b)"), ParseRuleError::Unbalanced(')'));
+        assert_eq!(
+            error("[+foo]"),
+            ParseRuleError::UnknownFeature("+foo".into())
+        );
+    }
+
+    #[test]
+    fn named_classes() {
+        use classes::{named, one_of};
+        use rules::{Categories, Rule};
+        let word = |s: &str| parser::parse(s).unwrap();
+        let render = |w: &[Segment]| w.iter().map(|s| s.to_string()).collect::<String>();
+        assert!(named("N").unwrap().matches(&ipa!("ŋ")));
+        assert!(named("S").unwrap().matches(&ipa!("ʃ")));
+        assert!(!named("S").unwrap().matches(&ipa!("f")));
+        assert!(named("X").is_none());
+
+        let rule: Rule = "a → ã / _ N #".parse().unwrap();
+        assert_eq!(render(&rule.apply(&word("taman"))), "tama\u{303}n");
+        let rule: Rule = "i → ɨ / S _".parse().unwrap();
+        assert_eq!(render(&rule.apply(&word("siti"))), "sɨti");
+
+        let categories = Categories::new()
+            .with("L", one_of(word("lr")))
+            .with("N", one_of(word("n")));
+        assert!(categories.get("V").is_some());
+        assert!(categories.get("L").unwrap().matches(&ipa!("r")));
+        let rule = categories.rule("a → ã / _ N").unwrap();
+        assert_eq!(render(&rule.apply(&word("tamana"))), "tama\u{303}na");
+        let pattern = categories.pattern("# C* V L").unwrap();
+        assert_eq!(pattern.find(&word("stal")), Some(0..4));
+    }
+
+    #[test]
+    fn structural_changes() {
+        use classes::vowel;
+        use rules::{Change, ParseRuleError, Pattern, Rule};
+        let word = |s: &str| parser::parse(s).unwrap();
+        let render = |w: &[Segment]| w.iter().map(|s| s.to_string()).collect::<String>();
+        let apply = |rule: &str, w: &str| {
+            let rule: Rule = rule.parse().unwrap();
+            render(&rule.apply(&word(w)))
+        };
+        assert_eq!(apply("h → ∅ / V _ V", "ahaha"), "aaa");
+        assert_eq!(apply("[-son] → 0 / _ #", "kapt"), "kap");
+        assert_eq!(apply("∅ → ə / C _ C #", "kapt"), "kapət");
+        assert_eq!(apply("∅ → i / # _ s C", "sta"), "ista");
+        assert_eq!(apply("s k → 2 1 / V _", "askaski"), "aksaksi");
+        assert_eq!(apply("V C → 2 1 / _ #", "kapat"), "kapta");
+        assert_eq!(apply("a s k → 3 1 2", "askask"), "kaskas");
+        assert_eq!(apply("k s → 2 1", "kʰsa"), "kʰsa");
+        assert_eq!(apply("kʰ s → 2 1", "kʰsa"), "skʰa");
+
+        let syncope = Rule::deletion(vowel()).with_environment([vowel().into()], []);
+        assert_eq!(render(&syncope.apply(&word("aia"))), "a");
+        assert_eq!(syncope.change(), &Change::Delete);
+        let prothesis =
+            Rule::epenthesis(ipa!("e")).with_environment([Pattern::Boundary], [ipa!("s").into()]);
+        assert_eq!(prothesis.target().len(), 0);
+        assert_eq!(render(&prothesis.apply(&word("spana"))), "espana");
+        let swap = Rule::metathesis([ipa!("r").into(), vowel().into()], [1, 0]);
+        assert_eq!(render(&swap.apply(&word("bron"))), "born");
+
+        let error = |rule: &str| rule.parse::<Rule>().unwrap_err();
+        assert_eq!(
+            error("s k → 1 1"),
+            ParseRuleError::InvalidChange("1 1".into())
+        );
+        assert_eq!(
+            error("∅ → [+voice]"),
+            ParseRuleError::InvalidChange("[+voice]".into())
+        );
+        assert_eq!(error(" → a"), ParseRuleError::InvalidTarget("".into()));
+    }
+
+    #[test]
+    fn derivations() {
+        use rules::Rule;
+        let rule = |s: &str| s.parse::<Rule>().unwrap();
+        let cascade = [
+            rule("t → d / V _ V"),
+            rule("k → tʃ / _ i"),
+            rule("V → ∅ / _ #"),
+        ];
+        let word = parser::parse("atata").unwrap();
+        let derivation = rules::derive(&cascade, &word);
+        assert_eq!(derivation.underlying(), &word[..]);
+        assert_eq!(derivation.surface(), &rules::cascade(&cascade, &word)[..]);
+        assert_eq!(derivation.steps().len(), 3);
+        let applied: Vec<usize> = derivation.applied().map(|step| step.rule).collect();
+        assert_eq!(applied, [0, 2]);
+        assert_eq!(
+            derivation.table(&["Voicing", "Palatalization"]),
+            "UR              /atata/\n\
+             Voicing         adada\n\
+             Palatalization  \u{2014}\n\
+             Rule 3          adad\n\
+             SR              [adad]\n"
+        );
+        assert!(derivation
+            .to_string()
+            .starts_with("UR      /atata/\nRule 1  adada\n"));
+
+        let none = rules::derive(&[], &word);
+        assert_eq!(none.surface(), &word[..]);
+        assert_eq!(none.to_string(), "UR  /atata/\nSR  [atata]\n");
+    }
+
+    #[test]
+    fn natural_class_of_a_set() {
+        use features::{natural_class, Feature, FeatureBundle};
+        let segments = |s: &str| parser::parse(s).unwrap();
+        let inventory = segments("ptkbdɡmnszaiu");
+        assert_eq!(
+            natural_class(&segments("mn"), &inventory),
+            Some(FeatureBundle::new().with(Feature::Nasal, true))
+        );
+        assert_eq!(
+            natural_class(&segments("aiu"), &inventory),
+            Some(FeatureBundle::new().with(Feature::Syllabic, true))
+        );
+        assert_eq!(
+            natural_class(&segments("ptks"), &inventory).map(|b| b.len()),
+            Some(1)
+        );
+        assert_eq!(
+            natural_class(&inventory, &inventory),
+            Some(FeatureBundle::new())
+        );
+        assert_eq!(natural_class(&segments("pz"), &inventory), None);
+        assert_eq!(natural_class(&[], &inventory), None);
+
+        let class = natural_class(&segments("bdɡz"), &inventory).unwrap();
+        let picked: Vec<_> = inventory
+            .iter()
+            .filter(|s| s.features().is_some_and(|f| class.subsumes(&f)))
+            .collect();
+        assert_eq!(picked.len(), 4);
+
+        let inventory: inventory::Inventory = inventory.into_iter().collect();
+        assert_eq!(
+            inventory.natural_class(&segments("mn")),
+            Some(FeatureBundle::new().with(Feature::Nasal, true))
+        );
+    }
+
+    #[test]
+    fn feature_geometry() {
+        use features::Feature;
+        use geometry::{delink, spread, Node};
+        assert_eq!(Node::of(Feature::Voice), Node::Laryngeal);
+        assert_eq!(Node::of(Feature::Anterior), Node::Coronal);
+        assert_eq!(Node::Coronal.parent(), Some(Node::Place));
+        assert!(Node::Place.dominates(Feature::High));
+        assert!(Node::Root.dominates(Feature::Round));
+        assert!(!Node::Laryngeal.dominates(Feature::Nasal));
+
+        let t = ipa!("t").features().unwrap();
+        let k = ipa!("k").features().unwrap();
+        assert!(Node::Coronal.is_present(&t) && !Node::Dorsal.is_present(&t));
+        assert!(Node::Place.is_present(&t));
+        let glottal = delink(Node::Place, &t);
+        assert!(!Node::Place.is_present(&glottal));
+        assert_eq!(glottal.get(Feature::Anterior), None);
+        assert_eq!(glottal.get(Feature::Voice), Some(false));
+        assert_eq!(delink(Node::Laryngeal, &t).get(Feature::Voice), None);
+
+        let spread_t = spread(Node::Place, &k, &t);
+        assert!(spread_t.is(Feature::Dorsal, true) && spread_t.is(Feature::Coronal, false));
+        assert_eq!(spread_t.get(Feature::Anterior), None);
+        assert_eq!(
+            spread(Node::Laryngeal, &ipa!("d").features().unwrap(), &t),
+            ipa!("d").features().unwrap()
+        );
+    }
+
+    #[test]
+    fn place_assimilation() {
+        use geometry::{assimilate, Node};
+        assert_eq!(
+            assimilate(Node::Place, &ipa!("k"), &ipa!("n")),
+            Some(ipa!("ŋ"))
+        );
+        assert_eq!(
+            assimilate(Node::Place, &ipa!("b"), &ipa!("n")),
+            Some(ipa!("m"))
+        );
+        assert_eq!(
+            assimilate(Node::Laryngeal, &ipa!("d"), &ipa!("s")),
+            Some(ipa!("z"))
+        );
+        assert_eq!(assimilate(Node::Place, &ipa!("ˈ"), &ipa!("n")), None);
+        assert_eq!(
+            assimilate(Node::Place, &ipa!("k"), &ipa!("nː")),
+            Some(ipa!("ŋː"))
+        );
+    }
+
+    #[test]
+    fn feature_systems() {
+        use features::FeatureSystem;
+        let m = FeatureSystem::Hayes.featurize(&ipa!("m")).unwrap();
+        assert_eq!(m.get("approximant"), Some(false));
+        assert_eq!(m.get("labial"), Some(true));
+        let r = FeatureSystem::Hayes.featurize(&ipa!("r")).unwrap();
+        assert_eq!(r.get("trill"), Some(true));
+        assert_eq!(
+            FeatureSystem::Spe
+                .featurize(&ipa!("l"))
+                .unwrap()
+                .get("vocalic"),
+            Some(true)
+        );
+        assert_eq!(
+            FeatureSystem::Spe
+                .featurize(&ipa!("j"))
+                .unwrap()
+                .get("vocalic"),
+            Some(false)
+        );
+        let a = FeatureSystem::PanPhon.featurize(&ipa!("aː")).unwrap();
+        assert_eq!(a.get("long"), Some(true));
+        assert_eq!(a.get("hitone"), None);
+        assert_eq!(a.iter().count(), 24);
+        assert!(a.to_string().starts_with("[+syl, +son, -cons"));
+        assert_eq!(FeatureSystem::from_name("spe"), Some(FeatureSystem::Spe));
+        let native = FeatureSystem::Native.featurize(&ipa!("t")).unwrap();
+        assert_eq!(native.get("voice"), Some(false));
+    }
+
+    #[test]
+    fn feature_transforms() {
+        use ipa::Articulation;
+        assert_eq!(ipa!("b").devoiced(), ipa!("p"));
+        assert_eq!(ipa!("s").voiced(), ipa!("z"));
+        assert_eq!(ipa!("n").devoiced(), ipa!("n̥"));
+        assert_eq!(ipa!("i").devoiced().to_string(), "i\u{325}");
+        assert_eq!(ipa!("i").devoiced().voiced(), ipa!("i"));
+        assert_eq!(ipa!("t͡s").voiced(), ipa!("d͡z"));
+        assert_eq!(ipa!("d").nasalized(), ipa!("n"));
+        assert_eq!(ipa!("l").nasalized().to_string(), "l\u{303}");
+        assert_eq!(ipa!("ʔ").nasalized().to_string(), "ʔ\u{303}");
+        assert_eq!(ipa!("m").nasalized(), ipa!("m"));
+        assert_eq!(ipa!("s").with_place(Articulation::Retroflex), ipa!("ʂ"));
+        assert_eq!(ipa!("s").with_place(Articulation::Velar), ipa!("x"));
+        assert_eq!(
+            ipa!("r").with_place(Articulation::Dental).to_string(),
+            "r\u{32A}"
+        );
+        assert_eq!(
+            ipa!("r").with_place(Articulation::Velar).to_string(),
+            "ʀ\u{31F}"
+        );
+        assert_eq!(ipa!("d͡ʒ").with_place(Articulation::Alveolar), ipa!("d͡z"));
+        assert_eq!(ipa!("a").with_place(Articulation::Velar), ipa!("a"));
+        // places further than one step from any symbol take the nearest
+        use Articulation::*;
+        assert_eq!(ipa!("r").with_place(Palatal).to_string(), "ʀ\u{31F}");
+        assert_eq!(ipa!("ɾ").with_place(Uvular).to_string(), "ɽ\u{320}");
+        assert_eq!(ipa!("ɬ").with_place(Glottal).to_string(), "ɬ\u{320}");
+        let moved = [
+            (ipa!("r"), [Retroflex, Palatal, Linguolabial].as_slice()),
+            (ipa!("ɾ"), &[Velar, Uvular, Pharyngeal]),
+            (ipa!("ɬ"), &Articulation::ALL),
+            (ipa!("ɺ"), &Articulation::ALL),
+        ];
+        for (segment, places) in moved {
+            for place in places {
+                let written = segment.with_place(*place).to_string();
+                assert!(!written.contains('\u{FFFD}'), "{} at {:?}", segment, place);
+            }
+        }
+    }
+
+    #[test]
+    fn base_segments() {
+        assert_eq!(ipa!("t̪ʰ").base(), ipa!("t"));
+        assert_eq!(ipa!("ŋ̊").base(), ipa!("ŋ"));
+        assert_eq!(ipa!("b̤").base(), ipa!("b"));
+        assert_eq!(ipa!("kʷʼ").base(), ipa!("kʼ"));
+        assert_eq!(ipa!("ɱ").base(), ipa!("ɱ"));
+        assert_eq!(ipa!("a\u{303}ː").base(), ipa!("a"));
+        assert_eq!(ipa!("ɯ\u{339}").base(), ipa!("ɯ"));
+        assert!(ipa!("d̪ʲ").eq_base(&ipa!("d")));
+        assert!(!ipa!("t").eq_base(&ipa!("d")));
+        let word = parser::parse("t̪ʰat̠ː").unwrap();
+        let hits = word.iter().filter(|s| s.eq_base(&ipa!("t"))).count();
+        assert_eq!(hits, 2);
+    }
+
+    #[test]
+    fn chart_order() {
+        let mut segments = parser::parse("əuht͡ʃʃtʰdtnʼ").unwrap();
+        segments.push(ipa!("ˈ"));
+        segments.sort_by(Segment::chart_cmp);
+        let sorted: String = segments.iter().map(|s| s.to_string()).collect();
+        assert_eq!(sorted, "ttʰdnʼt͡ʃʃhuəˈ");
+        assert_eq!(ipa!("p").chart_cmp(&ipa!("a")), core::cmp::Ordering::Less);
+        assert_eq!(ipa!("i").chart_cmp(&ipa!("y")), core::cmp::Ordering::Less);
+    }
+
+    #[test]
+    fn inventory_gaps() {
+        let inventory: inventory::Inventory = parser::parse("ptkpʰtʰmnsʃzaiu")
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(inventory.gaps(), [ipa!("kʰ")]);
+        assert_eq!(inventory.symmetry(), 13.0 / 14.0);
+        let symmetric: inventory::Inventory =
+            parser::parse("ptkbdɡaiu").unwrap().into_iter().collect();
+        assert!(symmetric.gaps().is_empty());
+        assert_eq!(symmetric.symmetry(), 1.0);
+        let glottal: inventory::Inventory = parser::parse("ptkbdɡʔ").unwrap().into_iter().collect();
+        assert!(glottal.gaps().is_empty());
+        assert_eq!(inventory::Inventory::new().symmetry(), 1.0);
+    }
+
+    #[test]
+    fn inventory_typology() {
+        let inventory: inventory::Inventory = parser::parse("ptkpʰtʰkʰpʼɓǀmnsaeiouyaːã")
+            .unwrap()
+            .into_iter()
+            .collect();
+        let typology = inventory.typology();
+        assert_eq!(typology.consonants, 12);
+        assert_eq!(typology.vowels, 6);
+        assert_eq!(typology.laryngeal_series, 4);
+        assert!(typology.clicks && typology.front_rounded_vowels);
+        let categories: Vec<&str> = typology.compare().iter().map(|c| c.category).collect();
+        assert_eq!(
+            categories,
+            ["small", "average", "moderately low", "present", "present"]
+        );
+        assert!(typology.compare()[4].share < 0.02);
+    }
+
+    #[test]
+    fn inventory_similarity() {
+        let inventory =
+            |s| -> inventory::Inventory { parser::parse(s).unwrap().into_iter().collect() };
+        let english = inventory("ptkbdɡmnŋfvszʃʒhlrjwiɪeɛæəuʊoɔɑ");
+        let german = inventory("ptkbdɡmnŋfvszʃʒxhlrjiɪeɛəuʊoɔayøœ");
+        let hawaiian = inventory("pkʔmnhlwaeiou");
+        let a = english.similarity(&german);
+        assert!(a > english.similarity(&hawaiian));
+        assert_eq!(a, german.similarity(&english));
+        assert!((0.0..1.0).contains(&a));
+        assert_eq!(english.similarity(&inventory::Inventory::new()), 0.0);
+    }
+
+    #[test]
+    fn delimited_files() {
+        use delimited::{Column, Entry, Format, ParseErrorKind};
+        let format = Format::tsv().with_columns(&[Column::Gloss, Column::Ignore, Column::Phoneme]);
+        let entries = format
+            .read_entries("gloss\tnotes\tipa\ndog\tx\t[hʊnt]\n\ncat\t\t\n")
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].phonemes, parser::parse("hʊnt").unwrap());
+        assert_eq!(entries[1].phonemes, []);
+        assert_eq!(entries[1].grapheme, None);
+        let written = format.write_entries(&entries);
+        assert_eq!(written, "gloss\t\tphoneme\ndog\t\thʊnt\ncat\t\t\n");
+        assert_eq!(format.read_entries(&written).unwrap(), entries);
+        let quoted = Entry {
+            grapheme: Some("say \"hi\"".into()),
+            ..Entry::default()
+        };
+        let csv = Format::csv().with_header(false);
+        assert_eq!(
+            csv.write_entries(core::slice::from_ref(&quoted)),
+            "\"say \"\"hi\"\"\",,\n"
+        );
+        assert_eq!(csv.read_entries("\"say \"\"hi\"\"\",,").unwrap(), [quoted]);
+
+        let phonemes = Format::csv().with_columns(&[Column::Phoneme, Column::Grapheme]);
+        let inventory = phonemes
+            .read_inventory("phoneme,grapheme\nʃ,sh\np,p\n")
+            .unwrap();
+        assert_eq!(inventory.len(), 2);
+        assert_eq!(
+            phonemes.write_inventory(&inventory),
+            "phoneme,grapheme\np,\nʃ,\n"
+        );
+        let error = phonemes.read_inventory("phoneme\n\"p\n").unwrap_err();
+        assert_eq!(
+            (error.line, error.kind),
+            (2, ParseErrorKind::UnterminatedQuote)
+        );
+        let error = phonemes.read_inventory("phoneme\n,p\n").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::MissingPhoneme);
+    }
+
+    #[test]
+    fn orthography_profiles() {
+        use orthography::{ParseErrorKind, Profile, UnknownGrapheme};
+        let profile: Profile = "# Spanish, roughly\nGrapheme\tNotes\tIPA\nc\t\tk\nch\t\tt͡ʃ\nqu\t\tk\nn\t\tn\nñ\t\tɲ\na\t\ta\no\t\to\ni\t\ti\ne\t\te\ns\t\ts\nd$\t\tð\nd\t\td\n^h\t\tNULL\nh\t\th\n"
+            .parse()
+            .unwrap();
+        let words = profile.transliterate("niño hacha quesadid").unwrap();
+        let render = |w: &[Segment]| w.iter().map(|s| s.to_string()).collect::<String>();
+        let words: Vec<String> = words.iter().map(|w| render(w)).collect();
+        assert_eq!(words, ["niɲo", "at͡ʃa", "kesadið"]);
+        // Decomposed text matches a precomposed grapheme.
+        assert_eq!(
+            profile.segment("nin\u{303}o").unwrap(),
+            [["n", "i", "n\u{303}", "o"]]
+        );
+        assert_eq!(
+            profile.segment("casa xo"),
+            Err(UnknownGrapheme { offset: 5 })
+        );
+        let plain: Profile = "Grapheme\nʃ\na".parse().unwrap();
+        assert_eq!(render(&plain.transliterate("ʃaʃ").unwrap()[0]), "ʃaʃ");
+        let error = "Letter\tIPA\na\ta".parse::<Profile>().unwrap_err();
+        assert_eq!(
+            (error.line, error.kind),
+            (1, ParseErrorKind::MissingGrapheme)
+        );
+    }
+
+    #[test]
+    fn grapheme_to_phoneme() {
+        use g2p::{Dictionary, Transcriber};
+        let entries = delimited::Format::csv()
+            .read_entries("grapheme,phoneme,gloss\nyes,jɛs,\nno,,\n")
+            .unwrap();
+        let dictionary = Dictionary::from_entries(&entries);
+        assert_eq!(dictionary.len(), 1);
+        let profile: orthography::Profile = "Grapheme\tIPA\nn\tn\no\toʊ\ny\tj".parse().unwrap();
+        let transcriber: Box<dyn Transcriber> = Box::new(dictionary.or(&profile));
+        let words = transcriber.transcribe_text("yes no").unwrap();
+        assert_eq!(words[0], parser::parse("jɛs").unwrap());
+        assert_eq!(words[1], parser::parse("noʊ").unwrap());
+        assert_eq!(transcriber.transcribe_text("yes maybe"), None);
+        let devoiced = (&profile).then(vec!["[+son] → [−voice] / _ #".parse().unwrap()]);
+        assert_eq!(
+            devoiced.transcribe("no"),
+            Some(parser::parse("noʊ̥").unwrap())
+        );
+    }
+
+    #[test]
+    fn english_transcription() {
+        use english::{Dialect, English};
+        use g2p::Transcriber;
+        let render = |w: &[Segment]| w.iter().map(|s| s.to_string()).collect::<String>();
+        let ga = English::new(Dialect::GeneralAmerican);
+        let rp = English::new(Dialect::ReceivedPronunciation);
+        for (word, american, british) in [
+            ("ship", "ʃɪp", "ʃɪp"),
+            ("making", "meɪkɪŋ", "meɪkɪŋ"),
+            ("night", "naɪt", "naɪt"),
+            ("car", "kɑɹ", "kɑː"),
+            ("bird", "bɜ˞d", "bɜːd"),
+            ("here", "hiɹ", "hɪə"),
+            ("hot", "hɑt", "hɒt"),
+            ("snow", "snoʊ", "snəʊ"),
+            ("walked", "wɔkt", "wɔːkt"),
+            ("wanted", "wɑntɪd", "wɒntɪd"),
+            ("happy", "hæpi", "hæpi"),
+            ("the", "ðə", "ðə"),
+            ("Water", "wɔtə˞", "wɔːtə"),
+        ] {
+            let american = render(&parser::parse(american).unwrap());
+            let british = render(&parser::parse(british).unwrap());
+            assert_eq!(ga.transcribe(word).map(|w| render(&w)), Some(american));
+            assert_eq!(rp.transcribe(word).map(|w| render(&w)), Some(british));
+        }
+        assert_eq!(ga.transcribe("café"), None);
+        let yacht = parser::parse("jɑt").unwrap();
+        let ga = ga.with_exception("yacht", yacht.clone());
+        assert_eq!(ga.transcribe("yacht"), Some(yacht));
+        assert_eq!(ga.exceptions().len(), 1);
+    }
+
+    #[test]
+    fn cmu_dictionary() {
+        use g2p::Transcriber;
+        let text = ";;; CMUdict\nREAD  R IY1 D\nREAD(2)  R EH1 D\nyacht Y AA1 T # from the current release\n";
+        let lexicon: cmudict::Lexicon = text.parse().unwrap();
+        assert_eq!(lexicon.len(), 2);
+        assert_eq!(lexicon.words().collect::<Vec<_>>(), ["read", "yacht"]);
+        assert_eq!(lexicon.pronunciations("read").len(), 2);
+        assert_eq!(
+            lexicon.transcribe("YACHT"),
+            Some(parser::parse("jˈɑt").unwrap())
+        );
+        assert_eq!(
+            lexicon.to_dictionary().get("read"),
+            Some(&parser::parse("ɹˈid").unwrap()[..])
+        );
+        assert!(lexicon.arpabet("write").is_empty());
+        let error = "READ\n".parse::<cmudict::Lexicon>().unwrap_err();
+        assert_eq!(error.kind, cmudict::ParseErrorKind::MissingPronunciation);
+        let error = "A  AH0\nB  B XX1".parse::<cmudict::Lexicon>().unwrap_err();
+        assert_eq!(error.line, 2);
+    }
+
+    #[test]
+    fn espeak_mnemonics() {
+        assert_eq!(espeak::to_ipa("h@l'oU").unwrap(), "həlˈoʊ");
+        assert_eq!(espeak::to_ipa("%@b'aUt").unwrap(), "əbˈaʊt");
+        let segments = espeak::to_segments("tS'3:tS").unwrap();
+        assert_eq!(segments.len(), 4);
+        assert_eq!(espeak::from_segments(&segments).unwrap(), "tS'3:tS");
+        assert_eq!(espeak::from_ipa("ˈbɑːθ").unwrap(), "'bA:T");
+        let error = espeak::to_ipa("k&t").unwrap_err();
+        assert_eq!(error.offset, 1);
+        assert_eq!(
+            notation::convert(
+                "T'INk",
+                notation::Notation::Espeak,
+                notation::Notation::XSampa
+            )
+            .unwrap(),
+            "T\"INk"
+        );
+        let segments = parser::parse("t\u{361}\u{26C}\u{E6}p\u{2B0}\u{E6}").unwrap();
+        assert_eq!(espeak::from_segments(&segments).unwrap(), "tl#apha");
+    }
+
+    #[test]
+    fn sampa_tables() {
+        use sampa::Language;
+        assert_eq!(
+            sampa::to_ipa("\"vIn.t6", Language::German).unwrap(),
+            "ˈvɪn.tɐ"
+        );
+        assert_eq!(sampa::to_ipa("\"hu:s", Language::Dutch).unwrap(), "ˈɦuːs");
+        assert_eq!(sampa::to_ipa("bO~", Language::French).unwrap(), "bɔ̃");
+        let segments = sampa::to_segments("\"tsaIt", Language::German).unwrap();
+        assert_eq!(segments.len(), 5);
+        assert_eq!(
+            sampa::from_segments(&segments, Language::German).unwrap(),
+            "\"tsaIt"
+        );
+        // the same IPA in two languages' SAMPA
+        assert_eq!(sampa::from_ipa("ɹ", Language::English).unwrap(), "r");
+        assert_eq!(sampa::from_ipa("ɾ", Language::Spanish).unwrap(), "r");
+        assert!(sampa::to_ipa("{", Language::Spanish).is_err());
+    }
+
+    #[test]
+    fn cxs_notation() {
+        assert_eq!(cxs::to_ipa("t)S_h").unwrap(), "t͡ʃʰ");
+        assert_eq!(cxs::from_ipa("t͡ʃʰ").unwrap(), "t)S_h");
+        assert_eq!(xsampa::from_ipa("t͡ʃʰ").unwrap(), "t_S_h");
+        assert_eq!(cxs::TABLE.len(), xsampa::TABLE.len());
+        assert_eq!(cxs::to_ipa("&").unwrap(), "ɶ");
+        assert_eq!(cxs::from_ipa("ɶ").unwrap(), xsampa::from_ipa("ɶ").unwrap());
+        assert_eq!(
+            notation::convert("t_S", notation::Notation::XSampa, notation::Notation::Cxs).unwrap(),
+            "t)S"
+        );
+    }
+
+    #[test]
+    fn zsampa_additions() {
+        assert_eq!(zsampa::to_ipa("t_<a").unwrap(), "ƭa");
+        assert_eq!(zsampa::to_ipa("d`_<3`").unwrap(), "ᶑɜ\u{2DE}");
+        assert!(parser::parse(&zsampa::to_ipa("3`").unwrap()).is_ok());
+        assert!(xsampa::to_ipa("t_<a").is_err());
+        assert_eq!(zsampa::from_ipa("ƙʃ").unwrap(), "k_<S");
+        assert_eq!(zsampa::to_ipa("tS").unwrap(), xsampa::to_ipa("tS").unwrap());
+        assert_eq!(
+            notation::convert("ʠ", notation::Notation::Ipa, notation::Notation::ZSampa).unwrap(),
+            "q_<"
+        );
+    }
+
+    #[test]
+    fn americanist_notation() {
+        assert_eq!(americanist::to_ipa("šəx̣").unwrap(), "ʃəχ");
+        assert_eq!(americanist::to_ipa("k\u{313}ʷac").unwrap(), "kʼʷat͡s");
+        assert_eq!(americanist::to_ipa("ʒaža").unwrap(), "d͡zaʒa");
+        let segments = americanist::to_segments("ƛ'ą·").unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(americanist::from_segments(&segments).unwrap(), "ƛʼą·");
+        assert_eq!(americanist::from_ipa("ɲ").unwrap(), "n\u{303}");
+        assert_eq!(americanist::to_ipa("aꞵ").unwrap_err().offset, 1);
+    }
+
+    #[test]
+    fn uralic_phonetic_alphabet() {
+        assert_eq!(upa::to_ipa("ńāľ").unwrap_err().offset, 4);
+        assert_eq!(upa::to_ipa("ńāl\u{301}").unwrap(), "ɲaːʎ");
+        assert_eq!(upa::to_ipa("i̮χ").unwrap(), "ɨx");
+        assert!(parser::parse(&upa::to_ipa("łu").unwrap()).is_ok());
+        // small capitals are voiceless, not the IPA letters
+        let segments = upa::to_segments("ʟɪ").unwrap();
+        assert_eq!(segments, parser::parse("l̥i̥").unwrap());
+        assert_eq!(upa::from_segments(&segments).unwrap(), "ʟɪ");
+        assert_eq!(
+            notation::convert("ʃaː", notation::Notation::Ipa, notation::Notation::Upa).unwrap(),
+            "s\u{30C}a\u{304}"
+        );
+        let segments = parser::parse("\u{26C}a t\u{361}\u{26C}a p\u{2B0}a \u{E7}a").unwrap();
+        assert_eq!(
+            upa::from_segments(&segments).unwrap(),
+            "λatλap\u{2B0}aχ\u{301}a"
+        );
+        assert_eq!(
+            upa::to_segments("λa χ\u{301}a").unwrap(),
+            parser::parse("ɬa ça").unwrap()
+        );
+    }
+
+    #[test]
+    fn asjp_code() {
+        let code = |ipa: &str| asjp::from_ipa(ipa).unwrap();
+        assert_eq!(code("ˈhɛˌloʊ"), "helou");
+        assert_eq!(code("t͡sʰa ɖʒ"), "ch~a dZ");
+        assert_eq!(code("kʼɑ̃ ɬ ǀ ɲ n̪"), "k\"a* L ! 5 4");
+        assert_eq!(code("p͡f ʂ ç ʁ"), "pf~ S x X");
+        assert_eq!(asjp::to_ipa("kw~h~o*").unwrap(), "kʷʰo\u{303}");
+        assert_eq!(asjp::to_ipa("ndy$").unwrap(), "n͡dʲ");
+        let segments = asjp::to_segments("C3N").unwrap();
+        assert_eq!(asjp::from_segments(&segments), "C3N");
+        assert_eq!(asjp::to_ipa("~").unwrap_err().offset, 0);
+        assert!(asjp::to_ipa("aQ").is_err());
+    }
+
+    #[test]
+    fn dolgopolsky_classes() {
+        use crate::dolgopolsky::{self, Dolgopolsky};
+        let class = |s: &str| s.parse::<Segment>().unwrap().dolgopolsky_class();
+        assert_eq!(class("b"), Some(Dolgopolsky::P));
+        assert_eq!(class("ð"), Some(Dolgopolsky::T));
+        assert_eq!(class("ʃ"), Some(Dolgopolsky::S));
+        assert_eq!(class("q"), Some(Dolgopolsky::K));
+        assert_eq!(class("t͡s"), Some(Dolgopolsky::K));
+        assert_eq!(class("ɱ"), Some(Dolgopolsky::M));
+        assert_eq!(class("ŋ"), Some(Dolgopolsky::N));
+        assert_eq!(class("ɾ"), Some(Dolgopolsky::R));
+        assert_eq!(class("w"), Some(Dolgopolsky::W));
+        assert_eq!(class("j"), Some(Dolgopolsky::J));
+        assert_eq!(class("ʔ"), Some(Dolgopolsky::H));
+        assert_eq!(class("a"), None);
+        assert_eq!(class("ˈ"), None);
+        // Two words are candidate cognates if their first two classes agree.
+        let first_two = |s: &str| dolgopolsky::reduce(&parser::parse(s).unwrap())[..2].to_string();
+        assert_eq!(first_two("ˈsnjeɡ"), "SN");
+        assert_eq!(first_two("ˈʃnɛː"), first_two("ˈsnjeɡ"));
+        assert_ne!(first_two("ˈnoʊ̯"), first_two("ˈsnjeɡ"));
+    }
+
+    #[test]
+    fn lingpy_sound_classes() {
+        use crate::lingpy::{self, SoundClass};
+        let classes = |s: &str| lingpy::reduce(&parser::parse(s).unwrap());
+        assert_eq!(classes("fvθχɬ"), "BWDGL");
+        assert_eq!(classes("pʙbⱱ"), "PRPR");
+        assert_eq!(classes("yʊɔə"), "YUOE");
+        assert_eq!(classes("ma˨˩˦ma˥˩"), "MA6MA2");
+        assert_eq!(classes("ˈkwaː"), "KWA");
+        for class in SoundClass::ALL {
+            assert_eq!(SoundClass::from_symbol(class.symbol()), Some(class));
+        }
+        assert_eq!(ipa!("ʘ").lingpy_class(), Some(SoundClass::K));
+    }
+
+    #[test]
+    fn weighted_edit_distance() {
+        use align::{edit_distance, Costs, Edit};
+        let word = |s: &str| parser::parse(s).unwrap();
+        let s = |s: &str| s.parse::<Segment>().unwrap();
+        let (distance, script) = edit_distance(&word("pata"), &word("bat"), &Costs::default());
+        assert_eq!(distance, 1.0 + 3.0);
+        assert_eq!(
+            script,
+            [
+                Edit::Substitute(s("p"), s("b")),
+                Edit::Keep(s("a")),
+                Edit::Keep(s("t")),
+                Edit::Delete(s("a")),
+            ]
+        );
+        let costs = Costs {
+            deletion: 0.5,
+            ..Costs::default()
+        };
+        assert_eq!(edit_distance(&word("pata"), &word("bat"), &costs).0, 1.5);
+        let (distance, script) = edit_distance(&[], &word("ab"), &costs);
+        assert_eq!(distance, 6.0);
+        assert!(script.iter().all(|edit| matches!(edit, Edit::Insert(_))));
+        assert_eq!(edit_distance(&word("ab"), &word("ab"), &costs).0, 0.0);
+    }
+
+    #[test]
+    fn transcription_diff() {
+        use align::{diff, Change};
+        let word = |s: &str| parser::parse(s).unwrap();
+        let s = |s: &str| s.parse::<Segment>().unwrap();
+        let show = |a: &str, b: &str| {
+            diff(&word(a), &word(b))
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(show("ˈfiʃ", "ˈfis"), ["ʃ → s at position 3"]);
+        assert_eq!(show("kat", "kast"), ["s inserted at position 2"]);
+        assert_eq!(show("kast", "kat"), ["s deleted at position 2"]);
+        assert_eq!(show("mãt", "mat"), ["◌̃ deleted at position 1"]);
+        assert_eq!(show("pa", "pʰa"), ["ʰ added at position 0"]);
+        assert!(show("pater", "pater").is_empty());
+        assert_eq!(
+            diff(&word("ab"), &word("ap"))[0],
+            Change::Substituted {
+                position: 1,
+                from: s("b"),
+                to: s("p")
+            }
+        );
+    }
+
+    #[test]
+    fn cognate_scoring() {
+        use cognates::{score, Word};
+        use delimited::Entry;
+        let word = |s: &str| parser::parse(s).unwrap();
+        let entry = |gloss: &str, s: &str| Entry {
+            grapheme: None,
+            phonemes: word(s),
+            gloss: Some(gloss.to_string()),
+        };
+
+        let same = score(&word("ˈpater"), &word("pater"));
+        assert_eq!((same.similarity, same.class_match), (1.0, 1.0));
+        let near = score(&word("pater"), &word("fadar"));
+        let far = score(&word("manus"), &word("kheir"));
+        assert!(near.combined() > far.combined());
+        assert_eq!(far.class_match, 0.0);
+
+        let latin = [entry("father", "pater"), entry("hand", "manus")];
+        let gothic = [entry("father", "fadar"), entry("hand", "handus")];
+        let greek = [
+            entry("father", "pateːr"),
+            entry("hand", "kheir"),
+            entry("hand", ""),
+        ];
+        let lists: [&[Entry]; 3] = [&latin, &gothic, &greek];
+        let pairs = cognates::pairs(&lists);
+        assert_eq!(pairs.len(), 6);
+        assert_eq!(
+            (pairs[0].a, pairs[0].b),
+            (Word { list: 0, entry: 0 }, Word { list: 2, entry: 0 })
+        );
+        let sets = cognates::sets(&lists, 0.6);
+        assert_eq!(sets.len(), 2);
+        assert_eq!(sets[0].gloss, "father");
+        assert_eq!(sets[0].members.len(), 3);
+        assert_eq!(
+            sets[1].members,
+            [Word { list: 0, entry: 1 }, Word { list: 1, entry: 1 }]
+        );
+        assert!(cognates::sets(&lists, 1.1).is_empty());
+    }
+
+    #[test]
+    fn pinyin_conversion() {
+        use mandarin::{from_ipa, from_segments, to_ipa, to_segments};
+        assert_eq!(to_ipa("Běijīng").unwrap(), "peɪ̯˨˩˦t͡ɕiŋ˥˥");
+        assert_eq!(to_ipa("bei3jing1").unwrap(), "peɪ̯˨˩˦t͡ɕiŋ˥˥");
+        assert_eq!(to_ipa("nǚrén").unwrap(), "ny˨˩˦ɻən˧˥");
+        assert_eq!(to_ipa("lv3 nu:3 jue2").unwrap(), "ly˨˩˦ ny˨˩˦ t͡ɕɥɛ˧˥");
+        assert_eq!(to_ipa("shì zi ri4").unwrap(), "ʂɻ̩˥˩ t͡sɹ̩ ɻɻ̩˥˩");
+        assert_eq!(to_ipa("fangan").unwrap(), "fankan");
+        assert_eq!(to_ipa("Tiān'ānmén").unwrap(), "tʰjɛn˥˥an˥˥mən˧˥");
+        assert_eq!(to_ipa("Xīān").unwrap(), "ɕi˥˥an˥˥");
+        assert_eq!(to_ipa("fǎngǎn").unwrap(), "fan˨˩˦kan˨˩˦");
+        assert!(to_ipa("guāí").is_err());
+        assert_eq!(to_ipa("er4 huār kòngr").unwrap(), "ə˞˥˩ xwa˞˥˥ kʰʊ̃˞˥˩");
+        assert_eq!(to_ipa("ma5").unwrap(), "ma");
+        let error = to_ipa("hao3!").unwrap_err();
+        assert_eq!((error.offset, error.symbol), (4, '!'));
+        assert_eq!(to_ipa("zhx").unwrap_err().offset, 0);
+        let error = to_ipa("ma12").unwrap_err();
+        assert_eq!((error.offset, error.symbol), (3, '2'));
+        assert!(to_ipa("ma 3").is_err());
+        assert_eq!(mandarin::SYLLABLES[0], ("", "a", "", "a"));
+        assert!(mandarin::SYLLABLES.contains(&("j", "uan", "t͡ɕ", "ɥɛn")));
+
+        assert_eq!(from_ipa("ɕi˥˥an˥˥ ɕjɛn˥˥").unwrap(), "xī'ān xiān");
+        assert_eq!(from_ipa("kʰʊ̃˞˥˩").unwrap(), "kòngr");
+        assert!(from_ipa("ma˩˩˩").is_err());
+        let error = from_ipa("ˈmaq").unwrap_err();
+        assert_eq!((error.offset, error.symbol), (4, 'q'));
+        for word in ["zhōngguó", "shìjiè", "lǜsè", "yǒuyì", "xióngmāo"] {
+            assert_eq!(from_segments(&to_segments(word).unwrap()).unwrap(), word);
+        }
+    }
+
+    #[test]
+    fn kana_conversion() {
+        use japanese::{from_ipa, from_segments, to_ipa, to_segments, Script};
+        assert_eq!(to_ipa("とうきょう").unwrap(), "toːkʲoː");
+        assert_eq!(to_ipa("Tōkyō").unwrap(), "toːkʲoː");
+        assert_eq!(to_ipa("トウキョウ").unwrap(), "toːkʲoː");
+        assert_eq!(to_ipa("コーヒー").unwrap(), "koːçiː");
+        assert_eq!(to_ipa("せんせい sensei").unwrap(), "seɴseː seɴseː");
+        assert_eq!(
+            to_ipa("さんぽ けんか こんにちは").unwrap(),
+            "sampo keŋka koɲɲit͡ɕiha"
+        );
+        assert_eq!(to_ipa("kan'i").unwrap(), "kaɴi");
+        assert_eq!(to_ipa("がっこう matcha あっ").unwrap(), "ɡakkoː matt͡ɕa aʔ");
+        assert_eq!(to_ipa("si tya hu").unwrap(), "ɕi t͡ɕa ɸɯ");
+        assert_eq!(to_ipa("ファイル").unwrap(), "ɸaiɾɯ");
+        let error = to_ipa("ka2").unwrap_err();
+        assert_eq!((error.offset, error.symbol), (2, '2'));
+
+        assert_eq!(from_ipa("toːkʲoː", Script::Hiragana).unwrap(), "とうきょう");
+        assert_eq!(from_ipa("toːkʲoː", Script::Katakana).unwrap(), "トーキョー");
+        assert_eq!(from_ipa("ˈkitːe", Script::Hiragana).unwrap(), "きって");
+        assert_eq!(from_ipa("sushi", Script::Hiragana).unwrap_err().symbol, 's');
+        let error = from_ipa("gugq", Script::Hiragana).unwrap_err();
+        assert_eq!((error.offset, error.symbol), (2, 'g'));
+        let error = to_ipa("ヵ").unwrap_err();
+        assert_eq!((error.offset, error.symbol), (0, 'ヵ'));
+        assert_eq!(to_ipa("ぢゃ ぢゅ ぢょ").unwrap(), "d͡ʑa d͡ʑɯ d͡ʑo");
+        assert_eq!(to_ipa("ゐゑヰヱ").unwrap(), "ieie");
+        assert_eq!(from_ipa("haꜜɕi", Script::Hiragana).unwrap(), "はし");
+        assert_eq!(from_ipa("onːa", Script::Hiragana).unwrap(), "おんな");
+        assert_eq!(from_ipa("samːa", Script::Hiragana).unwrap(), "さんま");
+        assert_eq!(
+            from_ipa("koɲːit͡ɕiha", Script::Hiragana).unwrap(),
+            "こんにちは"
+        );
+        for word in ["にっぽん", "しんぶん", "ちょっと", "ゔぁいおりん"] {
+            let segments = to_segments(word).unwrap();
+            assert_eq!(from_segments(&segments, Script::Hiragana).unwrap(), word);
+        }
+    }
+
+    #[test]
+    fn hangul_conversion() {
+        use korean::{from_ipa, from_segments, to_ipa, to_segments, Transcription};
+        let phonetic = |s| to_ipa(s, Transcription::Phonetic).unwrap();
+        let phonemic = |s| to_ipa(s, Transcription::Phonemic).unwrap();
+        assert_eq!(phonetic("한국어"), "hanɡuɡʌ");
+        assert_eq!(phonemic("한국어"), "hankukʌ");
+        assert_eq!(phonetic("학교"), "hak̚k͈jo");
+        assert_eq!(phonemic("닭이 값을"), "talki kaps͈ɯl");
+        assert_eq!(phonemic("좋아요 좋고 축하"), "t͡ɕoajo t͡ɕokʰo t͡ɕʰukʰa");
+        assert_eq!(phonemic("신라 설날 독립 심리"), "silla sʌllal toŋnip simni");
+        assert_eq!(phonetic("사랑 시간 달력"), "saɾaŋ ɕiɡan talljʌk̚");
+        assert_eq!(phonetic("강아지"), "kaŋad͡ʑi");
+        assert_eq!(phonemic("앉다 넓다 읽어"), "ant͈a nʌlt͈a ilkʌ");
+        assert_eq!(phonemic("밟다 넓죽하다"), "papt͈a nʌpt͡ɕ͈ukʰata");
+        assert_eq!(phonemic("닫히다"), "tat͡ɕʰita");
+        let error = to_ipa("한a", Transcription::Phonemic).unwrap_err();
+        assert_eq!((error.offset, error.symbol), (3, 'a'));
+
+        assert_eq!(from_ipa("ˈsaɾaŋ").unwrap(), "사랑");
+        assert_eq!(from_ipa("hanɡuɡʌ").unwrap(), "한구거");
+        assert_eq!(from_ipa("spa").unwrap_err().symbol, 's');
+        let error = from_ipa("gaq").unwrap_err();
+        assert_eq!((error.offset, error.symbol), (2, 'q'));
+        assert_eq!(from_ipa("ˈspa").unwrap_err().offset, 2);
+        for word in ["괜찮아요", "없어요", "읽다"] {
+            let segments = to_segments(word, Transcription::Phonetic).unwrap();
+            let heard = from_ipa(&phonemic(word)).unwrap();
+            assert_eq!(from_segments(&segments).unwrap(), heard);
+        }
+    }
 }
@@ -0,0 +1,302 @@
+//! The SCA sound-class model of LingPy (List 2012), so that sound
+//! classes computed here can be compared with, and passed to, the
+//! alignments and cognate judgments of LingPy workflows. The model
+//! extends `dolgopolsky`'s classes, splitting them by manner and
+//! adding classes for vowels and tones.
+//!
+//! The model has 28 classes: 21 for consonants and vowels, each written
+//! with a capital letter, and 7 for tones, written with a digit, as
+//! LingPy's `tokens2class(tokens, "sca")` writes them:
+//!
+//! | Class | Sounds                     |   | Class | Sounds                 |
+//! |-------|----------------------------|---|-------|------------------------|
+//! | `A`   | open unrounded vowels      |   | `M`   | labial nasals          |
+//! | `B`   | labial fricatives          |   | `N`   | other nasals           |
+//! | `C`   | coronal affricates         |   | `O`   | rounded back vowels    |
+//! | `D`   | dental fricatives          |   | `P`   | labial plosives        |
+//! | `E`   | mid unrounded vowels       |   | `R`   | trills, taps and flaps |
+//! | `G`   | dorsal fricatives          |   | `S`   | sibilant fricatives    |
+//! | `H`   | laryngeals                 |   | `T`   | coronal plosives       |
+//! | `I`   | close unrounded vowels     |   | `U`   | close rounded vowels   |
+//! | `J`   | palatal approximants       |   | `W`   | labial approximants    |
+//! | `K`   | dorsal plosives and clicks |   | `Y`   | front rounded vowels   |
+//! | `L`   | laterals                   |   |       |                        |
+//!
+//! ```
+//! use allo::lingpy::{self, SoundClass};
+//! use allo::parser;
+//!
+//! let segments = parser::parse("ʈ͡ʂʰɤ˥˩").unwrap();
+//! assert_eq!(segments[0].lingpy_class(), Some(SoundClass::C));
+//! assert_eq!(segments[2].lingpy_class(), Some(SoundClass::FallingTone));
+//! assert_eq!(lingpy::reduce(&segments), "CE2");
+//! ```
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::affricate::Affricate;
+use crate::dolgopolsky::{self, Dolgopolsky};
+use crate::ipa::{Airstream, Articulation, Backness, Consonant, Height, Manner, Vowel};
+use crate::segment::Segment;
+use crate::tone::{Contour, Level};
+
+/// One of the SCA sound classes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SoundClass {
+    /// Open unrounded vowels.
+    A,
+    /// Labial fricatives.
+    B,
+    /// Coronal affricates.
+    C,
+    /// Dental fricatives.
+    D,
+    /// Mid unrounded vowels.
+    E,
+    /// Velar and uvular fricatives.
+    G,
+    /// Laryngeals.
+    H,
+    /// Close unrounded vowels.
+    I,
+    /// Palatal approximants.
+    J,
+    /// Velar, uvular and palatal plosives, and clicks.
+    K,
+    /// Lateral approximants and fricatives.
+    L,
+    /// Labial nasals.
+    M,
+    /// Other nasals.
+    N,
+    /// Rounded back and central vowels other than close ones.
+    O,
+    /// Labial plosives.
+    P,
+    /// Trills, taps and flaps, and rhotic approximants.
+    R,
+    /// Sibilant fricatives.
+    S,
+    /// Dental, alveolar and retroflex plosives.
+    T,
+    /// Close rounded back and central vowels.
+    U,
+    /// Labial approximants and the voiced labiodental fricative.
+    W,
+    /// Front rounded vowels.
+    Y,
+    /// Low level tones.
+    LowTone,
+    /// Rising tones.
+    RisingTone,
+    /// Falling tones.
+    FallingTone,
+    /// Mid level tones.
+    MidTone,
+    /// High level tones.
+    HighTone,
+    /// Short (checked) tones. A `Contour` has no duration, so
+    /// `lingpy_class` never gives this class; it is here for reading
+    /// LingPy's output.
+    ShortTone,
+    /// Tones that both rise and fall.
+    ComplexTone,
+}
+
+impl SoundClass {
+    /// Every class, in LingPy's order.
+    pub const ALL: [SoundClass; 28] = [
+        SoundClass::A,
+        SoundClass::B,
+        SoundClass::C,
+        SoundClass::D,
+        SoundClass::E,
+        SoundClass::G,
+        SoundClass::H,
+        SoundClass::I,
+        SoundClass::J,
+        SoundClass::K,
+        SoundClass::L,
+        SoundClass::M,
+        SoundClass::N,
+        SoundClass::O,
+        SoundClass::P,
+        SoundClass::R,
+        SoundClass::S,
+        SoundClass::T,
+        SoundClass::U,
+        SoundClass::W,
+        SoundClass::Y,
+        SoundClass::LowTone,
+        SoundClass::RisingTone,
+        SoundClass::FallingTone,
+        SoundClass::MidTone,
+        SoundClass::HighTone,
+        SoundClass::ShortTone,
+        SoundClass::ComplexTone,
+    ];
+
+    /// The character LingPy writes the class with: a letter, or a digit
+    /// from `0` to `6` for tones.
+    pub const fn symbol(&self) -> char {
+        match self {
+            SoundClass::A => 'A',
+            SoundClass::B => 'B',
+            SoundClass::C => 'C',
+            SoundClass::D => 'D',
+            SoundClass::E => 'E',
+            SoundClass::G => 'G',
+            SoundClass::H => 'H',
+            SoundClass::I => 'I',
+            SoundClass::J => 'J',
+            SoundClass::K => 'K',
+            SoundClass::L => 'L',
+            SoundClass::M => 'M',
+            SoundClass::N => 'N',
+            SoundClass::O => 'O',
+            SoundClass::P => 'P',
+            SoundClass::R => 'R',
+            SoundClass::S => 'S',
+            SoundClass::T => 'T',
+            SoundClass::U => 'U',
+            SoundClass::W => 'W',
+            SoundClass::Y => 'Y',
+            SoundClass::LowTone => '0',
+            SoundClass::RisingTone => '1',
+            SoundClass::FallingTone => '2',
+            SoundClass::MidTone => '3',
+            SoundClass::HighTone => '4',
+            SoundClass::ShortTone => '5',
+            SoundClass::ComplexTone => '6',
+        }
+    }
+
+    /// The class written with `c`, as in LingPy's output.
+    pub const fn from_symbol(c: char) -> Option<SoundClass> {
+        let mut i = 0;
+        while i < SoundClass::ALL.len() {
+            if SoundClass::ALL[i].symbol() == c {
+                return Some(SoundClass::ALL[i]);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Whether the class is one of the tone classes.
+    pub const fn is_tone(&self) -> bool {
+        matches!(
+            self,
+            SoundClass::LowTone
+                | SoundClass::RisingTone
+                | SoundClass::FallingTone
+                | SoundClass::MidTone
+                | SoundClass::HighTone
+                | SoundClass::ShortTone
+                | SoundClass::ComplexTone
+        )
+    }
+}
+
+impl fmt::Display for SoundClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// The SCA class of `segment`, or `None` for suprasegmentals.
+pub fn of(segment: &Segment) -> Option<SoundClass> {
+    match segment {
+        Segment::Consonant(c) => Some(consonant(c)),
+        Segment::Affricate(a) => Some(affricate(a)),
+        Segment::Vowel(v) => Some(vowel(v)),
+        Segment::Tone(t) => Some(tone(t)),
+        Segment::Suprasegmental(_) => None,
+    }
+}
+
+/// Refines the consonant's Dolgopolsky class, which the SCA model
+/// grew out of, by manner.
+fn consonant(c: &Consonant) -> SoundClass {
+    if c.airstream() == Airstream::VelaricIngressive {
+        return SoundClass::K;
+    }
+    let fricative = matches!(c.manner(), Manner::Fricative { .. });
+    match dolgopolsky::consonant(c) {
+        Dolgopolsky::P if !fricative => SoundClass::P,
+        Dolgopolsky::P
+            if c.articulation() == Articulation::Labiodental && c.phonation().is_voiced() =>
+        {
+            SoundClass::W
+        }
+        Dolgopolsky::P => SoundClass::B,
+        Dolgopolsky::T if fricative => SoundClass::D,
+        Dolgopolsky::T => SoundClass::T,
+        Dolgopolsky::K if c.manner() == (Manner::Fricative { sibilant: true }) => SoundClass::S,
+        Dolgopolsky::K if fricative => SoundClass::G,
+        Dolgopolsky::K => SoundClass::K,
+        Dolgopolsky::S => SoundClass::S,
+        Dolgopolsky::M => SoundClass::M,
+        Dolgopolsky::N => SoundClass::N,
+        Dolgopolsky::W if c.manner() == Manner::Approximant => SoundClass::W,
+        Dolgopolsky::W => SoundClass::R,
+        Dolgopolsky::J => SoundClass::J,
+        Dolgopolsky::H => SoundClass::H,
+        Dolgopolsky::R => match c.manner() {
+            Manner::LatFric | Manner::LatApprox | Manner::LatTapFlap => SoundClass::L,
+            _ => SoundClass::R,
+        },
+    }
+}
+
+/// Coronal affricates are `C`; others go by their stop.
+fn affricate(a: &Affricate) -> SoundClass {
+    match a.stop().articulation() {
+        Articulation::Bilabial
+        | Articulation::Labiodental
+        | Articulation::Velar
+        | Articulation::Uvular => consonant(&a.stop()),
+        _ => SoundClass::C,
+    }
+}
+
+fn vowel(v: &Vowel) -> SoundClass {
+    let front = matches!(v.backness(), Backness::Front | Backness::NearFront);
+    let close = matches!(v.height(), Height::Close | Height::NearClose);
+    match v.height() {
+        _ if v.is_rounded() && (front || close && v.backness() == Backness::Central) => {
+            SoundClass::Y
+        }
+        _ if v.is_rounded() && close => SoundClass::U,
+        _ if v.is_rounded() => SoundClass::O,
+        _ if close => SoundClass::I,
+        Height::Open => SoundClass::A,
+        Height::NearOpen if !front => SoundClass::A,
+        _ => SoundClass::E,
+    }
+}
+
+fn tone(t: &Contour) -> SoundClass {
+    match t.levels() {
+        [Level::ExtraLow | Level::Low] => SoundClass::LowTone,
+        [Level::Mid] => SoundClass::MidTone,
+        [_] => SoundClass::HighTone,
+        _ if t.is_rising() => SoundClass::RisingTone,
+        _ if t.is_falling() => SoundClass::FallingTone,
+        _ => SoundClass::ComplexTone,
+    }
+}
+
+/// Reduces `segments` to LingPy's SCA class string, leaving out stress,
+/// length and other suprasegmentals, as in `"CE2"` for `ʈ͡ʂʰɤ˥˩`.
+#[cfg(feature = "alloc")]
+pub fn reduce(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .filter_map(of)
+        .map(|class| class.symbol())
+        .collect()
+}
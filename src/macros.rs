@@ -0,0 +1,26 @@
+/// A segment literal, parsed at compile time so that invalid IPA fails
+/// the build rather than erroring at run time. The literal is read by
+/// `Segment::parse_const`, so affricates need their tie bar.
+///
+/// ```
+/// use allo::{ipa, Segment};
+///
+/// const CH: Segment = ipa!("t͡ʃʰ");
+/// assert_eq!(CH, "t͡ʃʰ".parse().unwrap());
+/// ```
+///
+/// ```compile_fail
+/// let _ = allo::ipa!("t͡ʃ7");
+/// ```
+#[macro_export]
+macro_rules! ipa {
+    ($symbol:literal) => {{
+        const SEGMENT: $crate::Segment = match $crate::Segment::parse_const($symbol) {
+            ::core::option::Option::Some(segment) => segment,
+            ::core::option::Option::None => {
+                ::core::panic!(::core::concat!("invalid IPA segment `", $symbol, "`"))
+            }
+        };
+        SEGMENT
+    }};
+}
@@ -0,0 +1,138 @@
+//! The `allo` command-line tool.
+
+use std::error::Error;
+use std::io::{self, BufRead};
+use std::process::ExitCode;
+
+use allo::notation::{self, Notation};
+use allo::{parser, Segment};
+
+const USAGE: &str = "\
+usage: allo describe <symbol>...
+       allo convert --from <notation> --to <notation> [text]
+       allo tokenize [text]
+
+Notations: ipa, xsampa, cxs, zsampa, kirshenbaum, arpabet, espeak,
+americanist, upa, asjp. Without text, convert and tokenize read lines
+from standard input.";
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("allo: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<()> {
+    let (command, rest) = args.split_first().ok_or(USAGE)?;
+    match command.as_str() {
+        "describe" if !rest.is_empty() => {
+            for symbol in rest {
+                describe(symbol)?;
+            }
+            Ok(())
+        }
+        "convert" => {
+            let mut from = None;
+            let mut to = None;
+            let mut text = Vec::new();
+            let mut rest = rest.iter();
+            while let Some(arg) = rest.next() {
+                match arg.as_str() {
+                    "--from" => from = rest.next(),
+                    "--to" => to = rest.next(),
+                    _ => text.push(arg.as_str()),
+                }
+            }
+            let from: Notation = from.ok_or(USAGE)?.parse()?;
+            let to: Notation = to.ok_or(USAGE)?.parse()?;
+            each_line(&text, |line| {
+                println!("{}", notation::convert(line, from, to)?);
+                Ok(())
+            })
+        }
+        "tokenize" => each_line(
+            &rest.iter().map(String::as_str).collect::<Vec<_>>(),
+            |line| {
+                println!("{}", parser::tokenize(line).join(" "));
+                Ok(())
+            },
+        ),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(USAGE.into()),
+    }
+}
+
+/// Runs `f` on `text` joined by spaces, or on each line of standard
+/// input if there is no text.
+fn each_line(text: &[&str], mut f: impl FnMut(&str) -> Result<()>) -> Result<()> {
+    if !text.is_empty() {
+        return f(&text.join(" "));
+    }
+    for line in io::stdin().lock().lines() {
+        f(&line?)?;
+    }
+    Ok(())
+}
+
+fn describe(symbol: &str) -> Result<()> {
+    let segment: Segment = symbol.parse()?;
+    println!("{}", segment);
+    println!("  {}", segment.name());
+    match segment {
+        Segment::Consonant(c) => {
+            println!("  consonant");
+            println!("  place: {}", c.place().name());
+            println!("  articulation: {}", c.articulation().name());
+            if let Some(second) = c.coarticulation() {
+                println!("  coarticulation: {}", second.name());
+            }
+            println!("  manner: {}", c.manner().name());
+            println!("  voicing: {}", c.phonation().name());
+            println!("  airstream: {}", c.airstream().name());
+            if let Some(secondary) = c.secondary() {
+                println!("  secondary articulation: {}", secondary.name());
+            }
+            if let Some(release) = c.release() {
+                println!("  release: {}", release.name());
+            }
+        }
+        Segment::Affricate(a) => {
+            println!("  affricate");
+            println!("  stop: {}", a.stop());
+            println!("  fricative: {}", a.fricative());
+        }
+        Segment::Vowel(v) => {
+            println!("  vowel");
+            println!("  height: {}", v.height().name());
+            println!("  backness: {}", v.backness().name());
+            println!("  roundedness: {}", v.roundedness().name());
+        }
+        Segment::Suprasegmental(s) => println!("  suprasegmental: {}", s.name()),
+        Segment::Tone(t) => println!("  tone: {}", t.chao()),
+    }
+    if let Some(length) = segment.length().filter(|l| !l.is_short()) {
+        println!("  length: {}", length.name());
+    }
+    let diacritics: Vec<String> = segment
+        .diacritics()
+        .iter()
+        .map(|d| d.name().to_string())
+        .collect();
+    if !diacritics.is_empty() {
+        println!("  diacritics: {}", diacritics.join(", "));
+    }
+    if let Some(features) = segment.features() {
+        println!("  features: {}", features);
+    }
+    Ok(())
+}
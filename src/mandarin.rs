@@ -0,0 +1,540 @@
+//! Hanyu Pinyin, the romanization of Standard Mandarin, and its IPA.
+//!
+//! A syllable is an initial consonant and a final, with its tone
+//! written as a mark over the vowel (`mā`) or a number after the
+//! syllable (`ma1`), and comes out a broad transcription with Chao tone
+//! letters: `˥˥`, `˧˥`, `˨˩˦` and `˥˩` for the four tones, and none for
+//! the neutral tone (`5`, `0` or no mark). Pinyin hides a few sounds
+//! behind its spelling, which are transcribed as spoken:
+//!
+//! * `ü` is `y`, and is written `u` after `j`, `q`, `x` and `y`
+//!   (`ju`, `yue`); `v` and `u:` are read as `ü`.
+//! * `i` after `z`, `c` and `s` is the syllabic `ɹ̩`, and after `zh`,
+//!   `ch`, `sh` and `r` the syllabic `ɻ̩`.
+//! * An `r` after a syllable (erhua) makes its final rhotic, dropping a
+//!   final `-n` or `-i` and nasalizing the vowel before a final `-ng`.
+//!
+//! Syllables run together are split as Pinyin's apostrophe rule implies:
+//! a syllable beginning with `a`, `o` or `e` is only read after another
+//! when an apostrophe separates them, so `xian` is one syllable and
+//! `xi'an` two.
+//!
+//! ```
+//! use allo::mandarin;
+//!
+//! assert_eq!(mandarin::to_ipa("zhōngguó").unwrap(), "ʈ͡ʂʊŋ˥˥kwo˧˥");
+//! assert_eq!(mandarin::to_ipa("lü4 si1").unwrap(), "ly˥˩ sɹ̩˥˥");
+//! assert_eq!(mandarin::to_ipa("wánr").unwrap(), "wa˞˧˥");
+//! assert_eq!(mandarin::from_ipa("ɕi˥˥an˥˥").unwrap(), "xī'ān");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::notation::UnknownSymbol;
+use crate::parser;
+use crate::reading::{self, Reader};
+use crate::segment::{ParseSegmentError, Segment};
+
+/// The initials and their IPA.
+pub const INITIALS: [(&str, &str); 21] = [
+    ("b", "p"),
+    ("p", "pʰ"),
+    ("m", "m"),
+    ("f", "f"),
+    ("d", "t"),
+    ("t", "tʰ"),
+    ("n", "n"),
+    ("l", "l"),
+    ("g", "k"),
+    ("k", "kʰ"),
+    ("h", "x"),
+    ("j", "t͡ɕ"),
+    ("q", "t͡ɕʰ"),
+    ("x", "ɕ"),
+    ("zh", "ʈ͡ʂ"),
+    ("ch", "ʈ͡ʂʰ"),
+    ("sh", "ʂ"),
+    ("r", "ɻ"),
+    ("z", "t͡s"),
+    ("c", "t͡sʰ"),
+    ("s", "s"),
+];
+
+/// The finals as written after an initial, and their IPA. `ü` finals
+/// are listed as written after `n` and `l`.
+pub const FINALS: [(&str, &str); 35] = [
+    ("a", "a"),
+    ("o", "o"),
+    ("e", "ɤ"),
+    ("ai", "aɪ̯"),
+    ("ei", "eɪ̯"),
+    ("ao", "ɑʊ̯"),
+    ("ou", "oʊ̯"),
+    ("an", "an"),
+    ("en", "ən"),
+    ("ang", "ɑŋ"),
+    ("eng", "ɤŋ"),
+    ("ong", "ʊŋ"),
+    ("i", "i"),
+    ("ia", "ja"),
+    ("ie", "jɛ"),
+    ("iao", "jɑʊ̯"),
+    ("iu", "joʊ̯"),
+    ("ian", "jɛn"),
+    ("in", "in"),
+    ("iang", "jɑŋ"),
+    ("ing", "iŋ"),
+    ("iong", "jʊŋ"),
+    ("u", "u"),
+    ("ua", "wa"),
+    ("uo", "wo"),
+    ("uai", "waɪ̯"),
+    ("ui", "weɪ̯"),
+    ("uan", "wan"),
+    ("un", "wən"),
+    ("uang", "wɑŋ"),
+    ("ü", "y"),
+    ("üe", "ɥɛ"),
+    ("üan", "ɥɛn"),
+    ("ün", "yn"),
+    ("er", "ə˞"),
+];
+
+/// The syllables without an initial, spelled with `y` and `w` where
+/// the final begins with `i`, `u` or `ü`.
+pub const ZERO_INITIAL: [(&str, &str); 35] = [
+    ("a", "a"),
+    ("o", "o"),
+    ("e", "ɤ"),
+    ("ai", "aɪ̯"),
+    ("ei", "eɪ̯"),
+    ("ao", "ɑʊ̯"),
+    ("ou", "oʊ̯"),
+    ("an", "an"),
+    ("en", "ən"),
+    ("ang", "ɑŋ"),
+    ("eng", "ɤŋ"),
+    ("er", "ə˞"),
+    ("yi", "i"),
+    ("ya", "ja"),
+    ("ye", "jɛ"),
+    ("yao", "jɑʊ̯"),
+    ("you", "joʊ̯"),
+    ("yan", "jɛn"),
+    ("yin", "in"),
+    ("yang", "jɑŋ"),
+    ("ying", "iŋ"),
+    ("yong", "jʊŋ"),
+    ("wu", "u"),
+    ("wa", "wa"),
+    ("wo", "wo"),
+    ("wai", "waɪ̯"),
+    ("wei", "weɪ̯"),
+    ("wan", "wan"),
+    ("wen", "wən"),
+    ("wang", "wɑŋ"),
+    ("weng", "wɤŋ"),
+    ("yu", "y"),
+    ("yue", "ɥɛ"),
+    ("yuan", "ɥɛn"),
+    ("yun", "yn"),
+];
+
+/// The Chao tone letters of the four tones.
+pub const TONES: [&str; 4] = ["˥˥", "˧˥", "˨˩˦", "˥˩"];
+
+/// The vowels with each tone mark, first to fourth.
+const MARKED: [(char, [char; 4]); 6] = [
+    ('a', ['ā', 'á', 'ǎ', 'à']),
+    ('e', ['ē', 'é', 'ě', 'è']),
+    ('i', ['ī', 'í', 'ǐ', 'ì']),
+    ('o', ['ō', 'ó', 'ǒ', 'ò']),
+    ('u', ['ū', 'ú', 'ǔ', 'ù']),
+    ('ü', ['ǖ', 'ǘ', 'ǚ', 'ǜ']),
+];
+
+/// Combining tone marks, first to fourth.
+const MARKS: [char; 4] = ['\u{304}', '\u{301}', '\u{30C}', '\u{300}'];
+
+/// Every syllable, zero-initial syllables first, as its initial and
+/// final as written and the IPA of each. Zero-initial syllables have
+/// an empty initial.
+pub const SYLLABLES: &[(&str, &str, &str, &str)] = &syllables();
+
+const fn syllables() -> [(&'static str, &'static str, &'static str, &'static str); LEN] {
+    let mut syllables = [("", "", "", ""); LEN];
+    let mut n = 0;
+    while n < ZERO_INITIAL.len() {
+        let (pinyin, ipa) = ZERO_INITIAL[n];
+        syllables[n] = ("", pinyin, "", ipa);
+        n += 1;
+    }
+    let mut i = 0;
+    while i < INITIALS.len() {
+        let mut f = 0;
+        while f < FINALS.len() {
+            if let Some(syllable) = syllable(i, f) {
+                syllables[n] = syllable;
+                n += 1;
+            }
+            f += 1;
+        }
+        i += 1;
+    }
+    syllables
+}
+
+const LEN: usize = {
+    let mut len = ZERO_INITIAL.len();
+    let mut i = 0;
+    while i < INITIALS.len() {
+        let mut f = 0;
+        while f < FINALS.len() {
+            if syllable(i, f).is_some() {
+                len += 1;
+            }
+            f += 1;
+        }
+        i += 1;
+    }
+    len
+};
+
+/// The syllable of the `i`th initial and `f`th final, or `None` if
+/// they do not combine.
+const fn syllable(
+    i: usize,
+    f: usize,
+) -> Option<(&'static str, &'static str, &'static str, &'static str)> {
+    let (initial, consonant) = INITIALS[i];
+    let (final_, vowel) = FINALS[f];
+    let front = starts_with(final_, "i");
+    let rounded = starts_with(final_, "ü");
+    let sibilant = matches!(initial.as_bytes(), b"z" | b"c" | b"s");
+    let retroflex = matches!(initial.as_bytes(), b"zh" | b"ch" | b"sh" | b"r");
+    let palatal = matches!(initial.as_bytes(), b"j" | b"q" | b"x");
+    let apical = eq(final_, "i") && (sibilant || retroflex);
+    let dorsal = matches!(initial.as_bytes(), b"g" | b"k" | b"h");
+    let labial_or_dental = matches!(initial.as_bytes(), b"b" | b"p" | b"m" | b"f" | b"d" | b"t");
+    if eq(final_, "er")
+        || palatal && !front && !rounded
+        || (dorsal || sibilant || retroflex) && (front || rounded) && !apical
+        || labial_or_dental && rounded
+    {
+        return None;
+    }
+    let vowel = match (apical, sibilant) {
+        (true, true) => "ɹ̩",
+        (true, false) => "ɻ̩",
+        (false, _) => vowel,
+    };
+    // ü is written u after j, q and x
+    let final_ = if !(palatal && rounded) {
+        final_
+    } else if eq(final_, "ü") {
+        "u"
+    } else if eq(final_, "üe") {
+        "ue"
+    } else if eq(final_, "ün") {
+        "un"
+    } else {
+        "uan"
+    };
+    Some((initial, final_, consonant, vowel))
+}
+
+const fn starts_with(s: &str, prefix: &str) -> bool {
+    let (s, prefix) = (s.as_bytes(), prefix.as_bytes());
+    if s.len() < prefix.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < prefix.len() {
+        if s[i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && starts_with(a, b)
+}
+
+/// Converts Pinyin to IPA. Whitespace is copied through; apostrophes
+/// and hyphens only separate syllables. A tone number not right after
+/// a syllable, as the second digit of `ma12`, is an unknown symbol.
+pub fn to_ipa(pinyin: &str) -> Result<String, UnknownSymbol> {
+    let mut out = String::new();
+    // the letters of the current run of syllables, each with its offset
+    // and the tone marked on it
+    let mut run: Vec<(char, usize, Option<u8>)> = Vec::new();
+    let mut chars = pinyin.char_indices().peekable();
+    while let Some((offset, c)) = chars.next() {
+        let c = c.to_lowercase().next().unwrap_or(c);
+        let error = UnknownSymbol { offset, symbol: c };
+        match c {
+            'a'..='z' | 'ü' if c != 'v' => {
+                let c = match chars.peek() {
+                    Some((_, '\u{308}')) if c == 'u' => {
+                        chars.next();
+                        'ü'
+                    }
+                    Some((_, ':')) if c == 'u' => {
+                        chars.next();
+                        'ü'
+                    }
+                    _ => c,
+                };
+                run.push((c, offset, None));
+            }
+            'v' => run.push(('ü', offset, None)),
+            c if MARKS.contains(&c) => {
+                let last = run.last_mut().ok_or(error)?;
+                last.2 = MARKS.iter().position(|&m| m == c).map(|t| t as u8 + 1);
+            }
+            c => match MARKED.iter().find_map(|(v, marked)| {
+                let tone = marked.iter().position(|&m| m == c)?;
+                Some((*v, tone as u8 + 1))
+            }) {
+                Some((vowel, tone)) => run.push((vowel, offset, Some(tone))),
+                None => {
+                    let tone = c.to_digit(10).filter(|&d| d <= 5).map(|d| d as u8);
+                    let separator = c.is_whitespace() || matches!(c, '\'' | '’' | '-');
+                    // a tone number follows a syllable, and only one
+                    if !separator && (tone.is_none() || run.is_empty()) {
+                        return Err(error);
+                    }
+                    syllabify(&run, tone, &mut out)?;
+                    run.clear();
+                    if c.is_whitespace() {
+                        out.push(c);
+                    }
+                }
+            },
+        }
+    }
+    syllabify(&run, None, &mut out)?;
+    Ok(out)
+}
+
+/// Splits `run` into syllables and writes them as IPA, `tone` (a tone
+/// number written after the run) going on the last.
+fn syllabify(
+    run: &[(char, usize, Option<u8>)],
+    tone: Option<u8>,
+    out: &mut String,
+) -> Result<(), UnknownSymbol> {
+    let Some(&(first, offset, _)) = run.first() else {
+        return Ok(());
+    };
+    let lengths = split(run, false)
+        .or_else(|| split(run, true))
+        .ok_or(UnknownSymbol {
+            offset,
+            symbol: first,
+        })?;
+    let mut start = 0;
+    for (i, &len) in lengths.iter().enumerate() {
+        let part = &run[start..start + len];
+        start += len;
+        let spelled: String = part.iter().map(|(c, ..)| *c).collect();
+        let ipa = ipa(&spelled).unwrap_or_default();
+        out.push_str(&ipa);
+        let marked = part.iter().find_map(|(.., t)| *t);
+        let tone = if i + 1 == lengths.len() {
+            tone.or(marked)
+        } else {
+            marked
+        };
+        if let Some(letters) = tone.and_then(|t| TONES.get(usize::from(t).checked_sub(1)?)) {
+            out.push_str(letters);
+        }
+    }
+    Ok(())
+}
+
+/// The lengths of the syllables the letters of `run` split into,
+/// longest first, or `None` if they do not spell syllables. A syllable
+/// has at most one tone mark, and one beginning with `a`, `o` or `e`
+/// needs an apostrophe before it unless `marked` and the tone marks
+/// show where it begins, as in `xīān`.
+fn split(run: &[(char, usize, Option<u8>)], marked: bool) -> Option<Vec<usize>> {
+    if run.is_empty() {
+        return Some(Vec::new());
+    }
+    (1..=run.len().min(7)).rev().find_map(|len| {
+        let (syllable, rest) = run.split_at(len);
+        let spelled: String = syllable.iter().map(|(c, ..)| *c).collect();
+        ipa(&spelled)?;
+        if syllable.iter().filter(|(.., t)| t.is_some()).count() > 1 {
+            return None;
+        }
+        if matches!(rest.first(), Some(('a' | 'o' | 'e', ..))) && !marked {
+            return None;
+        }
+        let mut lengths = split(rest, marked)?;
+        lengths.insert(0, len);
+        Some(lengths)
+    })
+}
+
+/// The IPA of one syllable without its tone, with erhua.
+fn ipa(spelled: &str) -> Option<String> {
+    let find = |s: &str| {
+        SYLLABLES
+            .iter()
+            .find(|(initial, final_, ..)| s.strip_prefix(initial) == Some(final_))
+            .map(|(.., consonant, vowel)| [*consonant, *vowel].concat())
+    };
+    if let Some(ipa) = find(spelled).or_else(|| find(&spelled.replace('ü', "u"))) {
+        return Some(ipa);
+    }
+    let base = spelled.strip_suffix('r')?;
+    let ipa = find(base).or_else(|| find(&base.replace('ü', "u")))?;
+    (ipa != "ə˞").then(|| rhotic(&ipa))
+}
+
+/// The erhua form of a syllable's IPA.
+fn rhotic(ipa: &str) -> String {
+    if ipa.ends_with("ɹ̩") || ipa.ends_with("ɻ̩") {
+        let apical = ipa.char_indices().nth_back(1).map_or(0, |(i, _)| i);
+        return [&ipa[..apical], "ə˞"].concat();
+    }
+    let (vowels, nasal) = match ipa.strip_suffix('ŋ') {
+        Some(rest) => (rest, true),
+        None => (
+            ipa.strip_suffix('n')
+                .or_else(|| ipa.strip_suffix("ɪ̯"))
+                .unwrap_or(ipa),
+            false,
+        ),
+    };
+    let mut out = match vowels.strip_suffix('e') {
+        Some(rest) => [rest, "ə"].concat(),
+        None => vowels.to_string(),
+    };
+    if out.ends_with(['i', 'y']) {
+        out.push('ə');
+    }
+    if nasal {
+        out.push('\u{303}');
+    }
+    out.push('˞');
+    out
+}
+
+/// Reads Pinyin directly into segments.
+pub fn to_segments(pinyin: &str) -> Result<Vec<Segment>, ParseSegmentError> {
+    parser::parse(&to_ipa(pinyin)?)
+}
+
+/// Writes IPA as Pinyin with tone marks, word by word. Syllables are
+/// matched longest first against `SYLLABLES` and their erhua forms,
+/// each followed by its tone letters if any. Erhua merges finals, so a
+/// rhotic syllable is read as the first that sounds so: `wa˞` is `war`,
+/// not `wanr`. Stress marks and syllable breaks are left out.
+pub fn from_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    let erhua: Vec<(String, String)> = SYLLABLES
+        .iter()
+        .filter(|(.., vowel)| *vowel != "ə˞")
+        .map(|&(initial, final_, consonant, vowel)| {
+            (
+                [initial, final_, "r"].concat(),
+                rhotic(&[consonant, vowel].concat()),
+            )
+        })
+        .collect();
+    let mut reader = Reader::new(s, &[], |c| c);
+    let mut out = String::new();
+    let mut previous = false;
+    while let Some(c) = reader.peek() {
+        if c.is_whitespace() {
+            out.push(c);
+            reader.advance(c.len_utf8());
+            previous = false;
+            continue;
+        }
+        let rest = reader.rest();
+        let plain = SYLLABLES
+            .iter()
+            .filter(|(.., consonant, vowel)| {
+                rest.strip_prefix(consonant)
+                    .is_some_and(|r| r.starts_with(vowel))
+            })
+            .map(|(initial, final_, consonant, vowel)| {
+                (consonant.len() + vowel.len(), [*initial, *final_].concat())
+            });
+        let rhotic = erhua
+            .iter()
+            .filter(|(_, ipa)| rest.starts_with(ipa.as_str()))
+            .map(|(pinyin, ipa)| (ipa.len(), pinyin.clone()));
+        let (len, pinyin) = reader.longest(plain.chain(rhotic))?;
+        reader.advance(len);
+        let at = reader.position();
+        let letters: String = reader
+            .rest()
+            .chars()
+            .take_while(|c| is_tone_letter(*c))
+            .collect();
+        reader.advance(letters.len());
+        let tone = match &letters[..] {
+            "" => None,
+            "˥" => Some(1),
+            "˨˩" => Some(3),
+            letters => Some(
+                TONES
+                    .iter()
+                    .position(|t| *t == letters)
+                    .ok_or_else(|| reader.error_at(at))? as u8
+                    + 1,
+            ),
+        };
+        if previous && pinyin.starts_with(['a', 'o', 'e']) {
+            out.push('\'');
+        }
+        out.push_str(&mark(&pinyin, tone));
+        previous = true;
+    }
+    Ok(out)
+}
+
+/// Writes `segments` as Pinyin.
+pub fn from_segments(segments: &[Segment]) -> Result<String, UnknownSymbol> {
+    from_ipa(&reading::ipa(segments))
+}
+
+fn is_tone_letter(c: char) -> bool {
+    matches!(c, '˥' | '˦' | '˧' | '˨' | '˩')
+}
+
+/// Puts the mark of `tone` on the vowel of `pinyin` that carries it:
+/// `a` or `e` if there is one, the `o` of `ou`, and otherwise the last
+/// vowel.
+fn mark(pinyin: &str, tone: Option<u8>) -> String {
+    let Some(tone) = tone else {
+        return pinyin.to_string();
+    };
+    let vowel = |c: char| MARKED.iter().any(|(v, _)| *v == c);
+    let at = pinyin
+        .find(['a', 'e'])
+        .or_else(|| pinyin.find("ou"))
+        .or_else(|| pinyin.rfind(vowel));
+    match at {
+        Some(at) => {
+            let c = pinyin[at..].chars().next().unwrap();
+            let (_, marked) = MARKED.iter().find(|(v, _)| *v == c).unwrap();
+            [
+                &pinyin[..at],
+                &marked[usize::from(tone) - 1].to_string(),
+                &pinyin[at + c.len_utf8()..],
+            ]
+            .concat()
+        }
+        None => pinyin.to_string(),
+    }
+}
@@ -0,0 +1,166 @@
+//! Stress assignment by metrical feet: syllables are grouped into
+//! trochees or iambs from one edge of the word, and each foot's head
+//! is stressed.
+
+use core::ops::Range;
+
+use alloc::vec::Vec;
+
+use crate::segment::Segment;
+use crate::suprasegmentals::Stress;
+use crate::syllable::{self, Syllable, Weight, WeightCriteria, Word};
+
+/// The shape of a foot.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Foot {
+    /// Stress on the first syllable: `ˈσσ`.
+    Trochee,
+    /// Stress on the second syllable: `σˈσ`.
+    Iamb,
+}
+
+/// An edge of the word.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Edge {
+    Left,
+    Right,
+}
+
+/// A stress system: how feet are built over a word's syllables and
+/// which of them bears primary stress. The other feet's heads get
+/// secondary stress.
+///
+/// ```
+/// use allo::metrical::Meter;
+///
+/// let word = allo::parser::parse("amiːkus").unwrap();
+/// assert_eq!(Meter::LATIN.stress(&word).to_string(), "aˈmiː.kus");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Meter {
+    pub foot: Foot,
+    /// The edge feet are built from.
+    pub edge: Edge,
+    /// Whether the syllable at `edge` is left out of the feet, unless
+    /// it is the only one.
+    pub extrametrical: bool,
+    /// What makes a syllable heavy, when foot building counts weight.
+    /// A heavy syllable is never the weak member of a foot; it forms a
+    /// foot alone if it can't head one, and a trochee must be two
+    /// light syllables.
+    pub quantity: Option<WeightCriteria>,
+    /// Whether a light syllable left over can form a foot by itself.
+    pub degenerate: bool,
+    /// The edge whose nearest foot bears primary stress.
+    pub main: Edge,
+}
+
+impl Meter {
+    /// Latin: moraic trochees from the right with the final syllable
+    /// extrametrical, so the penult is stressed if heavy and the
+    /// antepenult otherwise.
+    pub const LATIN: Meter = Meter {
+        extrametrical: true,
+        quantity: Some(WeightCriteria::LATIN),
+        ..Meter::new(Foot::Trochee, Edge::Right)
+    };
+
+    /// Warlpiri: syllabic trochees from the left, giving primary stress
+    /// on the first syllable and secondary stress on every other one
+    /// after it, except a final one.
+    pub const WARLPIRI: Meter = Meter::new(Foot::Trochee, Edge::Left);
+
+    /// Binary feet of `foot` built from `edge`, ignoring weight, with
+    /// no extrametricality or degenerate feet, and primary stress on
+    /// the foot nearest `edge`.
+    pub const fn new(foot: Foot, edge: Edge) -> Self {
+        Meter {
+            foot,
+            edge,
+            extrametrical: false,
+            quantity: None,
+            degenerate: false,
+            main: edge,
+        }
+    }
+
+    /// The feet over `syllables`, as ranges of indices in order from
+    /// left to right.
+    pub fn feet(&self, syllables: &[Syllable]) -> Vec<Range<usize>> {
+        let n = syllables.len();
+        let mut order: Vec<usize> = match self.edge {
+            Edge::Left => (0..n).collect(),
+            Edge::Right => (0..n).rev().collect(),
+        };
+        if self.extrametrical && n > 1 {
+            order.remove(0);
+        }
+        let heavy = |i: usize| {
+            self.quantity
+                .is_some_and(|c| syllables[i].weight(&c) != Weight::Light)
+        };
+        let mut feet = Vec::new();
+        let mut k = 0;
+        while k < order.len() {
+            let x = order[k];
+            if let Some(&y) = order.get(k + 1) {
+                let (left, right) = (x.min(y), x.max(y));
+                let (head, weak) = match self.foot {
+                    Foot::Trochee => (left, right),
+                    Foot::Iamb => (right, left),
+                };
+                let binary = !heavy(weak) && (self.foot == Foot::Iamb || !heavy(head));
+                if binary {
+                    feet.push(left..right + 1);
+                    k += 2;
+                    continue;
+                }
+            }
+            if heavy(x) || self.degenerate {
+                feet.push(x..x + 1);
+            }
+            k += 1;
+        }
+        feet.sort_by_key(|f| f.start);
+        feet
+    }
+
+    /// Sets the stress of each of `syllables`, replacing any they had.
+    /// If no foot can be built, the syllable nearest `edge` that is
+    /// not extrametrical gets primary stress.
+    pub fn assign(&self, syllables: &mut [Syllable]) {
+        for syllable in syllables.iter_mut() {
+            syllable.stress = None;
+        }
+        let n = syllables.len();
+        if n == 0 {
+            return;
+        }
+        let feet = self.feet(syllables);
+        let head = |foot: &Range<usize>| match self.foot {
+            Foot::Trochee => foot.start,
+            Foot::Iamb => foot.end - 1,
+        };
+        for foot in &feet {
+            syllables[head(foot)].stress = Some(Stress::Secondary);
+        }
+        let skip = usize::from(self.extrametrical && n > 1);
+        let main = match self.main {
+            Edge::Left => feet.first().map(head),
+            Edge::Right => feet.last().map(head),
+        };
+        let main = main.unwrap_or(match self.edge {
+            Edge::Left => skip,
+            Edge::Right => n - 1 - skip,
+        });
+        syllables[main].stress = Some(Stress::Primary);
+    }
+
+    /// Syllabifies `word` as `syllable::syllabify` does and stresses
+    /// it, replacing any stress it was marked with.
+    pub fn stress(&self, word: &[Segment]) -> Word {
+        let mut syllables = syllable::syllabify(word);
+        self.assign(&mut syllables);
+        Word::new(syllables)
+    }
+}
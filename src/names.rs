@@ -0,0 +1,477 @@
+//! Prose names for IPA symbols, e.g. `ɬ` is the voiceless alveolar
+//! lateral fricative.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::affricate::Affricate;
+use crate::diacritics::{Diacritic, Diacritics};
+use crate::ipa::{Airstream, Consonant, Manner, Phonation, Place, Vowel};
+use crate::segment::Segment;
+use crate::suprasegmentals::{Length, Suprasegmental};
+use crate::tone::{Contour, Level};
+
+/// Every symbol in `ipa::PULMONIC`, `graphemes::AFFRICATES`,
+/// `ipa::CLICKS`, `ipa::IMPLOSIVES`, `ipa::COARTICULATED` and
+/// `ipa::VOWELS`, and the labial-velar stops and nasal, paired with
+/// its name as given in the IPA handbook. Voicing is named for every
+/// pulmonic consonant except the epiglottal and glottal plosives,
+/// which can only be voiceless.
+#[rustfmt::skip]
+pub const NAMES: &[(&str, &str)] = &[
+    ("\u{70}", "voiceless bilabial plosive"), // p
+    ("\u{62}", "voiced bilabial plosive"), // b
+    ("\u{70}\u{32A}", "voiceless labiodental plosive"), // p̪
+    ("\u{62}\u{32A}", "voiced labiodental plosive"), // b̪
+    ("\u{74}\u{33C}", "voiceless linguolabial plosive"), // t̼
+    ("\u{64}\u{33C}", "voiced linguolabial plosive"), // d̼
+    ("\u{74}\u{32A}", "voiceless dental plosive"), // t̪
+    ("\u{64}\u{32A}", "voiced dental plosive"), // d̪
+    ("\u{74}", "voiceless alveolar plosive"), // t
+    ("\u{64}", "voiced alveolar plosive"), // d
+    ("\u{288}", "voiceless retroflex plosive"), // ʈ
+    ("\u{256}", "voiced retroflex plosive"), // ɖ
+    ("\u{63}", "voiceless palatal plosive"), // c
+    ("\u{25F}", "voiced palatal plosive"), // ɟ
+    ("\u{6B}", "voiceless velar plosive"), // k
+    ("\u{261}", "voiced velar plosive"), // ɡ
+    ("\u{71}", "voiceless uvular plosive"), // q
+    ("\u{262}", "voiced uvular plosive"), // ɢ
+    ("\u{2A1}", "epiglottal plosive"), // ʡ
+    ("\u{294}", "glottal plosive"), // ʔ
+    ("\u{6D}\u{325}", "voiceless bilabial nasal"), // m̥
+    ("\u{6D}", "voiced bilabial nasal"), // m
+    ("\u{271}\u{30A}", "voiceless labiodental nasal"), // ɱ̊
+    ("\u{271}", "voiced labiodental nasal"), // ɱ
+    ("\u{6E}\u{33C}", "voiced linguolabial nasal"), // n̼
+    ("\u{6E}\u{32A}\u{30A}", "voiceless dental nasal"), // n̪̊
+    ("\u{6E}\u{32A}", "voiced dental nasal"), // n̪
+    ("\u{6E}\u{325}", "voiceless alveolar nasal"), // n̥
+    ("\u{6E}", "voiced alveolar nasal"), // n
+    ("\u{273}\u{30A}", "voiceless retroflex nasal"), // ɳ̊
+    ("\u{273}", "voiced retroflex nasal"), // ɳ
+    ("\u{272}\u{30A}", "voiceless palatal nasal"), // ɲ̊
+    ("\u{272}", "voiced palatal nasal"), // ɲ
+    ("\u{14B}\u{30A}", "voiceless velar nasal"), // ŋ̊
+    ("\u{14B}", "voiced velar nasal"), // ŋ
+    ("\u{274}\u{325}", "voiceless uvular nasal"), // ɴ̥
+    ("\u{274}", "voiced uvular nasal"), // ɴ
+    ("\u{299}\u{325}", "voiceless bilabial trill"), // ʙ̥
+    ("\u{299}", "voiced bilabial trill"), // ʙ
+    ("\u{72}\u{325}", "voiceless alveolar trill"), // r̥
+    ("\u{72}", "voiced alveolar trill"), // r
+    ("\u{280}\u{325}", "voiceless uvular trill"), // ʀ̥
+    ("\u{280}", "voiced uvular trill"), // ʀ
+    ("\u{2C71}\u{31F}", "voiced bilabial flap"), // ⱱ̟
+    ("\u{2C71}", "voiced labiodental flap"), // ⱱ
+    ("\u{27E}\u{325}", "voiceless alveolar tap"), // ɾ̥
+    ("\u{27E}", "voiced alveolar tap"), // ɾ
+    ("\u{27D}", "voiced retroflex flap"), // ɽ
+    ("\u{278}", "voiceless bilabial fricative"), // ɸ
+    ("\u{3B2}", "voiced bilabial fricative"), // β
+    ("\u{66}", "voiceless labiodental fricative"), // f
+    ("\u{76}", "voiced labiodental fricative"), // v
+    ("\u{3B8}", "voiceless dental fricative"), // θ
+    ("\u{F0}", "voiced dental fricative"), // ð
+    ("\u{73}", "voiceless alveolar fricative"), // s
+    ("\u{7A}", "voiced alveolar fricative"), // z
+    ("\u{283}", "voiceless postalveolar fricative"), // ʃ
+    ("\u{292}", "voiced postalveolar fricative"), // ʒ
+    ("\u{282}", "voiceless retroflex fricative"), // ʂ
+    ("\u{290}", "voiced retroflex fricative"), // ʐ
+    ("\u{255}", "voiceless alveolo-palatal fricative"), // ɕ
+    ("\u{291}", "voiced alveolo-palatal fricative"), // ʑ
+    ("\u{E7}", "voiceless palatal fricative"), // ç
+    ("\u{29D}", "voiced palatal fricative"), // ʝ
+    ("\u{78}", "voiceless velar fricative"), // x
+    ("\u{263}", "voiced velar fricative"), // ɣ
+    ("\u{3C7}", "voiceless uvular fricative"), // χ
+    ("\u{281}", "voiced uvular fricative"), // ʁ
+    ("\u{127}", "voiceless pharyngeal fricative"), // ħ
+    ("\u{295}", "voiced pharyngeal fricative"), // ʕ
+    ("\u{29C}", "voiceless epiglottal fricative"), // ʜ
+    ("\u{2A2}", "voiced epiglottal fricative"), // ʢ
+    ("\u{68}", "voiceless glottal fricative"), // h
+    ("\u{266}", "voiced glottal fricative"), // ɦ
+    ("\u{26C}", "voiceless alveolar lateral fricative"), // ɬ
+    ("\u{26E}", "voiced alveolar lateral fricative"), // ɮ
+    ("\u{28B}", "voiced labiodental approximant"), // ʋ
+    ("\u{279}", "voiced alveolar approximant"), // ɹ
+    ("\u{27B}", "voiced retroflex approximant"), // ɻ
+    ("\u{6A}\u{30A}", "voiceless palatal approximant"), // j̊
+    ("\u{6A}", "voiced palatal approximant"), // j
+    ("\u{270}", "voiced velar approximant"), // ɰ
+    ("\u{6C}\u{325}", "voiceless alveolar lateral approximant"), // l̥
+    ("\u{6C}", "voiced alveolar lateral approximant"), // l
+    ("\u{26D}", "voiced retroflex lateral approximant"), // ɭ
+    ("\u{28E}\u{325}", "voiceless palatal lateral approximant"), // ʎ̥
+    ("\u{28E}", "voiced palatal lateral approximant"), // ʎ
+    ("\u{29F}", "voiced velar lateral approximant"), // ʟ
+    ("\u{27A}", "voiced alveolar lateral flap"), // ɺ
+    ("\u{70}\u{361}\u{66}", "voiceless bilabial-labiodental affricate"), // p͡f
+    ("\u{62}\u{361}\u{76}", "voiced bilabial-labiodental affricate"), // b͡v
+    ("\u{70}\u{32A}\u{361}\u{66}", "voiceless labiodental affricate"), // p̪͡f
+    ("\u{62}\u{32A}\u{361}\u{76}", "voiced labiodental affricate"), // b̪͡v
+    ("\u{74}\u{361}\u{3B8}", "voiceless dental affricate"), // t͡θ
+    ("\u{64}\u{361}\u{F0}", "voiced dental affricate"), // d͡ð
+    ("\u{74}\u{361}\u{73}", "voiceless alveolar affricate"), // t͡s
+    ("\u{64}\u{361}\u{7A}", "voiced alveolar affricate"), // d͡z
+    ("\u{74}\u{361}\u{283}", "voiceless postalveolar affricate"), // t͡ʃ
+    ("\u{64}\u{361}\u{292}", "voiced postalveolar affricate"), // d͡ʒ
+    ("\u{74}\u{361}\u{255}", "voiceless alveolo-palatal affricate"), // t͡ɕ
+    ("\u{64}\u{361}\u{291}", "voiced alveolo-palatal affricate"), // d͡ʑ
+    ("\u{288}\u{361}\u{282}", "voiceless retroflex affricate"), // ʈ͡ʂ
+    ("\u{256}\u{361}\u{290}", "voiced retroflex affricate"), // ɖ͡ʐ
+    ("\u{63}\u{361}\u{E7}", "voiceless palatal affricate"), // c͡ç
+    ("\u{25F}\u{361}\u{29D}", "voiced palatal affricate"), // ɟ͡ʝ
+    ("\u{6B}\u{361}\u{78}", "voiceless velar affricate"), // k͡x
+    ("\u{261}\u{361}\u{263}", "voiced velar affricate"), // ɡ͡ɣ
+    ("\u{71}\u{361}\u{3C7}", "voiceless uvular affricate"), // q͡χ
+    ("\u{262}\u{361}\u{281}", "voiced uvular affricate"), // ɢ͡ʁ
+    ("\u{74}\u{361}\u{26C}", "voiceless alveolar lateral affricate"), // t͡ɬ
+    ("\u{64}\u{361}\u{26E}", "voiced alveolar lateral affricate"), // d͡ɮ
+    ("\u{298}", "bilabial click"), // ʘ
+    ("\u{1C0}", "dental click"), // ǀ
+    ("\u{1C3}", "(post)alveolar click"), // ǃ
+    ("\u{1C2}", "palatoalveolar click"), // ǂ
+    ("\u{1C1}", "alveolar lateral click"), // ǁ
+    ("\u{253}", "voiced bilabial implosive"), // ɓ
+    ("\u{257}", "voiced dental/alveolar implosive"), // ɗ
+    ("\u{284}", "voiced palatal implosive"), // ʄ
+    ("\u{260}", "voiced velar implosive"), // ɠ
+    ("\u{29B}", "voiced uvular implosive"), // ʛ
+    ("\u{77}", "voiced labial-velar approximant"), // w
+    ("\u{28D}", "voiceless labial-velar fricative"), // ʍ
+    ("\u{265}", "voiced labial-palatal approximant"), // ɥ
+    ("\u{267}", "voiceless palatal-velar fricative"), // ɧ
+    ("\u{6B}\u{361}\u{70}", "voiceless labial-velar plosive"), // k͡p
+    ("\u{261}\u{361}\u{62}", "voiced labial-velar plosive"), // ɡ͡b
+    ("\u{14B}\u{361}\u{6D}", "voiced labial-velar nasal"), // ŋ͡m
+    ("\u{69}", "close front unrounded vowel"), // i
+    ("\u{79}", "close front rounded vowel"), // y
+    ("\u{268}", "close central unrounded vowel"), // ɨ
+    ("\u{289}", "close central rounded vowel"), // ʉ
+    ("\u{26F}", "close back unrounded vowel"), // ɯ
+    ("\u{75}", "close back rounded vowel"), // u
+    ("\u{26A}", "near-close near-front unrounded vowel"), // ɪ
+    ("\u{28F}", "near-close near-front rounded vowel"), // ʏ
+    ("\u{28A}", "near-close near-back rounded vowel"), // ʊ
+    ("\u{65}", "close-mid front unrounded vowel"), // e
+    ("\u{F8}", "close-mid front rounded vowel"), // ø
+    ("\u{258}", "close-mid central unrounded vowel"), // ɘ
+    ("\u{275}", "close-mid central rounded vowel"), // ɵ
+    ("\u{264}", "close-mid back unrounded vowel"), // ɤ
+    ("\u{6F}", "close-mid back rounded vowel"), // o
+    ("\u{65}\u{31E}", "mid front unrounded vowel"), // e̞
+    ("\u{F8}\u{31E}", "mid front rounded vowel"), // ø̞
+    ("\u{259}", "mid central unrounded vowel"), // ə
+    ("\u{264}\u{31E}", "mid back unrounded vowel"), // ɤ̞
+    ("\u{6F}\u{31E}", "mid back rounded vowel"), // o̞
+    ("\u{25B}", "open-mid front unrounded vowel"), // ɛ
+    ("\u{153}", "open-mid front rounded vowel"), // œ
+    ("\u{25C}", "open-mid central unrounded vowel"), // ɜ
+    ("\u{25E}", "open-mid central rounded vowel"), // ɞ
+    ("\u{28C}", "open-mid back unrounded vowel"), // ʌ
+    ("\u{254}", "open-mid back rounded vowel"), // ɔ
+    ("\u{E6}", "near-open front unrounded vowel"), // æ
+    ("\u{250}", "near-open central unrounded vowel"), // ɐ
+    ("\u{61}", "open front unrounded vowel"), // a
+    ("\u{276}", "open front rounded vowel"), // ɶ
+    ("\u{61}\u{308}", "open central unrounded vowel"), // ä
+    ("\u{251}", "open back unrounded vowel"), // ɑ
+    ("\u{252}", "open back rounded vowel"), // ɒ
+];
+
+/// The name of a segment, e.g. `"voiceless alveolar lateral
+/// fricative"` for `ɬ`.
+///
+/// Consonants and vowels are named from `NAMES`, or from their place
+/// and manner or their chart cell if the table has no symbol for them;
+/// a doubly articulated consonant names both places, as in `"voiceless
+/// labial-velar plosive"` for `k͡p`. Diacritics and secondary
+/// articulation are named before the symbol, except for tongue root and
+/// release, which follow it: `tʰ` is the aspirated voiceless alveolar
+/// plosive, `dⁿ` the voiced alveolar plosive with nasal release.
+/// Ejectives are named after their place and manner, as in `"alveolar
+/// ejective"` and `"alveolar ejective fricative"`. A length other than
+/// short comes first, as in `"long open front unrounded vowel"` for
+/// `aː`.
+pub fn name(segment: &Segment) -> String {
+    if let Some(length) = segment.length().filter(|l| !l.is_short()) {
+        return format!(
+            "{} {}",
+            length.name(),
+            name(&segment.with_length(Length::Short))
+        );
+    }
+    match segment {
+        Segment::Consonant(c) => {
+            let base = match c.secondary() {
+                Some(secondary) => format!("{} {}", secondary.name(), consonant(c)),
+                None => consonant(c),
+            };
+            with_diacritics(base, c.diacritics())
+        }
+        Segment::Affricate(a) => {
+            let base = match a.fricative().secondary() {
+                Some(secondary) => format!("{} {}", secondary.name(), affricate(a)),
+                None => affricate(a),
+            };
+            with_diacritics(base, segment.diacritics())
+        }
+        Segment::Vowel(v) => with_diacritics(vowel(v), v.diacritics()),
+        Segment::Suprasegmental(s) => s.name().to_string(),
+        Segment::Tone(t) => t.name(),
+    }
+}
+
+/// Every symbol in `NAMES` whose name contains `query`, ignoring case,
+/// in table order. `containing("lateral")` lists the lateral
+/// fricatives, approximants, flap, affricates and click.
+pub fn containing(query: &str) -> Vec<Segment> {
+    let query = query.to_lowercase();
+    NAMES
+        .iter()
+        .filter(|(_, name)| name.contains(&query))
+        .filter_map(|(grapheme, _)| grapheme.parse().ok())
+        .collect()
+}
+
+/// Looks up a segment by name, the inverse of `name`. Matching
+/// ignores case, word order and hyphens, treats `stop` as `plosive`,
+/// `flap` as `tap` and `unvoiced` as `voiceless`, accepts `sibilant`
+/// for `fricative` and allows `vowel` to be left out, so `"nasal velar
+/// voiced"` finds `ŋ`.
+/// Voicing may be left out too, in which case the voiced symbol is
+/// preferred: `"alveolar flap"` finds `ɾ`.
+///
+/// Consonants and vowels are found among `NAMES`, and ejectives by the
+/// name of their pulmonic counterpart with `ejective` added, as in
+/// `"velar ejective stop"`. Breathy (or murmured), creaky, slack and
+/// stiff consonants are found by the name of their voiced counterpart
+/// with the phonation in place of `voiced`, as in `"breathy-voiced
+/// bilabial plosive"`. Any of these may be given a length, as in
+/// `"long voiceless alveolar plosive"`. Suprasegmentals and level tones
+/// are found by their names.
+pub fn from_name(name: &str) -> Option<Segment> {
+    let mut query = words(name);
+    if let Some(segment) = lengthened(&query) {
+        return Some(segment);
+    }
+    if let Some(i) = query.iter().position(|w| w == "ejective") {
+        query.remove(i);
+        if !query.iter().any(|w| is_manner(w)) {
+            query.push("plosive".to_string());
+            query.sort();
+        }
+        return match lookup_words(&query, &["voiceless"])? {
+            Segment::Consonant(c) if c.airstream().is_pulmonic() => {
+                Some(Segment::Consonant(c.ejective()))
+            }
+            Segment::Affricate(a) => {
+                Affricate::new(a.stop(), a.fricative().ejective()).map(Segment::Affricate)
+            }
+            _ => None,
+        };
+    }
+    let voice = [
+        ("breathy", Phonation::Breathy),
+        ("murmured", Phonation::MURMURED),
+        ("creaky", Phonation::Creaky),
+        ("slack", Phonation::Slack),
+        ("stiff", Phonation::Stiff),
+    ];
+    for (word, phonation) in voice {
+        if let Some(i) = query.iter().position(|w| w == word) {
+            query.remove(i);
+            return match lookup_words(&query, &["voiced"])? {
+                Segment::Consonant(c) if c.phonation() == Phonation::Voiced => {
+                    Some(Segment::Consonant(c.with_phonation(phonation)))
+                }
+                _ => None,
+            };
+        }
+    }
+    if let Some(segment) = lookup_words(&query, &["voiced", "voiceless"]) {
+        return Some(segment);
+    }
+    let suprasegmental = Suprasegmental::ALL
+        .into_iter()
+        .find(|s| words(s.name()) == query)
+        .map(Segment::Suprasegmental);
+    let tone = Level::ALL
+        .into_iter()
+        .map(Contour::level)
+        .find(|t| words(&t.name()) == query)
+        .map(Segment::Tone);
+    suprasegmental.or(tone)
+}
+
+/// The segment named by `query` with the words of a length other than
+/// short taken out, with that length.
+fn lengthened(query: &[String]) -> Option<Segment> {
+    // Two-word lengths before `long`, which they contain.
+    let lengths = [
+        Length::ExtraShort,
+        Length::HalfLong,
+        Length::ExtraLong,
+        Length::Long,
+    ];
+    let (length, named) = lengths.into_iter().find_map(|length| {
+        let named = words(length.name());
+        named
+            .iter()
+            .all(|w| query.contains(w))
+            .then_some((length, named))
+    })?;
+    let rest: Vec<&str> = query
+        .iter()
+        .filter(|w| !named.contains(w))
+        .map(String::as_str)
+        .collect();
+    let segment = from_name(&rest.join(" "))?;
+    segment.length()?;
+    Some(segment.with_length(length))
+}
+
+/// The symbol in `NAMES` whose name has the words `query`, or failing
+/// that `query` and one of `voicings`, in order.
+fn lookup_words(query: &[String], voicings: &[&str]) -> Option<Segment> {
+    let find = |query: &[String]| {
+        NAMES
+            .iter()
+            .find(|(_, name)| words(name) == query)
+            .and_then(|(grapheme, _)| grapheme.parse().ok())
+    };
+    find(query).or_else(|| {
+        voicings.iter().find_map(|voicing| {
+            let mut query = query.to_vec();
+            query.push(voicing.to_string());
+            query.sort();
+            find(&query)
+        })
+    })
+}
+
+/// The words of a name as `from_name` compares them: lowercased,
+/// split at spaces and hyphens, with synonyms replaced and `vowel`
+/// left out, sorted and deduplicated. `sibilant` stands for
+/// `fricative`, so `"sibilant fricative"` is just `"fricative"`.
+fn words(name: &str) -> Vec<String> {
+    let mut words: Vec<String> = name
+        .to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|w| !matches!(*w, "" | "vowel"))
+        .map(|w| {
+            match w {
+                "stop" => "plosive",
+                "flap" => "tap",
+                "unvoiced" => "voiceless",
+                "sibilant" => "fricative",
+                w => w,
+            }
+            .to_string()
+        })
+        .collect();
+    words.sort();
+    words.dedup();
+    words
+}
+
+fn is_manner(word: &str) -> bool {
+    matches!(
+        word,
+        "plosive" | "nasal" | "trill" | "tap" | "fricative" | "affricate" | "approximant"
+    )
+}
+
+fn lookup(grapheme: &str) -> Option<&'static str> {
+    NAMES
+        .iter()
+        .find(|(g, _)| *g == grapheme)
+        .map(|(_, name)| *name)
+}
+
+fn consonant(c: &Consonant) -> String {
+    let bare = c.with_diacritics(Diacritics::new()).with_secondary(None);
+    if bare.airstream() == Airstream::GlottalicEgressive {
+        let pulmonic = consonant(&bare.with_airstream(Airstream::PulmonicEgressive));
+        let name = pulmonic
+            .trim_start_matches("voiceless ")
+            .trim_start_matches("voiced ");
+        return match name.strip_suffix(" plosive") {
+            Some(stop) => format!("{} ejective", stop),
+            None => {
+                let (place, manner) = name.rsplit_once(' ').unwrap();
+                format!("{} ejective {}", place, manner)
+            }
+        };
+    }
+    match lookup(&bare.to_string()) {
+        Some(name) => name.to_string(),
+        None => {
+            let manner = match c.manner() {
+                Manner::Fricative { .. } => "fricative",
+                manner => manner.name(),
+            };
+            let place = match c.coarticulation() {
+                Some(second) => match c.place() {
+                    Place::Labial => format!("labial-{}", second.name()),
+                    _ => format!("{}-{}", c.articulation().name(), second.name()),
+                },
+                None => c.articulation().name().to_string(),
+            };
+            format!("{} {} {}", c.phonation().name(), place, manner)
+        }
+    }
+}
+
+/// Affricates without a name of their own are named after their
+/// fricative, as in `"alveolar ejective affricate"` for `t͡sʼ`.
+fn affricate(a: &Affricate) -> String {
+    let bare = |c: Consonant| c.with_diacritics(Diacritics::new()).with_secondary(None);
+    let named = Affricate::new(bare(a.stop()), bare(a.fricative()))
+        .and_then(|bare| lookup(&bare.to_string()));
+    match named {
+        Some(name) => name.to_string(),
+        None => consonant(&a.fricative()).replace(" fricative", " affricate"),
+    }
+}
+
+fn vowel(v: &Vowel) -> String {
+    let bare = v.with_diacritics(Diacritics::new());
+    match lookup(&bare.to_string()) {
+        Some(name) => name.to_string(),
+        None => format!(
+            "{} {} {} vowel",
+            v.height().name(),
+            v.backness().name(),
+            v.roundedness().name()
+        ),
+    }
+}
+
+fn with_diacritics(base: String, diacritics: Diacritics) -> String {
+    let (after, before): (Vec<Diacritic>, Vec<Diacritic>) = diacritics.iter().partition(|d| {
+        matches!(
+            d,
+            Diacritic::AdvancedTongueRoot
+                | Diacritic::RetractedTongueRoot
+                | Diacritic::NasalRelease
+                | Diacritic::LateralRelease
+                | Diacritic::NoAudibleRelease
+        )
+    });
+    let mut name: Vec<&str> = before.iter().map(Diacritic::name).collect();
+    name.push(&base);
+    let mut name = name.join(" ");
+    if !after.is_empty() {
+        let after: Vec<&str> = after.iter().map(Diacritic::name).collect();
+        name.push_str(" with ");
+        name.push_str(&after.join(" and "));
+    }
+    name
+}
@@ -0,0 +1,262 @@
+//! Unicode normalization of IPA text. The same symbol can be typed
+//! precomposed, as `ã` (U+00E3), or decomposed, as `a` followed by the
+//! combining tilde U+0303; parsing and lookups accept either, and
+//! `normalize_ipa` rewrites text into one canonical form.
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "alloc")]
+use crate::diacritics::Diacritic;
+#[cfg(feature = "alloc")]
+use crate::ipa::Phonation;
+#[cfg(feature = "alloc")]
+use crate::parser;
+#[cfg(feature = "alloc")]
+use crate::tone::Contour;
+
+/// The combining cedilla, which with `c` makes the IPA letter `ç`.
+pub const CEDILLA: char = '\u{327}';
+
+/// Precomposed letters whose canonical decomposition is an IPA letter
+/// followed by IPA marks, from Latin-1 through Latin Extended
+/// Additional, sorted by character. `ç` is itself an IPA letter and is
+/// not included.
+#[rustfmt::skip]
+pub const PRECOMPOSED: [(char, &str); 137] = [
+    ('\u{E0}', "\u{61}\u{300}"), // à
+    ('\u{E1}', "\u{61}\u{301}"), // á
+    ('\u{E2}', "\u{61}\u{302}"), // â
+    ('\u{E3}', "\u{61}\u{303}"), // ã
+    ('\u{E4}', "\u{61}\u{308}"), // ä
+    ('\u{E5}', "\u{61}\u{30A}"), // å
+    ('\u{E8}', "\u{65}\u{300}"), // è
+    ('\u{E9}', "\u{65}\u{301}"), // é
+    ('\u{EA}', "\u{65}\u{302}"), // ê
+    ('\u{EB}', "\u{65}\u{308}"), // ë
+    ('\u{EC}', "\u{69}\u{300}"), // ì
+    ('\u{ED}', "\u{69}\u{301}"), // í
+    ('\u{EE}', "\u{69}\u{302}"), // î
+    ('\u{EF}', "\u{69}\u{308}"), // ï
+    ('\u{F1}', "\u{6E}\u{303}"), // ñ
+    ('\u{F2}', "\u{6F}\u{300}"), // ò
+    ('\u{F3}', "\u{6F}\u{301}"), // ó
+    ('\u{F4}', "\u{6F}\u{302}"), // ô
+    ('\u{F5}', "\u{6F}\u{303}"), // õ
+    ('\u{F6}', "\u{6F}\u{308}"), // ö
+    ('\u{F9}', "\u{75}\u{300}"), // ù
+    ('\u{FA}', "\u{75}\u{301}"), // ú
+    ('\u{FB}', "\u{75}\u{302}"), // û
+    ('\u{FC}', "\u{75}\u{308}"), // ü
+    ('\u{FD}', "\u{79}\u{301}"), // ý
+    ('\u{FF}', "\u{79}\u{308}"), // ÿ
+    ('\u{101}', "\u{61}\u{304}"), // ā
+    ('\u{103}', "\u{61}\u{306}"), // ă
+    ('\u{107}', "\u{63}\u{301}"), // ć
+    ('\u{109}', "\u{63}\u{302}"), // ĉ
+    ('\u{10D}', "\u{63}\u{30C}"), // č
+    ('\u{10F}', "\u{64}\u{30C}"), // ď
+    ('\u{113}', "\u{65}\u{304}"), // ē
+    ('\u{115}', "\u{65}\u{306}"), // ĕ
+    ('\u{11B}', "\u{65}\u{30C}"), // ě
+    ('\u{125}', "\u{68}\u{302}"), // ĥ
+    ('\u{129}', "\u{69}\u{303}"), // ĩ
+    ('\u{12B}', "\u{69}\u{304}"), // ī
+    ('\u{12D}', "\u{69}\u{306}"), // ĭ
+    ('\u{135}', "\u{6A}\u{302}"), // ĵ
+    ('\u{13A}', "\u{6C}\u{301}"), // ĺ
+    ('\u{13E}', "\u{6C}\u{30C}"), // ľ
+    ('\u{144}', "\u{6E}\u{301}"), // ń
+    ('\u{148}', "\u{6E}\u{30C}"), // ň
+    ('\u{14D}', "\u{6F}\u{304}"), // ō
+    ('\u{14F}', "\u{6F}\u{306}"), // ŏ
+    ('\u{151}', "\u{6F}\u{30B}"), // ő
+    ('\u{155}', "\u{72}\u{301}"), // ŕ
+    ('\u{159}', "\u{72}\u{30C}"), // ř
+    ('\u{15B}', "\u{73}\u{301}"), // ś
+    ('\u{15D}', "\u{73}\u{302}"), // ŝ
+    ('\u{161}', "\u{73}\u{30C}"), // š
+    ('\u{165}', "\u{74}\u{30C}"), // ť
+    ('\u{169}', "\u{75}\u{303}"), // ũ
+    ('\u{16B}', "\u{75}\u{304}"), // ū
+    ('\u{16D}', "\u{75}\u{306}"), // ŭ
+    ('\u{16F}', "\u{75}\u{30A}"), // ů
+    ('\u{171}', "\u{75}\u{30B}"), // ű
+    ('\u{175}', "\u{77}\u{302}"), // ŵ
+    ('\u{177}', "\u{79}\u{302}"), // ŷ
+    ('\u{17A}', "\u{7A}\u{301}"), // ź
+    ('\u{17E}', "\u{7A}\u{30C}"), // ž
+    ('\u{1CE}', "\u{61}\u{30C}"), // ǎ
+    ('\u{1D0}', "\u{69}\u{30C}"), // ǐ
+    ('\u{1D2}', "\u{6F}\u{30C}"), // ǒ
+    ('\u{1D4}', "\u{75}\u{30C}"), // ǔ
+    ('\u{1D6}', "\u{75}\u{308}\u{304}"), // ǖ
+    ('\u{1D8}', "\u{75}\u{308}\u{301}"), // ǘ
+    ('\u{1DA}', "\u{75}\u{308}\u{30C}"), // ǚ
+    ('\u{1DC}', "\u{75}\u{308}\u{300}"), // ǜ
+    ('\u{1DF}', "\u{61}\u{308}\u{304}"), // ǟ
+    ('\u{1E3}', "\u{E6}\u{304}"), // ǣ
+    ('\u{1E9}', "\u{6B}\u{30C}"), // ǩ
+    ('\u{1EF}', "\u{292}\u{30C}"), // ǯ
+    ('\u{1F0}', "\u{6A}\u{30C}"), // ǰ
+    ('\u{1F9}', "\u{6E}\u{300}"), // ǹ
+    ('\u{1FB}', "\u{61}\u{30A}\u{301}"), // ǻ
+    ('\u{1FD}', "\u{E6}\u{301}"), // ǽ
+    ('\u{1FF}', "\u{F8}\u{301}"), // ǿ
+    ('\u{201}', "\u{61}\u{30F}"), // ȁ
+    ('\u{203}', "\u{61}\u{311}"), // ȃ
+    ('\u{205}', "\u{65}\u{30F}"), // ȅ
+    ('\u{207}', "\u{65}\u{311}"), // ȇ
+    ('\u{209}', "\u{69}\u{30F}"), // ȉ
+    ('\u{20B}', "\u{69}\u{311}"), // ȋ
+    ('\u{20D}', "\u{6F}\u{30F}"), // ȍ
+    ('\u{20F}', "\u{6F}\u{311}"), // ȏ
+    ('\u{211}', "\u{72}\u{30F}"), // ȑ
+    ('\u{213}', "\u{72}\u{311}"), // ȓ
+    ('\u{215}', "\u{75}\u{30F}"), // ȕ
+    ('\u{217}', "\u{75}\u{311}"), // ȗ
+    ('\u{21F}', "\u{68}\u{30C}"), // ȟ
+    ('\u{22B}', "\u{6F}\u{308}\u{304}"), // ȫ
+    ('\u{22D}', "\u{6F}\u{303}\u{304}"), // ȭ
+    ('\u{233}', "\u{79}\u{304}"), // ȳ
+    ('\u{1E01}', "\u{61}\u{325}"), // ḁ
+    ('\u{1E15}', "\u{65}\u{304}\u{300}"), // ḕ
+    ('\u{1E17}', "\u{65}\u{304}\u{301}"), // ḗ
+    ('\u{1E1B}', "\u{65}\u{330}"), // ḛ
+    ('\u{1E27}', "\u{68}\u{308}"), // ḧ
+    ('\u{1E2D}', "\u{69}\u{330}"), // ḭ
+    ('\u{1E2F}', "\u{69}\u{308}\u{301}"), // ḯ
+    ('\u{1E31}', "\u{6B}\u{301}"), // ḱ
+    ('\u{1E3F}', "\u{6D}\u{301}"), // ḿ
+    ('\u{1E4D}', "\u{6F}\u{303}\u{301}"), // ṍ
+    ('\u{1E4F}', "\u{6F}\u{303}\u{308}"), // ṏ
+    ('\u{1E51}', "\u{6F}\u{304}\u{300}"), // ṑ
+    ('\u{1E53}', "\u{6F}\u{304}\u{301}"), // ṓ
+    ('\u{1E55}', "\u{70}\u{301}"), // ṕ
+    ('\u{1E73}', "\u{75}\u{324}"), // ṳ
+    ('\u{1E75}', "\u{75}\u{330}"), // ṵ
+    ('\u{1E79}', "\u{75}\u{303}\u{301}"), // ṹ
+    ('\u{1E7B}', "\u{75}\u{304}\u{308}"), // ṻ
+    ('\u{1E7D}', "\u{76}\u{303}"), // ṽ
+    ('\u{1E81}', "\u{77}\u{300}"), // ẁ
+    ('\u{1E83}', "\u{77}\u{301}"), // ẃ
+    ('\u{1E85}', "\u{77}\u{308}"), // ẅ
+    ('\u{1E8D}', "\u{78}\u{308}"), // ẍ
+    ('\u{1E91}', "\u{7A}\u{302}"), // ẑ
+    ('\u{1E97}', "\u{74}\u{308}"), // ẗ
+    ('\u{1E98}', "\u{77}\u{30A}"), // ẘ
+    ('\u{1E99}', "\u{79}\u{30A}"), // ẙ
+    ('\u{1EA5}', "\u{61}\u{302}\u{301}"), // ấ
+    ('\u{1EA7}', "\u{61}\u{302}\u{300}"), // ầ
+    ('\u{1EAB}', "\u{61}\u{302}\u{303}"), // ẫ
+    ('\u{1EAF}', "\u{61}\u{306}\u{301}"), // ắ
+    ('\u{1EB1}', "\u{61}\u{306}\u{300}"), // ằ
+    ('\u{1EB5}', "\u{61}\u{306}\u{303}"), // ẵ
+    ('\u{1EBD}', "\u{65}\u{303}"), // ẽ
+    ('\u{1EBF}', "\u{65}\u{302}\u{301}"), // ế
+    ('\u{1EC1}', "\u{65}\u{302}\u{300}"), // ề
+    ('\u{1EC5}', "\u{65}\u{302}\u{303}"), // ễ
+    ('\u{1ED1}', "\u{6F}\u{302}\u{301}"), // ố
+    ('\u{1ED3}', "\u{6F}\u{302}\u{300}"), // ồ
+    ('\u{1ED7}', "\u{6F}\u{302}\u{303}"), // ỗ
+    ('\u{1EF3}', "\u{79}\u{300}"), // ỳ
+    ('\u{1EF9}', "\u{79}\u{303}"), // ỹ
+];
+
+/// The canonical decomposition of `c` into an IPA letter and marks, if
+/// it is in `PRECOMPOSED`.
+pub const fn decompose(c: char) -> Option<&'static str> {
+    let (mut low, mut high) = (0, PRECOMPOSED.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        let (composed, decomposed) = PRECOMPOSED[mid];
+        if composed == c {
+            return Some(decomposed);
+        } else if composed < c {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    None
+}
+
+/// Rewrites `s` in the crate's canonical form:
+///
+/// * Every precomposed letter in `PRECOMPOSED` is decomposed, while
+///   `c` with a cedilla is composed into the IPA letter `ç`.
+/// * The combining marks after each letter are ordered: diacritics in
+///   the chart order of `Diacritic`, then the slack and stiff voice
+///   marks, then tone marks, then any other marks (such as tie bars) in
+///   the order written.
+///
+/// Spacing modifiers such as `ʰ` and `ː` stay where they are. This is
+/// not Unicode's NFD, whose canonical ordering sorts marks below the
+/// letter before marks above it.
+#[cfg(feature = "alloc")]
+pub fn normalize_ipa(s: &str) -> String {
+    normalize_mapped(s).0
+}
+
+/// `normalize_ipa`, with the start of each letter and its marks in the
+/// output paired with its start in `s`.
+#[cfg(feature = "alloc")]
+pub(crate) fn normalize_mapped(s: &str) -> (String, Vec<(usize, usize)>) {
+    let mut out = String::with_capacity(s.len());
+    let mut starts = Vec::new();
+    let mut chars = s.char_indices().peekable();
+    while let Some((offset, c)) = chars.next() {
+        starts.push((out.len(), offset));
+        let mut marks: Vec<char> = Vec::new();
+        let base = match decompose(c) {
+            Some(decomposed) => {
+                let mut decomposed = decomposed.chars();
+                let base = decomposed.next().unwrap();
+                marks.extend(decomposed);
+                base
+            }
+            None => c,
+        };
+        while let Some(&(_, m)) = chars.peek().filter(|(_, m)| parser::is_combining(*m)) {
+            marks.push(m);
+            chars.next();
+        }
+        let base = match marks.iter().position(|&m| m == CEDILLA) {
+            Some(i) if base == 'c' => {
+                marks.remove(i);
+                '\u{E7}'
+            }
+            _ => base,
+        };
+        marks.sort_by_key(|&m| rank(m));
+        out.push(base);
+        out.extend(marks);
+    }
+    (out, starts)
+}
+
+/// Where `m` goes among the marks on a letter.
+#[cfg(feature = "alloc")]
+fn rank(m: char) -> (u8, Option<Diacritic>) {
+    if let Some(d) = Diacritic::from_char(m) {
+        (0, Some(d))
+    } else if Phonation::from_mark(m).is_some() {
+        (1, None)
+    } else if Contour::from_diacritic(m).is_some() {
+        (2, None)
+    } else {
+        (3, None)
+    }
+}
+
+/// The offset in the original text of `offset` in text normalized by
+/// `normalize_mapped`, given its `starts`: the start of the letter it
+/// belongs to.
+#[cfg(feature = "alloc")]
+pub(crate) fn original_offset(starts: &[(usize, usize)], offset: usize) -> usize {
+    match starts.binary_search_by_key(&offset, |(normalized, _)| *normalized) {
+        Ok(i) => starts[i].1,
+        Err(0) => 0,
+        Err(i) => starts[i - 1].1,
+    }
+}
@@ -0,0 +1,234 @@
+use core::error;
+use core::fmt;
+use core::str::FromStr;
+
+use alloc::string::{String, ToString};
+
+use crate::segment::ParseSegmentError;
+use crate::{
+    americanist, arpabet, asjp, cxs, espeak, kirshenbaum, normalize, parser, upa, xsampa, zsampa,
+};
+
+/// The error returned when a transcription contains a symbol that the
+/// conversion table has no entry for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownSymbol {
+    /// Byte offset of the symbol in the input.
+    pub offset: usize,
+    pub symbol: char,
+}
+
+impl fmt::Display for UnknownSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no conversion for `{}` at byte {}",
+            self.symbol, self.offset
+        )
+    }
+}
+
+impl error::Error for UnknownSymbol {}
+
+/// A conversion table between some notation and IPA, as pairs of
+/// `(notation, ipa)` strings.
+///
+/// When converting from IPA, the first pair for a given IPA string is
+/// used, so tables list their canonical spelling first.
+pub type Table = [(&'static str, &'static str)];
+
+/// Rewrites `s` from the notation of `table` into IPA, matching the
+/// longest notation string at each position. Whitespace is copied
+/// through unchanged.
+pub fn to_ipa(s: &str, table: &Table) -> Result<String, UnknownSymbol> {
+    rewrite(s, table.iter().map(|(from, to)| (*from, *to)))
+}
+
+/// Rewrites IPA `s` into the notation of `table`; the inverse of
+/// `to_ipa`. `s` is put in the form of `normalize::normalize_ipa`
+/// first, so precomposed and decomposed letters convert alike.
+pub fn from_ipa(s: &str, table: &Table) -> Result<String, UnknownSymbol> {
+    let (normalized, starts) = normalize::normalize_mapped(s);
+    rewrite(&normalized, table.iter().map(|(to, from)| (*from, *to))).map_err(|e| UnknownSymbol {
+        offset: normalize::original_offset(&starts, e.offset),
+        ..e
+    })
+}
+
+/// `from_ipa`, writing `separator` between two symbols that would
+/// otherwise read back as something else, as `ǀǀ` would as `ǁ` in
+/// X-SAMPA. `table` must read `separator` as nothing.
+pub(crate) fn from_ipa_separated(
+    s: &str,
+    table: &Table,
+    separator: &str,
+) -> Result<String, UnknownSymbol> {
+    let (normalized, starts) = normalize::normalize_mapped(s);
+    let mut out = String::with_capacity(s.len());
+    let mut rest = normalized.as_str();
+    // the symbol last written, if nothing but it has been since
+    let mut last: Option<&str> = None;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+            last = None;
+            continue;
+        }
+        let Some((ipa, symbol)) = longest(rest, table.iter().map(|(to, from)| (*from, *to))) else {
+            return Err(UnknownSymbol {
+                offset: normalize::original_offset(&starts, normalized.len() - rest.len()),
+                symbol: c,
+            });
+        };
+        if let Some(last) = last {
+            let apart = to_ipa(last, table).map(|a| a + &to_ipa(symbol, table).unwrap_or_default());
+            if to_ipa(&[last, symbol].concat(), table).ok() != apart.ok() {
+                out.push_str(separator);
+            }
+        }
+        out.push_str(symbol);
+        rest = &rest[ipa.len()..];
+        last = Some(symbol);
+    }
+    Ok(out)
+}
+
+/// A transcription system the crate converts between.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Notation {
+    Ipa,
+    XSampa,
+    Kirshenbaum,
+    Arpabet,
+    Espeak,
+    Cxs,
+    ZSampa,
+    Americanist,
+    Upa,
+    Asjp,
+}
+
+impl Notation {
+    pub const ALL: [Notation; 10] = [
+        Notation::Ipa,
+        Notation::XSampa,
+        Notation::Kirshenbaum,
+        Notation::Arpabet,
+        Notation::Espeak,
+        Notation::Cxs,
+        Notation::ZSampa,
+        Notation::Americanist,
+        Notation::Upa,
+        Notation::Asjp,
+    ];
+
+    /// The notation's name as accepted by `from_str`, e.g. `"xsampa"`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Notation::Ipa => "ipa",
+            Notation::XSampa => "xsampa",
+            Notation::Kirshenbaum => "kirshenbaum",
+            Notation::Arpabet => "arpabet",
+            Notation::Espeak => "espeak",
+            Notation::Cxs => "cxs",
+            Notation::ZSampa => "zsampa",
+            Notation::Americanist => "americanist",
+            Notation::Upa => "upa",
+            Notation::Asjp => "asjp",
+        }
+    }
+}
+
+impl FromStr for Notation {
+    type Err = UnknownNotation;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Notation::ALL
+            .into_iter()
+            .find(|n| n.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownNotation(s.to_string()))
+    }
+}
+
+/// The error returned when parsing a `Notation` from an unknown name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownNotation(pub String);
+
+impl fmt::Display for UnknownNotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown notation `{}`", self.0)
+    }
+}
+
+impl error::Error for UnknownNotation {}
+
+/// Converts `s` from one notation to another, by way of IPA.
+pub fn convert(s: &str, from: Notation, to: Notation) -> Result<String, ParseSegmentError> {
+    let ipa = match from {
+        Notation::Ipa => s.to_string(),
+        Notation::XSampa => xsampa::to_ipa(s)?,
+        Notation::Kirshenbaum => kirshenbaum::to_ipa(s)?,
+        Notation::Arpabet => arpabet::to_ipa(s)?,
+        Notation::Espeak => espeak::to_ipa(s)?,
+        Notation::Cxs => cxs::to_ipa(s)?,
+        Notation::ZSampa => zsampa::to_ipa(s)?,
+        Notation::Americanist => americanist::to_ipa(s)?,
+        Notation::Upa => upa::to_ipa(s)?,
+        Notation::Asjp => asjp::to_ipa(s)?,
+    };
+    Ok(match to {
+        Notation::Ipa => ipa,
+        Notation::XSampa => xsampa::from_ipa(&ipa)?,
+        Notation::Kirshenbaum => kirshenbaum::from_ipa(&ipa)?,
+        Notation::Arpabet => arpabet::from_segments(&parser::parse(&ipa)?)?,
+        Notation::Espeak => espeak::from_ipa(&ipa)?,
+        Notation::Cxs => cxs::from_ipa(&ipa)?,
+        Notation::ZSampa => zsampa::from_ipa(&ipa)?,
+        Notation::Americanist => americanist::from_ipa(&ipa)?,
+        Notation::Upa => upa::from_ipa(&ipa)?,
+        Notation::Asjp => asjp::from_ipa(&ipa)?,
+    })
+}
+
+fn rewrite<'t, I>(s: &str, pairs: I) -> Result<String, UnknownSymbol>
+where
+    I: Iterator<Item = (&'t str, &'t str)> + Clone,
+{
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        match longest(rest, pairs.clone()) {
+            Some((from, to)) => {
+                out.push_str(to);
+                rest = &rest[from.len()..];
+            }
+            None => {
+                return Err(UnknownSymbol {
+                    offset: s.len() - rest.len(),
+                    symbol: c,
+                })
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// The pair whose first string is the longest at the start of `rest`;
+/// the first of several equally long matches wins.
+fn longest<'t>(
+    rest: &str,
+    pairs: impl Iterator<Item = (&'t str, &'t str)>,
+) -> Option<(&'t str, &'t str)> {
+    pairs
+        .filter(|(from, _)| !from.is_empty() && rest.starts_with(from))
+        .fold(None, |best: Option<(&str, &str)>, pair| match best {
+            Some(b) if b.0.len() >= pair.0.len() => Some(b),
+            _ => Some(pair),
+        })
+}
@@ -0,0 +1,202 @@
+//! Orthography profiles in the manner of Moran & Cysouw's _The Unicode
+//! Cookbook for Linguists_ (2018), as CLDF datasets ship them: a TSV
+//! file whose `Grapheme` column lists the strings of a spelling and
+//! whose `IPA` column gives what each stands for.
+//!
+//! ```text
+//! Grapheme  IPA
+//! a         a
+//! ch        t͡ʃ
+//! ^h        NULL
+//! ```
+//!
+//! Text is split into the profile's graphemes by longest match, word
+//! by word, and each grapheme replaced by its segments. A grapheme
+//! beginning with `^` matches only at the start of a word and one
+//! ending in `$` only at the end, and an IPA cell of `NULL` stands for
+//! nothing. Without an `IPA` column the graphemes are read as IPA
+//! themselves. Profile and text are both compared as `normalize_ipa`
+//! rewrites them, so precomposed and decomposed letters match.
+
+use core::error::Error;
+use core::fmt;
+use core::str::FromStr;
+
+use alloc::{string::String, vec::Vec};
+
+use crate::error::LineError;
+use crate::normalize;
+use crate::parser;
+use crate::segment::{ParseSegmentError, Segment};
+
+/// A line of a profile.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rule {
+    /// The grapheme, normalized and without its `^` or `$`.
+    pub grapheme: String,
+    /// Whether it matches only at the start of a word (`^`).
+    pub at_start: bool,
+    /// Whether it matches only at the end of a word (`$`).
+    pub at_end: bool,
+    pub segments: Vec<Segment>,
+}
+
+/// An orthography profile.
+///
+/// ```
+/// use allo::orthography::Profile;
+///
+/// let profile: Profile = "Grapheme\tIPA\na\ta\nch\tt͡ʃ\nc\tk\nh\th\n^h\tNULL".parse().unwrap();
+/// assert_eq!(profile.segment("chach").unwrap(), [["ch", "a", "ch"]]);
+/// let words = profile.transliterate("haca").unwrap();
+/// assert_eq!(words[0].iter().map(|s| s.to_string()).collect::<String>(), "aka");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Profile {
+    rules: Vec<Rule>,
+}
+
+impl Profile {
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Splits each whitespace-separated word of `text` into graphemes.
+    pub fn segment(&self, text: &str) -> Result<Vec<Vec<&str>>, UnknownGrapheme> {
+        Ok(self
+            .rules_for(text)?
+            .into_iter()
+            .map(|word| word.into_iter().map(|r| r.grapheme.as_str()).collect())
+            .collect())
+    }
+
+    /// The segments of each whitespace-separated word of `text`.
+    pub fn transliterate(&self, text: &str) -> Result<Vec<Vec<Segment>>, UnknownGrapheme> {
+        Ok(self
+            .rules_for(text)?
+            .into_iter()
+            .map(|word| {
+                word.into_iter()
+                    .flat_map(|r| r.segments.iter().copied())
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// The rule matched at each grapheme of each word.
+    fn rules_for(&self, text: &str) -> Result<Vec<Vec<&Rule>>, UnknownGrapheme> {
+        let (text, starts) = normalize::normalize_mapped(text);
+        let mut words = Vec::new();
+        let mut offset = 0;
+        for word in text.split_whitespace() {
+            let start = offset + text[offset..].find(word).unwrap_or(0);
+            offset = start + word.len();
+            let mut rules = Vec::new();
+            let mut i = 0;
+            while i < word.len() {
+                let rest = &word[i..];
+                let rule = self
+                    .rules
+                    .iter()
+                    .filter(|r| rest.starts_with(r.grapheme.as_str()))
+                    .filter(|r| !r.at_start || i == 0)
+                    .filter(|r| !r.at_end || r.grapheme.len() == rest.len())
+                    .max_by_key(|r| (r.grapheme.len(), r.at_start as u8 + r.at_end as u8))
+                    .ok_or(UnknownGrapheme {
+                        offset: normalize::original_offset(&starts, start + i),
+                    })?;
+                i += rule.grapheme.len();
+                rules.push(rule);
+            }
+            words.push(rules);
+        }
+        Ok(words)
+    }
+}
+
+impl FromStr for Profile {
+    type Err = ParseError;
+
+    /// Parses a profile from TSV with a header row. Columns other than
+    /// `Grapheme` and `IPA` (in any case) are ignored, as are blank
+    /// lines and lines starting with `#`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'));
+        let error = |line: usize, kind| ParseError {
+            line: line + 1,
+            kind,
+        };
+        let (n, header) = lines
+            .next()
+            .ok_or(error(0, ParseErrorKind::MissingGrapheme))?;
+        let column = |name: &str| {
+            header
+                .split('\t')
+                .position(|c| c.trim().eq_ignore_ascii_case(name))
+        };
+        let grapheme = column("Grapheme").ok_or(error(n, ParseErrorKind::MissingGrapheme))?;
+        let ipa = column("IPA");
+        let mut rules = Vec::new();
+        for (n, line) in lines {
+            let cells: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let cell = cells.get(grapheme).copied().unwrap_or("");
+            let at_start = cell.starts_with('^');
+            let at_end = cell.len() > at_start as usize && cell.ends_with('$');
+            let bare = &cell[at_start as usize..cell.len() - at_end as usize];
+            if bare.is_empty() {
+                return Err(error(n, ParseErrorKind::MissingGrapheme));
+            }
+            let segments = match ipa.map(|i| cells.get(i).copied().unwrap_or("")) {
+                Some("NULL") => Ok(Vec::new()),
+                Some(ipa) if !ipa.is_empty() => parser::parse(ipa),
+                _ => parser::parse(bare),
+            }
+            .map_err(|e| error(n, ParseErrorKind::Segment(e)))?;
+            rules.push(Rule {
+                grapheme: normalize::normalize_ipa(bare),
+                at_start,
+                at_end,
+                segments,
+            });
+        }
+        Ok(Profile { rules })
+    }
+}
+
+/// Text with a letter at byte `offset` that no grapheme of the profile
+/// matches.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnknownGrapheme {
+    pub offset: usize,
+}
+
+impl fmt::Display for UnknownGrapheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no grapheme of the profile at byte {}", self.offset)
+    }
+}
+
+impl Error for UnknownGrapheme {}
+
+/// What is wrong with a line of a profile.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// No `Grapheme` column in the header, or an empty grapheme.
+    MissingGrapheme,
+    Segment(ParseSegmentError),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::MissingGrapheme => write!(f, "no grapheme"),
+            ParseErrorKind::Segment(e) => e.fmt(f),
+        }
+    }
+}
+
+/// An error on a line of a profile.
+pub type ParseError = LineError<ParseErrorKind>;
@@ -0,0 +1,561 @@
+#[cfg(feature = "alloc")]
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+use crate::affricate::Affricate;
+use crate::diacritics::Diacritic;
+use crate::error::Error;
+use crate::graphemes;
+use crate::ipa::{self, Description};
+#[cfg(feature = "alloc")]
+use crate::normalize;
+#[cfg(feature = "alloc")]
+use crate::notation::{Notation, Table};
+#[cfg(feature = "alloc")]
+use crate::segment::ParseSegmentError;
+use crate::segment::Segment;
+#[cfg(feature = "alloc")]
+use crate::suprasegmentals::Gemination;
+use crate::suprasegmentals::{Length, Suprasegmental};
+use crate::tone::{Contour, Level};
+#[cfg(feature = "alloc")]
+use crate::{kirshenbaum, xsampa};
+
+/// Tie bars joining the two halves of an affricate or double
+/// articulation: `͡` above and `͜` below.
+pub const TIE_BARS: [char; 2] = ['\u{361}', '\u{35C}'];
+
+/// Whether `c` is a combining mark, i.e. drawn over, under or through
+/// the character before it.
+pub fn is_combining(c: char) -> bool {
+    matches!(c,
+        '\u{300}'..='\u{36F}'
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE20}'..='\u{FE2F}')
+}
+
+/// The first character of `s` and the rest of `s`, for `const fn`s,
+/// which cannot call `str::chars`.
+pub(crate) const fn split_first_char(s: &str) -> Option<(char, &str)> {
+    let bytes = s.as_bytes();
+    if bytes.is_empty() {
+        return None;
+    }
+    let (width, mut code) = match bytes[0] {
+        0x00..=0x7F => (1, bytes[0] as u32),
+        0xC0..=0xDF => (2, (bytes[0] & 0x1F) as u32),
+        0xE0..=0xEF => (3, (bytes[0] & 0x0F) as u32),
+        _ => (4, (bytes[0] & 0x07) as u32),
+    };
+    let mut i = 1;
+    while i < width {
+        code = (code << 6) | (bytes[i] & 0x3F) as u32;
+        i += 1;
+    }
+    match char::from_u32(code) {
+        Some(c) => Some((c, s.split_at(width).1)),
+        None => None,
+    }
+}
+
+/// `s.strip_suffix(suffix)`, as a `const fn`.
+pub(crate) const fn strip_suffix<'a>(s: &'a str, suffix: &str) -> Option<&'a str> {
+    let (bytes, end) = (s.as_bytes(), suffix.as_bytes());
+    if end.len() > bytes.len() {
+        return None;
+    }
+    let at = bytes.len() - end.len();
+    let mut i = 0;
+    while i < end.len() {
+        if bytes[at + i] != end[i] {
+            return None;
+        }
+        i += 1;
+    }
+    Some(s.split_at(at).0)
+}
+
+/// `s.split_once(TIE_BARS)`, as a `const fn`.
+pub(crate) const fn split_tie(s: &str) -> Option<(&str, &str)> {
+    let mut rest = s;
+    while let Some((c, after)) = split_first_char(rest) {
+        if c == TIE_BARS[0] || c == TIE_BARS[1] {
+            let at = s.len() - rest.len();
+            return Some((s.split_at(at).0, after));
+        }
+        rest = after;
+    }
+    None
+}
+
+/// Whether `c` is a spacing symbol that nonetheless belongs to the
+/// segment before it: superscript modifier letters (`ʰ ʷ ⁿ`), the
+/// ejective mark, and the long and half-long marks.
+pub fn is_modifier(c: char) -> bool {
+    if Diacritic::from_char(c).is_some() || graphemes::EJECTIVE.starts_with(c) {
+        return true;
+    }
+    if matches!(
+        Suprasegmental::from_char(c),
+        Some(Suprasegmental::Length(Length::Long | Length::HalfLong))
+    ) {
+        return true;
+    }
+    let superscript = matches!(c,
+        '\u{2B0}'..='\u{2B8}'
+        | '\u{2C0}'..='\u{2C1}'
+        | '\u{2E0}'..='\u{2E4}'
+        | '\u{1D2C}'..='\u{1D6A}'
+        | '\u{1D9B}'..='\u{1DBF}'
+        | '\u{2070}'..='\u{209F}');
+    superscript && Level::from_letter(c).is_none()
+}
+
+fn is_tone_letter(c: char) -> bool {
+    Level::from_letter(c).is_some()
+}
+
+/// Splits an IPA transcription into one token per segment.
+///
+/// Combining diacritics, superscript modifiers, and length marks stay
+/// attached to the base character they follow, so `"n̪ʰː"` is a single
+/// token. Characters joined by a tie bar form one token (`"t͡ʃ"`), as
+/// does a run of tone letters (`"˨˩˦"`). Stress marks and breaks each
+/// form their own token, and whitespace is dropped.
+#[cfg(feature = "alloc")]
+pub fn tokenize(s: &str) -> Vec<&str> {
+    Split { rest: s }.collect()
+}
+
+/// The tokens of `tokenize`, without collecting them.
+#[derive(Clone, Debug)]
+struct Split<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Split<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        self.rest = self.rest.trim_start();
+        let mut chars = self.rest.char_indices();
+        let (_, mut prev) = chars.next()?;
+        let mut tied = TIE_BARS.contains(&prev);
+        let mut end = self.rest.len();
+        for (i, c) in chars {
+            let attaches = if tied {
+                true
+            } else if is_tone_letter(prev) {
+                is_tone_letter(c)
+            } else {
+                !prev.is_whitespace() && (is_combining(c) || is_modifier(c))
+            };
+            if !attaches {
+                end = i;
+                break;
+            }
+            tied = TIE_BARS.contains(&c);
+            prev = c;
+        }
+        let (token, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(token)
+    }
+}
+
+/// A token of a transcription read by `IpaTokens`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Token<'a> {
+    /// The token as written.
+    pub text: &'a str,
+    /// Byte offset of the token in the input.
+    pub offset: usize,
+    pub segment: Segment,
+}
+
+/// An iterator over the segments of a transcription, split as by
+/// `tokenize` and read as by `parse`, that borrows its input and does
+/// not allocate, so it suits text of any length and `no_std` targets.
+///
+/// A token that cannot be read yields an `Error` with its offset in
+/// the input, and iteration carries on with the next token.
+/// Affricates must be written with a tie bar.
+///
+/// ```
+/// use allo::parser::IpaTokens;
+///
+/// let texts: Vec<&str> = IpaTokens::new("t͡ʃaʊ").map(|t| t.unwrap().text).collect();
+/// assert_eq!(texts, ["t͡ʃ", "a", "ʊ"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IpaTokens<'a> {
+    input: &'a str,
+    split: Split<'a>,
+}
+
+impl<'a> IpaTokens<'a> {
+    pub fn new(input: &'a str) -> Self {
+        IpaTokens {
+            input,
+            split: Split { rest: input },
+        }
+    }
+}
+
+impl<'a> Iterator for IpaTokens<'a> {
+    type Item = Result<Token<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.split.next()?;
+        let offset = self.input.len() - self.split.rest.len() - text.len();
+        Some(match segment(text) {
+            Ok(segment) => Ok(Token {
+                text,
+                offset,
+                segment,
+            }),
+            Err(e) => Err(e.shifted(offset)),
+        })
+    }
+}
+
+/// The segment spelled by a single token, with error offsets into the
+/// token.
+fn segment(token: &str) -> Result<Segment, Error> {
+    let mut chars = token.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if let Some(s) = Suprasegmental::from_char(c) {
+            return Ok(Segment::Suprasegmental(s));
+        }
+    }
+    if token.chars().all(is_tone_letter) {
+        if let Some(tone) = Contour::parse(token) {
+            return Ok(Segment::Tone(tone));
+        }
+    }
+    if let Some((stop, fricative)) = split_tie(token) {
+        if let (Some(Description::Consonant(stop)), Some(Description::Consonant(fricative))) =
+            (ipa::describe(stop), ipa::describe(fricative))
+        {
+            if let Some(a) = Affricate::new(stop, fricative) {
+                return Ok(Segment::Affricate(a));
+            }
+        }
+    }
+    match ipa::try_describe(token)? {
+        Description::Consonant(c) => Ok(Segment::Consonant(c)),
+        Description::Vowel(v) => Ok(Segment::Vowel(v)),
+    }
+}
+
+/// Parses a whole transcription into segments, one per token. Error
+/// offsets are into `s`.
+#[cfg(feature = "alloc")]
+pub fn parse(s: &str) -> Result<Vec<Segment>, ParseSegmentError> {
+    let mut segments = Vec::new();
+    for token in tokenize(s) {
+        let (segment, tone) = toned(token).map_err(|e| e.shifted(offset_in(s, token)))?;
+        segments.push(segment);
+        segments.extend(tone.map(Segment::Tone));
+    }
+    Ok(segments)
+}
+
+/// The segment `token` spells and the tone of a tone diacritic on it,
+/// so that `á` reads as `a` and a high tone. Error offsets are into
+/// `token` as written.
+#[cfg(feature = "alloc")]
+fn toned(token: &str) -> Result<(Segment, Option<Contour>), ParseSegmentError> {
+    let (text, starts) = normalize::normalize_mapped(token);
+    let Some((at, mark, tone)) = text
+        .char_indices()
+        .find_map(|(at, c)| Contour::from_diacritic(c).map(|tone| (at, c, tone)))
+    else {
+        return Ok((token.parse()?, None));
+    };
+    let mut base = text.clone();
+    base.remove(at);
+    let segment = base
+        .parse()
+        .map_err(|e: ParseSegmentError| match e.offset() {
+            Some(offset) => {
+                let offset = if offset < at {
+                    offset
+                } else {
+                    offset + mark.len_utf8()
+                };
+                e.at(normalize::original_offset(&starts, offset))
+            }
+            None => e,
+        })?;
+    Ok((segment, Some(tone)))
+}
+
+/// Parses `s` as `parse` does, reading double consonants as one long
+/// consonant if `gemination` says they are written that way, so that
+/// `"kata"` and `"katta"` differ in the length of their `t`.
+#[cfg(feature = "alloc")]
+pub fn parse_with(s: &str, gemination: Gemination) -> Result<Vec<Segment>, ParseSegmentError> {
+    parse(s).map(|segments| gemination.join(segments))
+}
+
+/// Characters commonly typed for the IPA symbols they resemble, paired
+/// with those symbols: Latin `g` for `ɡ`, a colon for the length mark,
+/// an apostrophe for the ejective mark, and so on. None of them is IPA
+/// itself.
+pub const CONFUSABLES: [(char, char); 6] = [
+    ('g', '\u{261}'),        // ɡ
+    (':', '\u{2D0}'),        // ː
+    ('\'', '\u{2BC}'),       // ʼ
+    ('\u{2019}', '\u{2BC}'), // ’ for ʼ
+    ('!', '\u{1C3}'),        // ǃ
+    ('?', '\u{294}'),        // ʔ
+];
+
+/// A look-alike replaced by `correct`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Correction {
+    /// Byte offset of the look-alike in the uncorrected input.
+    pub offset: usize,
+    pub found: char,
+    pub replacement: char,
+}
+
+/// Replaces the `CONFUSABLES` in `s` with the symbols they stand for,
+/// returning the corrected text and the corrections made.
+#[cfg(feature = "alloc")]
+pub fn correct(s: &str) -> (String, Vec<Correction>) {
+    let mut corrected = String::with_capacity(s.len());
+    let mut corrections = Vec::new();
+    for (offset, found) in s.char_indices() {
+        match CONFUSABLES.iter().find(|(c, _)| *c == found) {
+            Some(&(_, replacement)) => {
+                corrected.push(replacement);
+                corrections.push(Correction {
+                    offset,
+                    found,
+                    replacement,
+                });
+            }
+            None => corrected.push(found),
+        }
+    }
+    (corrected, corrections)
+}
+
+/// Parses `s` as `parse` does after correcting it with `correct`, so
+/// that `"k'"` reads as `kʼ`. Returns the corrections alongside the
+/// segments; error offsets are into `s` as written.
+#[cfg(feature = "alloc")]
+pub fn parse_lenient(s: &str) -> Result<(Vec<Segment>, Vec<Correction>), ParseSegmentError> {
+    let (corrected, corrections) = correct(s);
+    match parse(&corrected) {
+        Ok(segments) => Ok((segments, corrections)),
+        Err(e) => match e.offset() {
+            Some(offset) => {
+                // Each correction before the error moved the text after
+                // it by the difference in width, either way.
+                let mut moved = 0isize;
+                for c in &corrections {
+                    if c.offset.saturating_add_signed(moved) >= offset {
+                        break;
+                    }
+                    moved += c.replacement.len_utf8() as isize - c.found.len_utf8() as isize;
+                }
+                Err(e.at(offset.saturating_add_signed(-moved)))
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// Pairs of brackets that enclose transcriptions: phonetic `[ ]`,
+/// phonemic `/ /`, orthographic `⟨ ⟩`, and the parentheses of silent
+/// articulation.
+pub const BRACKETS: [(char, char); 4] =
+    [('[', ']'), ('/', '/'), ('\u{27E8}', '\u{27E9}'), ('(', ')')];
+
+/// A problem `validate` found in a transcription.
+#[cfg(feature = "alloc")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Problem {
+    /// A character that is not a known symbol or mark where it
+    /// appears.
+    UnknownSymbol(char),
+    /// A diacritic or modifier with no symbol before it to attach to.
+    DanglingDiacritic(char),
+    /// A mark that cannot join the symbols around it.
+    InvalidCombination(char),
+    /// A tie bar without a symbol on one side of it.
+    UnmatchedTieBar(char),
+    /// An opening bracket that is never closed, or a closing one that
+    /// was never opened.
+    UnmatchedBracket(char),
+    /// A symbol of another notation, such as the X-SAMPA `@` for `ə`.
+    MixedNotation { symbol: char, notation: Notation },
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::UnknownSymbol(c) => write!(f, "unknown IPA symbol `{}`", c),
+            Problem::DanglingDiacritic(c) => {
+                write!(f, "`\u{25CC}{}` has no symbol to attach to", c)
+            }
+            Problem::InvalidCombination(c) => {
+                write!(f, "`{}` cannot join the symbols around it", c)
+            }
+            Problem::UnmatchedTieBar(c) => write!(f, "`\u{25CC}{}` does not join two symbols", c),
+            Problem::UnmatchedBracket(c) => write!(f, "unmatched `{}`", c),
+            Problem::MixedNotation { symbol, notation } => {
+                write!(f, "`{}` is {}, not IPA", symbol, notation.name())
+            }
+        }
+    }
+}
+
+/// A problem in a transcription and the byte range of `validate`'s
+/// input it covers.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    pub range: Range<usize>,
+    pub problem: Problem,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at bytes {}..{}",
+            self.problem, self.range.start, self.range.end
+        )
+    }
+}
+
+/// Checks a whole transcription, such as `"[ˈtʰɪŋ.kɪŋ]"`, returning
+/// every problem found rather than stopping at the first: unknown
+/// symbols, diacritics with nothing to attach to, tie bars or
+/// `BRACKETS` without a partner, and ASCII symbols of X-SAMPA or
+/// Kirshenbaum. Each segment gets at most one diagnostic.
+///
+/// ```
+/// use allo::parser::{self, Problem};
+///
+/// assert!(parser::validate("[ˈt͡ʃɪp]").is_ok());
+/// let diagnostics = parser::validate("[t͡ʃ@").unwrap_err();
+/// assert_eq!(diagnostics[0].problem, Problem::UnmatchedBracket('['));
+/// assert_eq!(diagnostics[1].range, 6..7);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn validate(s: &str) -> Result<(), Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+    let mut open: Vec<(usize, char)> = Vec::new();
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        let Some(&(opening, closing)) = BRACKETS.iter().find(|(o, c2)| *o == c || *c2 == c) else {
+            continue;
+        };
+        check_segments(s, start..i, &mut diagnostics);
+        start = i + c.len_utf8();
+        match open.last() {
+            Some(&(_, o)) if c == closing && o == opening => {
+                open.pop();
+            }
+            _ if c == opening => open.push((i, c)),
+            _ => diagnostics.push(Diagnostic {
+                range: i..start,
+                problem: Problem::UnmatchedBracket(c),
+            }),
+        }
+    }
+    check_segments(s, start..s.len(), &mut diagnostics);
+    diagnostics.extend(open.into_iter().map(|(i, c)| Diagnostic {
+        range: i..i + c.len_utf8(),
+        problem: Problem::UnmatchedBracket(c),
+    }));
+    diagnostics.sort_by_key(|d| d.range.start);
+    match diagnostics.is_empty() {
+        true => Ok(()),
+        false => Err(diagnostics),
+    }
+}
+
+/// Adds a diagnostic for each segment of `s[range]` that does not
+/// parse.
+#[cfg(feature = "alloc")]
+fn check_segments(s: &str, range: Range<usize>, diagnostics: &mut Vec<Diagnostic>) {
+    for token in tokenize(&s[range]) {
+        let at = offset_in(s, token);
+        let span = |offset: usize, c: char| offset..offset + c.len_utf8();
+        // A tie bar first, last, or before a space joins nothing.
+        let mut chars = token.char_indices().peekable();
+        let mut unmatched = None;
+        while let Some((i, c)) = chars.next() {
+            let next = chars.peek().map(|&(_, n)| n);
+            if TIE_BARS.contains(&c) && (i == 0 || next.is_none_or(char::is_whitespace)) {
+                unmatched = Some((i, c));
+                break;
+            }
+        }
+        if let Some((i, c)) = unmatched {
+            diagnostics.push(Diagnostic {
+                range: span(at + i, c),
+                problem: Problem::UnmatchedTieBar(c),
+            });
+            continue;
+        }
+        let (range, problem) = match token.parse::<Segment>() {
+            Ok(_) | Err(ParseSegmentError::Empty) => continue,
+            Err(ParseSegmentError::Invalid(e)) => match e {
+                Error::Empty => continue,
+                Error::UnknownSymbol { offset, symbol } => {
+                    (span(at + offset, symbol), unknown(symbol))
+                }
+                Error::DanglingDiacritic { offset, mark } => {
+                    (span(at + offset, mark), Problem::DanglingDiacritic(mark))
+                }
+                Error::InvalidCombination { offset, mark } => {
+                    (span(at + offset, mark), Problem::InvalidCombination(mark))
+                }
+            },
+            Err(_) => {
+                let first = token.chars().next().unwrap_or_default();
+                (at..at + token.len(), unknown(first))
+            }
+        };
+        diagnostics.push(Diagnostic { range, problem });
+    }
+}
+
+/// The problem with unknown symbol `c`: mixed notation if it is ASCII
+/// that X-SAMPA or Kirshenbaum use.
+#[cfg(feature = "alloc")]
+fn unknown(c: char) -> Problem {
+    let uses = |table: &Table| table.iter().any(|(symbol, _)| symbol.starts_with(c));
+    match c.is_ascii() {
+        true if uses(xsampa::TABLE) => Problem::MixedNotation {
+            symbol: c,
+            notation: Notation::XSampa,
+        },
+        true if uses(kirshenbaum::TABLE) => Problem::MixedNotation {
+            symbol: c,
+            notation: Notation::Kirshenbaum,
+        },
+        _ => Problem::UnknownSymbol(c),
+    }
+}
+
+/// The byte offset of `slice` in `s`, which it must be a slice of.
+#[cfg(feature = "alloc")]
+pub(crate) fn offset_in(s: &str, slice: &str) -> usize {
+    slice.as_ptr() as usize - s.as_ptr() as usize
+}
@@ -0,0 +1,245 @@
+//! Patterns over sequences of segments, in the manner of regular
+//! expressions: `[+nasal] [−voice]* V` matches a nasal, any number of
+//! voiceless segments, then a vowel.
+//!
+//! A pattern is written as a sequence of segments, categories (those of
+//! `classes::named`, or others given to `Pattern::parse_with`), feature
+//! matrices in brackets and word boundaries (`#`). Any of them, or a
+//! group in parentheses, can be followed by `?` (optional), `*` (any
+//! number of times) or `+` (at least once), and alternatives are
+//! separated by `|`. Where `rules::Pattern` is a single position of a
+//! rule, these match whole stretches of a word.
+
+use core::ops::Range;
+use core::str::FromStr;
+
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use crate::classes::Class;
+use crate::rules::{self, ParseRuleError};
+use crate::segment::Segment;
+
+/// A compiled pattern.
+///
+/// ```
+/// use allo::parser;
+/// use allo::pattern::Pattern;
+///
+/// let cluster: Pattern = "[+nasal] [−voice]* V".parse().unwrap();
+/// let word = parser::parse("tamsta").unwrap();
+/// assert_eq!(cluster.find(&word), Some(2..6));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    node: Node,
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Segment(Segment),
+    Class(Class),
+    Boundary,
+    Sequence(Vec<Node>),
+    Alternation(Vec<Node>),
+    /// At least `min` and at most `max` repetitions, without limit if
+    /// `max` is `None`.
+    Repeat(Box<Node>, usize, Option<usize>),
+}
+
+impl From<rules::Pattern> for Node {
+    fn from(pattern: rules::Pattern) -> Node {
+        match pattern {
+            rules::Pattern::Segment(s) => Node::Segment(s),
+            rules::Pattern::Class(class) => Node::Class(class),
+            rules::Pattern::Boundary => Node::Boundary,
+            rules::Pattern::Optional(patterns) => {
+                let sequence = Node::Sequence(patterns.into_iter().map(Node::from).collect());
+                Node::Repeat(Box::new(sequence), 0, Some(1))
+            }
+        }
+    }
+}
+
+impl Node {
+    /// Every position a match starting at `start` can end at, in order.
+    fn ends(&self, word: &[Segment], start: usize) -> Vec<usize> {
+        let mut ends = match self {
+            Node::Segment(s) => match word.get(start) {
+                Some(segment) if segment == s => vec![start + 1],
+                _ => Vec::new(),
+            },
+            Node::Class(class) => match word.get(start) {
+                Some(segment) if class.matches(segment) => vec![start + 1],
+                _ => Vec::new(),
+            },
+            Node::Boundary => match start == 0 || start == word.len() {
+                true => vec![start],
+                false => Vec::new(),
+            },
+            Node::Sequence(nodes) => nodes.iter().fold(vec![start], |ends, node| {
+                ends.iter().flat_map(|&i| node.ends(word, i)).collect()
+            }),
+            Node::Alternation(nodes) => nodes.iter().flat_map(|n| n.ends(word, start)).collect(),
+            Node::Repeat(node, min, max) => {
+                let mut ends = Vec::new();
+                let mut frontier = vec![start];
+                let mut count = 0;
+                while !frontier.is_empty() {
+                    if count >= *min {
+                        ends.extend(&frontier);
+                    }
+                    if Some(count) == *max || count > word.len() + min {
+                        break;
+                    }
+                    let mut next: Vec<usize> =
+                        frontier.iter().flat_map(|&i| node.ends(word, i)).collect();
+                    next.sort_unstable();
+                    next.dedup();
+                    if count >= *min {
+                        next.retain(|i| !ends.contains(i));
+                    }
+                    frontier = next;
+                    count += 1;
+                }
+                ends
+            }
+        };
+        ends.sort_unstable();
+        ends.dedup();
+        ends
+    }
+}
+
+impl Pattern {
+    /// Parses a pattern with the built-in categories of
+    /// `classes::named`.
+    pub fn parse(s: &str) -> Result<Pattern, ParseRuleError> {
+        Pattern::parse_with(s, &[])
+    }
+
+    /// Parses a pattern in which `categories` name further classes or
+    /// redefine the built-in ones, as in `Rule::parse_with`.
+    pub fn parse_with(s: &str, categories: &[(&str, Class)]) -> Result<Pattern, ParseRuleError> {
+        let mut rest = s;
+        let node = alternation(&mut rest, categories)?;
+        match rest.chars().next() {
+            Some(c) => Err(ParseRuleError::Unbalanced(c)),
+            None => Ok(Pattern { node }),
+        }
+    }
+
+    /// The longest match starting at `word[start]`, if there is one.
+    pub fn match_at(&self, word: &[Segment], start: usize) -> Option<Range<usize>> {
+        if start > word.len() {
+            return None;
+        }
+        let end = self.node.ends(word, start).pop()?;
+        Some(start..end)
+    }
+
+    /// The first match in `word`, the longest if several start there.
+    pub fn find(&self, word: &[Segment]) -> Option<Range<usize>> {
+        (0..=word.len()).find_map(|start| self.match_at(word, start))
+    }
+
+    /// Every match in `word` that does not overlap an earlier one, as
+    /// `find` would find them in turn.
+    pub fn find_all(&self, word: &[Segment]) -> Vec<Range<usize>> {
+        let mut matches = Vec::new();
+        let mut start = 0;
+        while start <= word.len() {
+            match self.match_at(word, start) {
+                Some(m) => {
+                    start = m.end.max(start + 1);
+                    matches.push(m);
+                }
+                None => start += 1,
+            }
+        }
+        matches
+    }
+
+    /// Whether the pattern matches the whole of `word`.
+    pub fn matches(&self, word: &[Segment]) -> bool {
+        self.node.ends(word, 0).contains(&word.len())
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = ParseRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Pattern::parse(s)
+    }
+}
+
+/// Alternatives separated by `|`, up to an unmatched `)` or the end.
+fn alternation(rest: &mut &str, categories: &[(&str, Class)]) -> Result<Node, ParseRuleError> {
+    let mut alternatives = vec![sequence(rest, categories)?];
+    while let Some(tail) = rest.strip_prefix('|') {
+        *rest = tail;
+        alternatives.push(sequence(rest, categories)?);
+    }
+    Ok(match alternatives.len() {
+        1 => alternatives.remove(0),
+        _ => Node::Alternation(alternatives),
+    })
+}
+
+/// A sequence of quantified items, up to a `|`, an unmatched `)` or
+/// the end.
+fn sequence(rest: &mut &str, categories: &[(&str, Class)]) -> Result<Node, ParseRuleError> {
+    let mut nodes: Vec<Node> = Vec::new();
+    loop {
+        *rest = rest.trim_start();
+        let Some(c) = rest.chars().next() else {
+            break;
+        };
+        match c {
+            '|' | ')' => break,
+            '*' | '+' | '?' => {
+                let node = nodes.pop().ok_or(ParseRuleError::Dangling(c))?;
+                let (min, max) = match c {
+                    '*' => (0, None),
+                    '+' => (1, None),
+                    _ => (0, Some(1)),
+                };
+                nodes.push(Node::Repeat(Box::new(node), min, max));
+                *rest = &rest[1..];
+            }
+            '#' => {
+                nodes.push(Node::Boundary);
+                *rest = &rest[1..];
+            }
+            '(' => {
+                *rest = &rest[1..];
+                let group = alternation(rest, categories)?;
+                *rest = rest
+                    .strip_prefix(')')
+                    .ok_or(ParseRuleError::Unbalanced('('))?;
+                nodes.push(group);
+            }
+            '[' => {
+                let end = rest.find(']').ok_or(ParseRuleError::Unbalanced('['))?;
+                nodes.push(Node::Class(Class::Features(rules::bundle(&rest[..=end])?)));
+                *rest = &rest[end + 1..];
+            }
+            ']' => return Err(ParseRuleError::Unbalanced(c)),
+            _ => {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || "#[]()|*+?".contains(c))
+                    .unwrap_or(rest.len());
+                nodes.extend(
+                    rules::run(&rest[..end], categories)?
+                        .into_iter()
+                        .map(Node::from),
+                );
+                *rest = &rest[end..];
+            }
+        }
+    }
+    Ok(match nodes.len() {
+        1 => nodes.remove(0),
+        _ => Node::Sequence(nodes),
+    })
+}
@@ -0,0 +1,39 @@
+//! Phoneme inventories in the conventions of PHOIBLE
+//! (<https://phoible.org>), embedded in the crate. This is a sample
+//! of one inventory per language, not the full database.
+
+use crate::inventory::Inventory;
+
+const DATA: &str = include_str!("../data/phoible.tsv");
+
+fn records() -> impl Iterator<Item = (&'static str, &'static str, &'static str)> {
+    DATA.lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            Some((fields.next()?, fields.next()?, fields.next()?))
+        })
+}
+
+/// The ISO 639-3 codes and names of the languages available.
+pub fn languages() -> impl Iterator<Item = (&'static str, &'static str)> {
+    records().map(|(code, name, _)| (code, name))
+}
+
+/// The phonemes of the language with ISO 639-3 code `code`, as
+/// written in PHOIBLE.
+pub fn phonemes(code: &str) -> Option<impl Iterator<Item = &'static str>> {
+    records()
+        .find(|(c, _, _)| *c == code)
+        .map(|(_, _, phonemes)| phonemes.split(' '))
+}
+
+impl Inventory {
+    /// Loads the inventory of the language with ISO 639-3 code
+    /// `code`. Phonemes the crate cannot yet represent as a single
+    /// segment are left out.
+    pub fn from_phoible(code: &str) -> Option<Inventory> {
+        let phonemes = phonemes(code)?;
+        Some(phonemes.filter_map(|p| p.parse().ok()).collect())
+    }
+}
@@ -0,0 +1,303 @@
+use core::error::Error;
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use crate::rules::Pattern;
+use crate::segment::Segment;
+use crate::syllable::{self, Syllable};
+
+/// One slot of a syllable template.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Slot {
+    /// A consonant in the onset or coda.
+    C,
+    /// A segment of the nucleus.
+    V,
+}
+
+/// A syllable shape such as `CCVC`, with optional slots in
+/// parentheses: `(C)(C)V(C)`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Template(Vec<(Slot, bool)>);
+
+impl Template {
+    /// Parses a template written with `C`, `V` and parentheses around
+    /// optional slots, returning `None` for any other character or
+    /// unbalanced parentheses.
+    pub fn parse(s: &str) -> Option<Template> {
+        let mut slots = Vec::new();
+        let mut optional = false;
+        for c in s.chars() {
+            match c {
+                'C' => slots.push((Slot::C, optional)),
+                'V' => slots.push((Slot::V, optional)),
+                '(' if !optional => optional = true,
+                ')' if optional => optional = false,
+                _ => return None,
+            }
+        }
+        (!optional).then_some(Template(slots))
+    }
+
+    /// The slots, each with whether it is optional.
+    pub fn slots(&self) -> &[(Slot, bool)] {
+        &self.0
+    }
+
+    /// Whether a syllable with the given shape fits the template.
+    pub fn matches(&self, shape: &[Slot]) -> bool {
+        fn go(template: &[(Slot, bool)], shape: &[Slot]) -> bool {
+            match (template.split_first(), shape.split_first()) {
+                (None, None) => true,
+                (None, Some(_)) => false,
+                (Some((&(slot, optional), rest)), _) => {
+                    (shape.first() == Some(&slot) && go(rest, &shape[1..]))
+                        || (optional && go(rest, shape))
+                }
+            }
+        }
+        go(&self.0, shape)
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (slot, optional) in &self.0 {
+            match optional {
+                true => write!(f, "({:?})", slot)?,
+                false => write!(f, "{:?}", slot)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The shape of `syllable`, a `C` for each onset and coda segment and
+/// a `V` for each nucleus segment.
+pub fn shape(syllable: &Syllable) -> Vec<Slot> {
+    let c = |s: &Vec<Segment>| s.iter().map(|_| Slot::C).collect::<Vec<_>>();
+    let mut shape = c(&syllable.onset);
+    shape.extend(syllable.nucleus.iter().map(|_| Slot::V));
+    shape.extend(c(&syllable.coda));
+    shape
+}
+
+/// A language's phonotactics, given as data:
+///
+/// ```
+/// use allo::classes::{obstruent, sonorant};
+/// use allo::parser::parse;
+/// use allo::phonotactics::{Phonotactics, Template};
+///
+/// let p = Phonotactics::new()
+///     .with_template(Template::parse("(C)(C)V(C)").unwrap())
+///     .with_onset([obstruent().into(), sonorant().into()]);
+/// assert!(p.validate(&parse("plan").unwrap()).is_ok());
+/// assert_eq!(p.validate(&parse("ptan").unwrap()).unwrap_err().position, 0);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Phonotactics {
+    templates: Vec<Template>,
+    onsets: Vec<Vec<Pattern>>,
+    codas: Vec<Vec<Pattern>>,
+    banned: Vec<Vec<Pattern>>,
+    scale: syllable::Scale,
+}
+
+impl Phonotactics {
+    /// No constraints: every word is valid.
+    pub fn new() -> Self {
+        Phonotactics::default()
+    }
+
+    /// Allows syllables of this shape. With no templates, any shape
+    /// is allowed.
+    pub fn with_template(mut self, template: Template) -> Self {
+        self.templates.push(template);
+        self
+    }
+
+    pub fn templates(&self) -> &[Template] {
+        &self.templates
+    }
+
+    /// Allows an onset cluster of two or more consonants. Single
+    /// consonants are always allowed; with no clusters listed, any
+    /// cluster the sonority sequencing principle allows is.
+    pub fn with_onset(mut self, cluster: impl IntoIterator<Item = Pattern>) -> Self {
+        self.onsets.push(cluster.into_iter().collect());
+        self
+    }
+
+    /// Allows a coda cluster, as `with_onset`.
+    pub fn with_coda(mut self, cluster: impl IntoIterator<Item = Pattern>) -> Self {
+        self.codas.push(cluster.into_iter().collect());
+        self
+    }
+
+    /// Ranks segments on `scale` when checking clusters by sonority.
+    pub fn with_scale(mut self, scale: syllable::Scale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Bans a sequence of segments anywhere in the word, e.g. `[ŋ, #]`
+    /// for no word-final `ŋ`. `Pattern::Boundary` matches a word edge.
+    pub fn with_banned(mut self, sequence: impl IntoIterator<Item = Pattern>) -> Self {
+        self.banned.push(sequence.into_iter().collect());
+        self
+    }
+
+    /// Checks `word`, returning the earliest violation.
+    pub fn validate(&self, word: &[Segment]) -> Result<(), Violation> {
+        match self.violations(word).into_iter().next() {
+            Some(v) => Err(v),
+            None => Ok(()),
+        }
+    }
+
+    /// Every violation in `word`, ordered by position. Positions index
+    /// into `word`; suprasegmentals and tones are otherwise ignored.
+    pub fn violations(&self, word: &[Segment]) -> Vec<Violation> {
+        let positions: Vec<usize> = (0..word.len())
+            .filter(|&i| word[i].is_segmental())
+            .collect();
+        let segments: Vec<Segment> = positions.iter().map(|&i| word[i]).collect();
+        let mut violations = Vec::new();
+
+        for (start, &position) in positions.iter().enumerate() {
+            for (n, sequence) in self.banned.iter().enumerate() {
+                if banned_at(sequence, &segments, start) {
+                    violations.push(Violation {
+                        position,
+                        kind: ViolationKind::Banned(n),
+                    });
+                }
+            }
+        }
+
+        let mut offset = 0;
+        for syllable in syllable::syllabify_with(word, self) {
+            offset -= usize::from(syllable.ambisyllabic);
+            let at = |k: usize| positions[(offset + k).min(positions.len() - 1)];
+            let onset = syllable.onset.len();
+            let nucleus = syllable.nucleus.len();
+            if nucleus == 0 {
+                violations.push(Violation {
+                    position: at(0),
+                    kind: ViolationKind::NoNucleus,
+                });
+            }
+            if onset > 1 && !allowed(&self.onsets, &syllable.onset) {
+                violations.push(Violation {
+                    position: at(0),
+                    kind: ViolationKind::Onset,
+                });
+            }
+            if syllable.coda.len() > 1 && !allowed(&self.codas, &syllable.coda) {
+                violations.push(Violation {
+                    position: at(onset + nucleus),
+                    kind: ViolationKind::Coda,
+                });
+            }
+            let shape = shape(&syllable);
+            if !self.templates.is_empty() && !self.templates.iter().any(|t| t.matches(&shape)) {
+                violations.push(Violation {
+                    position: at(0),
+                    kind: ViolationKind::Template,
+                });
+            }
+            offset += shape.len();
+        }
+
+        violations.sort_by_key(|v| v.position);
+        violations
+    }
+}
+
+impl syllable::Phonotactics for Phonotactics {
+    fn sonority(&self, segment: &Segment) -> Option<u8> {
+        self.scale.sonority(segment)
+    }
+
+    fn is_onset(&self, cluster: &[Segment]) -> bool {
+        match self.onsets.is_empty() {
+            true => self.scale.is_onset(cluster),
+            false => cluster.len() < 2 || allowed(&self.onsets, cluster),
+        }
+    }
+
+    fn is_coda(&self, cluster: &[Segment]) -> bool {
+        match self.codas.is_empty() {
+            true => self.scale.is_coda(cluster),
+            false => cluster.len() < 2 || allowed(&self.codas, cluster),
+        }
+    }
+}
+
+/// Whether `cluster` is one of `clusters`, or `clusters` is empty.
+pub(crate) fn allowed(clusters: &[Vec<Pattern>], cluster: &[Segment]) -> bool {
+    clusters.is_empty()
+        || clusters
+            .iter()
+            .any(|c| c.len() == cluster.len() && c.iter().zip(cluster).all(|(p, s)| p.matches(s)))
+}
+
+/// Whether `sequence` matches `word` starting at `start`.
+fn banned_at(sequence: &[Pattern], word: &[Segment], start: usize) -> bool {
+    let mut i = start;
+    for (k, pattern) in sequence.iter().enumerate() {
+        match pattern {
+            Pattern::Boundary if k == 0 => {
+                if start != 0 {
+                    return false;
+                }
+            }
+            Pattern::Boundary => return k == sequence.len() - 1 && i == word.len(),
+            p => match word.get(i) {
+                Some(s) if p.matches(s) => i += 1,
+                _ => return false,
+            },
+        }
+    }
+    !sequence.is_empty()
+}
+
+/// What a word violates.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ViolationKind {
+    /// The banned sequence with this index, in the order they were
+    /// added.
+    Banned(usize),
+    /// An onset cluster that is not allowed.
+    Onset,
+    /// A coda cluster that is not allowed.
+    Coda,
+    /// A syllable matching none of the templates.
+    Template,
+    /// A stretch of segments with no nucleus.
+    NoNucleus,
+}
+
+/// A phonotactic violation at `position` in the word.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Violation {
+    pub position: usize,
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ViolationKind::Banned(n) => write!(f, "banned sequence #{}", n)?,
+            ViolationKind::Onset => write!(f, "onset not allowed")?,
+            ViolationKind::Coda => write!(f, "coda not allowed")?,
+            ViolationKind::Template => write!(f, "syllable matches no template")?,
+            ViolationKind::NoNucleus => write!(f, "syllable has no nucleus")?,
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for Violation {}
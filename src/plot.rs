@@ -0,0 +1,172 @@
+//! SVG plots of vowels on the vowel quadrilateral.
+
+use core::fmt::Write;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::formants::Formants;
+use crate::ipa::{Roundedness, Vowel};
+
+const WIDTH: f32 = 400.0;
+const HEIGHT: f32 = 300.0;
+const MARGIN: f32 = 30.0;
+/// How far a rounded vowel sits to the right of its unrounded pair.
+const PAIR_OFFSET: f32 = 14.0;
+
+/// The F1 and F2 ranges mapped onto the quadrilateral's height and
+/// width, covering the estimates in `formants` for adult males.
+const F1_RANGE: (f32, f32) = (250.0, 850.0);
+const F2_RANGE: (f32, f32) = (800.0, 2300.0);
+
+/// A set of labelled points on the vowel quadrilateral, rendered with
+/// `render_svg`:
+///
+/// ```
+/// use allo::plot::VowelPlot;
+///
+/// let svg = VowelPlot::new()
+///     .vowels(["i", "e", "a", "o", "u"].map(|v| v.parse().unwrap()))
+///     .render_svg();
+/// assert!(svg.starts_with("<svg"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VowelPlot {
+    points: Vec<Point>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Point {
+    label: String,
+    /// Position within the quadrilateral, each from `0.0` to `1.0`:
+    /// close to open, and front to back along the row.
+    openness: f32,
+    backness: f32,
+    rounded: bool,
+}
+
+impl VowelPlot {
+    pub fn new() -> Self {
+        VowelPlot::default()
+    }
+
+    /// Adds `vowel` at its place in the chart, labelled with its
+    /// symbol. As on the IPA chart, a rounded vowel sits to the right
+    /// of its unrounded counterpart.
+    pub fn vowel(mut self, vowel: Vowel) -> Self {
+        self.points.push(Point {
+            label: vowel.to_string(),
+            openness: vowel.height() as u8 as f32 / 6.0,
+            backness: vowel.backness() as u8 as f32 / 4.0,
+            rounded: vowel.roundedness() == Roundedness::Rounded,
+        });
+        self
+    }
+
+    pub fn vowels(self, vowels: impl IntoIterator<Item = Vowel>) -> Self {
+        vowels.into_iter().fold(self, VowelPlot::vowel)
+    }
+
+    /// Adds a point at measured formants, F1 giving height and F2
+    /// backness. Formants outside the adult male range are clamped
+    /// to the edge of the quadrilateral.
+    pub fn point(mut self, label: &str, formants: Formants) -> Self {
+        let scale = |v: f32, (lo, hi): (f32, f32)| ((v - lo) / (hi - lo)).clamp(0.0, 1.0);
+        self.points.push(Point {
+            label: label.to_string(),
+            openness: scale(formants.f1, F1_RANGE),
+            backness: 1.0 - scale(formants.f2, F2_RANGE),
+            rounded: false,
+        });
+        self
+    }
+
+    /// Renders the quadrilateral, with lines for the central column
+    /// and the mid heights, and each point as a dot and a label. The
+    /// shapes carry the classes `quadrilateral`, `grid`, `vowel` and
+    /// `label` for styling.
+    pub fn render_svg(&self) -> String {
+        let mut svg = String::new();
+        let (w, h) = (WIDTH + 2.0 * MARGIN, HEIGHT + 2.0 * MARGIN);
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}" width="{}" height="{}">"#,
+            w, h, w, h
+        )
+        .unwrap();
+
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .map(|(backness, openness)| coordinates(openness, backness));
+        let points: Vec<String> = corners
+            .iter()
+            .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+            .collect();
+        writeln!(
+            svg,
+            r#"<polygon class="quadrilateral" points="{}" fill="none" stroke="black"/>"#,
+            points.join(" ")
+        )
+        .unwrap();
+        let lines = [
+            ((0.0, 0.5), (1.0, 0.5)),
+            ((2.0 / 6.0, 0.0), (2.0 / 6.0, 1.0)),
+            ((4.0 / 6.0, 0.0), (4.0 / 6.0, 1.0)),
+        ];
+        for ((openness, backness), (to_openness, to_backness)) in lines {
+            let (x1, y1) = coordinates(openness, backness);
+            let (x2, y2) = coordinates(to_openness, to_backness);
+            writeln!(
+                svg,
+                r#"<line class="grid" x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="gray"/>"#,
+                x1, y1, x2, y2
+            )
+            .unwrap();
+        }
+
+        for point in &self.points {
+            let (mut x, y) = coordinates(point.openness, point.backness);
+            // keep pairs inside the quadrilateral: unrounded to the
+            // left of the point, rounded to the right, except at the
+            // edges
+            x += match (point.rounded, point.backness) {
+                (true, b) if b < 1.0 => PAIR_OFFSET / 2.0,
+                (false, b) if b > 0.0 => -PAIR_OFFSET / 2.0,
+                _ => 0.0,
+            };
+            writeln!(
+                svg,
+                r#"<circle class="vowel" cx="{:.1}" cy="{:.1}" r="3"/>"#,
+                x, y
+            )
+            .unwrap();
+            writeln!(
+                svg,
+                r#"<text class="label" x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+                x,
+                y - 6.0,
+                escape(&point.label)
+            )
+            .unwrap();
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Where a point lies in the image. The front edge slants, so open
+/// front vowels sit halfway across.
+fn coordinates(openness: f32, backness: f32) -> (f32, f32) {
+    let left = openness * WIDTH / 2.0;
+    let x = MARGIN + left + backness * (WIDTH - left);
+    let y = MARGIN + openness * HEIGHT;
+    (x, y)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
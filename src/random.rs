@@ -0,0 +1,66 @@
+//! Random numbers for the generators, without depending on an RNG
+//! crate: anything implementing `Rng` can drive them, and `SplitMix64`
+//! is a small seedable one for reproducible output.
+
+/// A source of random numbers.
+pub trait Rng {
+    /// A uniformly random `u64`.
+    fn next_u64(&mut self) -> u64;
+
+    /// A random number in `0..n`, which must not be empty.
+    fn below(&mut self, n: u64) -> u64 {
+        ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64
+    }
+
+    /// An index into `weights`, each chosen with probability in
+    /// proportion to its weight, or `None` if they are all 0.
+    fn weighted(&mut self, weights: &[u64]) -> Option<usize> {
+        let total: u64 = weights.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let mut r = self.below(total);
+        weights.iter().position(|&w| match r.checked_sub(w) {
+            Some(rest) => {
+                r = rest;
+                false
+            }
+            None => true,
+        })
+    }
+
+    /// A random number in `0.0..1.0`.
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// The SplitMix64 generator: fast, with good statistical quality, and
+/// the same sequence for the same seed on every platform. Not for
+/// cryptography.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub const fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl<R: Rng + ?Sized> Rng for &mut R {
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+}
@@ -0,0 +1,97 @@
+//! Reading IPA longest match first into a script, as `mandarin`,
+//! `japanese` and `korean` write it.
+
+use core::cmp::Reverse;
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::diacritics::Diacritic;
+use crate::normalize;
+use crate::notation::UnknownSymbol;
+use crate::segment::Segment;
+
+/// IPA being read: stress marks, syllable breaks and the `skipped`
+/// characters are left out, each other character is `fold`ed and marks
+/// written in another position (as `ɻ̍`) are put as the tables have
+/// them. Errors give the offset and character of the original text.
+pub(crate) struct Reader<'s> {
+    original: &'s str,
+    text: String,
+    // the start of each character of `text` and its start in `original`
+    starts: Vec<(usize, usize)>,
+    position: usize,
+}
+
+impl<'s> Reader<'s> {
+    pub(crate) fn new(s: &'s str, skipped: &[char], fold: impl Fn(char) -> char) -> Self {
+        let mut text = String::with_capacity(s.len());
+        let mut starts = Vec::new();
+        for (offset, c) in s.char_indices() {
+            if matches!(c, 'ˈ' | 'ˌ' | '.') || skipped.contains(&c) {
+                continue;
+            }
+            let c = fold(c);
+            starts.push((text.len(), offset));
+            text.push(Diacritic::from_char(c).map_or(c, |d| d.mark()));
+        }
+        Reader {
+            original: s,
+            text,
+            starts,
+            position: 0,
+        }
+    }
+
+    /// The text not yet read.
+    pub(crate) fn rest(&self) -> &str {
+        &self.text[self.position..]
+    }
+
+    /// The next character, if any is left.
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// Where the reader is in the text, to pass to `error_at` later.
+    pub(crate) const fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(crate) fn advance(&mut self, len: usize) {
+        self.position += len;
+    }
+
+    /// The longest of `matches`, lengths of text at the current
+    /// position paired with what they read as, with its length. The
+    /// first of several equally long matches wins.
+    pub(crate) fn longest<T>(
+        &self,
+        matches: impl Iterator<Item = (usize, T)>,
+    ) -> Result<(usize, T), UnknownSymbol> {
+        matches
+            .min_by_key(|(len, _)| Reverse(*len))
+            .ok_or_else(|| self.error())
+    }
+
+    /// An unknown symbol at the current position.
+    pub(crate) fn error(&self) -> UnknownSymbol {
+        self.error_at(self.position)
+    }
+
+    /// An unknown symbol at `position`.
+    pub(crate) fn error_at(&self, position: usize) -> UnknownSymbol {
+        let offset = normalize::original_offset(&self.starts, position);
+        UnknownSymbol {
+            offset,
+            symbol: self.original[offset..].chars().next().unwrap_or_default(),
+        }
+    }
+}
+
+/// `segments` written out as IPA, for reading into a script.
+pub(crate) fn ipa(segments: &[Segment]) -> String {
+    segments.iter().map(Segment::to_string).collect()
+}
@@ -0,0 +1,1017 @@
+use core::error::Error;
+use core::fmt;
+use core::str::FromStr;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::classes::{self, Class};
+use crate::error::LineError;
+use crate::features::{self, Feature, FeatureBundle};
+use crate::parser;
+use crate::pattern;
+use crate::segment::{ParseSegmentError, Segment};
+
+/// One position of a rule's target or environment.
+#[derive(Clone, Debug)]
+pub enum Pattern {
+    /// Exactly this segment, diacritics included.
+    Segment(Segment),
+    /// Any segment in the class.
+    Class(Class),
+    /// The edge of the word, `#`. Only meaningful as the outermost
+    /// position of an environment.
+    Boundary,
+    /// A sequence that may be absent, written in parentheses. Only
+    /// meaningful in an environment.
+    Optional(Vec<Pattern>),
+}
+
+impl Pattern {
+    pub fn matches(&self, segment: &Segment) -> bool {
+        match self {
+            Pattern::Segment(s) => s == segment,
+            Pattern::Class(class) => class.matches(segment),
+            Pattern::Boundary | Pattern::Optional(_) => false,
+        }
+    }
+}
+
+impl From<Segment> for Pattern {
+    fn from(s: Segment) -> Self {
+        Pattern::Segment(s)
+    }
+}
+
+impl From<Class> for Pattern {
+    fn from(class: Class) -> Self {
+        Pattern::Class(class)
+    }
+}
+
+impl From<FeatureBundle> for Pattern {
+    fn from(bundle: FeatureBundle) -> Self {
+        Pattern::Class(Class::Features(bundle))
+    }
+}
+
+/// What a matched target is rewritten to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    /// Replace the target with this segment, or insert it if the
+    /// target is empty.
+    Segment(Segment),
+    /// Overwrite these features of the target, realizing the result as
+    /// the closest segment (see `features::change`).
+    Features(FeatureBundle),
+    /// Delete the target: `A → ∅`.
+    Delete,
+    /// Reorder the target sequence, putting its segment at `order[k]`
+    /// in place `k`: `[1, 0]` swaps two segments. Each segment moves
+    /// with its diacritics.
+    Metathesis(Vec<usize>),
+}
+
+impl Change {
+    /// What a single-segment `target` becomes. Deletion and metathesis
+    /// leave it as it is; `Rule::apply` carries them out.
+    pub fn apply(&self, target: &Segment) -> Segment {
+        match self {
+            Change::Segment(s) => *s,
+            Change::Features(bundle) => features::change(target, bundle).unwrap_or(*target),
+            Change::Delete | Change::Metathesis(_) => *target,
+        }
+    }
+
+    /// What the segments of a matched target sequence become.
+    fn rewrite(&self, matched: &[Segment]) -> Vec<Segment> {
+        match self {
+            Change::Segment(s) => vec![*s],
+            Change::Features(_) => matched.iter().map(|s| self.apply(s)).collect(),
+            Change::Delete => Vec::new(),
+            Change::Metathesis(order) => order
+                .iter()
+                .filter_map(|&k| matched.get(k))
+                .copied()
+                .collect(),
+        }
+    }
+}
+
+impl From<Segment> for Change {
+    fn from(s: Segment) -> Self {
+        Change::Segment(s)
+    }
+}
+
+impl From<FeatureBundle> for Change {
+    fn from(bundle: FeatureBundle) -> Self {
+        Change::Features(bundle)
+    }
+}
+
+/// A rewrite rule `A → B / C _ D`: a target `A` is changed to `B`
+/// when preceded by the sequence `C` and followed by the sequence `D`.
+///
+/// The target is usually one segment, but is empty for epenthesis
+/// (`∅ → B`) and a sequence for metathesis.
+#[derive(Clone, Debug)]
+pub struct Rule {
+    target: Vec<Pattern>,
+    change: Change,
+    left: Vec<Pattern>,
+    right: Vec<Pattern>,
+}
+
+impl Rule {
+    /// A context-free rule `target → change`.
+    pub fn new(target: impl Into<Pattern>, change: impl Into<Change>) -> Self {
+        Rule {
+            target: vec![target.into()],
+            change: change.into(),
+            left: Vec::new(),
+            right: Vec::new(),
+        }
+    }
+
+    /// A context-free rule `target → ∅`.
+    pub fn deletion(target: impl Into<Pattern>) -> Self {
+        Rule::new(target, Change::Delete)
+    }
+
+    /// A rule `∅ → segment`, inserting `segment` wherever its
+    /// environment matches; with no environment, between every two
+    /// segments and at both edges.
+    pub fn epenthesis(segment: Segment) -> Self {
+        Rule {
+            target: Vec::new(),
+            ..Rule::new(segment, segment)
+        }
+    }
+
+    /// A context-free rule reordering a sequence matching `target` so
+    /// that its segment at `order[k]` comes `k`th.
+    ///
+    /// # Panics
+    ///
+    /// If `order` is not a permutation of the target's indices.
+    pub fn metathesis(
+        target: impl IntoIterator<Item = Pattern>,
+        order: impl IntoIterator<Item = usize>,
+    ) -> Self {
+        let target: Vec<Pattern> = target.into_iter().collect();
+        let order: Vec<usize> = order.into_iter().collect();
+        assert!(
+            is_permutation(&order, target.len()),
+            "not a permutation of the target"
+        );
+        Rule {
+            target,
+            change: Change::Metathesis(order),
+            left: Vec::new(),
+            right: Vec::new(),
+        }
+    }
+
+    /// Restricts the rule to the environment `left _ right`.
+    pub fn with_environment(
+        mut self,
+        left: impl IntoIterator<Item = Pattern>,
+        right: impl IntoIterator<Item = Pattern>,
+    ) -> Self {
+        self.left = left.into_iter().collect();
+        self.right = right.into_iter().collect();
+        self
+    }
+
+    /// The sequence the rule rewrites: one pattern, none for
+    /// epenthesis, or several for metathesis.
+    pub fn target(&self) -> &[Pattern] {
+        &self.target
+    }
+
+    pub fn change(&self) -> &Change {
+        &self.change
+    }
+
+    pub fn left(&self) -> &[Pattern] {
+        &self.left
+    }
+
+    pub fn right(&self) -> &[Pattern] {
+        &self.right
+    }
+
+    /// Whether the rule applies to the target starting at `word[i]`,
+    /// or for epenthesis, to the gap before `word[i]` (or at the end,
+    /// if `i` is the length of the word).
+    pub fn applies_at(&self, word: &[Segment], i: usize) -> bool {
+        let end = i + self.target.len();
+        end <= word.len()
+            && self
+                .target
+                .iter()
+                .zip(&word[i..])
+                .all(|(p, s)| p.matches(s))
+            && matches_left(&self.left, &word[..i])
+            && matches_right(&self.right, &word[end..])
+    }
+
+    /// Rewrites every position of `word` the rule applies to.
+    ///
+    /// Application is simultaneous: environments are always checked
+    /// against the input word, so a change made at one position never
+    /// feeds or bleeds the rule at another. Targets of more than one
+    /// segment are matched left to right without overlapping.
+    pub fn apply(&self, word: &[Segment]) -> Vec<Segment> {
+        apply_together(core::slice::from_ref(self), word)
+    }
+
+    /// The segments `word[i..]` is rewritten to, if the rule applies
+    /// there, and how many it consumes.
+    fn rewrite_at(&self, word: &[Segment], i: usize) -> Option<(Vec<Segment>, usize)> {
+        let n = self.target.len();
+        self.applies_at(word, i)
+            .then(|| (self.change.rewrite(&word[i..i + n]), n))
+    }
+}
+
+/// Applies `rules` together, simultaneously as `Rule::apply` does:
+/// each gap between segments gets what the first epenthesis rule that
+/// applies to it inserts, and each position is rewritten by the first
+/// other rule that applies to it.
+pub fn apply_together(rules: &[Rule], word: &[Segment]) -> Vec<Segment> {
+    let (insertions, rewrites): (Vec<&Rule>, Vec<&Rule>) =
+        rules.iter().partition(|r| r.target.is_empty());
+    let mut output = Vec::with_capacity(word.len());
+    let mut i = 0;
+    loop {
+        if let Some((inserted, _)) = insertions.iter().find_map(|r| r.rewrite_at(word, i)) {
+            output.extend(inserted);
+        }
+        if i == word.len() {
+            return output;
+        }
+        match rewrites.iter().find_map(|r| r.rewrite_at(word, i)) {
+            Some((rewritten, n)) => {
+                output.extend(rewritten);
+                i += n;
+            }
+            None => {
+                output.push(word[i]);
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Whether `order` holds each of `0..n` exactly once.
+fn is_permutation(order: &[usize], n: usize) -> bool {
+    order.len() == n && (0..n).all(|k| order.contains(&k))
+}
+
+/// A phoneme with its allophones: the realizations it has in given
+/// environments, and the one it has elsewhere. English `/t/`, for
+/// instance, is `[ɾ]` between vowels and `[tʰ]` at the start of a
+/// word:
+///
+/// ```
+/// use allo::classes::vowel;
+/// use allo::rules::{Allophone, Pattern};
+/// use allo::{ipa, parser};
+///
+/// let t = Allophone::new(ipa!("t"))
+///     .with_realization(ipa!("ɾ"), [vowel().into()], [vowel().into()])
+///     .with_realization(ipa!("tʰ"), [Pattern::Boundary], []);
+/// let phonetic = t.realize(&parser::parse("tata").unwrap());
+/// assert_eq!(phonetic.iter().map(|s| s.to_string()).collect::<String>(), "tʰaɾa");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Allophone {
+    phoneme: Segment,
+    realizations: Vec<Rule>,
+    elsewhere: Segment,
+}
+
+impl Allophone {
+    /// A phoneme realized as itself everywhere.
+    pub fn new(phoneme: Segment) -> Self {
+        Allophone {
+            phoneme,
+            realizations: Vec::new(),
+            elsewhere: phoneme,
+        }
+    }
+
+    /// Realizes the phoneme as `realization` in the environment
+    /// `left _ right`. Where several environments match, the one added
+    /// first wins.
+    pub fn with_realization(
+        mut self,
+        realization: impl Into<Change>,
+        left: impl IntoIterator<Item = Pattern>,
+        right: impl IntoIterator<Item = Pattern>,
+    ) -> Self {
+        let rule = Rule::new(self.phoneme, realization).with_environment(left, right);
+        self.realizations.push(rule);
+        self
+    }
+
+    /// Realizes the phoneme as `segment` where no environment matches.
+    pub fn with_elsewhere(mut self, segment: Segment) -> Self {
+        self.elsewhere = segment;
+        self
+    }
+
+    pub fn phoneme(&self) -> Segment {
+        self.phoneme
+    }
+
+    /// The conditioned realizations, as rules rewriting the phoneme,
+    /// in the order they are tried.
+    pub fn realizations(&self) -> &[Rule] {
+        &self.realizations
+    }
+
+    pub fn elsewhere(&self) -> Segment {
+        self.elsewhere
+    }
+
+    /// How `word[i]` is realized, or `None` if it is not the phoneme.
+    pub fn realize_at(&self, word: &[Segment], i: usize) -> Option<Segment> {
+        if word.get(i) != Some(&self.phoneme) {
+            return None;
+        }
+        Some(
+            match self.realizations.iter().find(|r| r.applies_at(word, i)) {
+                Some(rule) => rule.change.apply(&self.phoneme),
+                None => self.elsewhere,
+            },
+        )
+    }
+
+    /// Realizes every occurrence of the phoneme in the phonemic `word`,
+    /// with environments checked against the input as in `Rule::apply`.
+    pub fn realize(&self, word: &[Segment]) -> Vec<Segment> {
+        (0..word.len())
+            .map(|i| self.realize_at(word, i).unwrap_or(word[i]))
+            .collect()
+    }
+}
+
+/// Applies `rules` one after another, each to the output of the last.
+pub fn cascade(rules: &[Rule], word: &[Segment]) -> Vec<Segment> {
+    rules
+        .iter()
+        .fold(word.to_vec(), |word, rule| rule.apply(&word))
+}
+
+/// Applies `rules` as `cascade` does, recording each form on the way.
+///
+/// ```
+/// use allo::rules::{self, Rule};
+///
+/// let voicing: Rule = "t → d / V _ V".parse().unwrap();
+/// let apocope: Rule = "V → ∅ / _ #".parse().unwrap();
+/// let word = allo::parser::parse("patata").unwrap();
+/// let derivation = rules::derive(&[voicing, apocope], &word);
+/// assert_eq!(
+///     derivation.table(&["Voicing", "Apocope"]),
+///     "UR       /patata/\nVoicing  padada\nApocope  padad\nSR       [padad]\n"
+/// );
+/// ```
+pub fn derive(rules: &[Rule], word: &[Segment]) -> Derivation {
+    let mut form = word.to_vec();
+    let steps = rules
+        .iter()
+        .enumerate()
+        .map(|(rule, r)| {
+            let next = r.apply(&form);
+            let applied = next != form;
+            form = next;
+            Step {
+                rule,
+                form: form.clone(),
+                applied,
+            }
+        })
+        .collect();
+    Derivation {
+        underlying: word.to_vec(),
+        steps,
+    }
+}
+
+/// A word's derivation through a cascade of rules, from its
+/// underlying form to its surface form.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Derivation {
+    underlying: Vec<Segment>,
+    steps: Vec<Step>,
+}
+
+/// The form after the rule at index `rule` of a cascade, and whether
+/// the rule changed anything.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Step {
+    pub rule: usize,
+    pub form: Vec<Segment>,
+    pub applied: bool,
+}
+
+impl Derivation {
+    pub fn underlying(&self) -> &[Segment] {
+        &self.underlying
+    }
+
+    pub fn surface(&self) -> &[Segment] {
+        self.steps
+            .last()
+            .map_or(&self.underlying, |step| &step.form)
+    }
+
+    /// A step for each rule of the cascade, in order.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// The steps whose rules changed the form.
+    pub fn applied(&self) -> impl Iterator<Item = &Step> + '_ {
+        self.steps.iter().filter(|step| step.applied)
+    }
+
+    /// The derivation as a table: the underlying form between slashes,
+    /// a row per rule labelled by `names` (or its number, counting from
+    /// 1, past the end of `names`) showing the form it gave or `—` if
+    /// it did not apply, and the surface form in brackets.
+    pub fn table(&self, names: &[&str]) -> String {
+        let render = |w: &[Segment]| w.iter().map(ToString::to_string).collect::<String>();
+        let mut rows = vec![("UR".to_string(), format!("/{}/", render(&self.underlying)))];
+        for step in &self.steps {
+            let name = match names.get(step.rule) {
+                Some(name) => name.to_string(),
+                None => format!("Rule {}", step.rule + 1),
+            };
+            let form = match step.applied {
+                true => render(&step.form),
+                false => "\u{2014}".to_string(),
+            };
+            rows.push((name, form));
+        }
+        rows.push(("SR".to_string(), format!("[{}]", render(self.surface()))));
+        let width = rows
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut table = String::new();
+        for (name, form) in rows {
+            let padding = width - name.chars().count() + 2;
+            table.push_str(&format!("{}{:padding$}{}\n", name, "", form));
+        }
+        table
+    }
+}
+
+impl fmt::Display for Derivation {
+    /// Writes the derivation as `table` does, with numbered rules.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.table(&[]))
+    }
+}
+
+/// How an earlier rule in an ordering affects a later one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InteractionKind {
+    /// The earlier rule creates forms the later one applies to.
+    Feeding,
+    /// The earlier rule removes forms the later one would apply to.
+    Bleeding,
+    /// The later rule creates forms the earlier one would apply to,
+    /// had it come first.
+    Counterfeeding,
+    /// The later rule removes forms the earlier one applied to, so
+    /// had it come first the earlier rule would not have applied.
+    Counterbleeding,
+}
+
+/// An interaction between `rules[earlier]` and `rules[later]`, seen
+/// in the words of the lexicon at `words`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Interaction {
+    pub earlier: usize,
+    pub later: usize,
+    pub kind: InteractionKind,
+    pub words: Vec<usize>,
+}
+
+/// How each pair of `rules`, applied in order, interact on `lexicon`,
+/// sorted by the rules' positions.
+///
+/// Each pair is compared on every word as it stands just before the
+/// earlier rule applies, ignoring the rules between them: the later
+/// rule is fed or bled if the earlier one makes it change positions
+/// it otherwise would not, or not change positions it otherwise would,
+/// and the reverse, had the order been swapped, makes them counterfed
+/// or counterbled. A pair can interact in several ways at once.
+///
+/// ```
+/// use allo::rules::{self, InteractionKind, Rule};
+///
+/// // Voicing between vowels, then raising of vowels before voiced
+/// // stops: voicing feeds raising.
+/// let voicing: Rule = "t → d / V _ V".parse().unwrap();
+/// let raising: Rule = "a → ə / _ d".parse().unwrap();
+/// let found = rules::interactions(&[voicing, raising], &["atad"]).unwrap();
+/// assert_eq!(found[0].kind, InteractionKind::Feeding);
+/// ```
+pub fn interactions(
+    rules: &[Rule],
+    lexicon: &[&str],
+) -> Result<Vec<Interaction>, ParseSegmentError> {
+    let lexicon = lexicon
+        .iter()
+        .map(|word| parser::parse(word))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut found: Vec<Interaction> = Vec::new();
+    for (w, word) in lexicon.iter().enumerate() {
+        let mut form = word.clone();
+        for (i, earlier) in rules.iter().enumerate() {
+            let after_earlier = earlier.apply(&form);
+            for (j, later) in rules.iter().enumerate().skip(i + 1) {
+                let after_later = later.apply(&form);
+                let kinds = [
+                    (
+                        InteractionKind::Feeding,
+                        gains(later, &form, &after_earlier),
+                    ),
+                    (
+                        InteractionKind::Bleeding,
+                        gains(later, &after_earlier, &form),
+                    ),
+                    (
+                        InteractionKind::Counterfeeding,
+                        gains(earlier, &form, &after_later),
+                    ),
+                    (
+                        InteractionKind::Counterbleeding,
+                        gains(earlier, &after_later, &form),
+                    ),
+                ];
+                for (kind, _) in kinds.into_iter().filter(|(_, found)| *found) {
+                    match found
+                        .iter_mut()
+                        .find(|f| (f.earlier, f.later, f.kind) == (i, j, kind))
+                    {
+                        Some(f) => f.words.push(w),
+                        None => found.push(Interaction {
+                            earlier: i,
+                            later: j,
+                            kind,
+                            words: vec![w],
+                        }),
+                    }
+                }
+            }
+            form = after_earlier;
+        }
+    }
+    found.sort_by_key(|f| (f.earlier, f.later, f.kind));
+    Ok(found)
+}
+
+/// Whether `rule` changes some position of `to` that it leaves alone
+/// in `from`.
+fn gains(rule: &Rule, from: &[Segment], to: &[Segment]) -> bool {
+    let changes = |word: &[Segment], i: usize| {
+        rule.rewrite_at(word, i)
+            .is_some_and(|(rewritten, n)| rewritten[..] != word[i..i + n])
+    };
+    (0..=to.len()).any(|i| changes(to, i) && !changes(from, i))
+}
+
+/// Whether `before` ends with `env`.
+fn matches_left(env: &[Pattern], before: &[Segment]) -> bool {
+    match (env.split_last(), before.split_last()) {
+        (None, _) => true,
+        (Some((Pattern::Boundary, init)), _) => init.is_empty() && before.is_empty(),
+        (Some((Pattern::Optional(inner), init)), _) => {
+            let with: Vec<Pattern> = init.iter().chain(inner).cloned().collect();
+            matches_left(&with, before) || matches_left(init, before)
+        }
+        (Some((p, init)), Some((last, rest))) => p.matches(last) && matches_left(init, rest),
+        (Some(_), None) => false,
+    }
+}
+
+/// Whether `after` starts with `env`.
+fn matches_right(env: &[Pattern], after: &[Segment]) -> bool {
+    match (env.split_first(), after.split_first()) {
+        (None, _) => true,
+        (Some((Pattern::Boundary, tail)), _) => tail.is_empty() && after.is_empty(),
+        (Some((Pattern::Optional(inner), tail)), _) => {
+            let with: Vec<Pattern> = inner.iter().chain(tail).cloned().collect();
+            matches_right(&with, after) || matches_right(tail, after)
+        }
+        (Some((p, tail)), Some((first, rest))) => p.matches(first) && matches_right(tail, rest),
+        (Some(_), None) => false,
+    }
+}
+
+/// A project's named classes, for writing its rules and patterns
+/// with categories beyond the built-in ones of `classes::named`.
+///
+/// ```
+/// use allo::classes::one_of;
+/// use allo::parser;
+/// use allo::rules::Categories;
+///
+/// let categories = Categories::new().with("L", one_of(parser::parse("lr").unwrap()));
+/// let rule = categories.rule("n → l / _ L").unwrap();
+/// let word = rule.apply(&parser::parse("anla").unwrap());
+/// assert_eq!(word.iter().map(|s| s.to_string()).collect::<String>(), "alla");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Categories {
+    classes: Vec<(String, Class)>,
+}
+
+impl Categories {
+    /// Only the built-in categories.
+    pub fn new() -> Self {
+        Categories::default()
+    }
+
+    /// Names `class` `name`, replacing any class of that name,
+    /// built-in or not.
+    pub fn with(mut self, name: &str, class: Class) -> Self {
+        self.classes.retain(|(n, _)| n != name);
+        self.classes.push((name.to_string(), class));
+        self
+    }
+
+    /// The class called `name`.
+    pub fn get(&self, name: &str) -> Option<Class> {
+        self.classes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, class)| class.clone())
+            .or_else(|| classes::named(name))
+    }
+
+    /// Parses a rule as `Rule::parse_with` does, with these categories.
+    pub fn rule(&self, s: &str) -> Result<Rule, ParseRuleError> {
+        Rule::parse_with(s, &self.as_slice())
+    }
+
+    /// Parses a pattern as `Pattern::parse_with` does, with these
+    /// categories.
+    pub fn pattern(&self, s: &str) -> Result<pattern::Pattern, ParseRuleError> {
+        pattern::Pattern::parse_with(s, &self.as_slice())
+    }
+
+    fn as_slice(&self) -> Vec<(&str, Class)> {
+        self.classes
+            .iter()
+            .map(|(name, class)| (name.as_str(), class.clone()))
+            .collect()
+    }
+}
+
+/// Why a rule or pattern written in generative notation could not
+/// be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseRuleError {
+    /// There is no arrow (`→`, `->` or `>`) between target and change.
+    MissingArrow,
+    /// The environment after `/` has no `_`, or more than one.
+    MissingFocus,
+    /// The target is not a single segment, category or feature
+    /// matrix.
+    InvalidTarget(String),
+    /// The change is not a single segment or feature matrix.
+    InvalidChange(String),
+    /// A `*`, `+` or `?` in a pattern with nothing before it to
+    /// repeat.
+    Dangling(char),
+    /// A feature matrix names a feature that does not exist, or gives
+    /// it no `+` or `-`.
+    UnknownFeature(String),
+    /// A bracket or parenthesis with no partner.
+    Unbalanced(char),
+    /// A segment could not be read.
+    Segment(ParseSegmentError),
+}
+
+impl fmt::Display for ParseRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseRuleError::MissingArrow => write!(f, "rule has no arrow"),
+            ParseRuleError::MissingFocus => write!(f, "environment needs exactly one `_`"),
+            ParseRuleError::InvalidTarget(s) => write!(f, "`{}` is not a single target", s),
+            ParseRuleError::InvalidChange(s) => write!(f, "`{}` is not a single change", s),
+            ParseRuleError::Dangling(c) => write!(f, "`{}` has nothing to repeat", c),
+            ParseRuleError::UnknownFeature(s) => write!(f, "unknown feature `{}`", s),
+            ParseRuleError::Unbalanced(c) => write!(f, "unbalanced `{}`", c),
+            ParseRuleError::Segment(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for ParseRuleError {}
+
+impl From<ParseSegmentError> for ParseRuleError {
+    fn from(e: ParseSegmentError) -> Self {
+        ParseRuleError::Segment(e)
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ParseRuleError;
+
+    /// Parses a rule in generative notation, as `Rule::parse_with`
+    /// with no further categories.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rule::parse_with(s, &[])
+    }
+}
+
+impl Rule {
+    /// Parses a rule written `A → B / C _ D`, such as `"s → z / V _ V"`
+    /// or `"[-son] → [+voice] / _ [+voice]"`. The arrow may also be
+    /// `->` or `>`, and the environment may be left out.
+    ///
+    /// The target is a segment, a category or a feature matrix, and the
+    /// change a segment or feature matrix. Either may be `∅` (or `0`),
+    /// for deletion and epenthesis, and a target of several segments
+    /// with a change listing their places from 1, as in `s k → 2 1`, is
+    /// a metathesis. Environments may also have word boundaries (`#`)
+    /// and optional sequences in parentheses, as in `_ (C) #`. The
+    /// categories of `classes::named` (`V`, `C`, `N` and `S`) are built
+    /// in, and `categories` can name further classes or redefine these.
+    ///
+    /// ```
+    /// use allo::parser;
+    /// use allo::rules::Rule;
+    ///
+    /// let voicing: Rule = "[-son] → [+voice] / V _ (C) V".parse().unwrap();
+    /// let word = parser::parse("apsa").unwrap();
+    /// let voiced: String = voicing.apply(&word).iter().map(|s| s.to_string()).collect();
+    /// assert_eq!(voiced, "absa");
+    /// ```
+    pub fn parse_with(s: &str, categories: &[(&str, Class)]) -> Result<Rule, ParseRuleError> {
+        let (target, rest) = ["\u{2192}", "->", ">"]
+            .iter()
+            .find_map(|arrow| s.split_once(arrow))
+            .ok_or(ParseRuleError::MissingArrow)?;
+        let (change, environment) = rest.split_once('/').unwrap_or((rest, "_"));
+        let empty = |s: &str| matches!(s.trim(), "\u{2205}" | "0");
+        let invalid_target = || ParseRuleError::InvalidTarget(target.trim().to_string());
+        let invalid_change = || ParseRuleError::InvalidChange(change.trim().to_string());
+        let patterns = match empty(target) {
+            true => Vec::new(),
+            false => sequence(target, categories)?,
+        };
+        let boundary = |p: &Pattern| matches!(p, Pattern::Boundary | Pattern::Optional(_));
+        if patterns.is_empty() && !empty(target) || patterns.iter().any(boundary) {
+            return Err(invalid_target());
+        }
+        let target = patterns;
+        let order: Option<Vec<usize>> = change
+            .split_whitespace()
+            .map(|k| k.parse::<usize>().ok()?.checked_sub(1))
+            .collect();
+        let change = match change.trim() {
+            deleted if empty(deleted) && !target.is_empty() => Change::Delete,
+            _ if target.len() > 1 => match order {
+                Some(order) if is_permutation(&order, target.len()) => Change::Metathesis(order),
+                Some(_) => return Err(invalid_change()),
+                None => return Err(invalid_target()),
+            },
+            matrix if matrix.starts_with('[') && !target.is_empty() => {
+                Change::Features(bundle(matrix)?)
+            }
+            segment => Change::Segment(segment.parse().map_err(|_| invalid_change())?),
+        };
+        let (left, right) = match environment.split('_').collect::<Vec<_>>()[..] {
+            [left, right] => (sequence(left, categories)?, sequence(right, categories)?),
+            _ => return Err(ParseRuleError::MissingFocus),
+        };
+        Ok(Rule {
+            target,
+            change,
+            left,
+            right,
+        })
+    }
+}
+
+/// The sequence of patterns written in `s`.
+fn sequence(s: &str, categories: &[(&str, Class)]) -> Result<Vec<Pattern>, ParseRuleError> {
+    let mut patterns = Vec::new();
+    let mut rest = s.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let (pattern, len) = match c {
+            '#' => (vec![Pattern::Boundary], 1),
+            '[' => {
+                let end = rest.find(']').ok_or(ParseRuleError::Unbalanced('['))?;
+                (vec![bundle(&rest[..=end])?.into()], end + 1)
+            }
+            '(' => {
+                let end = rest.find(')').ok_or(ParseRuleError::Unbalanced('('))?;
+                if rest[1..end].contains('(') {
+                    return Err(ParseRuleError::Unbalanced('('));
+                }
+                let inner = sequence(&rest[1..end], categories)?;
+                (vec![Pattern::Optional(inner)], end + 1)
+            }
+            ']' | ')' => return Err(ParseRuleError::Unbalanced(c)),
+            _ => {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || "#[]()".contains(c))
+                    .unwrap_or(rest.len());
+                (run(&rest[..end], categories)?, end)
+            }
+        };
+        patterns.extend(pattern);
+        rest = rest[len..].trim_start();
+    }
+    Ok(patterns)
+}
+
+/// The patterns in a run of segments and category names.
+pub(crate) fn run(s: &str, categories: &[(&str, Class)]) -> Result<Vec<Pattern>, ParseRuleError> {
+    let category = |name: &str| {
+        categories
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, class)| class.clone())
+            .or_else(|| classes::named(name))
+    };
+    if let Some(class) = category(s) {
+        return Ok(vec![Pattern::Class(class)]);
+    }
+    parser::tokenize(s)
+        .into_iter()
+        .map(|token| match category(token) {
+            Some(class) => Ok(Pattern::Class(class)),
+            None => Ok(Pattern::Segment(token.parse()?)),
+        })
+        .collect()
+}
+
+/// The feature matrix written `[+voice, -son]`, with the features
+/// separated by commas or spaces. A minus sign (`−`) may stand for
+/// `-`.
+pub(crate) fn bundle(s: &str) -> Result<FeatureBundle, ParseRuleError> {
+    let inner = s
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or(ParseRuleError::Unbalanced('['))?;
+    let mut bundle = FeatureBundle::new();
+    for spec in inner.split([',', ' ']).filter(|s| !s.is_empty()) {
+        let unknown = || ParseRuleError::UnknownFeature(spec.to_string());
+        let (value, name) = match (spec.strip_prefix('+'), spec.strip_prefix(['-', '\u{2212}'])) {
+            (Some(name), _) => (true, name),
+            (_, Some(name)) => (false, name),
+            _ => return Err(unknown()),
+        };
+        bundle = bundle.with(Feature::from_name(name).ok_or_else(unknown)?, value);
+    }
+    Ok(bundle)
+}
+
+/// What is wrong with a line of a sound-change file written for another
+/// applier, as `lexurgy` and `sca` read them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImportErrorKind {
+    /// A class or category used before it is defined.
+    UnknownClass(String),
+    /// Syntax the importer does not handle.
+    Unsupported(String),
+    /// A list of targets and a list of results of different lengths.
+    Mismatched(String),
+    Rule(ParseRuleError),
+    Segment(ParseSegmentError),
+}
+
+impl From<ParseRuleError> for ImportErrorKind {
+    fn from(e: ParseRuleError) -> Self {
+        ImportErrorKind::Rule(e)
+    }
+}
+
+impl From<ParseSegmentError> for ImportErrorKind {
+    fn from(e: ParseSegmentError) -> Self {
+        ImportErrorKind::Segment(e)
+    }
+}
+
+impl fmt::Display for ImportErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportErrorKind::UnknownClass(name) => write!(f, "unknown class `{}`", name),
+            ImportErrorKind::Unsupported(s) => write!(f, "unsupported syntax `{}`", s),
+            ImportErrorKind::Mismatched(s) => {
+                write!(f, "targets and results do not pair up in `{}`", s)
+            }
+            ImportErrorKind::Rule(e) => e.fmt(f),
+            ImportErrorKind::Segment(e) => e.fmt(f),
+        }
+    }
+}
+
+/// An error on a line of an imported sound-change file.
+pub type ImportError = LineError<ImportErrorKind>;
+
+/// The start of an environment written for another applier, as an
+/// importer reads it.
+pub(crate) enum Item {
+    /// The first `len` bytes are a class or list of these segments.
+    Class(usize, Vec<Segment>),
+    /// The first `len` bytes are written `text` in this crate's
+    /// notation.
+    Written(usize, &'static str),
+    /// The first character is written the same in this crate's
+    /// notation.
+    Same,
+}
+
+/// An environment written for another applier, rewritten in this
+/// crate's notation. `item` reads each item from its first character
+/// and the text it starts. The classes the environment uses are
+/// returned as categories for `Rule::parse_with`.
+pub(crate) fn environment<E: From<ParseRuleError>>(
+    s: &str,
+    mut item: impl FnMut(char, &str) -> Result<Item, E>,
+) -> Result<(String, Vec<(String, Class)>), E> {
+    let mut env = String::new();
+    let mut categories: Vec<(String, Class)> = Vec::new();
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        let len = match item(c, rest)? {
+            Item::Class(len, members) => {
+                // numbered, so no name can be mistaken for a segment
+                let name = format!("{{{}}}", categories.len());
+                env.push_str(&format!(" {} ", name));
+                categories.push((name, classes::one_of(members)));
+                len
+            }
+            Item::Written(len, text) => {
+                env.push_str(text);
+                len
+            }
+            Item::Same => {
+                env.push(c);
+                c.len_utf8()
+            }
+        };
+        rest = &rest[len..];
+    }
+    Ok((env, categories))
+}
+
+/// The rules of one `line` written for another applier: `target`
+/// changed to `result` in `environment`, as `environment` reads it.
+/// The target and result are each given as written and, if they are a
+/// class or list, with their members. A list of targets pairs with a
+/// list of results of the same length member by member, or has every
+/// member changed to a single result.
+pub(crate) fn imported(
+    line: &str,
+    (target, targets): (&str, Option<Vec<Segment>>),
+    (result, results): (&str, Option<Vec<Segment>>),
+    (environment, categories): &(String, Vec<(String, Class)>),
+) -> Result<Vec<Rule>, ImportErrorKind> {
+    let pairs: Vec<(String, String)> = match (targets, results) {
+        (Some(targets), Some(results)) if targets.len() == results.len() => targets
+            .iter()
+            .zip(&results)
+            .map(|(t, r)| (t.to_string(), r.to_string()))
+            .collect(),
+        (Some(targets), None) => targets
+            .iter()
+            .map(|t| (t.to_string(), result.to_string()))
+            .collect(),
+        (None, None) => vec![(target.to_string(), result.to_string())],
+        _ => return Err(ImportErrorKind::Mismatched(line.to_string())),
+    };
+    let categories: Vec<(&str, Class)> = categories
+        .iter()
+        .map(|(name, class)| (name.as_str(), class.clone()))
+        .collect();
+    pairs
+        .iter()
+        .map(|(t, r)| {
+            let rule = match environment.trim() {
+                "" => format!("{} → {}", t, r),
+                env => format!("{} → {} / {}", t, r, env),
+            };
+            Ok(Rule::parse_with(&rule, &categories)?)
+        })
+        .collect()
+}
@@ -0,0 +1,388 @@
+//! SAMPA, the per-language ASCII alphabets X-SAMPA grew out of, as
+//! Wells's SAMPA pages give them and the speech corpora of the 1990s
+//! (Verbmobil, the Spoken Dutch Corpus CGN, Albayzin) transcribe with.
+//!
+//! Each language uses only the symbols its phonemes need, and the same
+//! symbol can stand for different sounds: `r` is `ɹ` in English but
+//! `ɾ` in Spanish, where the trill is `rr`. Tables are looked up by
+//! `Language`, and each lists the marks it shares with the others:
+//! `"` and `%` for stress, `:` for length, `=` for a syllabic consonant,
+//! `~` for nasalization and `.` between syllables.
+//!
+//! ```
+//! use allo::sampa::{self, Language};
+//!
+//! assert_eq!(sampa::to_ipa("\"m{n", Language::English).unwrap(), "ˈmæn");
+//! assert_eq!(sampa::to_ipa("\"pero", Language::Spanish).unwrap(), "ˈpeɾo");
+//! assert_eq!(sampa::from_ipa("ˈpero", Language::Spanish).unwrap(), "\"perro");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::notation::{self, Table, UnknownSymbol};
+use crate::parser;
+use crate::segment::{ParseSegmentError, Segment};
+
+/// A language with a SAMPA table.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Language {
+    English,
+    German,
+    Dutch,
+    French,
+    Spanish,
+    Italian,
+}
+
+impl Language {
+    pub const ALL: [Language; 6] = [
+        Language::English,
+        Language::German,
+        Language::Dutch,
+        Language::French,
+        Language::Spanish,
+        Language::Italian,
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "German",
+            Language::Dutch => "Dutch",
+            Language::French => "French",
+            Language::Spanish => "Spanish",
+            Language::Italian => "Italian",
+        }
+    }
+
+    /// The language's symbols and their IPA equivalents. Where several
+    /// map to the same IPA, the first listed is the one produced by
+    /// `from_ipa`.
+    pub const fn table(&self) -> &'static Table {
+        match self {
+            Language::English => ENGLISH,
+            Language::German => GERMAN,
+            Language::Dutch => DUTCH,
+            Language::French => FRENCH,
+            Language::Spanish => SPANISH,
+            Language::Italian => ITALIAN,
+        }
+    }
+}
+
+/// Converts a SAMPA transcription in `language` to IPA.
+pub fn to_ipa(s: &str, language: Language) -> Result<String, UnknownSymbol> {
+    notation::to_ipa(s, language.table())
+}
+
+/// Converts an IPA transcription to the SAMPA of `language`.
+pub fn from_ipa(s: &str, language: Language) -> Result<String, UnknownSymbol> {
+    notation::from_ipa(s, language.table())
+}
+
+/// Parses a SAMPA transcription in `language` directly into segments.
+pub fn to_segments(s: &str, language: Language) -> Result<Vec<Segment>, ParseSegmentError> {
+    parser::parse(&to_ipa(s, language)?)
+}
+
+/// Writes `segments` in the SAMPA of `language`.
+pub fn from_segments(segments: &[Segment], language: Language) -> Result<String, UnknownSymbol> {
+    let ipa: String = segments.iter().map(Segment::to_string).collect();
+    from_ipa(&ipa, language)
+}
+
+/// British English (Wells 1997).
+pub const ENGLISH: &Table = &[
+    // consonants
+    ("p", "p"),
+    ("b", "b"),
+    ("t", "t"),
+    ("d", "d"),
+    ("k", "k"),
+    ("g", "ɡ"),
+    ("tS", "t͡ʃ"),
+    ("dZ", "d͡ʒ"),
+    ("f", "f"),
+    ("v", "v"),
+    ("T", "θ"),
+    ("D", "ð"),
+    ("s", "s"),
+    ("z", "z"),
+    ("S", "ʃ"),
+    ("Z", "ʒ"),
+    ("h", "h"),
+    ("m", "m"),
+    ("n", "n"),
+    ("N", "ŋ"),
+    ("l", "l"),
+    ("r", "ɹ"),
+    ("w", "w"),
+    ("j", "j"),
+    ("?", "ʔ"),
+    ("x", "x"),
+    // vowels
+    ("i", "i"),
+    ("I", "ɪ"),
+    ("e", "e"),
+    ("E", "ɛ"),
+    ("{", "æ"),
+    ("a", "a"),
+    ("A", "ɑ"),
+    ("Q", "ɒ"),
+    ("V", "ʌ"),
+    ("O", "ɔ"),
+    ("o", "o"),
+    ("U", "ʊ"),
+    ("u", "u"),
+    ("3", "ɜ"),
+    ("@", "ə"),
+    // marks
+    ("\"", "ˈ"),
+    ("%", "ˌ"),
+    (":", "ː"),
+    ("=", "\u{329}"),
+    ("~", "\u{303}"),
+    (".", "."),
+];
+
+/// German, as in Verbmobil and the PHONDAT corpora.
+pub const GERMAN: &Table = &[
+    // consonants
+    ("p", "p"),
+    ("b", "b"),
+    ("t", "t"),
+    ("d", "d"),
+    ("k", "k"),
+    ("g", "ɡ"),
+    ("?", "ʔ"),
+    ("pf", "p͡f"),
+    ("ts", "t͡s"),
+    ("tS", "t͡ʃ"),
+    ("dZ", "d͡ʒ"),
+    ("f", "f"),
+    ("v", "v"),
+    ("s", "s"),
+    ("z", "z"),
+    ("S", "ʃ"),
+    ("Z", "ʒ"),
+    ("C", "ç"),
+    ("j", "j"),
+    ("x", "x"),
+    ("h", "h"),
+    ("m", "m"),
+    ("n", "n"),
+    ("N", "ŋ"),
+    ("l", "l"),
+    ("R", "ʁ"),
+    ("r", "r"),
+    // vowels
+    ("i", "i"),
+    ("I", "ɪ"),
+    ("y", "y"),
+    ("Y", "ʏ"),
+    ("e", "e"),
+    ("E", "ɛ"),
+    ("2", "ø"),
+    ("9", "œ"),
+    ("a", "a"),
+    ("o", "o"),
+    ("O", "ɔ"),
+    ("u", "u"),
+    ("U", "ʊ"),
+    ("@", "ə"),
+    ("6", "ɐ"),
+    // marks
+    ("\"", "ˈ"),
+    ("%", "ˌ"),
+    (":", "ː"),
+    ("=", "\u{329}"),
+    ("~", "\u{303}"),
+    (".", "."),
+];
+
+/// Dutch, as in the Spoken Dutch Corpus (CGN).
+pub const DUTCH: &Table = &[
+    // consonants
+    ("p", "p"),
+    ("b", "b"),
+    ("t", "t"),
+    ("d", "d"),
+    ("k", "k"),
+    ("g", "ɡ"),
+    ("f", "f"),
+    ("v", "v"),
+    ("s", "s"),
+    ("z", "z"),
+    ("S", "ʃ"),
+    ("Z", "ʒ"),
+    ("x", "x"),
+    ("G", "ɣ"),
+    ("h", "ɦ"),
+    ("m", "m"),
+    ("n", "n"),
+    ("N", "ŋ"),
+    ("J", "ɲ"),
+    ("l", "l"),
+    ("r", "r"),
+    ("w", "ʋ"),
+    ("j", "j"),
+    ("?", "ʔ"),
+    // vowels
+    ("i", "i"),
+    ("I", "ɪ"),
+    ("y", "y"),
+    ("Y", "ʏ"),
+    ("e", "e"),
+    ("E", "ɛ"),
+    ("2", "ø"),
+    ("9", "œ"),
+    ("a", "a"),
+    ("A", "ɑ"),
+    ("o", "o"),
+    ("O", "ɔ"),
+    ("u", "u"),
+    ("@", "ə"),
+    // marks
+    ("\"", "ˈ"),
+    ("%", "ˌ"),
+    (":", "ː"),
+    ("=", "\u{329}"),
+    ("~", "\u{303}"),
+    (".", "."),
+];
+
+/// French.
+pub const FRENCH: &Table = &[
+    // consonants
+    ("p", "p"),
+    ("b", "b"),
+    ("t", "t"),
+    ("d", "d"),
+    ("k", "k"),
+    ("g", "ɡ"),
+    ("f", "f"),
+    ("v", "v"),
+    ("s", "s"),
+    ("z", "z"),
+    ("S", "ʃ"),
+    ("Z", "ʒ"),
+    ("j", "j"),
+    ("m", "m"),
+    ("n", "n"),
+    ("J", "ɲ"),
+    ("N", "ŋ"),
+    ("l", "l"),
+    ("R", "ʁ"),
+    ("w", "w"),
+    ("H", "ɥ"),
+    // vowels
+    ("i", "i"),
+    ("e", "e"),
+    ("E", "ɛ"),
+    ("a", "a"),
+    ("A", "ɑ"),
+    ("O", "ɔ"),
+    ("o", "o"),
+    ("u", "u"),
+    ("y", "y"),
+    ("2", "ø"),
+    ("9", "œ"),
+    ("@", "ə"),
+    // marks
+    ("\"", "ˈ"),
+    ("%", "ˌ"),
+    (":", "ː"),
+    ("=", "\u{329}"),
+    ("~", "\u{303}"),
+    (".", "."),
+];
+
+/// Peninsular Spanish, as in the Albayzin corpus.
+pub const SPANISH: &Table = &[
+    // consonants
+    ("p", "p"),
+    ("b", "b"),
+    ("t", "t"),
+    ("d", "d"),
+    ("k", "k"),
+    ("g", "ɡ"),
+    ("B", "β"),
+    ("D", "ð"),
+    ("G", "ɣ"),
+    ("tS", "t͡ʃ"),
+    ("jj", "ʝ"),
+    ("f", "f"),
+    ("T", "θ"),
+    ("s", "s"),
+    ("z", "z"),
+    ("x", "x"),
+    ("m", "m"),
+    ("n", "n"),
+    ("J", "ɲ"),
+    ("N", "ŋ"),
+    ("l", "l"),
+    ("L", "ʎ"),
+    ("r", "ɾ"),
+    ("rr", "r"),
+    ("j", "j"),
+    ("w", "w"),
+    // vowels
+    ("i", "i"),
+    ("e", "e"),
+    ("a", "a"),
+    ("o", "o"),
+    ("u", "u"),
+    // marks
+    ("\"", "ˈ"),
+    ("%", "ˌ"),
+    (":", "ː"),
+    ("=", "\u{329}"),
+    ("~", "\u{303}"),
+    (".", "."),
+];
+
+/// Italian.
+pub const ITALIAN: &Table = &[
+    // consonants
+    ("p", "p"),
+    ("b", "b"),
+    ("t", "t"),
+    ("d", "d"),
+    ("k", "k"),
+    ("g", "ɡ"),
+    ("ts", "t͡s"),
+    ("dz", "d͡z"),
+    ("tS", "t͡ʃ"),
+    ("dZ", "d͡ʒ"),
+    ("f", "f"),
+    ("v", "v"),
+    ("s", "s"),
+    ("z", "z"),
+    ("S", "ʃ"),
+    ("m", "m"),
+    ("n", "n"),
+    ("J", "ɲ"),
+    ("l", "l"),
+    ("L", "ʎ"),
+    ("r", "r"),
+    ("j", "j"),
+    ("w", "w"),
+    // vowels
+    ("i", "i"),
+    ("e", "e"),
+    ("E", "ɛ"),
+    ("a", "a"),
+    ("O", "ɔ"),
+    ("o", "o"),
+    ("u", "u"),
+    // marks
+    ("\"", "ˈ"),
+    ("%", "ˌ"),
+    (":", "ː"),
+    ("=", "\u{329}"),
+    ("~", "\u{303}"),
+    (".", "."),
+];
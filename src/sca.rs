@@ -0,0 +1,168 @@
+//! Importing sound changes written for zompist's SCA², a sound-change
+//! applier for conlangers. Categories are defined one per line as a
+//! character and its members, and each rule is a target, a
+//! replacement and an environment separated by slashes:
+//!
+//! ```text
+//! V=aeiou
+//! P=ptk
+//! B=bdɡ
+//!
+//! P/B/V_V
+//! s/z/V_V
+//! e/ə/_#
+//! ```
+//!
+//! Categories and rules may be given in one text, as SCA²'s category
+//! and rule boxes pasted together, and lines starting with `*` are
+//! comments. A category target with a category replacement maps each
+//! member to the member at the same place. Environments may have
+//! categories, word boundaries (`#`), optional elements in
+//! parentheses and lists such as `[ptk]`. An empty replacement deletes
+//! the target, and an empty target inserts the replacement. Rewrite
+//! rules, exceptions, `…` and `²`, and targets longer than one segment
+//! are not supported.
+
+use alloc::{string::ToString, vec::Vec};
+
+use crate::parser;
+use crate::rules::{self, ImportError, ImportErrorKind, Item, ParseRuleError, Rule};
+use crate::segment::Segment;
+
+/// An SCA² category and rule file.
+///
+/// ```
+/// use allo::parser;
+/// use allo::sca::SoundChanges;
+///
+/// let changes: SoundChanges = "V=aiu\nP=ptk\nB=bdɡ\nP/B/V_V".parse().unwrap();
+/// let word = changes.apply(&parser::parse("apataka").unwrap());
+/// assert_eq!(word.iter().map(|s| s.to_string()).collect::<String>(), "abadaɡa");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SoundChanges {
+    categories: Vec<(char, Vec<Segment>)>,
+    rules: Vec<Vec<Rule>>,
+}
+
+impl SoundChanges {
+    /// The categories, in the order defined.
+    pub fn categories(&self) -> &[(char, Vec<Segment>)] {
+        &self.categories
+    }
+
+    /// The rules on each line, in order. Rules from the same line, as
+    /// a category maps to another, apply together.
+    pub fn rules(&self) -> &[Vec<Rule>] {
+        &self.rules
+    }
+
+    /// Runs every rule on `word` in order.
+    pub fn apply(&self, word: &[Segment]) -> Vec<Segment> {
+        self.rules.iter().fold(word.to_vec(), |word, rules| {
+            rules::apply_together(rules, &word)
+        })
+    }
+
+    fn category(&self, c: char) -> Option<&[Segment]> {
+        self.categories
+            .iter()
+            .find(|(name, _)| *name == c)
+            .map(|(_, members)| &members[..])
+    }
+
+    /// The segments in a category, a list such as `[ptk]`, or a single
+    /// segment.
+    fn members(&self, s: &str) -> Result<Vec<Segment>, ImportErrorKind> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if let Some(members) = self.category(c) {
+                return Ok(members.to_vec());
+            }
+        }
+        let list = s.strip_prefix('[').and_then(|s| s.strip_suffix(']'));
+        let mut members = Vec::new();
+        for token in parser::tokenize(list.unwrap_or(s)) {
+            let mut chars = token.chars();
+            match (chars.next().and_then(|c| self.category(c)), chars.next()) {
+                (Some(category), None) => members.extend_from_slice(category),
+                _ => members.push(token.parse()?),
+            }
+        }
+        Ok(members)
+    }
+
+    /// The rules for one line, `target/replacement/environment`.
+    fn rule(&self, line: &str) -> Result<Vec<Rule>, ImportErrorKind> {
+        let unsupported = || ImportErrorKind::Unsupported(line.to_string());
+        let fields: Vec<&str> = line.split(['/', '\u{2192}']).map(str::trim).collect();
+        let (target, result, environment) = match fields[..] {
+            [target, result] => (target, result, "_"),
+            [target, result, environment] => (target, result, environment),
+            _ => return Err(unsupported()),
+        };
+        if target.is_empty() && result.is_empty() || environment.contains(['…', '²']) {
+            return Err(unsupported());
+        }
+        let [target, result] = [target, result].map(|s| match s {
+            "" => "\u{2205}",
+            s => s,
+        });
+
+        let environment = rules::environment(environment, |c, rest| {
+            Ok::<_, ImportErrorKind>(match (c, self.category(c)) {
+                ('[', _) => {
+                    let len = rest.find(']').ok_or(ParseRuleError::Unbalanced('['))? + 1;
+                    Item::Class(len, self.members(&rest[..len])?)
+                }
+                (_, Some(members)) => Item::Class(c.len_utf8(), members.to_vec()),
+                (_, None) => Item::Same,
+            })
+        })?;
+        let members = |s: &str| {
+            let mut chars = s.chars();
+            let category = chars.next().and_then(|c| self.category(c)).is_some();
+            match s.starts_with('[') || category && chars.next().is_none() {
+                true => self.members(s).map(Some),
+                false => Ok(None),
+            }
+        };
+        rules::imported(
+            line,
+            (target, members(target)?),
+            (result, members(result)?),
+            &environment,
+        )
+    }
+}
+
+impl core::str::FromStr for SoundChanges {
+    type Err = ImportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut changes = SoundChanges::default();
+        for (n, line) in s.lines().enumerate() {
+            let error = |kind| ImportError { line: n + 1, kind };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('*') {
+                continue;
+            }
+            if line.contains('|') {
+                return Err(error(ImportErrorKind::Unsupported(line.to_string())));
+            }
+            if let Some((name, members)) = line.split_once('=') {
+                let mut chars = name.trim().chars();
+                let (Some(name), None) = (chars.next(), chars.next()) else {
+                    return Err(error(ImportErrorKind::Unsupported(line.to_string())));
+                };
+                let members = changes.members(members.trim()).map_err(error)?;
+                changes.categories.retain(|(c, _)| *c != name);
+                changes.categories.push((name, members));
+                continue;
+            }
+            let rules = changes.rule(line).map_err(error)?;
+            changes.rules.push(rules);
+        }
+        Ok(changes)
+    }
+}
@@ -0,0 +1,692 @@
+use core::cmp::Ordering;
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::{error, str::FromStr};
+
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+
+use crate::affricate::Affricate;
+use crate::diacritics::{Diacritic, Diacritics};
+use crate::dolgopolsky::{self, Dolgopolsky};
+#[cfg(feature = "alloc")]
+use crate::error::Error;
+use crate::features::{self, FeatureBundle, Weights};
+use crate::ipa::{self, Articulation, Consonant, Description, Manner, Phonation, PoA, Vowel};
+use crate::lingpy::{self, SoundClass};
+#[cfg(feature = "alloc")]
+use crate::names;
+#[cfg(feature = "alloc")]
+use crate::notation::UnknownSymbol;
+use crate::parser;
+use crate::suprasegmentals::{Length, Suprasegmental};
+use crate::tone::{Contour, Level};
+
+/// A single IPA sound or prosodic symbol, carrying its full
+/// articulatory description. Formatting a `Segment` with `Display`
+/// produces its IPA grapheme, diacritics included.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Segment {
+    Consonant(Consonant),
+    Affricate(Affricate),
+    Vowel(Vowel),
+    Suprasegmental(Suprasegmental),
+    Tone(Contour),
+}
+
+impl Segment {
+    /// Whether the segment is a consonant or an affricate.
+    pub const fn is_consonant(&self) -> bool {
+        matches!(self, Segment::Consonant(_) | Segment::Affricate(_))
+    }
+
+    pub const fn is_vowel(&self) -> bool {
+        matches!(self, Segment::Vowel(_))
+    }
+
+    /// Whether the segment is a sound, as opposed to a suprasegmental
+    /// or tone mark.
+    pub const fn is_segmental(&self) -> bool {
+        matches!(
+            self,
+            Segment::Consonant(_) | Segment::Affricate(_) | Segment::Vowel(_)
+        )
+    }
+
+    pub const fn as_consonant(&self) -> Option<&Consonant> {
+        match self {
+            Segment::Consonant(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    pub const fn as_affricate(&self) -> Option<&Affricate> {
+        match self {
+            Segment::Affricate(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    pub const fn as_vowel(&self) -> Option<&Vowel> {
+        match self {
+            Segment::Vowel(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// The diacritics attached to the segment, on either half of an
+    /// affricate. Suprasegmentals and tones never carry any.
+    pub const fn diacritics(&self) -> Diacritics {
+        match self {
+            Segment::Consonant(c) => c.diacritics(),
+            Segment::Affricate(a) => a.stop().diacritics().union(a.fricative().diacritics()),
+            Segment::Vowel(v) => v.diacritics(),
+            Segment::Suprasegmental(_) | Segment::Tone(_) => Diacritics::new(),
+        }
+    }
+
+    /// The segment's name, e.g. `"voiceless alveolar lateral
+    /// fricative"`; see `names::name`.
+    #[cfg(feature = "alloc")]
+    pub fn name(&self) -> String {
+        names::name(self)
+    }
+
+    /// Looks up a segment by name, e.g. `"voiced velar nasal"`; see
+    /// `names::from_name`.
+    #[cfg(feature = "alloc")]
+    pub fn from_name(name: &str) -> Option<Segment> {
+        names::from_name(name)
+    }
+
+    /// The segment's distinctive features, or `None` for
+    /// suprasegmentals and tones.
+    pub fn features(&self) -> Option<FeatureBundle> {
+        features::of(self)
+    }
+
+    /// How far apart two segments are in distinctive features, with
+    /// every feature weighted equally; see `features::distance`.
+    pub fn distance(&self, other: &Segment) -> f32 {
+        features::distance(self, other, &Weights::default())
+    }
+
+    /// As `distance`, with the given feature weights.
+    pub fn distance_with(&self, other: &Segment, weights: &Weights) -> f32 {
+        features::distance(self, other, weights)
+    }
+
+    /// Parses exactly one segment as `FromStr` does, but in a `const
+    /// fn`, as `ipa!` does at compile time. Affricates must be written
+    /// with a tie bar, and there is no error to say what went wrong.
+    pub const fn parse_const(s: &str) -> Option<Segment> {
+        if let Some((c, rest)) = parser::split_first_char(s) {
+            if let (Some(s), true) = (Suprasegmental::from_char(c), rest.is_empty()) {
+                return Some(Segment::Suprasegmental(s));
+            }
+        }
+        let mut rest = s;
+        let mut letters = true;
+        while let Some((c, after)) = parser::split_first_char(rest) {
+            letters &= Level::from_letter(c).is_some();
+            rest = after;
+        }
+        if letters {
+            if let Some(tone) = Contour::parse(s) {
+                return Some(Segment::Tone(tone));
+            }
+        }
+        if let Some((stop, fricative)) = parser::split_tie(s) {
+            if let (Some(Description::Consonant(stop)), Some(Description::Consonant(fricative))) =
+                (ipa::describe_const(stop), ipa::describe_const(fricative))
+            {
+                if let Some(a) = Affricate::new(stop, fricative) {
+                    return Some(Segment::Affricate(a));
+                }
+            }
+        }
+        match ipa::describe_const(s) {
+            Some(Description::Consonant(c)) => Some(Segment::Consonant(c)),
+            Some(Description::Vowel(v)) => Some(Segment::Vowel(v)),
+            None => None,
+        }
+    }
+
+    /// The length of a consonant, affricate or vowel.
+    pub const fn length(&self) -> Option<Length> {
+        match self {
+            Segment::Consonant(c) => Some(c.length()),
+            Segment::Affricate(a) => Some(a.length()),
+            Segment::Vowel(v) => Some(v.length()),
+            Segment::Suprasegmental(_) | Segment::Tone(_) => None,
+        }
+    }
+
+    /// The sonority of the segment on a six-step scale: plosives and
+    /// affricates 1, fricatives 2, nasals 3, liquids (laterals, taps and
+    /// trills) 4, glides (approximants and non-syllabic vowels) 5,
+    /// vowels 6. Suprasegmentals and tones have no sonority.
+    pub const fn sonority(&self) -> Option<u8> {
+        match self {
+            Segment::Consonant(c) => Some(match c.manner() {
+                Manner::Plosive => 1,
+                Manner::Fricative { .. } | Manner::LatFric => 2,
+                Manner::Nasal => 3,
+                Manner::TapFlap | Manner::Trill | Manner::LatApprox | Manner::LatTapFlap => 4,
+                Manner::Approximant => 5,
+            }),
+            Segment::Affricate(_) => Some(1),
+            Segment::Vowel(v) if v.diacritics().contains(Diacritic::NonSyllabic) => Some(5),
+            Segment::Vowel(_) => Some(6),
+            Segment::Suprasegmental(_) | Segment::Tone(_) => None,
+        }
+    }
+
+    /// The segment's Dolgopolsky class, or `None` for syllabic vowels,
+    /// suprasegmentals and tones; see `dolgopolsky`.
+    pub const fn dolgopolsky_class(&self) -> Option<Dolgopolsky> {
+        dolgopolsky::of(self)
+    }
+
+    /// The segment's class in LingPy's SCA model, or `None` for
+    /// suprasegmentals; see `lingpy`.
+    pub fn lingpy_class(&self) -> Option<SoundClass> {
+        lingpy::of(self)
+    }
+
+    /// Sets the length of a consonant, affricate or vowel; other
+    /// segments are returned unchanged.
+    pub const fn with_length(self, length: Length) -> Self {
+        match self {
+            Segment::Consonant(c) => Segment::Consonant(c.with_length(length)),
+            Segment::Affricate(a) => Segment::Affricate(a.with_length(length)),
+            Segment::Vowel(v) => Segment::Vowel(v.with_length(length)),
+            other => other,
+        }
+    }
+
+    /// The voiceless counterpart of the segment: `d` becomes `t`, and
+    /// `ŋ` and `a` become `ŋ̊` and `ḁ`, since the IPA has no letters
+    /// for them. Suprasegmentals and tones are returned unchanged.
+    ///
+    /// ```
+    /// use allo::ipa;
+    ///
+    /// assert_eq!(ipa!("d͡ʒ").devoiced(), ipa!("t͡ʃ"));
+    /// assert_eq!(ipa!("m").devoiced().to_string(), "m\u{325}");
+    /// ```
+    pub const fn devoiced(self) -> Self {
+        self.with_voice(false)
+    }
+
+    /// The voiced counterpart of the segment, as `devoiced` is the
+    /// voiceless one. Breathy and creaky voice are kept.
+    pub const fn voiced(self) -> Self {
+        self.with_voice(true)
+    }
+
+    const fn with_voice(self, voice: bool) -> Self {
+        const fn consonant(c: Consonant, voice: bool) -> Consonant {
+            let phonation = match (voice, c.phonation()) {
+                (false, _) => Phonation::Voiceless,
+                (true, Phonation::Voiceless) => Phonation::Voiced,
+                (true, phonation) => phonation,
+            };
+            let diacritics = c
+                .diacritics()
+                .without(Diacritic::Voiceless)
+                .without(Diacritic::Voiced);
+            c.with_diacritics(diacritics).with_phonation(phonation)
+        }
+        match self {
+            Segment::Consonant(c) => Segment::Consonant(consonant(c, voice)),
+            Segment::Affricate(a) => {
+                match Affricate::new(consonant(a.stop(), voice), consonant(a.fricative(), voice)) {
+                    Some(a) => Segment::Affricate(a),
+                    None => self,
+                }
+            }
+            Segment::Vowel(v) => {
+                let diacritics = v.diacritics().without(Diacritic::Voiceless);
+                let diacritics = match voice {
+                    true => diacritics,
+                    false => diacritics
+                        .without(Diacritic::BreathyVoiced)
+                        .without(Diacritic::CreakyVoiced)
+                        .with(Diacritic::Voiceless),
+                };
+                Segment::Vowel(v.with_diacritics(diacritics))
+            }
+            other => other,
+        }
+    }
+
+    /// The nasal counterpart of the segment: a plosive becomes the
+    /// nasal at its place, `b` becoming `m`, and other consonants,
+    /// affricates and vowels take the tilde, as in `ã`. Nasals,
+    /// suprasegmentals and tones are returned unchanged.
+    ///
+    /// ```
+    /// use allo::ipa;
+    ///
+    /// assert_eq!(ipa!("ɡ").nasalized(), ipa!("ŋ"));
+    /// assert_eq!(ipa!("a").nasalized().to_string(), "a\u{303}");
+    /// ```
+    pub const fn nasalized(self) -> Self {
+        match self {
+            Segment::Consonant(c)
+                if matches!(c.manner(), Manner::Plosive)
+                    && c.with_manner(Manner::Nasal).is_writable() =>
+            {
+                Segment::Consonant(c.with_manner(Manner::Nasal))
+            }
+            Segment::Consonant(c) if matches!(c.manner(), Manner::Nasal) => self,
+            other => other.with_diacritic(Diacritic::Nasalized),
+        }
+    }
+
+    /// Moves a consonant or both halves of an affricate to
+    /// `articulation`. Where the chart has no symbol for the result,
+    /// the nearest one is used with a mark, so a postalveolar `t` is
+    /// written `t̠` and a dental `r` `r̪`, and a fricative may change
+    /// its sibilance: `s` at the velum is `x`. Vowels,
+    /// suprasegmentals and tones are returned unchanged.
+    ///
+    /// ```
+    /// use allo::ipa;
+    /// use allo::ipa::Articulation;
+    ///
+    /// assert_eq!(ipa!("n").with_place(Articulation::Velar), ipa!("ŋ"));
+    /// assert_eq!(ipa!("t͡s").with_place(Articulation::Postalveolar), ipa!("t͡ʃ"));
+    /// assert_eq!(
+    ///     ipa!("t").with_place(Articulation::Postalveolar).to_string(),
+    ///     "t\u{320}"
+    /// );
+    /// ```
+    pub const fn with_place(self, articulation: Articulation) -> Self {
+        match self {
+            Segment::Consonant(c) => Segment::Consonant(moved(c, articulation)),
+            Segment::Affricate(a) => {
+                // The stop is written with the nearest symbol and no
+                // mark, as in `t͡ʃ`; the fricative says where it is.
+                let stop = moved(a.stop(), articulation);
+                let stop = stop.with_diacritics(
+                    stop.diacritics()
+                        .without(Diacritic::Advanced)
+                        .without(Diacritic::Retracted),
+                );
+                match Affricate::new(stop, moved(a.fricative(), articulation)) {
+                    Some(a) => Segment::Affricate(a),
+                    None => self,
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// The segment its base letters stand for, without diacritics,
+    /// length or secondary articulation, as `Consonant::base` and
+    /// `Vowel::base` give them; suprasegmentals and tones are their
+    /// own base.
+    ///
+    /// ```
+    /// use allo::ipa;
+    ///
+    /// assert_eq!(ipa!("t̪ʰ").base(), ipa!("t"));
+    /// assert_eq!(ipa!("t͡ʃʷː").base(), ipa!("t͡ʃ"));
+    /// ```
+    pub fn base(&self) -> Segment {
+        match self {
+            Segment::Consonant(c) => Segment::Consonant(c.base()),
+            Segment::Affricate(a) => match Affricate::new(a.stop().base(), a.fricative().base()) {
+                Some(a) => Segment::Affricate(a),
+                None => *self,
+            },
+            Segment::Vowel(v) => Segment::Vowel(v.base()),
+            other => *other,
+        }
+    }
+
+    /// Whether two segments have the same `base`, so that a search for
+    /// `t` finds `t̪ʰ` and `tː` too.
+    pub fn eq_base(&self, other: &Segment) -> bool {
+        self.base() == other.base()
+    }
+
+    /// Compares segments in IPA chart order, as a phoneme list is
+    /// read: consonants place by place from the lips back, each place
+    /// by manner in the chart's rows (with affricates after the
+    /// plosives at the place of their fricative) and voiceless before
+    /// voiced, then vowels from close to open and front to back, then
+    /// suprasegmentals and tones. Segments in the same cell are
+    /// ordered with the bare one first.
+    ///
+    /// ```
+    /// use allo::parser;
+    /// use allo::segment::Segment;
+    ///
+    /// let mut segments = parser::parse("ŋɡkaibsmp").unwrap();
+    /// segments.sort_by(Segment::chart_cmp);
+    /// let sorted: String = segments.iter().map(|s| s.to_string()).collect();
+    /// assert_eq!(sorted, "pbmskɡŋia");
+    /// ```
+    pub fn chart_cmp(&self, other: &Segment) -> Ordering {
+        chart_key(self)
+            .cmp(&chart_key(other))
+            .then_with(|| self.cmp(other))
+    }
+
+    /// Attaches `diacritic` to a consonant, affricate or vowel; other
+    /// segments are returned unchanged.
+    pub const fn with_diacritic(self, diacritic: Diacritic) -> Self {
+        match self {
+            Segment::Consonant(c) => Segment::Consonant(c.with_diacritic(diacritic)),
+            Segment::Affricate(a) => Segment::Affricate(a.with_diacritic(diacritic)),
+            Segment::Vowel(v) => Segment::Vowel(v.with_diacritic(diacritic)),
+            other => other,
+        }
+    }
+}
+
+/// What `Segment::chart_cmp` sorts by: the kind of segment, then for
+/// consonants the articulation, manner, phonation and airstream, and
+/// for vowels the height, backness and rounding, then the number of
+/// diacritics.
+pub(crate) type ChartKey = (u8, [u8; 5], usize);
+
+/// The `ChartKey` of `segment`.
+pub(crate) fn chart_key(segment: &Segment) -> ChartKey {
+    let consonant = |c: &Consonant, affricate: Option<&Affricate>| {
+        let manner = match c.manner() {
+            Manner::Plosive if affricate.is_some() => 1,
+            Manner::Plosive => 0,
+            Manner::Nasal => 2,
+            Manner::Trill => 3,
+            Manner::TapFlap => 4,
+            Manner::Fricative { .. } => 5,
+            Manner::LatFric => 6,
+            Manner::Approximant => 7,
+            Manner::LatApprox => 8,
+            Manner::LatTapFlap => 9,
+        };
+        let phonation = Phonation::ALL
+            .iter()
+            .position(|p| *p == c.phonation())
+            .unwrap_or(0);
+        // An affricate is charted at the place of its fricative.
+        let articulation = affricate.map_or(c.articulation(), |a| a.fricative().articulation());
+        [
+            articulation as u8,
+            c.coarticulation().map_or(0, |a| a as u8 + 1),
+            manner,
+            phonation as u8,
+            c.airstream() as u8,
+        ]
+    };
+    let (kind, key) = match segment {
+        Segment::Consonant(c) => (0, consonant(c, None)),
+        Segment::Affricate(a) => (0, consonant(&a.stop(), Some(a))),
+        Segment::Vowel(v) => (
+            1,
+            [
+                v.height() as u8,
+                v.backness() as u8,
+                v.is_rounded() as u8,
+                0,
+                0,
+            ],
+        ),
+        Segment::Suprasegmental(_) => (2, [0; 5]),
+        Segment::Tone(_) => (3, [0; 5]),
+    };
+    (kind, key, segment.diacritics().len())
+}
+
+/// `c` at `articulation`. If the chart has no symbol for that, a
+/// dental tries the alveolar symbol with the dental mark, a fricative
+/// the other sibilance, and anything the symbol of the nearest
+/// articulation before or after it that has one, marked as retracted
+/// or advanced. Only a consonant with no symbol at any place is
+/// returned as it is.
+const fn moved(c: Consonant, articulation: Articulation) -> Consonant {
+    let c = c.with_diacritics(
+        c.diacritics()
+            .without(Diacritic::Advanced)
+            .without(Diacritic::Retracted)
+            .without(Diacritic::Dental),
+    );
+    const fn at(c: Consonant, articulation: Articulation) -> Consonant {
+        c.with_poa(PoA::new(articulation.place(), articulation))
+    }
+    let exact = at(c, articulation);
+    if exact.is_writable() {
+        return exact;
+    }
+    if matches!(articulation, Articulation::Dental) {
+        let alveolar = at(c, Articulation::Alveolar).with_diacritic(Diacritic::Dental);
+        if alveolar.is_writable() {
+            return alveolar;
+        }
+    }
+    if let Manner::Fricative { sibilant } = c.manner() {
+        let other = exact.with_manner(Manner::Fricative {
+            sibilant: !sibilant,
+        });
+        if other.is_writable() {
+            return other;
+        }
+    }
+    // the nearest articulation with a symbol, the one before first
+    let i = articulation as usize;
+    let mut distance = 1;
+    while distance < Articulation::ALL.len() {
+        if distance <= i {
+            let near = at(c, Articulation::ALL[i - distance]).with_diacritic(Diacritic::Retracted);
+            if near.is_writable() {
+                return near;
+            }
+        }
+        if i + distance < Articulation::ALL.len() {
+            let near = at(c, Articulation::ALL[i + distance]).with_diacritic(Diacritic::Advanced);
+            if near.is_writable() {
+                return near;
+            }
+        }
+        distance += 1;
+    }
+    exact
+}
+
+impl From<Consonant> for Segment {
+    fn from(c: Consonant) -> Self {
+        Segment::Consonant(c)
+    }
+}
+
+impl From<Affricate> for Segment {
+    fn from(a: Affricate) -> Self {
+        Segment::Affricate(a)
+    }
+}
+
+impl From<Vowel> for Segment {
+    fn from(v: Vowel) -> Self {
+        Segment::Vowel(v)
+    }
+}
+
+impl From<Suprasegmental> for Segment {
+    fn from(s: Suprasegmental) -> Self {
+        Segment::Suprasegmental(s)
+    }
+}
+
+impl From<Contour> for Segment {
+    fn from(t: Contour) -> Self {
+        Segment::Tone(t)
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Segment::Consonant(c) => c.fmt(f),
+            Segment::Affricate(a) => a.fmt(f),
+            Segment::Vowel(v) => v.fmt(f),
+            Segment::Suprasegmental(s) => s.fmt(f),
+            Segment::Tone(t) => t.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromStr for Segment {
+    type Err = ParseSegmentError;
+
+    /// Parses exactly one segment, e.g. `"ɮ"`, `"tʰ"`, `"ˈ"` or `"˨˩˦"`.
+    /// Affricates may be written with a tie bar or without, as in
+    /// `"t͡ʃ"` or `"tʃ"`; any other pair of consonants joined by a tie
+    /// bar, such as `"k͡p"`, is a doubly articulated consonant.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parser::tokenize(s)[..] {
+            [] => Err(ParseSegmentError::Empty),
+            [token] if token.contains(parser::TIE_BARS) => token
+                .parse()
+                .map(Segment::Affricate)
+                .or_else(|e| match ipa::try_describe(token) {
+                    Ok(Description::Consonant(c)) => Ok(Segment::Consonant(c)),
+                    Err(err @ (Error::UnknownSymbol { .. } | Error::DanglingDiacritic { .. })) => {
+                        Err(ParseSegmentError::Invalid(
+                            err.shifted(parser::offset_in(s, token)),
+                        ))
+                    }
+                    _ => Err(e),
+                }),
+            [token] => {
+                let mut chars = token.chars();
+                if let (Some(c), None) = (chars.next(), chars.next()) {
+                    if let Some(s) = Suprasegmental::from_char(c) {
+                        return Ok(Segment::Suprasegmental(s));
+                    }
+                }
+                if token.chars().all(|c| Level::from_letter(c).is_some()) {
+                    if let Some(tone) = Contour::parse(token) {
+                        return Ok(Segment::Tone(tone));
+                    }
+                }
+                match ipa::try_describe(token) {
+                    Ok(Description::Consonant(c)) => Ok(Segment::Consonant(c)),
+                    Ok(Description::Vowel(v)) => Ok(Segment::Vowel(v)),
+                    Err(e) => Err(ParseSegmentError::Invalid(
+                        e.shifted(parser::offset_in(s, token)),
+                    )),
+                }
+            }
+            [_, _] => s
+                .parse()
+                .map(Segment::Affricate)
+                .map_err(|_| ParseSegmentError::TooMany(s.to_string())),
+            _ => Err(ParseSegmentError::TooMany(s.to_string())),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&str> for Segment {
+    type Error = ParseSegmentError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// The error returned when a string does not spell exactly one known
+/// segment of the expected kind.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseSegmentError {
+    Empty,
+    /// The symbol is not in any of the crate's tables.
+    Unknown(String),
+    /// The input spells more than one segment.
+    TooMany(String),
+    /// The symbol is known, but is not a consonant.
+    NotConsonant(String),
+    /// The symbol is known, but is not a vowel.
+    NotVowel(String),
+    /// The symbols are known, but are not a stop and a fricative.
+    NotAffricate(String),
+    /// A symbol could not be read; see `Error`.
+    Invalid(Error),
+}
+
+#[cfg(feature = "alloc")]
+impl ParseSegmentError {
+    /// The error with any offset moved `by` bytes later, for an error
+    /// found in a slice starting `by` bytes into the input.
+    pub(crate) fn shifted(self, by: usize) -> Self {
+        match self {
+            ParseSegmentError::Invalid(e) => ParseSegmentError::Invalid(e.shifted(by)),
+            e => e,
+        }
+    }
+
+    /// The byte offset of the offending character, for `Invalid`
+    /// errors.
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            ParseSegmentError::Invalid(e) => e.offset(),
+            _ => None,
+        }
+    }
+
+    /// The error with its offset, if it has one, set to `offset`.
+    pub(crate) fn at(self, offset: usize) -> Self {
+        match self {
+            ParseSegmentError::Invalid(e) => ParseSegmentError::Invalid(e.at(offset)),
+            e => e,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ParseSegmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseSegmentError::Empty => write!(f, "no segment to parse"),
+            ParseSegmentError::Unknown(s) => write!(f, "unknown IPA symbol `{}`", s),
+            ParseSegmentError::TooMany(s) => write!(f, "`{}` contains more than one segment", s),
+            ParseSegmentError::NotConsonant(s) => write!(f, "`{}` is not a consonant", s),
+            ParseSegmentError::NotVowel(s) => write!(f, "`{}` is not a vowel", s),
+            ParseSegmentError::NotAffricate(s) => write!(f, "`{}` is not an affricate", s),
+            ParseSegmentError::Invalid(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl error::Error for ParseSegmentError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ParseSegmentError::Invalid(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Error> for ParseSegmentError {
+    fn from(e: Error) -> Self {
+        ParseSegmentError::Invalid(e)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<UnknownSymbol> for ParseSegmentError {
+    fn from(e: UnknownSymbol) -> Self {
+        ParseSegmentError::Invalid(Error::UnknownSymbol {
+            offset: e.offset,
+            symbol: e.symbol,
+        })
+    }
+}
@@ -0,0 +1,215 @@
+//! `Serialize` and `Deserialize` for the types whose schema is not
+//! simply derived. The simple enums (`Place`, `Articulation`, etc.)
+//! derive theirs, serializing as their `snake_case` variant names.
+
+use alloc::{format, string::String, vec::Vec};
+
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+use crate::affricate::Affricate;
+use crate::diacritics::{Diacritic, Diacritics};
+use crate::ipa::{
+    Airstream, Articulation, Backness, Consonant, Height, Manner, Phonation, Place, PoA,
+    Roundedness, SecondaryArticulation, Vowel,
+};
+use crate::segment::Segment;
+use crate::suprasegmentals::{Length, Suprasegmental};
+use crate::tone::{Contour, Level};
+
+const MANNERS: [(&str, Manner); 10] = [
+    ("nasal", Manner::Nasal),
+    ("plosive", Manner::Plosive),
+    ("fricative", Manner::Fricative { sibilant: false }),
+    ("sibilant_fricative", Manner::Fricative { sibilant: true }),
+    ("approximant", Manner::Approximant),
+    ("tap_flap", Manner::TapFlap),
+    ("trill", Manner::Trill),
+    ("lateral_fricative", Manner::LatFric),
+    ("lateral_approximant", Manner::LatApprox),
+    ("lateral_tap_flap", Manner::LatTapFlap),
+];
+
+impl Serialize for Manner {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (name, _) = MANNERS.iter().find(|(_, m)| m == self).unwrap();
+        serializer.serialize_str(name)
+    }
+}
+
+impl<'de> Deserialize<'de> for Manner {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        MANNERS
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, m)| *m)
+            .ok_or_else(|| de::Error::custom(format!("unknown manner `{}`", name)))
+    }
+}
+
+impl Serialize for Diacritics {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Diacritics {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<Diacritic>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
+impl Serialize for Contour {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.levels())
+    }
+}
+
+impl<'de> Deserialize<'de> for Contour {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let levels = Vec::<Level>::deserialize(deserializer)?;
+        Contour::new(&levels)
+            .ok_or_else(|| de::Error::invalid_length(levels.len(), &"between 1 and 4 tone levels"))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ConsonantRepr {
+    place: Place,
+    articulation: Articulation,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    coarticulation: Option<Articulation>,
+    manner: Manner,
+    voicing: Phonation,
+    #[serde(default)]
+    airstream: Airstream,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    secondary: Option<SecondaryArticulation>,
+    #[serde(default, skip_serializing_if = "Diacritics::is_empty")]
+    diacritics: Diacritics,
+    #[serde(default, skip_serializing_if = "Length::is_short")]
+    length: Length,
+}
+
+impl From<&Consonant> for ConsonantRepr {
+    fn from(c: &Consonant) -> Self {
+        ConsonantRepr {
+            place: c.place(),
+            articulation: c.articulation(),
+            coarticulation: c.coarticulation(),
+            manner: c.manner(),
+            voicing: c.phonation(),
+            airstream: c.airstream(),
+            secondary: c.secondary(),
+            diacritics: c.diacritics(),
+            length: c.length(),
+        }
+    }
+}
+
+impl From<ConsonantRepr> for Consonant {
+    fn from(r: ConsonantRepr) -> Self {
+        Consonant::new(PoA::new(r.place, r.articulation), r.manner, r.voicing)
+            .with_coarticulation(r.coarticulation)
+            .with_airstream(r.airstream)
+            .with_secondary(r.secondary)
+            .with_diacritics(r.diacritics)
+            .with_length(r.length)
+    }
+}
+
+impl Serialize for Consonant {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ConsonantRepr::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Consonant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ConsonantRepr::deserialize(deserializer).map(Consonant::from)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct VowelRepr {
+    height: Height,
+    backness: Backness,
+    roundedness: Roundedness,
+    #[serde(default, skip_serializing_if = "Diacritics::is_empty")]
+    diacritics: Diacritics,
+    #[serde(default, skip_serializing_if = "Length::is_short")]
+    length: Length,
+}
+
+impl Serialize for Vowel {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        VowelRepr {
+            height: self.height(),
+            backness: self.backness(),
+            roundedness: self.roundedness(),
+            diacritics: self.diacritics(),
+            length: self.length(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Vowel {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let r = VowelRepr::deserialize(deserializer)?;
+        Ok(Vowel::new(r.height, r.backness, r.roundedness)
+            .with_diacritics(r.diacritics)
+            .with_length(r.length))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SegmentRepr {
+    Consonant(Consonant),
+    Affricate {
+        stop: Consonant,
+        fricative: Consonant,
+    },
+    Vowel(Vowel),
+    Suprasegmental {
+        value: Suprasegmental,
+    },
+    Tone {
+        levels: Contour,
+    },
+}
+
+impl Serialize for Segment {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            Segment::Consonant(c) => SegmentRepr::Consonant(c),
+            Segment::Affricate(a) => SegmentRepr::Affricate {
+                stop: a.stop(),
+                fricative: a.fricative(),
+            },
+            Segment::Vowel(v) => SegmentRepr::Vowel(v),
+            Segment::Suprasegmental(value) => SegmentRepr::Suprasegmental { value },
+            Segment::Tone(levels) => SegmentRepr::Tone { levels },
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Segment {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match SegmentRepr::deserialize(deserializer)? {
+            SegmentRepr::Consonant(c) => Segment::Consonant(c),
+            SegmentRepr::Affricate { stop, fricative } => Affricate::new(stop, fricative)
+                .map(Segment::Affricate)
+                .ok_or_else(|| de::Error::custom("not a stop and a fricative"))?,
+            SegmentRepr::Vowel(v) => Segment::Vowel(v),
+            SegmentRepr::Suprasegmental { value } => Segment::Suprasegmental(value),
+            SegmentRepr::Tone { levels } => Segment::Tone(levels),
+        })
+    }
+}
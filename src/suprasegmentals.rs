@@ -0,0 +1,235 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::parser::split_first_char;
+#[cfg(feature = "alloc")]
+use crate::segment::Segment;
+
+/// Word-level prominence, written before the stressed syllable.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Stress {
+    Primary,
+    Secondary,
+}
+
+/// Segment duration, from extra-short to extra-long. Segments are
+/// `Short` unless marked.
+///
+/// **Note** that the extra-short mark is a combining breve drawn over
+/// the segment, while the half-long and long marks are spacing symbols
+/// written after it. Extra-long is written with two long marks, `ːː`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Length {
+    ExtraShort,
+    #[default]
+    Short,
+    HalfLong,
+    Long,
+    ExtraLong,
+}
+
+impl Length {
+    pub const ALL: [Length; 5] = [
+        Length::ExtraShort,
+        Length::Short,
+        Length::HalfLong,
+        Length::Long,
+        Length::ExtraLong,
+    ];
+
+    /// The marks written for the length, empty for `Short`.
+    pub const fn marks(&self) -> &'static str {
+        match self {
+            Length::ExtraShort => "\u{306}",
+            Length::Short => "",
+            Length::HalfLong => "\u{2D1}",
+            Length::Long => "\u{2D0}",
+            Length::ExtraLong => "\u{2D0}\u{2D0}",
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Length::ExtraShort => "extra-short",
+            Length::Short => "short",
+            Length::HalfLong => "half-long",
+            Length::Long => "long",
+            Length::ExtraLong => "extra-long",
+        }
+    }
+
+    pub const fn is_short(&self) -> bool {
+        matches!(self, Length::Short)
+    }
+
+    /// The length of a segment of length `self` given one more length
+    /// mark: a second long mark makes it extra-long, and any other
+    /// mark replaces the length.
+    pub const fn marked(self, mark: Length) -> Length {
+        match (self, mark) {
+            (Length::Long, Length::Long) => Length::ExtraLong,
+            (_, mark) => mark,
+        }
+    }
+}
+
+/// How long consonants are written.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Gemination {
+    /// With the length mark, `tː`. A double letter is two segments.
+    #[default]
+    Long,
+    /// As a double letter, `tt`, read as one long consonant, as in
+    /// languages such as Italian or Finnish that write length this way.
+    Geminate,
+}
+
+#[cfg(feature = "alloc")]
+impl Gemination {
+    /// Joins each pair of identical short consonants in `segments`
+    /// into one long consonant if consonants are written `Geminate`.
+    pub fn join(self, segments: Vec<Segment>) -> Vec<Segment> {
+        if self == Gemination::Long {
+            return segments;
+        }
+        let mut joined: Vec<Segment> = Vec::with_capacity(segments.len());
+        for segment in segments {
+            match joined.last_mut() {
+                Some(last)
+                    if *last == segment
+                        && is_consonant(last)
+                        && last.length() == Some(Length::Short) =>
+                {
+                    *last = last.with_length(Length::Long);
+                }
+                _ => joined.push(segment),
+            }
+        }
+        joined
+    }
+
+    /// Writes `segments`, doubling long consonants if they are written
+    /// `Geminate`.
+    pub fn write(self, segments: &[Segment]) -> String {
+        let mut out = String::new();
+        for segment in segments {
+            match segment.length() {
+                Some(Length::Long) if self == Gemination::Geminate && is_consonant(segment) => {
+                    let short = segment.with_length(Length::Short).to_string();
+                    out.push_str(&short);
+                    out.push_str(&short);
+                }
+                _ => out.push_str(&segment.to_string()),
+            }
+        }
+        out
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn is_consonant(segment: &Segment) -> bool {
+    matches!(segment, Segment::Consonant(_) | Segment::Affricate(_))
+}
+
+/// Prosodic boundaries, from smallest to largest.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Break {
+    Syllable,
+    /// Minor (foot) group.
+    MinorGroup,
+    /// Major (intonation) group.
+    MajorGroup,
+}
+
+/// The suprasegmental symbols of the IPA chart, excluding tone, which
+/// lives in its own module.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Suprasegmental {
+    Stress(Stress),
+    Length(Length),
+    Break(Break),
+    /// Linking (absence of a break), e.g. `/ʃə‿bɛl/`.
+    Linking,
+}
+
+impl Suprasegmental {
+    pub const ALL: [Suprasegmental; 9] = [
+        Suprasegmental::Stress(Stress::Primary),
+        Suprasegmental::Stress(Stress::Secondary),
+        Suprasegmental::Length(Length::Long),
+        Suprasegmental::Length(Length::HalfLong),
+        Suprasegmental::Length(Length::ExtraShort),
+        Suprasegmental::Break(Break::Syllable),
+        Suprasegmental::Break(Break::MinorGroup),
+        Suprasegmental::Break(Break::MajorGroup),
+        Suprasegmental::Linking,
+    ];
+
+    /// The symbol: one character for each of `ALL`, the two long marks
+    /// of `ExtraLong`, and nothing for `Short`.
+    ///
+    /// **Note** that this returned a `char` before lengths other than
+    /// those of `ALL` could be written.
+    pub const fn symbol(&self) -> &'static str {
+        match self {
+            Suprasegmental::Stress(Stress::Primary) => "\u{2C8}",
+            Suprasegmental::Stress(Stress::Secondary) => "\u{2CC}",
+            Suprasegmental::Length(length) => length.marks(),
+            Suprasegmental::Break(Break::Syllable) => ".",
+            Suprasegmental::Break(Break::MinorGroup) => "|",
+            Suprasegmental::Break(Break::MajorGroup) => "\u{2016}",
+            Suprasegmental::Linking => "\u{203F}",
+        }
+    }
+
+    /// The symbol's name on the IPA chart, e.g. `"half-long"`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Suprasegmental::Stress(Stress::Primary) => "primary stress",
+            Suprasegmental::Stress(Stress::Secondary) => "secondary stress",
+            Suprasegmental::Length(length) => length.name(),
+            Suprasegmental::Break(Break::Syllable) => "syllable break",
+            Suprasegmental::Break(Break::MinorGroup) => "minor (foot) group",
+            Suprasegmental::Break(Break::MajorGroup) => "major (intonation) group",
+            Suprasegmental::Linking => "linking (absence of a break)",
+        }
+    }
+
+    pub const fn from_char(c: char) -> Option<Suprasegmental> {
+        let mut i = 0;
+        while i < Suprasegmental::ALL.len() {
+            if let Some((symbol, rest)) = split_first_char(Suprasegmental::ALL[i].symbol()) {
+                if symbol == c && rest.is_empty() {
+                    return Some(Suprasegmental::ALL[i]);
+                }
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Whether the symbol is a combining mark drawn over the
+    /// preceding segment rather than a symbol in its own right.
+    pub const fn is_combining(&self) -> bool {
+        matches!(self, Suprasegmental::Length(Length::ExtraShort))
+    }
+}
+
+impl fmt::Display for Suprasegmental {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
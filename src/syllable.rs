@@ -0,0 +1,549 @@
+use core::fmt;
+use core::str::FromStr;
+
+use alloc::{vec, vec::Vec};
+
+use crate::classes::Class;
+use crate::diacritics::Diacritic;
+use crate::parser;
+use crate::phonotactics;
+use crate::rules::Pattern;
+use crate::segment::{ParseSegmentError, Segment};
+use crate::suprasegmentals::{Break, Length, Stress, Suprasegmental};
+
+/// A syllable: an optional onset, a nucleus of one or more segments
+/// (a vowel plus any non-syllabic vowels forming a diphthong with it,
+/// or a syllabic consonant), and an optional coda. A syllable written
+/// after a stress mark has that `stress`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Syllable {
+    pub stress: Option<Stress>,
+    pub onset: Vec<Segment>,
+    pub nucleus: Vec<Segment>,
+    pub coda: Vec<Segment>,
+    /// Whether the first segment of the onset is also the last of the
+    /// previous syllable's coda, as the `p` of English `happy` is.
+    pub ambisyllabic: bool,
+}
+
+impl Syllable {
+    /// Whether the syllable has no coda.
+    pub fn is_open(&self) -> bool {
+        self.coda.is_empty()
+    }
+
+    pub fn segments(&self) -> impl Iterator<Item = &Segment> {
+        self.onset.iter().chain(&self.nucleus).chain(&self.coda)
+    }
+
+    /// The rime: the nucleus and the coda.
+    pub fn rime(&self) -> impl Iterator<Item = &Segment> {
+        self.nucleus.iter().chain(&self.coda)
+    }
+
+    /// The number of morae under `criteria`: one for the nucleus, and
+    /// one more for each thing `criteria` counts. A syllable without a
+    /// nucleus has none.
+    pub fn morae(&self, criteria: &WeightCriteria) -> usize {
+        let Some((first, rest)) = self.nucleus.split_first() else {
+            return 0;
+        };
+        let long = |s: &Segment| matches!(s.length(), Some(Length::Long | Length::ExtraLong));
+        let mut morae = 1;
+        if criteria.long_nuclei {
+            morae += usize::from(long(first)) + rest.len();
+            morae += rest.iter().filter(|s| long(s)).count();
+        }
+        if criteria.coda {
+            morae += self.coda.len();
+        }
+        morae
+    }
+
+    /// The syllable's weight under `criteria`, from its `morae`.
+    pub fn weight(&self, criteria: &WeightCriteria) -> Weight {
+        match self.morae(criteria) {
+            0 | 1 => Weight::Light,
+            2 => Weight::Heavy,
+            _ => Weight::Superheavy,
+        }
+    }
+}
+
+/// How heavy a syllable is: light with one mora, heavy with two and
+/// superheavy with more.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weight {
+    Light,
+    Heavy,
+    Superheavy,
+}
+
+/// What adds weight to a syllable beyond its single nucleus mora,
+/// which varies between languages: in Latin both long vowels and codas
+/// make a syllable heavy, while in Khalkha Mongolian only long
+/// vowels and diphthongs do.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WeightCriteria {
+    /// Whether a long vowel, and each further vowel of a diphthong,
+    /// adds a mora.
+    pub long_nuclei: bool,
+    /// Whether each coda consonant adds a mora.
+    pub coda: bool,
+}
+
+impl WeightCriteria {
+    /// Long vowels, diphthongs and codas all count.
+    pub const LATIN: WeightCriteria = WeightCriteria {
+        long_nuclei: true,
+        coda: true,
+    };
+
+    /// Only long vowels and diphthongs count.
+    pub const NUCLEUS_ONLY: WeightCriteria = WeightCriteria {
+        long_nuclei: true,
+        coda: false,
+    };
+}
+
+impl Default for WeightCriteria {
+    fn default() -> Self {
+        WeightCriteria::LATIN
+    }
+}
+
+impl fmt::Display for Syllable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(stress) = self.stress {
+            Suprasegmental::Stress(stress).fmt(f)?;
+        }
+        for s in self.segments() {
+            write!(f, "{}", s)?;
+        }
+        Ok(())
+    }
+}
+
+/// The sonority of a segment, as given by `Segment::sonority`.
+pub fn sonority(segment: &Segment) -> Option<u8> {
+    segment.sonority()
+}
+
+/// Language-specific constraints on syllable structure. The defaults
+/// implement the sonority sequencing principle: vowels and syllabic
+/// consonants are nuclei, sonority rises strictly through the onset
+/// and falls strictly through the coda, on the scale `sonority`
+/// gives. A language with a different scale need only override
+/// `sonority`, or use a `Scale`.
+pub trait Phonotactics {
+    fn sonority(&self, segment: &Segment) -> Option<u8> {
+        segment.sonority()
+    }
+
+    fn is_nucleus(&self, segment: &Segment) -> bool {
+        match segment {
+            Segment::Vowel(v) => !v.diacritics().contains(Diacritic::NonSyllabic),
+            Segment::Consonant(c) => c.diacritics().contains(Diacritic::Syllabic),
+            _ => false,
+        }
+    }
+
+    fn is_onset(&self, cluster: &[Segment]) -> bool {
+        cluster
+            .windows(2)
+            .all(|w| self.sonority(&w[0]) < self.sonority(&w[1]))
+    }
+
+    fn is_coda(&self, cluster: &[Segment]) -> bool {
+        cluster
+            .windows(2)
+            .all(|w| self.sonority(&w[0]) > self.sonority(&w[1]))
+    }
+
+    /// How to divide the consonants between two nuclei when more than
+    /// one division is legal.
+    fn split(&self) -> Split {
+        Split::MaximalOnset
+    }
+
+    /// Whether `consonant`, alone between `nucleus` and the next
+    /// nucleus, closes the first syllable as well as opening the
+    /// second.
+    fn is_ambisyllabic(&self, _nucleus: &[Segment], _consonant: &Segment) -> bool {
+        false
+    }
+}
+
+/// Which syllable gets the consonants between two nuclei, when the
+/// phonotactics allow more than one division.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Split {
+    /// As many as possible go to the second syllable's onset.
+    #[default]
+    MaximalOnset,
+    /// As many as possible go to the first syllable's coda.
+    MaximalCoda,
+}
+
+/// The sonority sequencing principle with no further constraints.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sonority;
+
+impl Phonotactics for Sonority {}
+
+/// A sonority scale that ranks some classes of segments differently
+/// from `Segment::sonority`, such as one putting voiced fricatives with
+/// the nasals. Segments matching none of its classes keep their usual
+/// rank.
+///
+/// ```
+/// use allo::classes::{fricative, voiced};
+/// use allo::parser;
+/// use allo::syllable::{Phonotactics, Scale, Sonority};
+///
+/// let scale = Scale::new().with(voiced() & fricative(), 3);
+/// let zm = parser::parse("zm").unwrap();
+/// assert!(Sonority.is_onset(&zm));
+/// assert!(!scale.is_onset(&zm));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Scale {
+    ranks: Vec<(Class, u8)>,
+}
+
+impl Scale {
+    pub fn new() -> Self {
+        Scale::default()
+    }
+
+    /// Ranks the segments in `class` at `rank`, over any earlier rank
+    /// given to them.
+    pub fn with(mut self, class: Class, rank: u8) -> Self {
+        self.ranks.push((class, rank));
+        self
+    }
+}
+
+impl Phonotactics for Scale {
+    fn sonority(&self, segment: &Segment) -> Option<u8> {
+        match self
+            .ranks
+            .iter()
+            .rev()
+            .find(|(class, _)| class.matches(segment))
+        {
+            Some(&(_, rank)) if segment.is_segmental() => Some(rank),
+            _ => segment.sonority(),
+        }
+    }
+}
+
+/// A syllabification policy: which onsets and codas are legal, how
+/// clusters are divided, and which consonants are ambisyllabic.
+/// Languages divide the same cluster differently. Arabic allows no
+/// complex onsets, so `aksa` is `ak.sa`; Japanese allows only a nasal
+/// or the first half of a geminate as a coda; and English shares a
+/// consonant after a lax vowel between both syllables, as in `happy`.
+///
+/// ```
+/// use allo::classes::consonant;
+/// use allo::parser;
+/// use allo::rules::Pattern;
+/// use allo::syllable::{syllabify, syllabify_with, Policy};
+///
+/// let arabic = Policy::new().with_onset([Pattern::Class(consonant())]);
+/// let word = parser::parse("aspla").unwrap();
+/// assert_eq!(syllabify(&word)[0].to_string(), "as");
+/// assert_eq!(syllabify_with(&word, &arabic)[0].to_string(), "asp");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Policy {
+    split: Split,
+    onsets: Vec<Vec<Pattern>>,
+    codas: Vec<Vec<Pattern>>,
+    ambisyllabic: Option<(Class, Class)>,
+    scale: Scale,
+}
+
+impl Policy {
+    /// The sonority sequencing principle with maximal onsets, as
+    /// `syllabify` uses.
+    pub fn new() -> Self {
+        Policy::default()
+    }
+
+    pub fn with_split(mut self, split: Split) -> Self {
+        self.split = split;
+        self
+    }
+
+    /// Allows this onset, of one or more consonants. Once any onset is
+    /// listed, only listed onsets and the empty onset are legal; with
+    /// none listed, any onset the sonority scale allows is.
+    pub fn with_onset(mut self, onset: impl IntoIterator<Item = Pattern>) -> Self {
+        self.onsets.push(onset.into_iter().collect());
+        self
+    }
+
+    /// Allows this coda, as `with_onset`.
+    pub fn with_coda(mut self, coda: impl IntoIterator<Item = Pattern>) -> Self {
+        self.codas.push(coda.into_iter().collect());
+        self
+    }
+
+    /// Makes a lone consonant in `consonant` ambisyllabic after a
+    /// one-segment nucleus in `nucleus`.
+    pub fn with_ambisyllabic(mut self, nucleus: Class, consonant: Class) -> Self {
+        self.ambisyllabic = Some((nucleus, consonant));
+        self
+    }
+
+    /// Checks clusters by sonority on `scale`.
+    pub fn with_scale(mut self, scale: Scale) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+impl Phonotactics for Policy {
+    fn sonority(&self, segment: &Segment) -> Option<u8> {
+        self.scale.sonority(segment)
+    }
+
+    fn is_onset(&self, cluster: &[Segment]) -> bool {
+        match self.onsets.is_empty() {
+            true => self.scale.is_onset(cluster),
+            false => cluster.is_empty() || phonotactics::allowed(&self.onsets, cluster),
+        }
+    }
+
+    fn is_coda(&self, cluster: &[Segment]) -> bool {
+        match self.codas.is_empty() {
+            true => self.scale.is_coda(cluster),
+            false => cluster.is_empty() || phonotactics::allowed(&self.codas, cluster),
+        }
+    }
+
+    fn split(&self) -> Split {
+        self.split
+    }
+
+    fn is_ambisyllabic(&self, nucleus: &[Segment], consonant: &Segment) -> bool {
+        self.ambisyllabic.as_ref().is_some_and(|(n, c)| {
+            matches!(nucleus, [only] if n.matches(only)) && c.matches(consonant)
+        })
+    }
+}
+
+/// Syllabifies `word` by the sonority sequencing principle.
+pub fn syllabify(word: &[Segment]) -> Vec<Syllable> {
+    syllabify_with(word, &Sonority)
+}
+
+/// Syllabifies `word` under `rules`.
+///
+/// Consonants between two nuclei are divided as `rules.split()` says,
+/// by default going to the second syllable's onset as far as `rules`
+/// allow it (the maximal onset principle), and the rest to the first
+/// syllable's coda. A lone consonant `rules` call ambisyllabic goes to
+/// both. Syllable breaks (`.`) and stress marks in `word` are always
+/// respected, the syllable after a stress mark taking its stress, and
+/// where `word` has any, vowels next to each other between them are
+/// one nucleus, as `əʊ` in `ˈfəʊ.nɪm`. Other suprasegmentals and tones
+/// are dropped.
+pub fn syllabify_with(word: &[Segment], rules: &impl Phonotactics) -> Vec<Syllable> {
+    let mut parts: Vec<(Option<Stress>, Vec<Segment>)> = vec![(None, Vec::new())];
+    for segment in word {
+        match segment {
+            Segment::Suprasegmental(Suprasegmental::Break(Break::Syllable)) => {
+                parts.push((None, Vec::new()))
+            }
+            Segment::Suprasegmental(Suprasegmental::Stress(stress)) => {
+                parts.push((Some(*stress), Vec::new()))
+            }
+            s if s.is_segmental() => parts.last_mut().unwrap().1.push(*s),
+            _ => {}
+        }
+    }
+    let marked = parts.len() > 1;
+    parts
+        .into_iter()
+        .flat_map(|(stress, segments)| {
+            let mut syllables = syllabify_part(&segments, rules, marked);
+            if let Some(first) = syllables.first_mut() {
+                first.stress = stress;
+            }
+            syllables
+        })
+        .collect()
+}
+
+/// A word divided into syllables, read from a transcription such as
+/// `/ˈfəʊ̯.nɪm/`.
+///
+/// ```
+/// use allo::syllable::Word;
+///
+/// let word: Word = "/ˈfəʊ̯.nɪm/".parse().unwrap();
+/// assert_eq!(word.syllables().len(), 2);
+/// assert_eq!(word.stressed_syllable().unwrap().to_string(), "ˈfəʊ̯");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Word {
+    syllables: Vec<Syllable>,
+}
+
+impl Word {
+    pub fn new(syllables: Vec<Syllable>) -> Self {
+        Word { syllables }
+    }
+
+    /// Syllabifies `segments` as `syllabify` does.
+    pub fn from_segments(segments: &[Segment]) -> Self {
+        Word::new(syllabify(segments))
+    }
+
+    pub fn syllables(&self) -> &[Syllable] {
+        &self.syllables
+    }
+
+    /// The syllable with primary stress, if one is marked.
+    pub fn stressed_syllable(&self) -> Option<&Syllable> {
+        self.syllables
+            .iter()
+            .find(|s| s.stress == Some(Stress::Primary))
+    }
+}
+
+impl fmt::Display for Word {
+    /// Writes the syllables with a break between each, except before
+    /// a stress mark, which divides syllables itself: `ˈfəʊ̯.nɪm`. An
+    /// ambisyllabic consonant is written once, after the break.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, syllable) in self.syllables.iter().enumerate() {
+            if i > 0 && syllable.stress.is_none() {
+                Suprasegmental::Break(Break::Syllable).fmt(f)?;
+            }
+            let shared = self.syllables.get(i + 1).is_some_and(|s| s.ambisyllabic);
+            match shared {
+                true => {
+                    let mut written = syllable.clone();
+                    written.coda.pop();
+                    written.fmt(f)?;
+                }
+                false => syllable.fmt(f)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Word {
+    type Err = ParseSegmentError;
+
+    /// Parses and syllabifies a word, which may be enclosed in one
+    /// pair of `parser::BRACKETS`. An apostrophe is read as primary
+    /// stress, as in `/'fəʊ.nɪm/`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = parser::BRACKETS
+            .iter()
+            .find_map(|&(open, close)| s.strip_prefix(open)?.strip_suffix(close))
+            .unwrap_or(s);
+        let at = parser::offset_in(s, inner);
+        let stressed = inner.replace('\'', "ˈ");
+        let segments = parser::parse(&stressed).map_err(|e| match e.offset() {
+            Some(offset) => {
+                // the characters of `stressed` and `inner` correspond
+                let original = stressed
+                    .char_indices()
+                    .zip(inner.char_indices())
+                    .find(|((written, _), _)| *written >= offset)
+                    .map_or(inner.len(), |(_, (original, _))| original);
+                e.at(at + original)
+            }
+            None => e,
+        })?;
+        Ok(Word::from_segments(&segments))
+    }
+}
+
+fn syllabify_part(word: &[Segment], rules: &impl Phonotactics, marked: bool) -> Vec<Syllable> {
+    // Each nucleus as a range of `word`; a non-syllabic vowel next to
+    // a nucleus vowel joins it as a diphthong, as does any vowel if the
+    // word marks its syllables.
+    let glide = |s: &Segment| {
+        s.as_vowel()
+            .is_some_and(|v| marked || v.diacritics().contains(Diacritic::NonSyllabic))
+    };
+    let mut nuclei: Vec<(usize, usize)> = Vec::new();
+    let mut i = 0;
+    while i < word.len() {
+        if !rules.is_nucleus(&word[i]) {
+            i += 1;
+            continue;
+        }
+        let mut start = i;
+        if start > 0 && glide(&word[start - 1]) && nuclei.last().is_none_or(|&(_, e)| e < start - 1)
+        {
+            start -= 1;
+        }
+        let mut end = i + 1;
+        while end < word.len()
+            && glide(&word[end])
+            && (marked || !word.get(end + 1).is_some_and(|s| rules.is_nucleus(s)))
+        {
+            end += 1;
+        }
+        nuclei.push((start, end));
+        i = end;
+    }
+    if nuclei.is_empty() {
+        return match word.is_empty() {
+            true => Vec::new(),
+            false => vec![Syllable {
+                onset: word.to_vec(),
+                ..Syllable::default()
+            }],
+        };
+    }
+
+    let mut syllables: Vec<Syllable> = Vec::new();
+    let (mut onset_start, mut shared) = (0, false);
+    for (k, &(start, end)) in nuclei.iter().enumerate() {
+        let next = nuclei.get(k + 1).map_or(word.len(), |&(s, _)| s);
+        let cluster = &word[end..next];
+        let nucleus = &word[start..end];
+        let last = k + 1 == nuclei.len();
+        let legal = |j: usize| rules.is_onset(&cluster[j..]) && rules.is_coda(&cluster[..j]);
+        let split = match rules.split() {
+            _ if last => cluster.len(),
+            Split::MaximalOnset => (0..=cluster.len())
+                .find(|&j| legal(j))
+                .or_else(|| (0..=cluster.len()).find(|&j| rules.is_onset(&cluster[j..])))
+                .unwrap_or(cluster.len()),
+            Split::MaximalCoda => (0..=cluster.len())
+                .rev()
+                .find(|&j| legal(j))
+                .or_else(|| {
+                    (0..=cluster.len())
+                        .rev()
+                        .find(|&j| rules.is_coda(&cluster[..j]))
+                })
+                .unwrap_or(0),
+        };
+        let ambisyllabic = !last && matches!(cluster, [c] if rules.is_ambisyllabic(nucleus, c));
+        syllables.push(Syllable {
+            stress: None,
+            onset: word[onset_start..start].to_vec(),
+            nucleus: nucleus.to_vec(),
+            coda: match ambisyllabic {
+                true => cluster.to_vec(),
+                false => cluster[..split].to_vec(),
+            },
+            ambisyllabic: shared,
+        });
+        onset_start = if ambisyllabic { end } else { end + split };
+        shared = ambisyllabic;
+    }
+    syllables
+}
@@ -0,0 +1,285 @@
+use core::fmt::{self, Write};
+
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String};
+
+use crate::parser;
+
+/// The five Chao tone levels. The discriminant is the level's Chao
+/// number, so `Level::ExtraHigh as u8 == 5`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Level {
+    ExtraLow = 1,
+    Low = 2,
+    Mid = 3,
+    High = 4,
+    ExtraHigh = 5,
+}
+
+impl Level {
+    pub const ALL: [Level; 5] = [
+        Level::ExtraLow,
+        Level::Low,
+        Level::Mid,
+        Level::High,
+        Level::ExtraHigh,
+    ];
+
+    /// Graphemes: ˩ ˨ ˧ ˦ ˥
+    pub const fn letter(&self) -> char {
+        match self {
+            Level::ExtraLow => '\u{2E9}',
+            Level::Low => '\u{2E8}',
+            Level::Mid => '\u{2E7}',
+            Level::High => '\u{2E6}',
+            Level::ExtraHigh => '\u{2E5}',
+        }
+    }
+
+    /// The level's name, e.g. `"extra high"`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Level::ExtraLow => "extra low",
+            Level::Low => "low",
+            Level::Mid => "mid",
+            Level::High => "high",
+            Level::ExtraHigh => "extra high",
+        }
+    }
+
+    pub const fn from_letter(c: char) -> Option<Level> {
+        let mut i = 0;
+        while i < Level::ALL.len() {
+            if Level::ALL[i].letter() == c {
+                return Some(Level::ALL[i]);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    pub const fn chao(&self) -> u8 {
+        *self as u8
+    }
+
+    pub const fn from_chao(n: u8) -> Option<Level> {
+        match n {
+            1 => Some(Level::ExtraLow),
+            2 => Some(Level::Low),
+            3 => Some(Level::Mid),
+            4 => Some(Level::High),
+            5 => Some(Level::ExtraHigh),
+            _ => None,
+        }
+    }
+}
+
+/// How a tone is written.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Style {
+    /// Chao tone letters following the syllable, e.g. `ma˨˩˦`.
+    Letters,
+    /// A combining mark over the syllable nucleus, e.g. `mǎ`.
+    Diacritic,
+}
+
+/// A tone contour of one or more Chao levels. Level tones have a
+/// single level, e.g. `˥`; contour tones have up to four, e.g. `˨˩˦`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Contour {
+    levels: [Level; Contour::MAX_LEVELS],
+    len: u8,
+}
+
+/// Tone diacritics and the contours they stand for.
+const DIACRITICS: [(char, &[Level]); 12] = [
+    ('\u{30B}', &[Level::ExtraHigh]),
+    ('\u{301}', &[Level::High]),
+    ('\u{304}', &[Level::Mid]),
+    ('\u{300}', &[Level::Low]),
+    ('\u{30F}', &[Level::ExtraLow]),
+    ('\u{30C}', &[Level::ExtraLow, Level::ExtraHigh]),
+    ('\u{302}', &[Level::ExtraHigh, Level::ExtraLow]),
+    ('\u{1DC4}', &[Level::Mid, Level::ExtraHigh]),
+    ('\u{1DC5}', &[Level::ExtraLow, Level::Mid]),
+    ('\u{1DC7}', &[Level::ExtraHigh, Level::Mid]),
+    ('\u{1DC6}', &[Level::Mid, Level::ExtraLow]),
+    ('\u{1DC8}', &[Level::Mid, Level::High, Level::Mid]),
+];
+
+impl Contour {
+    pub const MAX_LEVELS: usize = 4;
+
+    /// A level (register) tone.
+    pub const fn level(level: Level) -> Self {
+        Contour {
+            levels: [level; Contour::MAX_LEVELS],
+            len: 1,
+        }
+    }
+
+    /// Builds a contour from `levels`, returning `None` if there are
+    /// none or more than `Contour::MAX_LEVELS`.
+    pub const fn new(levels: &[Level]) -> Option<Self> {
+        if levels.is_empty() || levels.len() > Contour::MAX_LEVELS {
+            return None;
+        }
+        let mut contour = Contour::level(levels[0]);
+        let mut i = 1;
+        while i < levels.len() {
+            contour.levels[i] = levels[i];
+            i += 1;
+        }
+        contour.len = levels.len() as u8;
+        Some(contour)
+    }
+
+    pub fn levels(&self) -> &[Level] {
+        &self.levels[..self.len as usize]
+    }
+
+    pub const fn is_level(&self) -> bool {
+        self.len == 1
+    }
+
+    pub fn is_rising(&self) -> bool {
+        self.levels().windows(2).all(|w| w[0] < w[1]) && !self.is_level()
+    }
+
+    pub fn is_falling(&self) -> bool {
+        self.levels().windows(2).all(|w| w[0] > w[1]) && !self.is_level()
+    }
+
+    /// The contour as Chao numbers, e.g. `"214"`.
+    #[cfg(feature = "alloc")]
+    pub fn chao(&self) -> String {
+        self.levels()
+            .iter()
+            .map(|l| char::from(b'0' + l.chao()))
+            .collect()
+    }
+
+    /// The contour as Chao tone letters, e.g. `"˨˩˦"`.
+    #[cfg(feature = "alloc")]
+    pub fn letters(&self) -> String {
+        self.levels().iter().map(Level::letter).collect()
+    }
+
+    /// The contour's name: the level's name for level tones, the
+    /// chart's name for the contours the IPA has diacritics for, and
+    /// the Chao numbers otherwise, e.g. `"extra high tone"`, `"low
+    /// rising tone"` or `"tone 214"`.
+    #[cfg(feature = "alloc")]
+    pub fn name(&self) -> String {
+        let named = match self.chao().as_str() {
+            "15" => "rising",
+            "51" => "falling",
+            "35" => "high rising",
+            "13" => "low rising",
+            "53" => "high falling",
+            "31" => "low falling",
+            "343" => "rising-falling",
+            _ if self.is_level() => self.levels[0].name(),
+            chao => return format!("tone {}", chao),
+        };
+        format!("{} tone", named)
+    }
+
+    /// The combining tone diacritic for this contour, if the IPA has
+    /// one.
+    pub fn diacritic(&self) -> Option<char> {
+        DIACRITICS
+            .iter()
+            .find(|(_, levels)| *levels == self.levels())
+            .map(|(c, _)| *c)
+    }
+
+    pub fn from_diacritic(c: char) -> Option<Contour> {
+        DIACRITICS
+            .iter()
+            .find(|(d, _)| *d == c)
+            .and_then(|(_, levels)| Contour::new(levels))
+    }
+
+    /// Parses a contour written either as tone letters (`"˨˩˦"`) or
+    /// as Chao numbers (`"214"`).
+    pub const fn parse(s: &str) -> Option<Contour> {
+        let mut levels = [Level::Mid; Contour::MAX_LEVELS];
+        let mut len = 0;
+        let mut rest = s;
+        while let Some((c, after)) = parser::split_first_char(rest) {
+            let level = match c.to_digit(10) {
+                Some(n) => Level::from_chao(n as u8),
+                None => Level::from_letter(c),
+            };
+            match level {
+                Some(level) if len < Contour::MAX_LEVELS => levels[len] = level,
+                _ => return None,
+            }
+            len += 1;
+            rest = after;
+        }
+        Contour::new(levels.split_at(len).0)
+    }
+
+    /// Writes the tone on `syllable` in the given style. In
+    /// `Style::Diacritic`, the mark goes on the first character of
+    /// the syllable nucleus passed as `syllable`, falling back to
+    /// tone letters if the contour has no diacritic.
+    #[cfg(feature = "alloc")]
+    pub fn render(&self, syllable: &str, style: Style) -> String {
+        match (style, self.diacritic()) {
+            (Style::Diacritic, Some(mark)) => {
+                let mut chars = syllable.chars();
+                let mut s = String::new();
+                s.extend(chars.next());
+                s.push(mark);
+                s.extend(chars);
+                s
+            }
+            _ => format!("{}{}", syllable, self.letters()),
+        }
+    }
+
+    /// Splits a syllable written in either style into its toneless
+    /// form and its tone, if any. Precomposed letters such as `á` are
+    /// decomposed first, so the toneless form is in the form of
+    /// `normalize::normalize_ipa`.
+    #[cfg(feature = "alloc")]
+    pub fn split(syllable: &str) -> (String, Option<Contour>) {
+        let syllable = &crate::normalize::normalize_ipa(syllable);
+        let letters: String = syllable
+            .chars()
+            .filter(|c| Level::from_letter(*c).is_some())
+            .collect();
+        if let Some(contour) = Contour::parse(&letters) {
+            let base = syllable
+                .chars()
+                .filter(|c| Level::from_letter(*c).is_none())
+                .collect();
+            return (base, Some(contour));
+        }
+        let mut tone = None;
+        let base = syllable
+            .chars()
+            .filter(|c| match Contour::from_diacritic(*c) {
+                Some(contour) if tone.is_none() => {
+                    tone = Some(contour);
+                    false
+                }
+                _ => true,
+            })
+            .collect();
+        (base, tone)
+    }
+}
+
+impl fmt::Display for Contour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.levels()
+            .iter()
+            .try_for_each(|l| f.write_char(l.letter()))
+    }
+}
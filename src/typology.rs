@@ -0,0 +1,189 @@
+//! Typological statistics for inventories, and how they compare with
+//! the world's languages as the World Atlas of Language Structures
+//! (WALS, Maddieson's chapters 1–4, 11 and 19) samples them.
+//!
+//! ```
+//! use allo::inventory::Inventory;
+//! use allo::typology::Typology;
+//!
+//! let inventory: Inventory = allo::parser::parse("ptkbdɡmnslaeiou").unwrap().into_iter().collect();
+//! let typology = Typology::of(&inventory);
+//! assert_eq!((typology.consonants, typology.vowels), (10, 5));
+//! assert_eq!(typology.laryngeal_series, 2);
+//!
+//! let consonants = &typology.compare()[0];
+//! assert_eq!(consonants.category, "small");
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::diacritics::Diacritic;
+use crate::inventory::Inventory;
+use crate::ipa::{Airstream, Backness, Manner};
+use crate::segment::Segment;
+
+/// The standard measures of an inventory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Typology {
+    /// Consonants and affricates.
+    pub consonants: usize,
+    /// Vowel qualities, counting vowels that differ only in length,
+    /// nasality or other diacritics once.
+    pub vowels: usize,
+    /// Consonants per vowel quality, or 0 without vowels.
+    pub ratio: f32,
+    /// The laryngeal settings plosives contrast: plain voiceless and
+    /// voiced, aspirated, breathy, ejective and implosive each count
+    /// as one.
+    pub laryngeal_series: usize,
+    /// Whether any consonant is a click.
+    pub clicks: bool,
+    /// Whether any vowel quality is front or near-front and rounded.
+    pub front_rounded_vowels: bool,
+}
+
+impl Typology {
+    /// Measures `inventory`.
+    pub fn of(inventory: &Inventory) -> Typology {
+        let consonants = inventory.consonants().count();
+        let mut qualities: Vec<Segment> = inventory.vowels().map(|v| v.base()).collect();
+        qualities.sort_unstable();
+        qualities.dedup();
+        let vowels = qualities.len();
+        let mut series: Vec<_> = inventory
+            .consonants()
+            .filter_map(|s| s.as_consonant().copied())
+            .filter(|c| c.manner() == Manner::Plosive)
+            .filter(|c| c.airstream() != Airstream::VelaricIngressive)
+            .map(|c| {
+                let aspirated = c.diacritics().contains(Diacritic::Aspirated);
+                (c.phonation(), c.airstream(), aspirated)
+            })
+            .collect();
+        series.sort_unstable();
+        series.dedup();
+        Typology {
+            consonants,
+            vowels,
+            ratio: match vowels {
+                0 => 0.0,
+                v => consonants as f32 / v as f32,
+            },
+            laryngeal_series: series.len(),
+            clicks: inventory
+                .consonants()
+                .filter_map(|s| s.as_consonant().copied())
+                .any(|c| c.airstream() == Airstream::VelaricIngressive),
+            front_rounded_vowels: qualities.iter().any(|v| {
+                v.as_vowel().is_some_and(|v| {
+                    v.is_rounded() && matches!(v.backness(), Backness::Front | Backness::NearFront)
+                })
+            }),
+        }
+    }
+
+    /// Where each measure falls in its distribution: the consonant
+    /// inventory, vowel inventory, consonant–vowel ratio, front
+    /// rounded vowels and clicks, in that order.
+    pub fn compare(&self) -> Vec<Comparison> {
+        let flag = |present: bool| match present {
+            true => 1.0,
+            false => 0.0,
+        };
+        [
+            (&CONSONANTS, self.consonants as f32),
+            (&VOWELS, self.vowels as f32),
+            (&RATIO, self.ratio),
+            (&FRONT_ROUNDED_VOWELS, flag(self.front_rounded_vowels)),
+            (&CLICKS, flag(self.clicks)),
+        ]
+        .into_iter()
+        .map(|(distribution, value)| distribution.place(value))
+        .collect()
+    }
+}
+
+/// The category a measure falls in, and how common it is.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Comparison {
+    /// The measure, e.g. `"consonant inventory"`.
+    pub measure: &'static str,
+    /// Its category in WALS, e.g. `"average"`.
+    pub category: &'static str,
+    /// The share of the WALS sample in the same category.
+    pub share: f32,
+}
+
+/// A WALS distribution: categories by the value each ends before,
+/// with the number of languages in each.
+pub struct Distribution {
+    /// The measure, as `Comparison::measure` reports it.
+    pub measure: &'static str,
+    /// In order, each category's name, the value it ends before, and
+    /// the number of languages in it. The last category also takes
+    /// any larger value.
+    pub categories: &'static [(&'static str, f32, u32)],
+}
+
+impl Distribution {
+    /// The category `value` falls in.
+    pub fn place(&self, value: f32) -> Comparison {
+        let total: u32 = self.categories.iter().map(|(_, _, n)| n).sum();
+        let (category, _, count) = self
+            .categories
+            .iter()
+            .find(|(_, end, _)| value < *end)
+            .unwrap_or(&self.categories[self.categories.len() - 1]);
+        Comparison {
+            measure: self.measure,
+            category,
+            share: *count as f32 / total as f32,
+        }
+    }
+}
+
+/// WALS chapter 1, 563 languages.
+pub const CONSONANTS: Distribution = Distribution {
+    measure: "consonant inventory",
+    categories: &[
+        ("small", 15.0, 89),
+        ("moderately small", 19.0, 122),
+        ("average", 26.0, 201),
+        ("moderately large", 34.0, 94),
+        ("large", f32::INFINITY, 57),
+    ],
+};
+
+/// WALS chapter 2, 564 languages.
+pub const VOWELS: Distribution = Distribution {
+    measure: "vowel quality inventory",
+    categories: &[
+        ("small", 5.0, 93),
+        ("average", 7.0, 288),
+        ("large", f32::INFINITY, 183),
+    ],
+};
+
+/// WALS chapter 3, 564 languages.
+pub const RATIO: Distribution = Distribution {
+    measure: "consonant-vowel ratio",
+    categories: &[
+        ("low", 2.0, 58),
+        ("moderately low", 2.75, 99),
+        ("average", 4.5, 234),
+        ("moderately high", 6.5, 101),
+        ("high", f32::INFINITY, 72),
+    ],
+};
+
+/// WALS chapter 11, 562 languages.
+pub const FRONT_ROUNDED_VOWELS: Distribution = Distribution {
+    measure: "front rounded vowels",
+    categories: &[("absent", 1.0, 525), ("present", f32::INFINITY, 37)],
+};
+
+/// WALS chapter 19, 567 languages.
+pub const CLICKS: Distribution = Distribution {
+    measure: "clicks",
+    categories: &[("absent", 1.0, 557), ("present", f32::INFINITY, 10)],
+};
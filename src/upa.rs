@@ -0,0 +1,152 @@
+//! The Uralic Phonetic Alphabet (UPA), after Setälä (1901), in which most
+//! Finno-Ugric dictionaries and text collections are written.
+//!
+//! UPA builds on the Latin and Greek alphabets rather than the IPA's
+//! letters, and gives some marks other meanings:
+//!
+//! * A háček makes a postalveolar, as `š` for `ʃ`, and `c` and `ʒ` are
+//!   the affricates `t͡s` and `d͡z`.
+//! * An acute palatalizes a consonant, as `ś` for `ɕ` and `t́` for `tʲ`.
+//! * A macron lengthens a vowel, as `ā` for `aː`.
+//! * A small capital is voiceless, as `ᴅ` for `d̥`.
+//! * A breve below backs a vowel, as `i̮` for `ɨ`; `ä`, `ö` and `ü` are
+//!   `æ`, `ø` and `y`.
+//! * `χ` is the velar fricative `x`, `λ` the lateral `ɬ`, and `ł` a
+//!   velarized `ɫ`.
+//!
+//! Small capitals in particular clash with the IPA, where `ʙ`, `ɢ`, `ʟ`,
+//! `ɴ`, `ʀ` and `ɪ` are letters of their own. Text is compared as
+//! `normalize::normalize_ipa` rewrites it, and `from_ipa` writes
+//! letters decomposed.
+//!
+//! ```
+//! use allo::upa;
+//!
+//! assert_eq!(upa::to_ipa("śūr").unwrap(), "ɕuːr");
+//! assert_eq!(upa::to_ipa("kaᴅ").unwrap(), "kad\u{325}");
+//! assert_eq!(upa::from_ipa("t͡ʃæ").unwrap(), "c\u{30C}a\u{308}");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::normalize;
+use crate::notation::{self, Table, UnknownSymbol};
+use crate::parser;
+use crate::segment::{ParseSegmentError, Segment};
+
+/// UPA letters and marks and their IPA equivalents. Where several map
+/// to the same IPA, the first listed is the one produced by `from_ipa`.
+pub const TABLE: &Table = &[
+    // stops and affricates
+    ("p", "p"),
+    ("b", "b"),
+    ("t", "t"),
+    ("d", "d"),
+    ("t\u{301}", "tʲ"),
+    ("d\u{301}", "dʲ"),
+    ("k", "k"),
+    ("g", "ɡ"),
+    ("q", "q"),
+    ("ʔ", "ʔ"),
+    ("c", "t͡s"),
+    ("ʒ", "d͡z"),
+    ("c\u{30C}", "t͡ʃ"),
+    ("ʒ\u{30C}", "d͡ʒ"),
+    ("c\u{301}", "t͡ɕ"),
+    ("ʒ\u{301}", "d͡ʑ"),
+    // fricatives
+    ("f", "f"),
+    ("v", "v"),
+    ("β", "β"),
+    ("ϑ", "θ"),
+    ("θ", "θ"),
+    ("δ", "ð"),
+    ("s", "s"),
+    ("z", "z"),
+    ("s\u{30C}", "ʃ"),
+    ("z\u{30C}", "ʒ"),
+    ("s\u{301}", "ɕ"),
+    ("z\u{301}", "ʑ"),
+    ("χ\u{301}", "ç"),
+    ("χ", "x"),
+    ("x", "x"),
+    ("γ", "ɣ"),
+    ("h", "h"),
+    ("λ", "ɬ"),
+    // sonorants
+    ("m", "m"),
+    ("n", "n"),
+    ("n\u{301}", "ɲ"),
+    ("ŋ", "ŋ"),
+    ("l", "l"),
+    ("l\u{301}", "ʎ"),
+    ("ł", "l\u{334}"),
+    ("r", "r"),
+    ("r\u{301}", "rʲ"),
+    ("j", "j"),
+    ("w", "w"),
+    // vowels
+    ("i", "i"),
+    ("e", "e"),
+    ("a", "a"),
+    ("o", "o"),
+    ("u", "u"),
+    ("ə", "ə"),
+    ("a\u{308}", "æ"),
+    ("o\u{308}", "ø"),
+    ("u\u{308}", "y"),
+    ("i\u{32E}", "ɨ"),
+    ("e\u{32E}", "ə"),
+    // small capitals, voiceless
+    ("ᴀ", "a\u{325}"),
+    ("ᴇ", "e\u{325}"),
+    ("ɪ", "i\u{325}"),
+    ("ᴏ", "o\u{325}"),
+    ("ᴜ", "u\u{325}"),
+    ("ʙ", "b\u{325}"),
+    ("ᴅ", "d\u{325}"),
+    ("ɢ", "ɡ\u{325}"),
+    ("ᴠ", "v\u{325}"),
+    ("ᴢ", "z\u{325}"),
+    ("ᴍ", "m\u{325}"),
+    ("ɴ", "n\u{325}"),
+    ("ʟ", "l\u{325}"),
+    ("ʀ", "r\u{325}"),
+    ("ᴊ", "j\u{325}"),
+    // marks
+    ("\u{304}", "ː"),
+    ("\u{306}", "\u{306}"),
+    ("\u{303}", "\u{303}"),
+    ("ʰ", "ʰ"),
+    // other tied affricates are written unbroken, as `tλ`
+    ("", "\u{361}"),
+    ("", "\u{35C}"),
+];
+
+/// Converts a UPA transcription to IPA.
+pub fn to_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    let (normalized, starts) = normalize::normalize_mapped(s);
+    notation::to_ipa(&normalized, TABLE).map_err(|e| UnknownSymbol {
+        offset: normalize::original_offset(&starts, e.offset),
+        ..e
+    })
+}
+
+/// Converts an IPA transcription to UPA.
+pub fn from_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    notation::from_ipa(s, TABLE)
+}
+
+/// Parses a UPA transcription directly into segments.
+pub fn to_segments(s: &str) -> Result<Vec<Segment>, ParseSegmentError> {
+    parser::parse(&to_ipa(s)?)
+}
+
+/// Writes `segments` in UPA.
+pub fn from_segments(segments: &[Segment]) -> Result<String, UnknownSymbol> {
+    let ipa: String = segments.iter().map(Segment::to_string).collect();
+    from_ipa(&ipa)
+}
@@ -0,0 +1,268 @@
+//! Voice Quality Symbols (VoQS): labels for stretches of speech said
+//! with a voice quality, written in braces around the stretch, as in
+//! `{W ɡʊd naɪt W}` for a whispered "good night".
+
+use core::fmt;
+use core::ops::Range;
+use core::str::FromStr;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::parser;
+use crate::segment::{ParseSegmentError, Segment};
+
+/// A voice quality from the VoQS chart.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum VoiceQuality {
+    Whisper,
+    Falsetto,
+    Creak,
+    Breathy,
+    Creaky,
+    Harsh,
+    /// Phonation with the ventricular folds ("false vocal folds").
+    Ventricular,
+}
+
+impl VoiceQuality {
+    pub const ALL: [VoiceQuality; 7] = [
+        VoiceQuality::Whisper,
+        VoiceQuality::Falsetto,
+        VoiceQuality::Creak,
+        VoiceQuality::Breathy,
+        VoiceQuality::Creaky,
+        VoiceQuality::Harsh,
+        VoiceQuality::Ventricular,
+    ];
+
+    /// The label written inside the braces, e.g. `"V!"` for harsh
+    /// voice.
+    pub const fn symbol(&self) -> &'static str {
+        match self {
+            VoiceQuality::Whisper => "W",
+            VoiceQuality::Falsetto => "F",
+            VoiceQuality::Creak => "C",
+            VoiceQuality::Breathy => "V\u{324}",
+            VoiceQuality::Creaky => "V\u{330}",
+            VoiceQuality::Harsh => "V!",
+            VoiceQuality::Ventricular => "V!!",
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            VoiceQuality::Whisper => "whisper",
+            VoiceQuality::Falsetto => "falsetto",
+            VoiceQuality::Creak => "creak",
+            VoiceQuality::Breathy => "breathy voice",
+            VoiceQuality::Creaky => "creaky voice",
+            VoiceQuality::Harsh => "harsh voice",
+            VoiceQuality::Ventricular => "ventricular phonation",
+        }
+    }
+
+    /// The voice quality labelled `symbol`. The ventricular label may
+    /// also be written with the double exclamation mark `‼`.
+    pub fn from_symbol(symbol: &str) -> Option<VoiceQuality> {
+        match symbol {
+            "V\u{203C}" => Some(VoiceQuality::Ventricular),
+            _ => VoiceQuality::ALL.into_iter().find(|q| q.symbol() == symbol),
+        }
+    }
+}
+
+impl fmt::Display for VoiceQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// A voice quality over a range of segments, optionally with a
+/// degree from 1 (slight) to 3 (extreme), written before the label as
+/// in `{3F ... 3F}`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Annotation {
+    quality: VoiceQuality,
+    degree: Option<u8>,
+    range: Range<usize>,
+}
+
+impl Annotation {
+    pub fn new(quality: VoiceQuality, range: Range<usize>) -> Self {
+        Annotation {
+            quality,
+            degree: None,
+            range,
+        }
+    }
+
+    /// Sets the degree, returning `None` unless it is 1, 2 or 3.
+    pub fn with_degree(mut self, degree: u8) -> Option<Self> {
+        (1..=3).contains(&degree).then(|| {
+            self.degree = Some(degree);
+            self
+        })
+    }
+
+    pub fn quality(&self) -> VoiceQuality {
+        self.quality
+    }
+
+    pub fn degree(&self) -> Option<u8> {
+        self.degree
+    }
+
+    /// The indices of the annotated segments.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    fn label(&self) -> String {
+        match self.degree {
+            Some(d) => format!("{}{}", d, self.quality),
+            None => self.quality.to_string(),
+        }
+    }
+}
+
+/// A sequence of segments with voice quality annotations over spans
+/// of them. Annotations may nest, as in a whispered stretch within a
+/// falsetto one.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Transcription {
+    segments: Vec<Segment>,
+    annotations: Vec<Annotation>,
+}
+
+impl Transcription {
+    pub fn new(segments: Vec<Segment>) -> Self {
+        Transcription {
+            segments,
+            annotations: Vec::new(),
+        }
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    /// Adds `annotation`, returning `false` without adding it if its
+    /// range is empty or runs past the last segment.
+    pub fn annotate(&mut self, annotation: Annotation) -> bool {
+        let Range { start, end } = annotation.range;
+        if start >= end || end > self.segments.len() {
+            return false;
+        }
+        self.annotations.push(annotation);
+        true
+    }
+
+    /// The annotations covering the segment at `index`, outermost
+    /// first.
+    pub fn qualities_at(&self, index: usize) -> Vec<&Annotation> {
+        let mut covering: Vec<&Annotation> = self
+            .annotations
+            .iter()
+            .filter(|a| a.range.contains(&index))
+            .collect();
+        covering.sort_by_key(|a| (a.range.start, core::cmp::Reverse(a.range.end)));
+        covering
+    }
+}
+
+impl fmt::Display for Transcription {
+    /// Writes the segments with each annotation's labels in braces
+    /// around its span: `{W ɡʊd W}`. Where spans start or end together,
+    /// the longer one is outside.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut opening: Vec<&Annotation> = self.annotations.iter().collect();
+        opening.sort_by_key(|a| (a.range.start, core::cmp::Reverse(a.range.end)));
+        let mut closing = opening.clone();
+        closing.sort_by_key(|a| (a.range.end, core::cmp::Reverse(a.range.start)));
+        // Whether a space is owed before the next segment or label.
+        let (mut space, mut opened) = (false, true);
+        for i in 0..=self.segments.len() {
+            for a in closing.iter().filter(|a| a.range.end == i) {
+                write!(f, " {}}}", a.label())?;
+                (space, opened) = (true, false);
+            }
+            for a in opening.iter().filter(|a| a.range.start == i) {
+                if !opened {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{{{} ", a.label())?;
+                (space, opened) = (false, true);
+            }
+            if let Some(segment) = self.segments.get(i) {
+                if space {
+                    f.write_str(" ")?;
+                }
+                segment.fmt(f)?;
+                (space, opened) = (false, false);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Transcription {
+    type Err = ParseSegmentError;
+
+    /// Parses segments with VoQS labels, e.g. `"{2F hɛloʊ 2F}"`. Labels
+    /// must be separated from the segments by whitespace, and each
+    /// closing label must match the innermost open one.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut transcription = Transcription::default();
+        let mut open: Vec<(Annotation, &str)> = Vec::new();
+        for word in s.split_whitespace() {
+            let at = transcription.segments.len();
+            if let Some(label) = word.strip_prefix('{') {
+                let annotation = annotation(label, at..at)
+                    .ok_or_else(|| ParseSegmentError::Unknown(word.to_string()))?;
+                open.push((annotation, word));
+            } else if let Some(label) = word.strip_suffix('}') {
+                let closing = annotation(label, at..at);
+                match open.pop() {
+                    Some((opened, _))
+                        if closing.as_ref().is_some_and(|c| {
+                            (c.quality, c.degree) == (opened.quality, opened.degree)
+                        }) =>
+                    {
+                        let range = opened.range.start..at;
+                        if !transcription.annotate(Annotation { range, ..opened }) {
+                            return Err(ParseSegmentError::Empty);
+                        }
+                    }
+                    _ => return Err(ParseSegmentError::Unknown(word.to_string())),
+                }
+            } else {
+                transcription.segments.extend(parser::parse(word)?);
+            }
+        }
+        match open.pop() {
+            Some((_, word)) => Err(ParseSegmentError::Unknown(word.to_string())),
+            None => Ok(transcription),
+        }
+    }
+}
+
+/// The annotation labelled `label`, a symbol with an optional leading
+/// degree.
+fn annotation(label: &str, range: Range<usize>) -> Option<Annotation> {
+    let symbol = label.trim_start_matches(|c: char| c.is_ascii_digit());
+    let annotation = Annotation::new(VoiceQuality::from_symbol(symbol)?, range);
+    match &label[..label.len() - symbol.len()] {
+        "" => Some(annotation),
+        degree => annotation.with_degree(degree.parse().ok()?),
+    }
+}
@@ -0,0 +1,206 @@
+//! JavaScript bindings, built with `wasm-bindgen`. Enum values are
+//! passed as the same `snake_case` names the `serde` feature uses.
+
+use std::fmt::Debug;
+
+use wasm_bindgen::prelude::*;
+
+use crate::classes::Segments;
+use crate::ipa::Manner;
+use crate::notation::{self, Notation};
+use crate::parser;
+use crate::segment::Segment;
+
+/// Splits a transcription into one string per segment.
+#[wasm_bindgen]
+pub fn tokenize(s: &str) -> Vec<String> {
+    parser::tokenize(s).into_iter().map(String::from).collect()
+}
+
+/// Parses a transcription, returning each segment in its normalized
+/// spelling.
+#[wasm_bindgen]
+pub fn parse(s: &str) -> Result<Vec<String>, JsError> {
+    let segments = parser::parse(s).map_err(js)?;
+    Ok(segments.iter().map(Segment::to_string).collect())
+}
+
+/// Converts between the notations `Notation` names: `"ipa"`,
+/// `"xsampa"`, `"cxs"`, `"zsampa"`, `"kirshenbaum"`, `"arpabet"`,
+/// `"espeak"`, `"americanist"`, `"upa"` and `"asjp"`.
+#[wasm_bindgen]
+pub fn convert(s: &str, from: &str, to: &str) -> Result<String, JsError> {
+    let from: Notation = from.parse().map_err(js)?;
+    let to: Notation = to.parse().map_err(js)?;
+    notation::convert(s, from, to).map_err(js)
+}
+
+/// Describes a single segment.
+#[wasm_bindgen]
+pub fn describe(symbol: &str) -> Result<Description, JsError> {
+    Ok(Description(symbol.parse().map_err(js)?))
+}
+
+/// The symbol for a consonant, e.g. `lookup("velar", "nasal",
+/// "voiced")` is `"ŋ"`.
+#[wasm_bindgen]
+pub fn lookup(articulation: &str, manner: &str, voicing: &str) -> Option<String> {
+    Segments::consonants()
+        .iter()
+        .find(|s| {
+            let c = s.as_consonant().unwrap();
+            c.coarticulation().is_none()
+                && snake(&c.articulation()) == articulation
+                && manner_name(c.manner()) == manner
+                && snake(&c.phonation()) == voicing
+        })
+        .map(|s| s.to_string())
+}
+
+/// The description of a segment, as returned by `describe`. Fields
+/// that do not apply to the segment are `undefined`.
+#[wasm_bindgen]
+pub struct Description(Segment);
+
+#[wasm_bindgen]
+impl Description {
+    /// `"consonant"`, `"affricate"`, `"vowel"`, `"suprasegmental"` or
+    /// `"tone"`. The consonant fields of an affricate describe its
+    /// fricative.
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> String {
+        match self.0 {
+            Segment::Consonant(_) => "consonant",
+            Segment::Affricate(_) => "affricate",
+            Segment::Vowel(_) => "vowel",
+            Segment::Suprasegmental(_) => "suprasegmental",
+            Segment::Tone(_) => "tone",
+        }
+        .to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn symbol(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// The segment's name, e.g. `"voiced velar nasal"`.
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.0.name()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn place(&self) -> Option<String> {
+        self.0.as_consonant().map(|c| snake(&c.place()))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn articulation(&self) -> Option<String> {
+        self.0.as_consonant().map(|c| snake(&c.articulation()))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn coarticulation(&self) -> Option<String> {
+        self.0
+            .as_consonant()
+            .and_then(|c| c.coarticulation())
+            .map(|a| snake(&a))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn manner(&self) -> Option<String> {
+        self.0
+            .as_consonant()
+            .map(|c| manner_name(c.manner()).to_string())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn voicing(&self) -> Option<String> {
+        self.0.as_consonant().map(|c| snake(&c.phonation()))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn airstream(&self) -> Option<String> {
+        self.0.as_consonant().map(|c| snake(&c.airstream()))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn secondary(&self) -> Option<String> {
+        self.0
+            .as_consonant()
+            .and_then(|c| c.secondary())
+            .map(|s| snake(&s))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn release(&self) -> Option<String> {
+        self.0
+            .as_consonant()
+            .and_then(|c| c.release())
+            .map(|r| snake(&r))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> Option<String> {
+        self.0.length().map(|l| snake(&l))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> Option<String> {
+        self.0.as_vowel().map(|v| snake(&v.height()))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn backness(&self) -> Option<String> {
+        self.0.as_vowel().map(|v| snake(&v.backness()))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn roundedness(&self) -> Option<String> {
+        self.0.as_vowel().map(|v| snake(&v.roundedness()))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn diacritics(&self) -> Vec<String> {
+        self.0.diacritics().iter().map(|d| snake(&d)).collect()
+    }
+
+    /// The feature matrix, e.g. `"[+voice, -sonorant]"`.
+    #[wasm_bindgen(getter)]
+    pub fn features(&self) -> Option<String> {
+        self.0.features().map(|f| f.to_string())
+    }
+}
+
+fn js(e: impl std::error::Error) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+fn manner_name(manner: Manner) -> &'static str {
+    match manner {
+        Manner::Nasal => "nasal",
+        Manner::Plosive => "plosive",
+        Manner::Fricative { sibilant: false } => "fricative",
+        Manner::Fricative { sibilant: true } => "sibilant_fricative",
+        Manner::Approximant => "approximant",
+        Manner::TapFlap => "tap_flap",
+        Manner::Trill => "trill",
+        Manner::LatFric => "lateral_fricative",
+        Manner::LatApprox => "lateral_approximant",
+        Manner::LatTapFlap => "lateral_tap_flap",
+    }
+}
+
+/// A unit variant's name in `snake_case`, e.g. `CloseMid` to
+/// `close_mid`.
+fn snake(value: &impl Debug) -> String {
+    let mut s = String::new();
+    for c in format!("{:?}", value).chars() {
+        if c.is_uppercase() && !s.is_empty() {
+            s.push('_');
+        }
+        s.extend(c.to_lowercase());
+    }
+    s
+}
@@ -0,0 +1,207 @@
+use alloc::string::String;
+
+use crate::notation::{self, Table, UnknownSymbol};
+
+/// X-SAMPA symbols and their IPA equivalents.
+///
+/// Where X-SAMPA offers several spellings for the same IPA symbol
+/// (`v\` and `P` for `ʋ`, `_~` and `~` for nasalization), the first
+/// listed is the one produced by `from_ipa`.
+pub const TABLE: &Table = &[
+    // lowercase
+    ("a", "a"),
+    ("b", "b"),
+    ("b_<", "ɓ"),
+    ("c", "c"),
+    ("d", "d"),
+    ("d`", "ɖ"),
+    ("d_<", "ɗ"),
+    ("e", "e"),
+    ("f", "f"),
+    ("g", "ɡ"),
+    ("g_<", "ɠ"),
+    ("h", "h"),
+    (r"h\", "ɦ"),
+    ("i", "i"),
+    ("j", "j"),
+    (r"j\", "ʝ"),
+    ("k", "k"),
+    ("l", "l"),
+    ("l`", "ɭ"),
+    (r"l\", "ɺ"),
+    ("m", "m"),
+    ("n", "n"),
+    ("n`", "ɳ"),
+    ("o", "o"),
+    ("p", "p"),
+    (r"p\", "ɸ"),
+    ("q", "q"),
+    ("r", "r"),
+    ("r`", "ɽ"),
+    (r"r\", "ɹ"),
+    (r"r\`", "ɻ"),
+    ("s", "s"),
+    ("s`", "ʂ"),
+    (r"s\", "ɕ"),
+    ("t", "t"),
+    ("t`", "ʈ"),
+    ("u", "u"),
+    ("v", "v"),
+    (r"v\", "ʋ"),
+    ("P", "ʋ"),
+    ("w", "w"),
+    ("x", "x"),
+    (r"x\", "ɧ"),
+    ("y", "y"),
+    ("z", "z"),
+    ("z`", "ʐ"),
+    (r"z\", "ʑ"),
+    // uppercase
+    ("A", "ɑ"),
+    ("B", "β"),
+    (r"B\", "ʙ"),
+    ("C", "ç"),
+    ("D", "ð"),
+    ("E", "ɛ"),
+    ("F", "ɱ"),
+    ("G", "ɣ"),
+    (r"G\", "ɢ"),
+    (r"G\_<", "ʛ"),
+    ("H", "ɥ"),
+    (r"H\", "ʜ"),
+    ("I", "ɪ"),
+    (r"I\", "ᵻ"),
+    ("J", "ɲ"),
+    (r"J\", "ɟ"),
+    (r"J\_<", "ʄ"),
+    ("K", "ɬ"),
+    (r"K\", "ɮ"),
+    ("L", "ʎ"),
+    (r"L\", "ʟ"),
+    ("M", "ɯ"),
+    (r"M\", "ɰ"),
+    ("N", "ŋ"),
+    (r"N\", "ɴ"),
+    ("O", "ɔ"),
+    (r"O\", "ʘ"),
+    ("Q", "ɒ"),
+    ("R", "ʁ"),
+    (r"R\", "ʀ"),
+    ("S", "ʃ"),
+    ("T", "θ"),
+    ("U", "ʊ"),
+    (r"U\", "ᵿ"),
+    ("V", "ʌ"),
+    ("W", "ʍ"),
+    ("X", "χ"),
+    (r"X\", "ħ"),
+    ("Y", "ʏ"),
+    ("Z", "ʒ"),
+    // other symbols
+    ("@", "ə"),
+    (r"@\", "ɘ"),
+    ("@`", "ə\u{2DE}"),
+    ("{", "æ"),
+    ("}", "ʉ"),
+    ("1", "ɨ"),
+    ("2", "ø"),
+    ("3", "ɜ"),
+    (r"3\", "ɞ"),
+    ("4", "ɾ"),
+    ("5", "l\u{334}"),
+    ("6", "ɐ"),
+    ("7", "ɤ"),
+    ("8", "ɵ"),
+    ("9", "œ"),
+    ("&", "ɶ"),
+    ("?", "ʔ"),
+    (r"?\", "ʕ"),
+    (r"<\", "ʢ"),
+    (r">\", "ʡ"),
+    (r"!\", "ǃ"),
+    (r"|\", "ǀ"),
+    (r"|\|\", "ǁ"),
+    (r"=\", "ǂ"),
+    // suprasegmentals
+    ("\"", "ˈ"),
+    ("%", "ˌ"),
+    (":", "ː"),
+    (r":\", "ˑ"),
+    (".", "."),
+    ("|", "|"),
+    ("||", "‖"),
+    (r"-\", "‿"),
+    // the separator, between symbols that would otherwise run together
+    ("-", ""),
+    ("^", "ꜛ"),
+    ("!", "ꜜ"),
+    ("<R>", "↗"),
+    ("<F>", "↘"),
+    // diacritics
+    ("_\"", "\u{308}"),
+    ("_+", "\u{31F}"),
+    ("_-", "\u{320}"),
+    ("_0", "\u{325}"),
+    ("=", "\u{329}"),
+    ("_=", "\u{329}"),
+    ("_>", "ʼ"),
+    (r"_?\", "ˤ"),
+    ("_^", "\u{32F}"),
+    ("_}", "\u{31A}"),
+    ("`", "\u{2DE}"),
+    ("_~", "\u{303}"),
+    ("~", "\u{303}"),
+    ("_A", "\u{318}"),
+    ("_a", "\u{33A}"),
+    ("_c", "\u{31C}"),
+    ("_d", "\u{32A}"),
+    ("_e", "\u{334}"),
+    ("_G", "ˠ"),
+    ("_h", "ʰ"),
+    ("_j", "ʲ"),
+    ("'", "ʲ"),
+    ("_k", "\u{330}"),
+    ("_l", "ˡ"),
+    ("_m", "\u{33B}"),
+    ("_N", "\u{33C}"),
+    ("_n", "ⁿ"),
+    ("_O", "\u{339}"),
+    ("_o", "\u{31E}"),
+    ("_q", "\u{319}"),
+    ("_r", "\u{31D}"),
+    ("_t", "\u{324}"),
+    ("_v", "\u{32C}"),
+    ("_w", "ʷ"),
+    ("_X", "\u{306}"),
+    ("_x", "\u{33D}"),
+    // tone diacritics
+    ("_T", "\u{30B}"),
+    ("_H", "\u{301}"),
+    ("_M", "\u{304}"),
+    ("_L", "\u{300}"),
+    ("_B", "\u{30F}"),
+    ("_R", "\u{30C}"),
+    ("_/", "\u{30C}"),
+    ("_F", "\u{302}"),
+    (r"_\", "\u{302}"),
+    ("_H_T", "\u{1DC4}"),
+    ("_B_L", "\u{1DC5}"),
+    ("_R_F", "\u{1DC8}"),
+    // a lone underscore is the tie bar
+    ("_", "\u{361}"),
+];
+
+/// Converts an X-SAMPA transcription to IPA, e.g. `"tS_h"` to `"tʃʰ"`.
+pub fn to_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    notation::to_ipa(s, TABLE)
+}
+
+/// Converts an IPA transcription to X-SAMPA.
+///
+/// Any IPA produced by `to_ipa` converts back to X-SAMPA that
+/// `to_ipa` maps to the same IPA; X-SAMPA written with a non-canonical
+/// spelling comes back in its canonical one. Symbols that would run
+/// together are separated with `-`, as `|\-|\` for `ǀǀ`.
+pub fn from_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    notation::from_ipa_separated(s, TABLE, "-")
+}
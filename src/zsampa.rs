@@ -0,0 +1,51 @@
+use alloc::string::String;
+
+use crate::notation::{self, Table, UnknownSymbol};
+use crate::xsampa;
+
+/// The symbols Z-SAMPA adds to X-SAMPA, for IPA letters X-SAMPA has no
+/// spelling of: the voiceless and retroflex implosives and the
+/// retroflex laterals. Its `` 3` `` for `ɝ` is left out, as X-SAMPA
+/// already reads it as `ɜ˞`.
+pub const ADDITIONS: &Table = &[
+    ("p_<", "ƥ"),
+    ("t_<", "ƭ"),
+    ("c_<", "ƈ"),
+    ("k_<", "ƙ"),
+    ("q_<", "ʠ"),
+    ("d`_<", "ᶑ"),
+    ("K`", "ꞎ"),
+    (r"l\`", "𝼈"),
+];
+
+/// Z-SAMPA symbols and their IPA equivalents: `ADDITIONS` followed by
+/// `xsampa::TABLE`. X-SAMPA reads the same as Z-SAMPA, except that
+/// `` K` `` and `` l\` `` become single letters rather than a letter
+/// with a rhotic hook.
+pub const TABLE: &Table = &zsampa();
+
+const LEN: usize = ADDITIONS.len() + xsampa::TABLE.len();
+
+const fn zsampa() -> [(&'static str, &'static str); LEN] {
+    let mut table = [("", ""); LEN];
+    let mut i = 0;
+    while i < LEN {
+        table[i] = match i < ADDITIONS.len() {
+            true => ADDITIONS[i],
+            false => xsampa::TABLE[i - ADDITIONS.len()],
+        };
+        i += 1;
+    }
+    table
+}
+
+/// Converts a Z-SAMPA transcription to IPA, e.g. `"t_<a"` to `"ƭa"`.
+pub fn to_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    notation::to_ipa(s, TABLE)
+}
+
+/// Converts an IPA transcription to Z-SAMPA. Letters X-SAMPA can spell
+/// come out as `xsampa::from_ipa` writes them.
+pub fn from_ipa(s: &str) -> Result<String, UnknownSymbol> {
+    notation::from_ipa_separated(s, TABLE, "-")
+}
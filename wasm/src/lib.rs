@@ -0,0 +1,6 @@
+//! The WebAssembly module of `allo`'s JavaScript bindings, built with
+//! `wasm-pack build wasm`. The bindings themselves are `allo::wasm`,
+//! behind the `wasm` feature; this crate only links them into a
+//! `cdylib`, which `allo` itself is not.
+
+pub use allo::wasm::*;